@@ -0,0 +1,174 @@
+use crate::dynamics::{RigidBodyBuilder, RigidBodyHandle, RigidBodySet};
+use crate::geometry::{ColliderHandle, ColliderSet};
+use crate::math::Point;
+use crate::utils::WCross;
+
+/// A rigid-body created by splitting another rigid-body with `fracture` or
+/// `fracture_with_groups`.
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde-serialize", derive(Serialize, Deserialize))]
+pub struct Fragment {
+    /// The rigid-body of this fragment.
+    pub body: RigidBodyHandle,
+    /// The colliders moved to the rigid-body of this fragment.
+    ///
+    /// The colliders are removed from the collider set and inserted again, so these handles
+    /// differ from the handles the colliders had before the fracture.
+    pub colliders: Vec<ColliderHandle>,
+}
+
+/// Splits a rigid-body into one new rigid-body per collider attached to it.
+///
+/// See `fracture_with_groups` for details.
+pub fn fracture(
+    body: RigidBodyHandle,
+    bodies: &mut RigidBodySet,
+    colliders: &mut ColliderSet,
+) -> Vec<Fragment> {
+    let groups: Vec<_> = bodies[body].colliders().iter().map(|h| vec![*h]).collect();
+    fracture_with_groups(body, &groups, bodies, colliders)
+}
+
+/// Splits a rigid-body into one new rigid-body per group of colliders attached to it.
+///
+/// Each fragment is a new dynamic rigid-body, with the same position, damping, and CCD setting as
+/// the fractured rigid-body, to which the colliders of its group are moved. The velocity of each
+/// fragment is the velocity the fractured rigid-body had at the center-of-mass of this fragment,
+/// so the fragments keep moving as if they were still attached together. The colliders that aren't
+/// part of any group stay attached to the fractured rigid-body, the velocity of which is adjusted to
+/// its new center-of-mass. This rigid-body is left without colliders if all its colliders are part
+/// of a group.
+///
+/// Nothing pushes the fragments apart: apply impulses to the fragments to simulate an explosion.
+/// Panics if a collider is not attached to the fractured rigid-body, or is part of several groups.
+pub fn fracture_with_groups(
+    body: RigidBodyHandle,
+    groups: &[Vec<ColliderHandle>],
+    bodies: &mut RigidBodySet,
+    colliders: &mut ColliderSet,
+) -> Vec<Fragment> {
+    let rb = &bodies[body];
+    for handle in groups.iter().flatten() {
+        assert!(
+            rb.colliders().contains(handle),
+            "The colliders of a fracture group must be attached to the fractured rigid-body."
+        );
+    }
+
+    let (linvel, angvel, world_com) = (rb.linvel, rb.angvel, rb.world_com);
+    let velocity_at = |point: &Point<f32>| linvel + angvel.gcross(point - world_com);
+    let fragment_builder = RigidBodyBuilder::new_dynamic()
+        .position(rb.position)
+        .linear_damping(rb.linear_damping)
+        .angular_damping(rb.angular_damping)
+        .ccd_enabled(rb.is_ccd_enabled());
+
+    let fragments = groups
+        .iter()
+        .map(|group| {
+            let fragment = bodies.insert(fragment_builder.build());
+            let fragment_colliders = group
+                .iter()
+                .map(|handle| {
+                    let collider = colliders
+                        .remove(*handle, bodies, false)
+                        .expect("A collider must be part of at most one fracture group.");
+                    colliders.insert(collider, fragment, bodies)
+                })
+                .collect();
+
+            let fragment_rb = bodies.get_mut(fragment).unwrap();
+            fragment_rb.linvel = velocity_at(&fragment_rb.world_com);
+            fragment_rb.angvel = angvel;
+
+            Fragment {
+                body: fragment,
+                colliders: fragment_colliders,
+            }
+        })
+        .collect();
+
+    // The center-of-mass of the fractured rigid-body moved with the removal of its colliders.
+    let rb = bodies.get_mut(body).unwrap();
+    rb.linvel = velocity_at(&rb.world_com);
+
+    fragments
+}
+
+#[cfg(test)]
+mod test {
+    use super::fracture_with_groups;
+    use crate::dynamics::{RigidBodyBuilder, RigidBodySet};
+    use crate::geometry::{ColliderBuilder, ColliderSet};
+    use crate::math::{Isometry, Vector};
+
+    #[test]
+    fn fragments_keep_the_velocity_of_the_fractured_body() {
+        let mut bodies = RigidBodySet::new();
+        let mut colliders = ColliderSet::new();
+
+        #[cfg(feature = "dim2")]
+        let angvel = 2.0;
+        #[cfg(feature = "dim3")]
+        let angvel = Vector::z() * 2.0;
+        let body = RigidBodyBuilder::new_dynamic()
+            .position(Isometry::new(Vector::y() * 3.0, na::zero()))
+            .angvel(angvel)
+            .build();
+        let body = bodies.insert(body);
+        bodies
+            .get_mut(body)
+            .unwrap()
+            .set_linvel(Vector::x() * 1.0, true);
+
+        // Three balls along the `x` axis, with different masses.
+        let handles: Vec<_> = [(-2.0, 1.0), (0.0, 1.0), (2.0, 3.0)]
+            .iter()
+            .map(|(x, density)| {
+                let collider = ColliderBuilder::ball(0.5).density(*density);
+                #[cfg(feature = "dim2")]
+                let collider = collider.translation(*x, 0.0);
+                #[cfg(feature = "dim3")]
+                let collider = collider.translation(*x, 0.0, 0.0);
+                colliders.insert(collider.build(), body, &mut bodies)
+            })
+            .collect();
+        assert!((bodies[body].center_of_mass().x - 0.8).abs() < 1.0e-5);
+
+        let momentum = |bodies: &RigidBodySet, handles: &[_]| {
+            handles
+                .iter()
+                .map(|h| *bodies[*h].linvel() * bodies[*h].mass())
+                .fold(Vector::zeros(), |a, b| a + b)
+        };
+        let initial_momentum = momentum(&bodies, &[body]);
+
+        let fragments = fracture_with_groups(
+            body,
+            &[vec![handles[0]], vec![handles[2]]],
+            &mut bodies,
+            &mut colliders,
+        );
+        assert_eq!(fragments.len(), 2);
+        assert_eq!(bodies[body].colliders().len(), 1);
+        assert!(!colliders.contains(handles[0]) && !colliders.contains(handles[2]));
+
+        // Each fragment moves like the point of the fractured body at its center-of-mass,
+        // and so does the fractured body itself at its new center-of-mass.
+        let expected = [
+            (&fragments[0].body, Vector::x() * 1.0 + Vector::y() * -5.6),
+            (&fragments[1].body, Vector::x() * 1.0 + Vector::y() * 2.4),
+            (&body, Vector::x() * 1.0 + Vector::y() * -1.6),
+        ];
+        for (handle, linvel) in expected.iter() {
+            let rb = &bodies[**handle];
+            assert!((rb.linvel() - linvel).norm() < 1.0e-5);
+            assert_eq!(rb.angvel(), bodies[body].angvel());
+            assert!((rb.position().translation.y - 3.0).abs() < 1.0e-5);
+        }
+
+        let handles = [body, fragments[0].body, fragments[1].body];
+        let final_momentum = momentum(&bodies, &handles);
+        assert!((final_momentum - initial_momentum).norm() < 1.0e-4);
+    }
+}
@@ -0,0 +1,7 @@
+//! Helpers for building and controlling common assemblies of rigid-bodies, colliders, and joints.
+
+pub use self::fracture::{fracture, fracture_with_groups, Fragment};
+pub use self::ragdoll::{Ragdoll, RagdollBone, RagdollBuilder};
+
+mod fracture;
+mod ragdoll;
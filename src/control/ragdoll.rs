@@ -0,0 +1,284 @@
+#[cfg(feature = "dim3")]
+use crate::dynamics::RevoluteJoint;
+use crate::dynamics::{
+    BallJoint, JointHandle, JointParams, JointSet, MassProperties, RigidBodyBuilder,
+    RigidBodyHandle, RigidBodySet,
+};
+use crate::geometry::{ColliderBuilder, ColliderHandle, ColliderSet, SharedShape};
+use crate::math::{Isometry, Point, Vector};
+use crate::utils;
+#[cfg(feature = "dim3")]
+use na::Unit;
+
+/// The description of one bone of a ragdoll skeleton.
+#[derive(Copy, Clone, Debug)]
+#[cfg_attr(feature = "serde-serialize", derive(Serialize, Deserialize))]
+pub struct RagdollBone {
+    /// The index of the parent bone, if any, i.e., the number of bones added to the
+    /// `RagdollBuilder` before the parent bone.
+    pub parent: Option<usize>,
+    /// The world-space position of this bone.
+    ///
+    /// The bone starts at the origin of this frame, where it is attached to its parent,
+    /// and extends along its local `y` axis.
+    pub position: Isometry<f32>,
+    /// The length of this bone, along its local `y` axis.
+    pub length: f32,
+    /// The radius of the capsule collider of this bone.
+    pub radius: f32,
+    /// The rotation axis of the joint attaching this bone to its parent, expressed in the
+    /// local-space of this bone.
+    ///
+    /// If this is `None`, the bone is attached to its parent with a ball joint.
+    #[cfg(feature = "dim3")]
+    pub hinge_axis: Option<Unit<Vector<f32>>>,
+}
+
+impl RagdollBone {
+    /// Initializes a bone without parent, with the given world-space position, length, and radius.
+    pub fn new(position: Isometry<f32>, length: f32, radius: f32) -> Self {
+        Self {
+            parent: None,
+            position,
+            length,
+            radius,
+            #[cfg(feature = "dim3")]
+            hinge_axis: None,
+        }
+    }
+
+    /// Sets the index of the parent bone this bone is attached to.
+    pub fn parent(mut self, parent: usize) -> Self {
+        self.parent = Some(parent);
+        self
+    }
+
+    /// Attaches this bone to its parent with a revolute joint, e.g., for an elbow or a knee,
+    /// instead of a ball joint.
+    ///
+    /// The rotation axis is expressed in the local-space of this bone.
+    #[cfg(feature = "dim3")]
+    pub fn hinge(mut self, axis: Unit<Vector<f32>>) -> Self {
+        self.hinge_axis = Some(axis);
+        self
+    }
+}
+
+/// The handles of the rigid-bodies, colliders, and joints created by a `RagdollBuilder`.
+///
+/// All the vectors are indexed by the index of each bone, i.e., by the order in which the
+/// bones were added to the builder.
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde-serialize", derive(Serialize, Deserialize))]
+pub struct Ragdoll {
+    /// The rigid-body of each bone.
+    pub bodies: Vec<RigidBodyHandle>,
+    /// The capsule collider of each bone.
+    pub colliders: Vec<ColliderHandle>,
+    /// The joint attaching each bone to its parent, or `None` for the bones without parent.
+    pub joints: Vec<Option<JointHandle>>,
+}
+
+/// A builder for generating the rigid-bodies, colliders, and joints of a ragdoll from the
+/// description of its skeleton.
+///
+/// Each bone is simulated by a dynamic rigid-body with a capsule collider spanning the whole
+/// length of the bone. Each bone with a parent is attached to it, at the origin of the bone, by
+/// a ball joint, or by a revolute joint in 3D if the bone has a hinge axis. The contacts between
+/// the colliders of a bone and of its parent are disabled by the joint, but the contacts with
+/// the other bones are not.
+///
+/// The joints are not limited: ball and revolute joints don't support angular limits yet.
+#[derive(Clone, Debug)]
+pub struct RagdollBuilder {
+    /// The bones of the ragdoll.
+    pub bones: Vec<RagdollBone>,
+    /// The density of the bone colliders (default: `1.0`).
+    pub density: f32,
+    /// The total mass of the ragdoll.
+    ///
+    /// If this is set, it overrides `self.density` with the uniform density resulting in
+    /// this total mass.
+    pub total_mass: Option<f32>,
+}
+
+impl RagdollBuilder {
+    /// Initializes a builder for a ragdoll without any bone.
+    pub fn new() -> Self {
+        Self {
+            bones: Vec::new(),
+            density: 1.0,
+            total_mass: None,
+        }
+    }
+
+    /// Adds a bone to the ragdoll.
+    ///
+    /// The index of this bone, used to set it as the parent of other bones, is the number of
+    /// bones added before it. Panics if the parent of this bone hasn't been added yet.
+    pub fn bone(mut self, bone: RagdollBone) -> Self {
+        if let Some(parent) = bone.parent {
+            assert!(
+                parent < self.bones.len(),
+                "The parent of a ragdoll bone must be added before the bone itself."
+            );
+        }
+
+        self.bones.push(bone);
+        self
+    }
+
+    /// Sets the density of the bone colliders.
+    pub fn density(mut self, density: f32) -> Self {
+        self.density = density;
+        self
+    }
+
+    /// Sets the total mass of the ragdoll, distributed uniformly over the volume of its bones.
+    pub fn total_mass(mut self, total_mass: f32) -> Self {
+        self.total_mass = Some(total_mass);
+        self
+    }
+
+    /// Inserts the rigid-bodies, colliders, and joints of the ragdoll into the given sets.
+    pub fn build(
+        &self,
+        bodies: &mut RigidBodySet,
+        colliders: &mut ColliderSet,
+        joints: &mut JointSet,
+    ) -> Ragdoll {
+        let shapes: Vec<_> = self.bones.iter().map(Self::bone_shape).collect();
+        let density = match self.total_mass {
+            Some(total_mass) => {
+                let volume: f32 = shapes
+                    .iter()
+                    .map(|shape| utils::inv(MassProperties::from_shape(&**shape, 1.0).inv_mass))
+                    .sum();
+                total_mass * utils::inv(volume)
+            }
+            None => self.density,
+        };
+
+        let mut result = Ragdoll {
+            bodies: Vec::with_capacity(self.bones.len()),
+            colliders: Vec::with_capacity(self.bones.len()),
+            joints: Vec::with_capacity(self.bones.len()),
+        };
+
+        for (bone, shape) in self.bones.iter().zip(shapes) {
+            let body = RigidBodyBuilder::new_dynamic()
+                .position(bone.position)
+                .build();
+            let body = bodies.insert(body);
+            let collider = ColliderBuilder::new(shape).density(density).build();
+            result
+                .colliders
+                .push(colliders.insert(collider, body, bodies));
+
+            let joint = bone.parent.map(|parent| {
+                let parent_bone = &self.bones[parent];
+                let joint = Self::bone_joint(parent_bone, bone);
+                joints.insert(bodies, result.bodies[parent], body, joint)
+            });
+
+            result.bodies.push(body);
+            result.joints.push(joint);
+        }
+
+        result
+    }
+
+    // A capsule spanning the whole length of the bone, along its local `y` axis.
+    fn bone_shape(bone: &RagdollBone) -> SharedShape {
+        let center = bone.length * 0.5;
+        let half_height = (center - bone.radius).max(0.0);
+        SharedShape::capsule(
+            Point::from(Vector::y() * (center - half_height)),
+            Point::from(Vector::y() * (center + half_height)),
+            bone.radius,
+        )
+    }
+
+    // The joint attaching the origin of `bone` to `parent`.
+    fn bone_joint(parent: &RagdollBone, bone: &RagdollBone) -> JointParams {
+        let relative_pos = parent.position.inverse() * bone.position;
+        let anchor1 = Point::from(relative_pos.translation.vector);
+
+        #[cfg(feature = "dim3")]
+        {
+            if let Some(axis) = bone.hinge_axis {
+                let axis1 = Unit::new_normalize(relative_pos.transform_vector(&axis));
+                let joint = RevoluteJoint::new(anchor1, axis1, Point::origin(), axis);
+                return joint.into();
+            }
+        }
+
+        BallJoint::new(anchor1, Point::origin()).into()
+    }
+}
+
+impl Default for RagdollBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{RagdollBone, RagdollBuilder};
+    use crate::math::{Isometry, Point, Vector};
+    use crate::pipeline::TestWorld;
+
+    #[test]
+    fn ragdoll_bones_stay_attached() {
+        let mut world = TestWorld::falling();
+
+        // A torso with an arm made of two bones, stretched horizontally.
+        let arm_rotation = -std::f32::consts::FRAC_PI_2;
+        #[cfg(feature = "dim2")]
+        let (shoulder, elbow) = (
+            Isometry::new(Vector::y() * 1.0, arm_rotation),
+            Isometry::new(Vector::new(0.5, 1.0), arm_rotation),
+        );
+        #[cfg(feature = "dim3")]
+        let (shoulder, elbow) = (
+            Isometry::new(Vector::y() * 1.0, Vector::z() * arm_rotation),
+            Isometry::new(Vector::new(0.5, 1.0, 0.0), Vector::z() * arm_rotation),
+        );
+
+        let forearm = RagdollBone::new(elbow, 0.5, 0.1).parent(1);
+        #[cfg(feature = "dim3")]
+        let forearm = forearm.hinge(Vector::z_axis());
+        let ragdoll = RagdollBuilder::new()
+            .bone(RagdollBone::new(Isometry::identity(), 1.0, 0.2))
+            .bone(RagdollBone::new(shoulder, 0.5, 0.1).parent(0))
+            .bone(forearm)
+            .total_mass(10.0)
+            .build(&mut world.bodies, &mut world.colliders, &mut world.joints);
+
+        assert_eq!(ragdoll.bodies.len(), 3);
+        assert_eq!(ragdoll.joints.iter().filter(|j| j.is_some()).count(), 2);
+        assert!(ragdoll.joints[0].is_none());
+
+        let bodies = &world.bodies;
+        let total_mass: f32 = ragdoll.bodies.iter().map(|h| bodies[*h].mass()).sum();
+        assert!((total_mass - 10.0).abs() < 1.0e-3);
+
+        world.steps(60);
+        let bodies = &world.bodies;
+
+        // The ragdoll fell, but its bones are still attached at their joints.
+        let forearm = &bodies[ragdoll.bodies[2]];
+        assert!(forearm.position().translation.vector.y < -1.0);
+
+        let anchors = [
+            (0, Point::from(Vector::y() * 1.0), 1),
+            (1, Point::from(Vector::y() * 0.5), 2),
+        ];
+        for (parent, anchor, child) in anchors.iter() {
+            let parent_anchor = bodies[ragdoll.bodies[*parent]].position() * anchor;
+            let child_anchor = bodies[ragdoll.bodies[*child]].position() * Point::origin();
+            assert!(na::distance(&parent_anchor, &child_anchor) < 1.0e-2);
+        }
+    }
+}
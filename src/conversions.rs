@@ -0,0 +1,175 @@
+//! Conversions between the isometries and rotations of Rapier and the `mint` types.
+//!
+//! Vectors, points, matrices, and 3D rotations already implement `From`/`Into` for their `mint`
+//! equivalents. `mint` has no isometry type, and no 2D rotation type, so these are converted by
+//! the functions of this module instead. They accept any type convertible to the `mint` types,
+//! e.g., the `glam` types if the `mint` feature of `glam` is enabled.
+//!
+//! This module requires the `convert-mint` feature.
+
+use crate::math::{Isometry, Rotation, Translation};
+
+#[cfg(feature = "dim2")]
+/// Converts an isometry to its translation and its rotation angle, in radians.
+pub fn isometry_to_mint(pos: &Isometry<f32>) -> (mint::Vector2<f32>, f32) {
+    (pos.translation.vector.into(), pos.rotation.angle())
+}
+
+#[cfg(feature = "dim2")]
+/// Creates an isometry from a translation and a rotation angle, in radians.
+pub fn isometry_from_mint(translation: impl Into<mint::Vector2<f32>>, angle: f32) -> Isometry<f32> {
+    let translation: mint::Vector2<f32> = translation.into();
+    Isometry::from_parts(
+        Translation::new(translation.x, translation.y),
+        Rotation::new(angle),
+    )
+}
+
+#[cfg(feature = "dim2")]
+/// Converts a rotation to a 2x2 rotation matrix.
+pub fn rotation_to_mint(rot: &Rotation<f32>) -> mint::ColumnMatrix2<f32> {
+    rot.to_rotation_matrix().into_inner().into()
+}
+
+#[cfg(feature = "dim2")]
+/// Creates a rotation from a 2x2 rotation matrix.
+///
+/// The matrix doesn't need to be orthonormal: the closest rotation is extracted from it.
+pub fn rotation_from_mint(rot: impl Into<mint::ColumnMatrix2<f32>>) -> Rotation<f32> {
+    let rot: na::Matrix2<f32> = rot.into().into();
+    Rotation::from_matrix(&rot)
+}
+
+#[cfg(feature = "dim2")]
+/// Converts an isometry to a 3x3 homogeneous transformation matrix.
+pub fn isometry_to_mint_matrix(pos: &Isometry<f32>) -> mint::ColumnMatrix3<f32> {
+    pos.to_homogeneous().into()
+}
+
+#[cfg(feature = "dim3")]
+/// Converts an isometry to its translation and its rotation.
+pub fn isometry_to_mint(pos: &Isometry<f32>) -> (mint::Vector3<f32>, mint::Quaternion<f32>) {
+    (
+        pos.translation.vector.into(),
+        rotation_to_mint(&pos.rotation),
+    )
+}
+
+#[cfg(feature = "dim3")]
+/// Creates an isometry from a translation and a rotation.
+///
+/// The rotation quaternion is normalized.
+pub fn isometry_from_mint(
+    translation: impl Into<mint::Vector3<f32>>,
+    rotation: impl Into<mint::Quaternion<f32>>,
+) -> Isometry<f32> {
+    let translation: mint::Vector3<f32> = translation.into();
+    Isometry::from_parts(
+        Translation::new(translation.x, translation.y, translation.z),
+        rotation_from_mint(rotation),
+    )
+}
+
+#[cfg(feature = "dim3")]
+/// Converts a rotation to a quaternion.
+pub fn rotation_to_mint(rot: &Rotation<f32>) -> mint::Quaternion<f32> {
+    (*rot).into()
+}
+
+#[cfg(feature = "dim3")]
+/// Creates a rotation from a quaternion.
+///
+/// The quaternion is normalized.
+pub fn rotation_from_mint(rot: impl Into<mint::Quaternion<f32>>) -> Rotation<f32> {
+    Rotation::new_normalize(na::Quaternion::from(rot.into()))
+}
+
+#[cfg(feature = "dim3")]
+/// Converts an isometry to a 4x4 homogeneous transformation matrix.
+pub fn isometry_to_mint_matrix(pos: &Isometry<f32>) -> mint::ColumnMatrix4<f32> {
+    pos.to_homogeneous().into()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::math::Vector;
+    use approx::assert_relative_eq;
+
+    #[cfg(feature = "dim2")]
+    #[test]
+    fn isometry_and_rotation_roundtrip() {
+        let pos = Isometry::new(Vector::new(1.0, 2.0), 0.5);
+
+        let (translation, angle) = isometry_to_mint(&pos);
+        assert_eq!(translation, mint::Vector2 { x: 1.0, y: 2.0 });
+        assert_relative_eq!(angle, 0.5);
+        assert_relative_eq!(
+            isometry_from_mint(translation, angle),
+            pos,
+            epsilon = 1.0e-6
+        );
+
+        let rot = rotation_to_mint(&pos.rotation);
+        assert_relative_eq!(rot.x.x, 0.5f32.cos());
+        assert_relative_eq!(rot.x.y, 0.5f32.sin());
+        assert_relative_eq!(rotation_from_mint(rot), pos.rotation, epsilon = 1.0e-6);
+
+        let matrix = isometry_to_mint_matrix(&pos);
+        assert_eq!(
+            matrix.z,
+            mint::Vector3 {
+                x: 1.0,
+                y: 2.0,
+                z: 1.0
+            }
+        );
+    }
+
+    #[cfg(feature = "dim3")]
+    #[test]
+    fn isometry_and_rotation_roundtrip() {
+        let pos = Isometry::new(Vector::new(1.0, 2.0, 3.0), Vector::new(0.1, 0.2, 0.3));
+
+        let (translation, rotation) = isometry_to_mint(&pos);
+        assert_eq!(
+            translation,
+            mint::Vector3 {
+                x: 1.0,
+                y: 2.0,
+                z: 3.0
+            }
+        );
+        assert_eq!(rotation.s, pos.rotation.w);
+        assert_relative_eq!(
+            isometry_from_mint(translation, rotation),
+            pos,
+            epsilon = 1.0e-6
+        );
+
+        let rot = rotation_to_mint(&pos.rotation);
+        assert_relative_eq!(rotation_from_mint(rot), pos.rotation, epsilon = 1.0e-6);
+
+        // The quaternion is normalized.
+        let scaled = mint::Quaternion {
+            v: mint::Vector3 {
+                x: rot.v.x * 2.0,
+                y: rot.v.y * 2.0,
+                z: rot.v.z * 2.0,
+            },
+            s: rot.s * 2.0,
+        };
+        assert_relative_eq!(rotation_from_mint(scaled), pos.rotation, epsilon = 1.0e-6);
+
+        let matrix = isometry_to_mint_matrix(&pos);
+        assert_eq!(
+            matrix.w,
+            mint::Vector4 {
+                x: 1.0,
+                y: 2.0,
+                z: 3.0,
+                w: 1.0
+            }
+        );
+    }
+}
@@ -35,9 +35,11 @@ pub struct Counters {
 
 impl Counters {
     /// Create a new set of counters initialized to wero.
+    ///
+    /// The counters are never enabled if the `std` feature is disabled, see [`Counters::enable`].
     pub fn new(enabled: bool) -> Self {
         Counters {
-            enabled,
+            enabled: enabled && cfg!(feature = "std"),
             step_time: Timer::new(),
             custom: Timer::new(),
             stages: StagesCounters::new(),
@@ -48,8 +50,11 @@ impl Counters {
     }
 
     /// Enable all the counters.
+    ///
+    /// This does nothing if the `std` feature is disabled: there is no clock to measure the
+    /// timings so the counters stay disabled instead of reporting zero durations.
     pub fn enable(&mut self) {
-        self.enabled = true;
+        self.enabled = cfg!(feature = "std");
     }
 
     /// Return `true` if the counters are enabled.
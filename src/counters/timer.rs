@@ -24,20 +24,20 @@ impl Timer {
     /// Start the timer.
     pub fn start(&mut self) {
         self.time = 0.0;
-        self.start = Some(instant::now());
+        self.start = Some(now());
     }
 
     /// Pause the timer.
     pub fn pause(&mut self) {
         if let Some(start) = self.start {
-            self.time += instant::now() - start;
+            self.time += now() - start;
         }
         self.start = None;
     }
 
     /// Resume the timer.
     pub fn resume(&mut self) {
-        self.start = Some(instant::now());
+        self.start = Some(now());
     }
 
     /// The measured time between the last `.start()` and `.pause()` calls.
@@ -46,6 +46,16 @@ impl Timer {
     }
 }
 
+#[cfg(feature = "std")]
+fn now() -> f64 {
+    instant::now()
+}
+
+#[cfg(not(feature = "std"))]
+fn now() -> f64 {
+    0.0
+}
+
 impl Display for Timer {
     fn fmt(&self, f: &mut Formatter) -> Result<(), Error> {
         write!(f, "{}s", self.time)
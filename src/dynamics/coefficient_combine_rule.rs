@@ -0,0 +1,62 @@
+/// Rules used to combine two coefficients.
+///
+/// This is used to determine the effective restitution and
+/// friction coefficients for a contact between two colliders.
+/// Each collider has its combination rule of type
+/// `CoefficientCombineRule`. And the rule
+/// actually used is given by `max(first_combine_rule as usize, second_combine_rule as usize)`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde-serialize", derive(Serialize, Deserialize))]
+pub enum CoefficientCombineRule {
+    /// The two coefficients are averaged.
+    #[default]
+    Average = 0,
+    /// The smallest coefficient is chosen.
+    Min,
+    /// The two coefficients are multiplied.
+    Multiply,
+    /// The greatest coefficient is chosen.
+    Max,
+}
+
+impl CoefficientCombineRule {
+    pub(crate) fn combine(coeff1: f32, coeff2: f32, rule1: Self, rule2: Self) -> f32 {
+        let effective_rule = if rule1 as usize > rule2 as usize {
+            rule1
+        } else {
+            rule2
+        };
+
+        match effective_rule {
+            CoefficientCombineRule::Average => (coeff1 + coeff2) / 2.0,
+            CoefficientCombineRule::Min => coeff1.min(coeff2),
+            CoefficientCombineRule::Multiply => coeff1 * coeff2,
+            CoefficientCombineRule::Max => coeff1.max(coeff2),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::CoefficientCombineRule;
+
+    #[test]
+    fn combine_uses_the_rule_with_the_highest_precedence() {
+        use CoefficientCombineRule::*;
+        let rules = [Average, Min, Multiply, Max];
+        let expected = [0.4, 0.2, 0.12, 0.6];
+
+        for (i1, rule1) in rules.iter().enumerate() {
+            for (i2, rule2) in rules.iter().enumerate() {
+                // The result doesn't depend on the order of the colliders.
+                let combined = CoefficientCombineRule::combine(0.2, 0.6, *rule1, *rule2);
+                let swapped = CoefficientCombineRule::combine(0.6, 0.2, *rule2, *rule1);
+                let expected = expected[i1.max(i2)];
+                assert!((combined - expected).abs() < 1.0e-6);
+                assert!((swapped - expected).abs() < 1.0e-6);
+            }
+        }
+
+        assert_eq!(CoefficientCombineRule::default(), Average);
+    }
+}
@@ -0,0 +1,100 @@
+use crate::math::{Rotation, Vector};
+
+/// The friction between a rigid-body and an implicit ground plane, for top-down 2D simulations.
+///
+/// In a top-down game, the ground supporting cars, pucks, or characters is orthogonal to the
+/// simulation plane, so it can't be simulated with colliders. Instead, this friction model
+/// assumes the rigid-body is pushed against the ground by its weight, and applies the
+/// corresponding Coulomb friction: it decelerates the rigid-body by `friction * gravity` until it
+/// stops, independently from its mass. Unlike linear damping, it brings the rigid-body to a
+/// complete stop in finite time, and doesn't slow down fast motions more than slow ones.
+///
+/// The friction coefficients can differ along the local axes of the rigid-body, e.g., to
+/// simulate a car rolling easily along its local `x` axis while its tires grip the ground
+/// laterally.
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde-serialize", derive(Serialize, Deserialize))]
+pub struct GroundFriction {
+    /// The friction coefficient along the local `x` axis of the rigid-body.
+    pub longitudinal: f32,
+    /// The friction coefficient along the local `y` axis of the rigid-body.
+    pub lateral: f32,
+    /// The friction coefficient opposing the rotation of the rigid-body.
+    ///
+    /// The friction torque is computed as if the weight of the rigid-body was applied at its
+    /// radius of gyration.
+    pub angular: f32,
+    /// The magnitude of the gravity pushing the rigid-body against the ground (default: `9.81`).
+    pub gravity: f32,
+}
+
+impl GroundFriction {
+    /// Initializes a ground friction with the same friction coefficient along all directions.
+    pub fn new(friction: f32) -> Self {
+        Self {
+            longitudinal: friction,
+            lateral: friction,
+            angular: friction,
+            gravity: 9.81,
+        }
+    }
+
+    /// Initializes a ground friction with different friction coefficients along the local `x`
+    /// and `y` axes of the rigid-body.
+    ///
+    /// The rotation of the rigid-body is opposed by the lateral friction coefficient.
+    pub fn anisotropic(longitudinal: f32, lateral: f32) -> Self {
+        Self {
+            longitudinal,
+            lateral,
+            angular: lateral,
+            gravity: 9.81,
+        }
+    }
+
+    /// Sets the magnitude of the gravity pushing the rigid-body against the ground.
+    pub fn gravity(mut self, gravity: f32) -> Self {
+        self.gravity = gravity;
+        self
+    }
+
+    /// Applies this friction, during a timestep of length `dt`, to the velocities of a
+    /// rigid-body with the given rotation and mass properties.
+    pub(crate) fn apply(
+        &self,
+        dt: f32,
+        rotation: &Rotation<f32>,
+        inv_mass: f32,
+        inv_inertia_sqrt: f32,
+        linvel: &mut Vector<f32>,
+        angvel: &mut f32,
+    ) {
+        let max_dvel = self.gravity * dt;
+        let local_linvel = rotation.inverse_transform_vector(linvel);
+        let weighted_speed = Vector::new(
+            self.longitudinal * local_linvel.x,
+            self.lateral * local_linvel.y,
+        )
+        .norm();
+
+        if weighted_speed > 0.0 {
+            // The friction force maximizing the dissipation of energy, given the elliptic
+            // friction limit defined by both friction coefficients. This is the usual Coulomb
+            // friction if both coefficients are equal. The velocity components don't change
+            // sign, so the rigid-body stops instead of oscillating.
+            let mut dvel = Vector::new(
+                self.longitudinal * self.longitudinal * local_linvel.x,
+                self.lateral * self.lateral * local_linvel.y,
+            ) * (max_dvel / weighted_speed);
+            dvel.x = dvel.x.min(local_linvel.x.abs()).max(-local_linvel.x.abs());
+            dvel.y = dvel.y.min(local_linvel.y.abs()).max(-local_linvel.y.abs());
+            *linvel -= rotation * dvel;
+        }
+
+        if inv_mass != 0.0 && inv_inertia_sqrt != 0.0 {
+            // The inverse of the radius of gyration is `sqrt(mass / inertia)`.
+            let max_dangvel = self.angular * max_dvel * inv_inertia_sqrt / inv_mass.sqrt();
+            *angvel -= angvel.abs().min(max_dangvel).copysign(*angvel);
+        }
+    }
+}
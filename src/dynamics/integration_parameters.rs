@@ -19,25 +19,48 @@ pub struct IntegrationParameters {
     pub return_after_ccd_substep: bool,
     /// The Error Reduction Parameter in `[0, 1]` is the proportion of
     /// the positional error to be corrected at each time step (default: `0.2`).
+    ///
+    /// This is used by the position-based contact solver. Higher values resolve penetrations
+    /// faster, but may cause jitter on resting bodies.
     pub erp: f32,
     /// The Error Reduction Parameter for joints in `[0, 1]` is the proportion of
     /// the positional error to be corrected at each time step (default: `0.2`).
     pub joint_erp: f32,
+    /// If `false`, the impulses computed at the previous timestep are not re-used to initialize
+    /// the constraints solver (default: `true`).
+    ///
+    /// Disabling warm-starting makes each timestep independent from the previous one, at the cost
+    /// of a slower convergence of the solver.
+    pub warmstart_enabled: bool,
     /// Each cached impulse are multiplied by this coefficient in `[0, 1]`
     /// when they are re-used to initialize the solver (default `1.0`).
     pub warmstart_coeff: f32,
     /// Contacts at points where the involved bodies have a relative
     /// velocity smaller than this threshold wont be affected by the restitution force (default: `1.0`).
     pub restitution_velocity_threshold: f32,
-    /// Amount of penetration the engine wont attempt to correct (default: `0.001m`).
+    /// Amount of penetration the engine wont attempt to correct (default: `0.005m`).
+    ///
+    /// This penetration slop lets resting contacts remain active from one step to the next,
+    /// which reduces jitter. Larger values give more stable stacks but make bodies visibly
+    /// sink into each other. It should be scaled according to the typical size of the
+    /// simulated objects.
     pub allowed_linear_error: f32,
     /// The maximal distance separating two objects that will generate predictive contacts (default: `0.002`).
+    ///
+    /// Colliders may override this distance with `Collider::set_prediction_distance`.
     pub prediction_distance: f32,
     /// Amount of angular drift of joint limits the engine wont
     /// attempt to correct (default: `0.001rad`).
     pub allowed_angular_error: f32,
     /// Maximum linear correction during one step of the non-linear position solver (default: `0.2`).
     pub max_linear_correction: f32,
+    /// Maximum velocity at which penetrations are corrected by the position solver (default: `f32::MAX`).
+    ///
+    /// This limits the distance a contact is corrected by during one step of the non-linear position
+    /// solver to `max_corrective_velocity * dt`, preventing deeply penetrating bodies from being
+    /// pushed apart too violently. Unlike `max_linear_correction`, this limit does not depend
+    /// on the timestep length.
+    pub max_corrective_velocity: f32,
     /// Maximum angular correction during one step of the non-linear position solver (default: `0.2`).
     pub max_angular_correction: f32,
     /// Maximum nonlinear SOR-prox scaling parameter when the constraint
@@ -87,6 +110,9 @@ pub struct IntegrationParameters {
 
 impl IntegrationParameters {
     /// Creates a set of integration parameters with the given values.
+    ///
+    /// The parameters that are not arguments of this constructor are set to their default
+    /// values, and can be modified afterwards.
     pub fn new(
         dt: f32,
         //        multithreading_enabled: bool,
@@ -114,12 +140,14 @@ impl IntegrationParameters {
             //            multithreading_enabled,
             erp,
             joint_erp,
+            warmstart_enabled: true,
             warmstart_coeff,
             restitution_velocity_threshold,
             allowed_linear_error,
             allowed_angular_error,
             max_linear_correction,
             max_angular_correction,
+            max_corrective_velocity: f32::MAX,
             prediction_distance,
             max_stabilization_multiplier,
             max_velocity_iterations,
@@ -152,6 +180,30 @@ impl IntegrationParameters {
         self.inv_dt
     }
 
+    /// The coefficient the cached impulses are multiplied by when they are re-used to initialize
+    /// the solver.
+    ///
+    /// This is `self.warmstart_coeff` if warm-starting is enabled, and zero otherwise.
+    #[inline]
+    pub fn effective_warmstart_coeff(&self) -> f32 {
+        if self.warmstart_enabled {
+            self.warmstart_coeff
+        } else {
+            0.0
+        }
+    }
+
+    /// The maximum distance a contact is corrected by during one step of the non-linear
+    /// position solver.
+    ///
+    /// This is the smallest value between `self.max_linear_correction` and
+    /// `self.max_corrective_velocity * self.dt()`.
+    #[inline]
+    pub fn max_contact_correction(&self) -> f32 {
+        self.max_linear_correction
+            .min(self.max_corrective_velocity * self.dt)
+    }
+
     /// Sets the time-stepping length.
     ///
     /// This automatically recompute `self.inv_dt`.
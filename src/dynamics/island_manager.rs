@@ -0,0 +1,116 @@
+use crate::dynamics::RigidBodyHandle;
+
+#[cfg_attr(feature = "serde-serialize", derive(Serialize, Deserialize))]
+#[derive(Clone)]
+/// The set of active rigid-bodies and their partition into simulation islands.
+///
+/// An island is a group of awake dynamic rigid-bodies interacting with each other
+/// through contacts or joints. Islands are recomputed at each timestep so the island
+/// identifiers are only valid until the next timestep. Islands smaller than
+/// `IntegrationParameters::min_island_size` are merged together.
+///
+/// The islands, and the bodies within each island, are ordered deterministically: two
+/// simulations with the same history end up with the same islands in the same order.
+///
+/// The island manager of a `RigidBodySet` can be accessed with `RigidBodySet::islands`.
+pub struct IslandManager {
+    pub(crate) active_dynamic_set: Vec<RigidBodyHandle>,
+    pub(crate) active_kinematic_set: Vec<RigidBodyHandle>,
+    pub(crate) active_islands: Vec<usize>,
+    pub(crate) activated_islands: Vec<bool>,
+    pub(crate) activated_bodies: Vec<RigidBodyHandle>,
+    pub(crate) deactivated_bodies: Vec<RigidBodyHandle>,
+    pub(crate) active_set_timestamp: u32,
+    #[cfg_attr(feature = "serde-serialize", serde(skip))]
+    pub(crate) can_sleep: Vec<RigidBodyHandle>, // Workspace.
+    #[cfg_attr(feature = "serde-serialize", serde(skip))]
+    pub(crate) stack: Vec<RigidBodyHandle>, // Workspace.
+}
+
+impl IslandManager {
+    /// Creates a new empty island manager.
+    pub fn new() -> Self {
+        Self {
+            active_dynamic_set: Vec::new(),
+            active_kinematic_set: Vec::new(),
+            active_islands: vec![0],
+            activated_islands: Vec::new(),
+            activated_bodies: Vec::new(),
+            deactivated_bodies: Vec::new(),
+            // NOTE: this starts at 1 so that bodies inserted before the first
+            // timestep are detected as newly activated, like any other
+            // body inserted afterwards.
+            active_set_timestamp: 1,
+            can_sleep: Vec::new(),
+            stack: Vec::new(),
+        }
+    }
+
+    /// The number of active islands.
+    pub fn num_active_islands(&self) -> usize {
+        self.active_islands.len() - 1
+    }
+
+    /// The handles of the dynamic rigid-bodies of the `island_id`-th active island.
+    pub fn active_island(&self, island_id: usize) -> &[RigidBodyHandle] {
+        &self.active_dynamic_set[self.active_island_range(island_id)]
+    }
+
+    /// Iterates through all the active islands, each given as the handles of its dynamic rigid-bodies.
+    pub fn active_islands(&self) -> impl ExactSizeIterator<Item = &[RigidBodyHandle]> {
+        (0..self.num_active_islands()).map(move |i| self.active_island(i))
+    }
+
+    /// The handles of all the awake dynamic rigid-bodies.
+    pub fn active_dynamic_bodies(&self) -> &[RigidBodyHandle] {
+        &self.active_dynamic_set
+    }
+
+    /// The handles of all the active kinematic rigid-bodies.
+    pub fn active_kinematic_bodies(&self) -> &[RigidBodyHandle] {
+        &self.active_kinematic_set
+    }
+
+    /// Has the `island_id`-th active island been activated during the last timestep?
+    ///
+    /// An island is activated if it contains at least one rigid-body that was not awake
+    /// during the previous timestep, i.e., a body that has just been woken up, or that has just
+    /// been inserted.
+    pub fn is_island_activated(&self, island_id: usize) -> bool {
+        self.activated_islands[island_id]
+    }
+
+    /// Iterates through the identifiers of the active islands activated during the last timestep.
+    ///
+    /// See `IslandManager::is_island_activated` for details.
+    pub fn activated_islands(&self) -> impl Iterator<Item = usize> + '_ {
+        self.activated_islands
+            .iter()
+            .enumerate()
+            .filter(|(_, activated)| **activated)
+            .map(|(i, _)| i)
+    }
+
+    /// The handles of the dynamic rigid-bodies that were woken up during the last timestep.
+    ///
+    /// These are the awake bodies that were not awake during the previous timestep, including
+    /// the awake bodies that have just been inserted.
+    pub fn activated_bodies(&self) -> &[RigidBodyHandle] {
+        &self.activated_bodies
+    }
+
+    /// The handles of the dynamic rigid-bodies that fell asleep during the last timestep.
+    pub fn deactivated_bodies(&self) -> &[RigidBodyHandle] {
+        &self.deactivated_bodies
+    }
+
+    pub(crate) fn active_island_range(&self, island_id: usize) -> std::ops::Range<usize> {
+        self.active_islands[island_id]..self.active_islands[island_id + 1]
+    }
+}
+
+impl Default for IslandManager {
+    fn default() -> Self {
+        Self::new()
+    }
+}
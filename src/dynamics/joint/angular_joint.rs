@@ -0,0 +1,25 @@
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde-serialize", derive(Serialize, Deserialize))]
+/// A joint that removes the relative rotation between two bodies, but not their relative translations.
+///
+/// The angle of the second body relative to the first body is kept equal to `self.angle`, while
+/// both bodies are free to translate independently, e.g., for a platform kept level as it moves.
+pub struct AngularJoint {
+    /// The angle of the second body relative to the first body, maintained by this joint.
+    pub angle: f32,
+    /// The angular impulse applied by this joint on the first body.
+    ///
+    /// The impulse applied to the second body is given by `-impulse`.
+    pub impulse: f32,
+}
+
+impl AngularJoint {
+    /// Creates a new angular joint keeping the angle of the second body relative to the
+    /// first body equal to `angle`.
+    pub fn new(angle: f32) -> Self {
+        Self {
+            angle,
+            impulse: 0.0,
+        }
+    }
+}
@@ -1,4 +1,5 @@
 use crate::math::{Point, Vector};
+use crate::utils;
 
 #[derive(Copy, Clone)]
 #[cfg_attr(feature = "serde-serialize", derive(Serialize, Deserialize))]
@@ -11,6 +12,8 @@ pub struct BallJoint {
     /// The impulse applied by this joint on the first body.
     ///
     /// The impulse applied to the second body is given by `-impulse`.
+    /// This is the world-space linear impulse applied at the anchor. See `Self::force` for the
+    /// corresponding force.
     pub impulse: Vector<f32>,
 }
 
@@ -31,4 +34,52 @@ impl BallJoint {
             impulse,
         }
     }
+
+    /// The world-space force applied by this joint at the anchor of the first body during the
+    /// last timestep, of length `dt`.
+    ///
+    /// The force applied to the second body is given by `-force`.
+    pub fn force(&self, dt: f32) -> Vector<f32> {
+        self.impulse * utils::inv(dt)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::BallJoint;
+    use crate::dynamics::RigidBodyBuilder;
+    use crate::geometry::ColliderBuilder;
+    use crate::math::{Point, Vector};
+    use crate::pipeline::TestWorld;
+
+    #[test]
+    fn force_balances_a_static_load() {
+        let mut world = TestWorld::falling();
+        let dt = world.params.dt();
+
+        // A ball hanging from a static body.
+        let ground = world.bodies.insert(RigidBodyBuilder::new_static().build());
+        #[cfg(feature = "dim2")]
+        let rb = RigidBodyBuilder::new_dynamic().translation(0.0, -1.0);
+        #[cfg(feature = "dim3")]
+        let rb = RigidBodyBuilder::new_dynamic().translation(0.0, -1.0, 0.0);
+        let rb = rb.build();
+        let (ball, _) = world.insert(rb, ColliderBuilder::ball(0.5).build());
+        let joint = BallJoint::new(Point::origin(), Point::from(Vector::y()));
+        let joint = world.joints.insert(&mut world.bodies, ground, ball, joint);
+
+        world.steps(100);
+
+        // The joint pulls the static body down with the weight of the ball.
+        let weight = world.gravity * world.bodies[ball].mass();
+        let force = world
+            .joints
+            .get(joint)
+            .unwrap()
+            .params
+            .as_ball_joint()
+            .unwrap()
+            .force(dt);
+        assert!((force - weight).norm() < weight.norm() * 1.0e-2);
+    }
 }
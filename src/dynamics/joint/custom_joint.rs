@@ -0,0 +1,217 @@
+use crate::dynamics::solver::DeltaVel;
+use crate::dynamics::{IntegrationParameters, RigidBody};
+use crate::math::{AngVector, AngularInertia, Vector};
+use crate::utils::{WAngularInertia, WDot};
+#[cfg(feature = "dim2")]
+use num::Zero;
+use std::sync::Arc;
+
+/// A scalar velocity constraint between the two bodies attached to a custom joint.
+///
+/// At each velocity iteration, the solver applies an impulse along the jacobian of this row so
+/// that `linear1.dot(linvel1) + angular1.dot(angvel1) + linear2.dot(linvel2) + angular2.dot(angvel2)`
+/// reaches `target_velocity`, while keeping the total impulse applied by this row during the
+/// timestep between `min_impulse` and `max_impulse`.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct CustomConstraintRow {
+    /// The linear part of the jacobian of this row for the first body.
+    pub linear1: Vector<f32>,
+    /// The angular part of the jacobian of this row for the first body.
+    pub angular1: AngVector<f32>,
+    /// The linear part of the jacobian of this row for the second body.
+    pub linear2: Vector<f32>,
+    /// The angular part of the jacobian of this row for the second body.
+    pub angular2: AngVector<f32>,
+    /// The velocity this row attempts to reach (default: `0.0`).
+    ///
+    /// Set it to a multiple of the position error divided by the timestep length to also
+    /// correct the drift of the constraint.
+    pub target_velocity: f32,
+    /// The lower bound of the impulse applied by this row (default: `-f32::MAX`).
+    pub min_impulse: f32,
+    /// The upper bound of the impulse applied by this row (default: `f32::MAX`).
+    pub max_impulse: f32,
+}
+
+impl CustomConstraintRow {
+    /// Initializes a bilateral row with the given jacobian, and a target velocity of zero.
+    pub fn new(
+        linear1: Vector<f32>,
+        angular1: AngVector<f32>,
+        linear2: Vector<f32>,
+        angular2: AngVector<f32>,
+    ) -> Self {
+        Self {
+            linear1,
+            angular1,
+            linear2,
+            angular2,
+            target_velocity: 0.0,
+            min_impulse: -f32::MAX,
+            max_impulse: f32::MAX,
+        }
+    }
+
+    /// Sets the velocity this row attempts to reach.
+    pub fn target_velocity(mut self, target_velocity: f32) -> Self {
+        self.target_velocity = target_velocity;
+        self
+    }
+
+    /// Sets the bounds of the impulse applied by this row, e.g., `(0.0, f32::MAX)` for a row
+    /// that can only push, or `(-max_force * dt, max_force * dt)` for a motor with a limited
+    /// force.
+    pub fn impulse_limits(mut self, min_impulse: f32, max_impulse: f32) -> Self {
+        self.min_impulse = min_impulse;
+        self.max_impulse = max_impulse;
+        self
+    }
+}
+
+/// The velocity of a body attached to a custom joint, as seen by the solver while it iterates.
+///
+/// A non-dynamic body has a zero inverse mass and inertia, so impulses don't affect it.
+#[derive(Copy, Clone, Debug)]
+pub struct CustomConstraintBody {
+    linvel: Vector<f32>,
+    angvel: AngVector<f32>,
+    inv_mass: f32,
+    world_inv_inertia_sqrt: AngularInertia<f32>,
+    pub(crate) delta: DeltaVel<f32>,
+}
+
+impl CustomConstraintBody {
+    pub(crate) fn new(rb: &RigidBody) -> Self {
+        let (inv_mass, world_inv_inertia_sqrt) = if rb.is_dynamic() {
+            (rb.mass_properties.inv_mass, rb.world_inv_inertia_sqrt)
+        } else {
+            (0.0, AngularInertia::zero())
+        };
+
+        Self {
+            linvel: rb.linvel,
+            angvel: rb.angvel,
+            inv_mass,
+            world_inv_inertia_sqrt,
+            delta: DeltaVel::zero(),
+        }
+    }
+
+    /// The current linear velocity of this body.
+    pub fn linvel(&self) -> Vector<f32> {
+        self.linvel + self.delta.linear
+    }
+
+    /// The current angular velocity of this body.
+    pub fn angvel(&self) -> AngVector<f32> {
+        self.angvel
+            + self
+                .world_inv_inertia_sqrt
+                .transform_vector(self.delta.angular)
+    }
+
+    /// The inverse mass of this body.
+    pub fn inv_mass(&self) -> f32 {
+        self.inv_mass
+    }
+
+    /// The square root of the inverse angular inertia of this body, expressed in world-space.
+    pub fn world_inv_inertia_sqrt(&self) -> &AngularInertia<f32> {
+        &self.world_inv_inertia_sqrt
+    }
+
+    /// Applies a linear and an angular impulse at the center-of-mass of this body.
+    pub fn apply_impulse(&mut self, linear: Vector<f32>, angular: AngVector<f32>) {
+        self.delta.linear += linear * self.inv_mass;
+        self.delta.angular += self.world_inv_inertia_sqrt.transform_vector(angular);
+    }
+
+    /// The velocity of this body along the given jacobian, and the jacobian of the angular
+    /// part premultiplied by the square root of the inverse inertia.
+    pub(crate) fn jacobian_velocity(
+        &self,
+        linear: &Vector<f32>,
+        angular: &AngVector<f32>,
+    ) -> (f32, AngVector<f32>) {
+        (
+            linear.dot(&self.linvel) + angular.gdot(self.angvel),
+            self.world_inv_inertia_sqrt.transform_vector(*angular),
+        )
+    }
+}
+
+/// A user-defined constraint, solved alongside contacts and joints by a `CustomJoint`.
+///
+/// A custom constraint can be expressed in two ways, possibly combined:
+/// - as jacobian rows, returned by `rows` at the beginning of each timestep, solved with
+///   warmstarting and impulse limits like the built-in joints;
+/// - as an arbitrary velocity correction applied by `solve_velocities` at each velocity
+///   iteration.
+///
+/// Custom constraints have no position correction: use the `target_velocity` of their rows to
+/// correct their drift.
+pub trait CustomConstraint: Send + Sync {
+    /// Pushes the rows of this constraint into `rows`, given the state of the attached bodies at
+    /// the beginning of the timestep.
+    ///
+    /// The impulses applied by the rows are warmstarted from the previous timestep as long as
+    /// the number of rows doesn't change.
+    fn rows(
+        &self,
+        _params: &IntegrationParameters,
+        _rb1: &RigidBody,
+        _rb2: &RigidBody,
+        _rows: &mut Vec<CustomConstraintRow>,
+    ) {
+    }
+
+    /// Corrects the velocities of the attached bodies, by applying impulses to them.
+    ///
+    /// This is called at each velocity iteration, after the rows of this constraint are solved.
+    fn solve_velocities(
+        &self,
+        _dt: f32,
+        _body1: &mut CustomConstraintBody,
+        _body2: &mut CustomConstraintBody,
+    ) {
+    }
+}
+
+#[derive(Clone)]
+#[cfg_attr(feature = "serde-serialize", derive(Serialize, Deserialize))]
+/// A joint solving a user-defined constraint.
+///
+/// This allows the simulation of custom mechanics, like look-at constraints or anti-roll bars,
+/// that benefit from everything joints support: they are solved by the same iterations as the
+/// contacts and the other joints, they keep the attached bodies in the same island, and they are
+/// removed with the attached bodies.
+///
+/// The user-defined constraint is not serialized: a deserialized custom joint has no effect
+/// until its constraint is set again with `set_constraint`.
+pub struct CustomJoint {
+    #[cfg_attr(feature = "serde-serialize", serde(skip))]
+    constraint: Option<Arc<dyn CustomConstraint>>,
+    /// The impulses applied by each row of this joint during the last timestep.
+    pub impulses: Vec<f32>,
+}
+
+impl CustomJoint {
+    /// Creates a new joint solving the given user-defined constraint.
+    pub fn new(constraint: Arc<dyn CustomConstraint>) -> Self {
+        Self {
+            constraint: Some(constraint),
+            impulses: Vec::new(),
+        }
+    }
+
+    /// The user-defined constraint solved by this joint.
+    pub fn constraint(&self) -> Option<&Arc<dyn CustomConstraint>> {
+        self.constraint.as_ref()
+    }
+
+    /// Sets the user-defined constraint solved by this joint, and resets its impulses.
+    pub fn set_constraint(&mut self, constraint: Arc<dyn CustomConstraint>) {
+        self.constraint = Some(constraint);
+        self.impulses.clear();
+    }
+}
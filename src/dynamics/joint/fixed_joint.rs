@@ -1,4 +1,5 @@
-use crate::math::{Isometry, SpacialVector};
+use crate::math::{AngVector, Isometry, SpacialVector, Vector};
+use crate::utils;
 
 #[derive(Copy, Clone)]
 #[cfg_attr(feature = "serde-serialize", derive(Serialize, Deserialize))]
@@ -15,9 +16,11 @@ pub struct FixedJoint {
     /// The impulse applied to the first body affected by this joint.
     ///
     /// The impulse applied to the second body affected by this joint is given by `-impulse`.
-    /// This combines both linear and angular impulses:
+    /// This combines both world-space linear and angular impulses:
     /// - In 2D, `impulse.xy()` gives the linear impulse, and `impulse.z` the angular impulse.
     /// - In 3D, `impulse.xyz()` gives the linear impulse, and `(impulse[3], impulse[4], impulse[5])` the angular impulse.
+    ///
+    /// See `Self::force` and `Self::torque` for the corresponding force and torque.
     pub impulse: SpacialVector<f32>,
 }
 
@@ -30,4 +33,73 @@ impl FixedJoint {
             impulse: SpacialVector::zeros(),
         }
     }
+
+    /// The world-space force applied by this joint at the anchor of the first body during the
+    /// last timestep, of length `dt`.
+    ///
+    /// The force applied to the second body is given by `-force`.
+    pub fn force(&self, dt: f32) -> Vector<f32> {
+        #[cfg(feature = "dim2")]
+        let impulse = self.impulse.xy();
+        #[cfg(feature = "dim3")]
+        let impulse = self.impulse.xyz();
+        impulse * utils::inv(dt)
+    }
+
+    /// The world-space torque applied by this joint on the first body during the last timestep,
+    /// of length `dt`, in addition to the torque resulting from `self.force(dt)`.
+    ///
+    /// The torque applied to the second body is given by `-torque`.
+    pub fn torque(&self, dt: f32) -> AngVector<f32> {
+        #[cfg(feature = "dim2")]
+        let impulse = self.impulse.z;
+        #[cfg(feature = "dim3")]
+        let impulse = Vector::new(self.impulse[3], self.impulse[4], self.impulse[5]);
+        impulse * utils::inv(dt)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::FixedJoint;
+    use crate::dynamics::RigidBodyBuilder;
+    use crate::geometry::ColliderBuilder;
+    use crate::math::{Isometry, Vector};
+    use crate::pipeline::TestWorld;
+
+    #[test]
+    fn force_and_torque_balance_a_static_load() {
+        let mut world = TestWorld::falling();
+        let dt = world.params.dt();
+
+        // A ball welded at the end of a horizontal cantilever of length 2, attached to a wall.
+        let wall = world.bodies.insert(RigidBodyBuilder::new_static().build());
+        let offset = Vector::x() * 2.0;
+        let rb = RigidBodyBuilder::new_dynamic()
+            .position(Isometry::new(offset, na::zero()))
+            .build();
+        let (ball, _) = world.insert(rb, ColliderBuilder::ball(0.5).build());
+        let joint = FixedJoint::new(Isometry::identity(), Isometry::new(-offset, na::zero()));
+        let joint = world.joints.insert(&mut world.bodies, wall, ball, joint);
+
+        world.steps(100);
+
+        // The joint pulls the wall down with the weight of the ball, and twists it by the
+        // moment of this weight around the anchor.
+        let weight = world.gravity * world.bodies[ball].mass();
+        let params = &world.joints.get(joint).unwrap().params;
+        let fixed = params.as_fixed_joint().unwrap();
+        assert!((fixed.force(dt) - weight).norm() < weight.norm() * 1.0e-2);
+
+        let moment = offset.x * weight.y;
+        #[cfg(feature = "dim2")]
+        let torque = fixed.torque(dt);
+        #[cfg(feature = "dim3")]
+        let torque = {
+            let torque = fixed.torque(dt);
+            assert!(torque.xy().norm() < moment.abs() * 1.0e-2);
+            torque.z
+        };
+        assert!((torque - moment).abs() < moment.abs() * 1.0e-2);
+    }
 }
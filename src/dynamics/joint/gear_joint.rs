@@ -0,0 +1,53 @@
+use crate::math::Vector;
+use na::Unit;
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde-serialize", derive(Serialize, Deserialize))]
+/// The coordinate of a body coupled by a gear joint.
+pub enum GearCoordinate {
+    /// The rotation angle of the body, typically attached to its frame by a ball joint
+    /// (which is a revolute joint in 2D).
+    Angle,
+    /// The translation of the body along the given axis, expressed in the local space of the body.
+    ///
+    /// The body is typically attached to its frame by a prismatic joint along this axis.
+    Translation(Unit<Vector<f32>>),
+}
+
+#[derive(Copy, Clone)]
+#[cfg_attr(feature = "serde-serialize", derive(Serialize, Deserialize))]
+/// A joint coupling the motions of two bodies driven by other joints, e.g., two meshing gears,
+/// or a rack and a pinion.
+///
+/// The gear joint enforces `coordinate1 + ratio * coordinate2 = constant`, where each coordinate
+/// is either the angle or the translation along an axis of the corresponding body. The other
+/// bodies attached to these joints (e.g. the frame of a clockwork) are assumed to be static or
+/// kinematic. The coupling is only enforced on the velocities, so the relative phase of both
+/// bodies may drift slightly over time.
+pub struct GearJoint {
+    /// The coordinate of the first body coupled by this joint.
+    pub coordinate1: GearCoordinate,
+    /// The coordinate of the second body coupled by this joint.
+    pub coordinate2: GearCoordinate,
+    /// The gear ratio.
+    ///
+    /// With two angular coordinates, a positive ratio makes both bodies rotate in opposite
+    /// directions. For two meshing gears with radii `r1` and `r2`, the ratio is `r2 / r1`.
+    pub ratio: f32,
+    /// The impulse applied by this joint on the first body along its coordinate.
+    ///
+    /// The impulse applied to the second body along its coordinate is given by `ratio * impulse`.
+    pub impulse: f32,
+}
+
+impl GearJoint {
+    /// Creates a new gear joint coupling the given coordinates of two bodies with the given ratio.
+    pub fn new(coordinate1: GearCoordinate, coordinate2: GearCoordinate, ratio: f32) -> Self {
+        Self {
+            coordinate1,
+            coordinate2,
+            ratio,
+            impulse: 0.0,
+        }
+    }
+}
@@ -1,8 +1,12 @@
 #[cfg(feature = "dim3")]
 use crate::dynamics::RevoluteJoint;
-use crate::dynamics::{BallJoint, FixedJoint, JointHandle, PrismaticJoint, RigidBodyHandle};
+#[cfg(feature = "dim2")]
+use crate::dynamics::{AngularJoint, GearJoint, LinearJoint};
+use crate::dynamics::{
+    BallJoint, CustomJoint, FixedJoint, JointHandle, PrismaticJoint, RigidBodyHandle, SpringJoint,
+};
 
-#[derive(Copy, Clone)]
+#[derive(Clone)]
 #[cfg_attr(feature = "serde-serialize", derive(Serialize, Deserialize))]
 /// An enum grouping all possible types of joints.
 pub enum JointParams {
@@ -17,6 +21,19 @@ pub enum JointParams {
     /// A revolute joint that removes all degrees of degrees of freedom between the affected
     /// bodies except for the translation along one axis.
     RevoluteJoint(RevoluteJoint),
+    /// A linear spring-damper pulling two points of the affected bodies toward a rest distance.
+    SpringJoint(SpringJoint),
+    #[cfg(feature = "dim2")]
+    /// A gear joint coupling the rotations and translations of the affected bodies.
+    GearJoint(GearJoint),
+    #[cfg(feature = "dim2")]
+    /// A joint that removes the relative rotation of the affected bodies, but not their relative translations.
+    AngularJoint(AngularJoint),
+    #[cfg(feature = "dim2")]
+    /// A joint that removes the relative translation of the affected bodies, but not their relative rotation.
+    LinearJoint(LinearJoint),
+    /// A joint solving a user-defined constraint.
+    CustomJoint(CustomJoint),
 }
 
 impl JointParams {
@@ -28,6 +45,36 @@ impl JointParams {
             JointParams::PrismaticJoint(_) => 2,
             #[cfg(feature = "dim3")]
             JointParams::RevoluteJoint(_) => 3,
+            JointParams::SpringJoint(_) => 4,
+            #[cfg(feature = "dim2")]
+            JointParams::GearJoint(_) => 5,
+            #[cfg(feature = "dim2")]
+            JointParams::AngularJoint(_) => 6,
+            #[cfg(feature = "dim2")]
+            JointParams::LinearJoint(_) => 7,
+            JointParams::CustomJoint(_) => 8,
+        }
+    }
+
+    /// Can this joint be solved with SIMD constraints?
+    ///
+    /// This is `false` for springs, gears, angular, linear and custom joints, and for joints with an
+    /// enabled motor.
+    #[cfg(feature = "simd-is-enabled")]
+    pub(crate) fn supports_simd_constraints(&self) -> bool {
+        match self {
+            JointParams::PrismaticJoint(joint) => !joint.is_motor_enabled(),
+            #[cfg(feature = "dim3")]
+            JointParams::RevoluteJoint(joint) => !joint.is_motor_enabled(),
+            JointParams::SpringJoint(_) => false,
+            #[cfg(feature = "dim2")]
+            JointParams::GearJoint(_) => false,
+            #[cfg(feature = "dim2")]
+            JointParams::AngularJoint(_) => false,
+            #[cfg(feature = "dim2")]
+            JointParams::LinearJoint(_) => false,
+            JointParams::CustomJoint(_) => false,
+            _ => true,
         }
     }
 
@@ -67,6 +114,54 @@ impl JointParams {
             None
         }
     }
+
+    /// Gets a reference to the underlying spring joint, if `self` is one.
+    pub fn as_spring_joint(&self) -> Option<&SpringJoint> {
+        if let JointParams::SpringJoint(j) = self {
+            Some(j)
+        } else {
+            None
+        }
+    }
+
+    /// Gets a reference to the underlying gear joint, if `self` is one.
+    #[cfg(feature = "dim2")]
+    pub fn as_gear_joint(&self) -> Option<&GearJoint> {
+        if let JointParams::GearJoint(j) = self {
+            Some(j)
+        } else {
+            None
+        }
+    }
+
+    /// Gets a reference to the underlying angular joint, if `self` is one.
+    #[cfg(feature = "dim2")]
+    pub fn as_angular_joint(&self) -> Option<&AngularJoint> {
+        if let JointParams::AngularJoint(j) = self {
+            Some(j)
+        } else {
+            None
+        }
+    }
+
+    /// Gets a reference to the underlying linear joint, if `self` is one.
+    #[cfg(feature = "dim2")]
+    pub fn as_linear_joint(&self) -> Option<&LinearJoint> {
+        if let JointParams::LinearJoint(j) = self {
+            Some(j)
+        } else {
+            None
+        }
+    }
+
+    /// Gets a reference to the underlying custom joint, if `self` is one.
+    pub fn as_custom_joint(&self) -> Option<&CustomJoint> {
+        if let JointParams::CustomJoint(j) = self {
+            Some(j)
+        } else {
+            None
+        }
+    }
 }
 
 impl From<BallJoint> for JointParams {
@@ -94,6 +189,39 @@ impl From<PrismaticJoint> for JointParams {
     }
 }
 
+impl From<SpringJoint> for JointParams {
+    fn from(j: SpringJoint) -> Self {
+        JointParams::SpringJoint(j)
+    }
+}
+
+#[cfg(feature = "dim2")]
+impl From<GearJoint> for JointParams {
+    fn from(j: GearJoint) -> Self {
+        JointParams::GearJoint(j)
+    }
+}
+
+#[cfg(feature = "dim2")]
+impl From<AngularJoint> for JointParams {
+    fn from(j: AngularJoint) -> Self {
+        JointParams::AngularJoint(j)
+    }
+}
+
+#[cfg(feature = "dim2")]
+impl From<LinearJoint> for JointParams {
+    fn from(j: LinearJoint) -> Self {
+        JointParams::LinearJoint(j)
+    }
+}
+
+impl From<CustomJoint> for JointParams {
+    fn from(j: CustomJoint) -> Self {
+        JointParams::CustomJoint(j)
+    }
+}
+
 #[cfg_attr(feature = "serde-serialize", derive(Serialize, Deserialize))]
 #[derive(Clone)]
 /// A joint attached to two bodies.
@@ -110,4 +238,21 @@ pub struct Joint {
     pub(crate) position_constraint_index: usize,
     /// The joint geometric parameters and impulse.
     pub params: JointParams,
+    /// Whether contacts are computed between the two bodies attached to this joint (default: `false`).
+    ///
+    /// If the two bodies are attached by several joints, their contacts are disabled as soon as
+    /// one of these joints has `contacts_enabled` set to `false`.
+    pub contacts_enabled: bool,
+    /// The number of velocity iterations, in addition to `IntegrationParameters::max_velocity_iterations`,
+    /// performed to solve the joints of the island containing this joint (default: `0`).
+    ///
+    /// All the joints of an island are solved with the largest number of extra iterations among them.
+    /// These extra iterations only solve joints, not contacts.
+    pub extra_velocity_iterations: usize,
+    /// The number of position iterations, in addition to `IntegrationParameters::max_position_iterations`,
+    /// performed to solve the joints of the island containing this joint (default: `0`).
+    ///
+    /// All the joints of an island are solved with the largest number of extra iterations among them.
+    /// These extra iterations only solve joints, not contacts.
+    pub extra_position_iterations: usize,
 }
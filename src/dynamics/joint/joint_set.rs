@@ -2,14 +2,19 @@ use super::Joint;
 use crate::geometry::{InteractionGraph, RigidBodyGraphIndex, TemporaryInteractionIndex};
 
 use crate::data::arena::{Arena, Index};
-use crate::dynamics::{JointParams, RigidBodyHandle, RigidBodySet};
+use crate::data::graph::Direction;
+use crate::dynamics::{JointParams, RigidBody, RigidBodyHandle, RigidBodySet};
 
 /// The unique identifier of a joint added to the joint set.
+///
+/// Handles are generational: the handle of a removed joint is never valid again, even if
+/// its storage slot is reused by another joint.
 pub type JointHandle = Index;
 pub(crate) type JointIndex = usize;
 pub(crate) type JointGraphEdge = crate::data::graph::Edge<Joint>;
 
 #[cfg_attr(feature = "serde-serialize", derive(Serialize, Deserialize))]
+#[derive(Clone)]
 /// A set of joints that can be handled by a physics `World`.
 pub struct JointSet {
     joint_ids: Arena<TemporaryInteractionIndex>, // Map joint handles to edge ids on the graph.
@@ -25,6 +30,24 @@ impl JointSet {
         }
     }
 
+    /// Creates a new empty set of joints, able to hold `capacity` joints without reallocating.
+    pub fn with_capacity(capacity: usize) -> Self {
+        let mut result = Self::new();
+        result.joint_ids = Arena::with_capacity(capacity);
+        result.joint_graph.graph.edges.reserve(capacity);
+        result
+    }
+
+    /// Reserves enough capacity for inserting at least `additional` more joints
+    /// into this set without reallocating.
+    pub fn reserve(&mut self, additional: usize) {
+        let available = self.joint_ids.capacity() - self.joint_ids.len();
+        if additional > available {
+            self.joint_ids.reserve(additional - available);
+        }
+        self.joint_graph.graph.edges.reserve(additional);
+    }
+
     /// An always-invalid joint handle.
     pub fn invalid_handle() -> JointHandle {
         JointHandle::from_raw_parts(crate::INVALID_USIZE, crate::INVALID_U64)
@@ -51,6 +74,15 @@ impl JointSet {
         self.joint_graph.graph.edge_weight(*id)
     }
 
+    /// Gets a mutable reference to the joint with the given handle.
+    ///
+    /// The bodies attached to this joint are not woken up automatically. Use
+    /// `RigidBodySet::wake_up` if the modification of the joint should affect sleeping bodies.
+    pub fn get_mut(&mut self, handle: JointHandle) -> Option<&mut Joint> {
+        let id = self.joint_ids.get(handle)?;
+        self.joint_graph.graph.edge_weight_mut(*id)
+    }
+
     /// Gets the joint with the given handle without a known generation.
     ///
     /// This is useful when you know you want the joint at position `i` but
@@ -92,7 +124,6 @@ impl JointSet {
     //     //     .map(|e| &mut e.weight)
     // }
 
-    #[cfg(not(feature = "parallel"))]
     pub(crate) fn joints_mut(&mut self) -> &mut [JointGraphEdge] {
         &mut self.joint_graph.graph.edges[..]
     }
@@ -123,6 +154,9 @@ impl JointSet {
             #[cfg(feature = "parallel")]
             position_constraint_index: 0,
             params: joint_params.into(),
+            contacts_enabled: false,
+            extra_velocity_iterations: 0,
+            extra_position_iterations: 0,
         };
 
         let (rb1, rb2) = bodies.get2_mut_internal(joint.body1, joint.body2);
@@ -149,6 +183,34 @@ impl JointSet {
         handle
     }
 
+    /// Are contacts allowed between the two given bodies?
+    ///
+    /// This is `false` if the bodies are attached by at least one joint with contacts disabled.
+    pub(crate) fn contacts_enabled_between(&self, rb1: &RigidBody, rb2: &RigidBody) -> bool {
+        let (id1, id2) = (rb1.joint_graph_index, rb2.joint_graph_index);
+
+        if !InteractionGraph::<Joint>::is_graph_index_valid(id1)
+            || !InteractionGraph::<Joint>::is_graph_index_valid(id2)
+        {
+            return true;
+        }
+
+        let graph = &self.joint_graph.graph;
+        for dir in [Direction::Outgoing, Direction::Incoming].iter() {
+            for edge in graph.edges_directed(id1, *dir) {
+                if !edge.weight().contacts_enabled {
+                    if let Some((src, dst)) = graph.edge_endpoints(edge.id()) {
+                        if src == id2 || dst == id2 {
+                            return false;
+                        }
+                    }
+                }
+            }
+        }
+
+        true
+    }
+
     /// Retrieve all the joints happening between two active bodies.
     // NOTE: this is very similar to the code from NarrowPhase::select_active_interactions.
     pub(crate) fn select_active_interactions(
@@ -167,6 +229,8 @@ impl JointSet {
             let rb2 = &bodies[joint.body2];
 
             if (rb1.is_dynamic() || rb2.is_dynamic())
+                && rb1.is_enabled()
+                && rb2.is_enabled()
                 && (!rb1.is_dynamic() || !rb1.is_sleeping())
                 && (!rb2.is_dynamic() || !rb2.is_sleeping())
             {
@@ -0,0 +1,29 @@
+use crate::math::Vector;
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde-serialize", derive(Serialize, Deserialize))]
+/// A joint that removes the relative translation between two bodies, but not their relative rotation.
+///
+/// The world-space offset between the centers of mass of both bodies is kept equal to
+/// `self.offset`, while both bodies are free to rotate independently, e.g., for a wheel
+/// spinning freely while carried by a sliding cart.
+pub struct LinearJoint {
+    /// The world-space offset from the center of mass of the first body to the center of mass
+    /// of the second body, maintained by this joint.
+    pub offset: Vector<f32>,
+    /// The world-space linear impulse applied by this joint on the first body.
+    ///
+    /// The impulse applied to the second body is given by `-impulse`.
+    pub impulse: Vector<f32>,
+}
+
+impl LinearJoint {
+    /// Creates a new linear joint keeping the center of mass of the second body at the world-space
+    /// `offset` from the center of mass of the first body.
+    pub fn new(offset: Vector<f32>) -> Self {
+        Self {
+            offset,
+            impulse: Vector::zeros(),
+        }
+    }
+}
@@ -1,16 +1,34 @@
+#[cfg(feature = "dim2")]
+pub use self::angular_joint::AngularJoint;
 pub use self::ball_joint::BallJoint;
+pub use self::custom_joint::{
+    CustomConstraint, CustomConstraintBody, CustomConstraintRow, CustomJoint,
+};
 pub use self::fixed_joint::FixedJoint;
+#[cfg(feature = "dim2")]
+pub use self::gear_joint::{GearCoordinate, GearJoint};
 pub use self::joint::{Joint, JointParams};
 pub(crate) use self::joint_set::{JointGraphEdge, JointIndex};
 pub use self::joint_set::{JointHandle, JointSet};
+#[cfg(feature = "dim2")]
+pub use self::linear_joint::LinearJoint;
 pub use self::prismatic_joint::PrismaticJoint;
 #[cfg(feature = "dim3")]
 pub use self::revolute_joint::RevoluteJoint;
+pub use self::spring_joint::SpringJoint;
 
+#[cfg(feature = "dim2")]
+mod angular_joint;
 mod ball_joint;
+mod custom_joint;
 mod fixed_joint;
+#[cfg(feature = "dim2")]
+mod gear_joint;
 mod joint;
 mod joint_set;
+#[cfg(feature = "dim2")]
+mod linear_joint;
 mod prismatic_joint;
 #[cfg(feature = "dim3")]
 mod revolute_joint;
+mod spring_joint;
@@ -1,5 +1,6 @@
-use crate::math::{Isometry, Point, Vector, DIM};
-use crate::utils::WBasis;
+use crate::dynamics::RigidBody;
+use crate::math::{AngVector, Isometry, Point, Vector, DIM};
+use crate::utils::{self, WBasis, WCross};
 use na::Unit;
 #[cfg(feature = "dim2")]
 use na::Vector2;
@@ -21,11 +22,19 @@ pub struct PrismaticJoint {
     /// The impulse applied by this joint on the first body.
     ///
     /// The impulse applied to the second body is given by `-impulse`.
+    /// The first two components are the linear impulses applied at the anchor along the basis
+    /// orthonormal to the joint axis, once transformed to world-space by the position of the first
+    /// body. The last three are the world-space angular impulse. Dividing this impulse by the
+    /// timestep length gives the force and torque applied by the joint during the last timestep.
     #[cfg(feature = "dim3")]
     pub impulse: Vector5<f32>,
     /// The impulse applied by this joint on the first body.
     ///
     /// The impulse applied to the second body is given by `-impulse`.
+    /// The first component is the linear impulse applied at the anchor along the direction
+    /// orthogonal to the joint axis, once transformed to world-space by the position of the first
+    /// body. The second is the angular impulse. Dividing this impulse by the timestep length gives
+    /// the force and torque applied by the joint during the last timestep.
     #[cfg(feature = "dim2")]
     pub impulse: Vector2<f32>,
     /// Whether or not this joint should enforce translational limits along its axis.
@@ -36,10 +45,28 @@ pub struct PrismaticJoint {
     ///
     /// The impulse applied to the second body is given by `-impulse`.
     pub limits_impulse: f32,
-    // pub motor_enabled: bool,
-    // pub target_motor_vel: f32,
-    // pub max_motor_impulse: f32,
-    // pub motor_impulse: f32,
+    /// The index in `self.limits` of the limit enforced during the last timestep, if any.
+    pub(crate) active_limit: Option<usize>,
+    /// The active limit reported by the last joint limit event.
+    pub(crate) reported_limit: Option<usize>,
+    /// The target relative translation along this joint's axis the motor tries to reach.
+    pub motor_target_pos: f32,
+    /// The target relative velocity along this joint's axis the motor tries to reach.
+    pub motor_target_vel: f32,
+    /// The motor's stiffness, i.e., the force applied per unit of distance to `motor_target_pos`.
+    ///
+    /// The motor is disabled if both its stiffness and damping are zero.
+    pub motor_stiffness: f32,
+    /// The motor's damping, i.e., the force applied per unit of difference with `motor_target_vel`.
+    ///
+    /// The motor is disabled if both its stiffness and damping are zero.
+    pub motor_damping: f32,
+    /// The maximal impulse the motor can apply along this joint's axis at each timestep.
+    pub motor_max_impulse: f32,
+    /// The impulse applied by the motor on the first body.
+    ///
+    /// The impulse applied to the second body is given by `-motor_impulse`.
+    pub motor_impulse: f32,
 }
 
 impl PrismaticJoint {
@@ -63,10 +90,14 @@ impl PrismaticJoint {
             limits_enabled: false,
             limits: [-f32::MAX, f32::MAX],
             limits_impulse: 0.0,
-            // motor_enabled: false,
-            // target_motor_vel: 0.0,
-            // max_motor_impulse: f32::MAX,
-            // motor_impulse: 0.0,
+            active_limit: None,
+            reported_limit: None,
+            motor_target_pos: 0.0,
+            motor_target_vel: 0.0,
+            motor_stiffness: 0.0,
+            motor_damping: 0.0,
+            motor_max_impulse: f32::MAX,
+            motor_impulse: 0.0,
         }
     }
 
@@ -118,10 +149,14 @@ impl PrismaticJoint {
             limits_enabled: false,
             limits: [-f32::MAX, f32::MAX],
             limits_impulse: 0.0,
-            // motor_enabled: false,
-            // target_motor_vel: 0.0,
-            // max_motor_impulse: f32::MAX,
-            // motor_impulse: 0.0,
+            active_limit: None,
+            reported_limit: None,
+            motor_target_pos: 0.0,
+            motor_target_vel: 0.0,
+            motor_stiffness: 0.0,
+            motor_damping: 0.0,
+            motor_max_impulse: f32::MAX,
+            motor_impulse: 0.0,
         }
     }
 
@@ -135,6 +170,125 @@ impl PrismaticJoint {
         self.local_axis2
     }
 
+    /// Is the motor of this joint enabled?
+    pub fn is_motor_enabled(&self) -> bool {
+        self.motor_stiffness != 0.0 || self.motor_damping != 0.0
+    }
+
+    /// The index in `self.limits` of the limit this joint enforced during the last timestep, if any.
+    ///
+    /// This is `Some(0)` at the min limit, `Some(1)` at the max limit, and `None` if the joint
+    /// is in-between its limits or if its limits are disabled.
+    pub fn active_limit(&self) -> Option<usize> {
+        self.active_limit
+    }
+
+    /// Sets the target translation of this joint's motor, and its stiffness and damping.
+    ///
+    /// The motor acts as a spring-damper pulling the attached bodies toward the relative
+    /// translation `target_pos` along this joint's axis.
+    pub fn configure_motor_position(&mut self, target_pos: f32, stiffness: f32, damping: f32) {
+        self.configure_motor(target_pos, 0.0, stiffness, damping)
+    }
+
+    /// Sets the target velocity of this joint's motor, and the damping used to reach it.
+    pub fn configure_motor_velocity(&mut self, target_vel: f32, damping: f32) {
+        self.configure_motor(0.0, target_vel, 0.0, damping)
+    }
+
+    /// Sets the target translation and velocity of this joint's motor, and its stiffness and damping.
+    pub fn configure_motor(
+        &mut self,
+        target_pos: f32,
+        target_vel: f32,
+        stiffness: f32,
+        damping: f32,
+    ) {
+        self.motor_target_pos = target_pos;
+        self.motor_target_vel = target_vel;
+        self.motor_stiffness = stiffness;
+        self.motor_damping = damping;
+    }
+
+    /// The current translation of this joint, i.e., the translation along this joint's axis of
+    /// `rb2` relative to `rb1`, where `rb1` and `rb2` are the first and second rigid-bodies
+    /// attached to it.
+    ///
+    /// This is the signed distance between the two anchors along the joint's axis, i.e., the same
+    /// translation as the one constrained by `self.limits` and driven toward `motor_target_pos`.
+    pub fn translation(&self, rb1: &RigidBody, rb2: &RigidBody) -> f32 {
+        self.translation_along_axis(rb1.position(), rb2.position())
+    }
+
+    /// The current linear velocity of this joint, i.e., the time derivative of
+    /// `self.translation(rb1, rb2)`, where `rb1` and `rb2` are the first and second
+    /// rigid-bodies attached to it.
+    pub fn linear_velocity(&self, rb1: &RigidBody, rb2: &RigidBody) -> f32 {
+        let anchor1 = rb1.position() * self.local_anchor1;
+        let anchor2 = rb2.position() * self.local_anchor2;
+        let axis1 = rb1.position() * self.local_axis1;
+        // The center-of-mass of the bodies is recomputed from their positions, as it is only
+        // updated at the beginning of each timestep.
+        let velocity_at_point = |rb: &RigidBody, point: &Point<f32>| {
+            let com = rb.mass_properties().world_com(rb.position());
+            rb.linvel() + rb.angvel().gcross(point - com)
+        };
+        let dvel = velocity_at_point(rb2, &anchor2) - velocity_at_point(rb1, &anchor1);
+        // The axis rotates with the first body.
+        let daxis1 = rb1.angvel().gcross(axis1.into_inner());
+        dvel.dot(&axis1) + (anchor2 - anchor1).dot(&daxis1)
+    }
+
+    /// The world-space force applied by this joint, including its limits and motor, on the first
+    /// body during the last timestep, of length `dt`.
+    ///
+    /// This force is applied at the anchor of the first body, except for the part enforcing the
+    /// limits which is applied at its center-of-mass. Here `rb1` is the first rigid-body attached
+    /// to this joint. The force applied to the second body is given by `-force`.
+    pub fn force(&self, rb1: &RigidBody, dt: f32) -> Vector<f32> {
+        let position1 = rb1.position();
+        let axis1 = position1 * self.local_axis1;
+        #[cfg(feature = "dim2")]
+        let mut impulse = position1 * self.basis1[0] * self.impulse.x;
+        #[cfg(feature = "dim3")]
+        let mut impulse = position1 * self.basis1[0] * self.impulse.x
+            + position1 * self.basis1[1] * self.impulse.y;
+
+        match self.active_limit {
+            Some(0) => impulse -= *axis1 * self.limits_impulse,
+            Some(1) => impulse += *axis1 * self.limits_impulse,
+            _ => {}
+        }
+
+        impulse += *axis1 * self.motor_impulse;
+        impulse * utils::inv(dt)
+    }
+
+    /// The world-space torque applied by this joint on the first body during the last timestep,
+    /// of length `dt`, in addition to the torque resulting from `self.force(rb1, dt)`.
+    ///
+    /// The torque applied to the second body is given by `-torque`.
+    pub fn torque(&self, dt: f32) -> AngVector<f32> {
+        #[cfg(feature = "dim2")]
+        let impulse = self.impulse.y;
+        #[cfg(feature = "dim3")]
+        let impulse = Vector::new(self.impulse[2], self.impulse[3], self.impulse[4]);
+        impulse * utils::inv(dt)
+    }
+
+    /// The translation along this joint's axis of the second body relative to the first body,
+    /// given the positions of both attached bodies.
+    pub(crate) fn translation_along_axis(
+        &self,
+        position1: &Isometry<f32>,
+        position2: &Isometry<f32>,
+    ) -> f32 {
+        let anchor1 = position1 * self.local_anchor1;
+        let anchor2 = position2 * self.local_anchor2;
+        let axis1 = position1 * self.local_axis1;
+        (anchor2 - anchor1).dot(&axis1)
+    }
+
     // FIXME: precompute this?
     #[cfg(feature = "dim2")]
     pub(crate) fn local_frame1(&self) -> Isometry<f32> {
@@ -191,3 +345,135 @@ impl PrismaticJoint {
         Isometry::from_parts(translation, rotation)
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::PrismaticJoint;
+    use crate::dynamics::RigidBodyBuilder;
+    use crate::geometry::ColliderBuilder;
+    use crate::math::{Point, Vector};
+    use crate::pipeline::TestWorld;
+
+    #[test]
+    fn force_and_torque_balance_a_static_load() {
+        let mut world = TestWorld::falling();
+        let dt = world.params.dt();
+
+        // A ball at the end of a horizontal cantilever of length 2, sliding vertically along a
+        // wall and held in place by the joint motor. Both the weight of the ball and its moment
+        // have to be resisted by the joint.
+        let wall = world.bodies.insert(RigidBodyBuilder::new_static().build());
+        #[cfg(feature = "dim2")]
+        let rb = RigidBodyBuilder::new_dynamic()
+            .translation(2.0, 0.0)
+            .build();
+        #[cfg(feature = "dim3")]
+        let rb = RigidBodyBuilder::new_dynamic()
+            .translation(2.0, 0.0, 0.0)
+            .build();
+        let (ball, _) = world.insert(rb, ColliderBuilder::ball(0.5).build());
+        #[cfg(feature = "dim2")]
+        let mut joint = PrismaticJoint::new(
+            Point::origin(),
+            Vector::y_axis(),
+            Point::new(-2.0, 0.0),
+            Vector::y_axis(),
+        );
+        #[cfg(feature = "dim3")]
+        let mut joint = PrismaticJoint::new(
+            Point::origin(),
+            Vector::y_axis(),
+            Vector::zeros(),
+            Point::new(-2.0, 0.0, 0.0),
+            Vector::y_axis(),
+            Vector::zeros(),
+        );
+        joint.configure_motor_position(0.0, 100.0, 20.0);
+        let joint = world.joints.insert(&mut world.bodies, wall, ball, joint);
+
+        world.steps(100);
+
+        let weight = world.gravity * world.bodies[ball].mass();
+        #[cfg(feature = "dim2")]
+        let moment = Vector::x().perp(&weight) * 2.0;
+        #[cfg(feature = "dim3")]
+        let moment = Vector::x().cross(&weight) * 2.0;
+        let params = &world.joints.get(joint).unwrap().params;
+        let prismatic = params.as_prismatic_joint().unwrap();
+        let force = prismatic.force(&world.bodies[wall], dt);
+        assert!((force - weight).norm() < weight.norm() * 1.0e-2);
+        #[cfg(feature = "dim2")]
+        assert!((prismatic.torque(dt) - moment).abs() < moment.abs() * 1.0e-2);
+        #[cfg(feature = "dim3")]
+        assert!((prismatic.torque(dt) - moment).norm() < moment.norm() * 1.0e-2);
+    }
+
+    #[test]
+    fn joint_coordinates_follow_attached_bodies() {
+        let mut world = TestWorld::new(Vector::zeros());
+        let dt = world.params.dt();
+
+        // Two free bodies, far from each other. The joint is not inserted into the joint set,
+        // so it only measures the relative motion of the bodies without constraining it.
+        #[cfg(feature = "dim2")]
+        let (rb1, rb2) = (
+            RigidBodyBuilder::new_dynamic()
+                .linvel(1.0, -2.0)
+                .angvel(0.7)
+                .build(),
+            RigidBodyBuilder::new_dynamic()
+                .translation(4.0, 3.0)
+                .linvel(-0.5, 1.5)
+                .angvel(-1.3)
+                .build(),
+        );
+        #[cfg(feature = "dim3")]
+        let (rb1, rb2) = (
+            RigidBodyBuilder::new_dynamic()
+                .linvel(1.0, -2.0, 0.5)
+                .angvel(Vector::y() * 0.7)
+                .build(),
+            RigidBodyBuilder::new_dynamic()
+                .translation(4.0, 3.0, -2.0)
+                .linvel(-0.5, 1.5, 1.0)
+                .angvel(Vector::y() * -1.3)
+                .build(),
+        );
+        let (body1, _) = world.insert(rb1, ColliderBuilder::ball(0.5).build());
+        let (body2, _) = world.insert(rb2, ColliderBuilder::ball(0.5).build());
+
+        #[cfg(feature = "dim2")]
+        let joint = PrismaticJoint::new(
+            Point::new(1.0, 0.5),
+            Vector::x_axis(),
+            Point::new(-0.5, 0.0),
+            Vector::x_axis(),
+        );
+        #[cfg(feature = "dim3")]
+        let joint = PrismaticJoint::new(
+            Point::new(1.0, 0.5, 0.0),
+            Vector::x_axis(),
+            Vector::zeros(),
+            Point::new(-0.5, 0.0, 0.2),
+            Vector::x_axis(),
+            Vector::zeros(),
+        );
+
+        let bodies = &world.bodies;
+        assert!((joint.translation(&bodies[body1], &bodies[body2]) - 2.5).abs() < 1.0e-5);
+
+        // The velocity matches the finite differences of the translation.
+        for _ in 0..20 {
+            let bodies = &world.bodies;
+            let translation0 = joint.translation(&bodies[body1], &bodies[body2]);
+            let linvel0 = joint.linear_velocity(&bodies[body1], &bodies[body2]);
+            world.step();
+            let bodies = &world.bodies;
+            let translation1 = joint.translation(&bodies[body1], &bodies[body2]);
+            let linvel1 = joint.linear_velocity(&bodies[body1], &bodies[body2]);
+
+            let linvel = (translation1 - translation0) / dt;
+            assert!((linvel - (linvel0 + linvel1) * 0.5).abs() < 1.0e-2);
+        }
+    }
+}
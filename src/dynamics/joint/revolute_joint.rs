@@ -1,5 +1,6 @@
-use crate::math::{Point, Vector};
-use crate::utils::WBasis;
+use crate::dynamics::RigidBody;
+use crate::math::{Isometry, Point, Vector};
+use crate::utils::{self, WBasis};
 use na::{Unit, Vector5};
 
 #[derive(Copy, Clone)]
@@ -21,7 +22,31 @@ pub struct RevoluteJoint {
     /// The impulse applied by this joint on the first body.
     ///
     /// The impulse applied to the second body is given by `-impulse`.
+    /// The first three components are the world-space linear impulse applied at the anchor. The
+    /// last two are the angular impulses along `basis1`, once transformed to world-space by the
+    /// position of the first body. See `Self::force` and `Self::torque` for the corresponding
+    /// force and torque.
     pub impulse: Vector5<f32>,
+    /// The target angle the motor tries to reach.
+    ///
+    /// The angle of this joint is the angle between `basis1[0]` and `basis2[0]`, around `local_axis1`.
+    pub motor_target_pos: f32,
+    /// The target relative angular velocity the motor tries to reach.
+    pub motor_target_vel: f32,
+    /// The motor's stiffness, i.e., the torque applied per radian of difference with `motor_target_pos`.
+    ///
+    /// The motor is disabled if both its stiffness and damping are zero.
+    pub motor_stiffness: f32,
+    /// The motor's damping, i.e., the torque applied per unit of difference with `motor_target_vel`.
+    ///
+    /// The motor is disabled if both its stiffness and damping are zero.
+    pub motor_damping: f32,
+    /// The maximal angular impulse the motor can apply at each timestep.
+    pub motor_max_impulse: f32,
+    /// The angular impulse applied by the motor on the first body.
+    ///
+    /// The impulse applied to the second body is given by `-motor_impulse`.
+    pub motor_impulse: f32,
 }
 
 impl RevoluteJoint {
@@ -41,6 +66,198 @@ impl RevoluteJoint {
             basis1: local_axis1.orthonormal_basis(),
             basis2: local_axis2.orthonormal_basis(),
             impulse: na::zero(),
+            motor_target_pos: 0.0,
+            motor_target_vel: 0.0,
+            motor_stiffness: 0.0,
+            motor_damping: 0.0,
+            motor_max_impulse: f32::MAX,
+            motor_impulse: 0.0,
         }
     }
+
+    /// Is the motor of this joint enabled?
+    pub fn is_motor_enabled(&self) -> bool {
+        self.motor_stiffness != 0.0 || self.motor_damping != 0.0
+    }
+
+    /// Sets the target angle of this joint's motor, and its stiffness and damping.
+    ///
+    /// The motor acts as a spring-damper pulling the attached bodies toward the relative
+    /// angle `target_pos` around this joint's axis.
+    pub fn configure_motor_position(&mut self, target_pos: f32, stiffness: f32, damping: f32) {
+        self.configure_motor(target_pos, 0.0, stiffness, damping)
+    }
+
+    /// Sets the target angular velocity of this joint's motor, and the damping used to reach it.
+    pub fn configure_motor_velocity(&mut self, target_vel: f32, damping: f32) {
+        self.configure_motor(0.0, target_vel, 0.0, damping)
+    }
+
+    /// Sets the target angle and angular velocity of this joint's motor, and its stiffness and damping.
+    pub fn configure_motor(
+        &mut self,
+        target_pos: f32,
+        target_vel: f32,
+        stiffness: f32,
+        damping: f32,
+    ) {
+        self.motor_target_pos = target_pos;
+        self.motor_target_vel = target_vel;
+        self.motor_stiffness = stiffness;
+        self.motor_damping = damping;
+    }
+
+    /// The current angle of this joint, i.e., the angle of `rb2` relative to `rb1` around this
+    /// joint's axis, where `rb1` and `rb2` are the first and second rigid-bodies attached to it.
+    ///
+    /// This is the angle between `basis1[0]` and `basis2[0]`, i.e., the same angle as the one the
+    /// motor drives toward `motor_target_pos`. The result is in `[-pi, pi]`, so it jumps from `pi`
+    /// to `-pi` when the joint makes more than half a turn.
+    pub fn angle(&self, rb1: &RigidBody, rb2: &RigidBody) -> f32 {
+        self.angle_between(rb1.position(), rb2.position())
+    }
+
+    /// The current angular velocity of this joint, i.e., the angular velocity of `rb2` relative
+    /// to `rb1` around this joint's axis, where `rb1` and `rb2` are the first and second
+    /// rigid-bodies attached to it.
+    ///
+    /// This is the time derivative of `self.angle(rb1, rb2)`, ignoring the jumps of this angle at
+    /// `pi`.
+    pub fn angular_velocity(&self, rb1: &RigidBody, rb2: &RigidBody) -> f32 {
+        let axis1 = rb1.position() * self.local_axis1;
+        (rb2.angvel() - rb1.angvel()).dot(&axis1)
+    }
+
+    /// The world-space force applied by this joint at the anchor of the first body during the
+    /// last timestep, of length `dt`.
+    ///
+    /// The force applied to the second body is given by `-force`.
+    pub fn force(&self, dt: f32) -> Vector<f32> {
+        self.impulse.xyz() * utils::inv(dt)
+    }
+
+    /// The world-space torque applied by this joint, including its motor, on the first body
+    /// during the last timestep, of length `dt`, in addition to the torque resulting from
+    /// `self.force(dt)`.
+    ///
+    /// Here `rb1` is the first rigid-body attached to this joint. The torque applied to the second
+    /// body is given by `-torque`.
+    pub fn torque(&self, rb1: &RigidBody, dt: f32) -> Vector<f32> {
+        let position1 = rb1.position();
+        let impulse = position1 * self.basis1[0] * self.impulse[3]
+            + position1 * self.basis1[1] * self.impulse[4]
+            + position1 * self.local_axis1.into_inner() * self.motor_impulse;
+        impulse * utils::inv(dt)
+    }
+
+    /// The angle of the second body relative to the first body around this joint's axis,
+    /// given the positions of both attached bodies.
+    ///
+    /// The result is in `[-pi, pi]`.
+    pub(crate) fn angle_between(
+        &self,
+        position1: &Isometry<f32>,
+        position2: &Isometry<f32>,
+    ) -> f32 {
+        let axis1 = position1 * self.local_axis1;
+        let basis1 = position1 * self.basis1[0];
+        let basis2 = position2 * self.basis2[0];
+        let bitangent1 = axis1.cross(&basis1);
+        basis2.dot(&bitangent1).atan2(basis2.dot(&basis1))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::RevoluteJoint;
+    use crate::dynamics::RigidBodyBuilder;
+    use crate::geometry::ColliderBuilder;
+    use crate::math::{Isometry, Point, Vector};
+    use crate::pipeline::TestWorld;
+
+    #[test]
+    fn force_and_torque_balance_a_static_load() {
+        let mut world = TestWorld::falling();
+        let dt = world.params.dt();
+
+        // A ball at the end of a horizontal cantilever of length 2, hinged to a wall along the
+        // cantilever itself, so the hinge has to resist the moment of the weight of the ball.
+        let wall = world.bodies.insert(RigidBodyBuilder::new_static().build());
+        let rb = RigidBodyBuilder::new_dynamic()
+            .translation(2.0, 0.0, 0.0)
+            .build();
+        let (ball, _) = world.insert(rb, ColliderBuilder::ball(0.5).build());
+        let joint = RevoluteJoint::new(
+            Point::origin(),
+            Vector::x_axis(),
+            Point::new(-2.0, 0.0, 0.0),
+            Vector::x_axis(),
+        );
+        let joint = world.joints.insert(&mut world.bodies, wall, ball, joint);
+
+        world.steps(100);
+
+        let weight = world.gravity * world.bodies[ball].mass();
+        let moment = Vector::x().cross(&weight) * 2.0;
+        let params = &world.joints.get(joint).unwrap().params;
+        let revolute = params.as_revolute_joint().unwrap();
+        let torque = revolute.torque(&world.bodies[wall], dt);
+        assert!((revolute.force(dt) - weight).norm() < weight.norm() * 1.0e-2);
+        assert!((torque - moment).norm() < moment.norm() * 1.0e-2);
+    }
+
+    #[test]
+    fn joint_coordinates_follow_attached_bodies() {
+        let mut world = TestWorld::new(Vector::zeros());
+        let dt = world.params.dt();
+
+        // Two free bodies rotating around the joint axis. The joint is not inserted into the
+        // joint set, so it only measures the relative motion of the bodies without constraining it.
+        let rb1 = RigidBodyBuilder::new_dynamic()
+            .linvel(1.0, -2.0, 0.5)
+            .angvel(Vector::y() * 0.7)
+            .build();
+        let rb2 = RigidBodyBuilder::new_dynamic()
+            .translation(4.0, 3.0, -2.0)
+            .linvel(-0.5, 1.5, 1.0)
+            .angvel(Vector::y() * -1.3)
+            .build();
+        let (body1, _) = world.insert(rb1, ColliderBuilder::ball(0.5).build());
+        let (body2, _) = world.insert(rb2, ColliderBuilder::ball(0.5).build());
+
+        let joint = RevoluteJoint::new(
+            Point::origin(),
+            Vector::y_axis(),
+            Point::origin(),
+            Vector::y_axis(),
+        );
+
+        // The angular velocity matches the finite differences of the angle.
+        for _ in 0..20 {
+            let bodies = &world.bodies;
+            let angle0 = joint.angle(&bodies[body1], &bodies[body2]);
+            let angvel0 = joint.angular_velocity(&bodies[body1], &bodies[body2]);
+            world.step();
+            let bodies = &world.bodies;
+            let angle1 = joint.angle(&bodies[body1], &bodies[body2]);
+            let angvel1 = joint.angular_velocity(&bodies[body1], &bodies[body2]);
+
+            let angvel = (angle1 - angle0) / dt;
+            assert!((angvel - (angvel0 + angvel1) * 0.5).abs() < 1.0e-2);
+        }
+
+        // The angle is wrapped to `[-pi, pi]` past half a turn.
+        let rotation = Vector::y() * 3.5;
+        let bodies = &mut world.bodies;
+        bodies
+            .get_mut(body1)
+            .unwrap()
+            .set_position(Isometry::identity(), false);
+        bodies
+            .get_mut(body2)
+            .unwrap()
+            .set_position(Isometry::new(Vector::zeros(), rotation), false);
+        let angle = joint.angle(&bodies[body1], &bodies[body2]);
+        assert!((angle - (3.5 - std::f32::consts::TAU)).abs() < 1.0e-5);
+    }
 }
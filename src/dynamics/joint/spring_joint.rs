@@ -0,0 +1,49 @@
+use crate::math::Point;
+
+#[derive(Copy, Clone)]
+#[cfg_attr(feature = "serde-serialize", derive(Serialize, Deserialize))]
+/// A linear spring-damper attaching a pair of points on two bodies.
+///
+/// The spring pulls (or pushes) the anchors toward each other until their distance reaches
+/// `rest_length`. It is solved implicitly as a soft constraint (instead of being applied as an
+/// explicit force), so very stiff springs remain stable even with large timesteps.
+pub struct SpringJoint {
+    /// Where the spring is attached on the first body, expressed in the first body local frame.
+    pub local_anchor1: Point<f32>,
+    /// Where the spring is attached on the second body, expressed in the second body local frame.
+    pub local_anchor2: Point<f32>,
+    /// The distance between the anchors at which the spring applies no force.
+    pub rest_length: f32,
+    /// The spring stiffness, i.e., the force applied per unit of distance to `rest_length`.
+    pub stiffness: f32,
+    /// The spring damping, i.e., the force applied per unit of relative velocity of the anchors
+    /// along the spring.
+    pub damping: f32,
+    /// The impulse applied by this spring on the first body, along the direction from the
+    /// first anchor to the second anchor.
+    ///
+    /// The impulse applied to the second body is given by `-impulse`.
+    pub impulse: f32,
+}
+
+impl SpringJoint {
+    /// Creates a new spring from two anchors given on the local spaces of the respective bodies.
+    ///
+    /// The spring is disabled if both its `stiffness` and its `damping` are zero.
+    pub fn new(
+        local_anchor1: Point<f32>,
+        local_anchor2: Point<f32>,
+        rest_length: f32,
+        stiffness: f32,
+        damping: f32,
+    ) -> Self {
+        Self {
+            local_anchor1,
+            local_anchor2,
+            rest_length,
+            stiffness,
+            damping,
+            impulse: 0.0,
+        }
+    }
+}
@@ -1,3 +1,4 @@
+use crate::geometry::Shape;
 use crate::math::{AngVector, AngularInertia, Isometry, Point, Rotation, Vector};
 use crate::utils;
 use num::Zero;
@@ -153,6 +154,15 @@ impl MassProperties {
         }
     }
 
+    /// Computes the mass properties of the given shape with a uniform density.
+    ///
+    /// The mass properties of triangle meshes are approximated by the mass properties of their
+    /// convex hull. Shapes without any area (in 2D) or volume (in 3D), like segments and
+    /// heightfields, have zero mass properties.
+    pub fn from_shape(shape: &dyn Shape, density: f32) -> Self {
+        shape.mass_properties(density)
+    }
+
     /// Transform each element of the mass properties.
     pub fn transform_by(&self, m: &Isometry<f32>) -> Self {
         // NOTE: we don't apply the parallel axis theorem here
@@ -439,4 +449,179 @@ mod test {
             epsilon = 1.0e-6
         );
     }
+
+    #[test]
+    fn mass_properties_trimesh_hull() {
+        use crate::geometry::Trimesh;
+
+        // A box, shifted so its center of mass isn't at the origin.
+        let shift = Vector::repeat(1.0);
+        #[cfg(feature = "dim2")]
+        let (half_extents, vertices, indices) = (
+            Vector::new(1.0, 2.0),
+            vec![
+                Point::new(-1.0, -2.0) + shift,
+                Point::new(1.0, -2.0) + shift,
+                Point::new(1.0, 2.0) + shift,
+                Point::new(-1.0, 2.0) + shift,
+            ],
+            vec![na::Point3::new(0, 1, 2), na::Point3::new(0, 2, 3)],
+        );
+        #[cfg(feature = "dim3")]
+        let (half_extents, vertices, indices) = {
+            let half_extents = Vector::new(1.0, 2.0, 3.0);
+            let mut hull = ncollide::procedural::cuboid(&(half_extents * 2.0));
+            hull.translate_by(&shift.into());
+            hull.unify_index_buffer();
+            (half_extents, hull.coords, hull.indices.unwrap_unified())
+        };
+
+        let mesh = Trimesh::new(vertices, indices);
+        let expected = MassProperties::from_cuboid(2.0, half_extents);
+        let result = MassProperties::from_shape(&mesh, 2.0);
+
+        assert_relative_eq!(result.local_com, Point::from(shift), epsilon = 1.0e-5);
+        assert_relative_eq!(result.inv_mass, expected.inv_mass, epsilon = 1.0e-5);
+        #[cfg(feature = "dim2")]
+        assert_relative_eq!(
+            result.inv_principal_inertia_sqrt,
+            expected.inv_principal_inertia_sqrt,
+            epsilon = 1.0e-5
+        );
+        #[cfg(feature = "dim3")]
+        assert_relative_eq!(
+            result.reconstruct_inertia_matrix(),
+            expected.reconstruct_inertia_matrix(),
+            epsilon = 1.0e-3
+        );
+
+        // The cached mass properties are only updated on demand after moving the vertices.
+        let mut mesh = mesh;
+        mesh.update_vertices(|vertices| {
+            vertices.iter_mut().for_each(|pt| *pt += shift);
+        });
+        let stale = MassProperties::from_shape(&mesh, 2.0);
+        assert_relative_eq!(stale.local_com, Point::from(shift), epsilon = 1.0e-5);
+        mesh.update_mass_properties();
+        let moved = MassProperties::from_shape(&mesh, 2.0);
+        assert_relative_eq!(moved.local_com, Point::from(shift * 2.0), epsilon = 1.0e-5);
+        assert_relative_eq!(moved.inv_mass, expected.inv_mass, epsilon = 1.0e-5);
+    }
+
+    #[test]
+    fn mass_properties_flat_trimesh() {
+        use crate::geometry::Trimesh;
+
+        // All the triangles are degenerate in 2D, and coplanar in 3D.
+        let vertices = vec![
+            Point::origin(),
+            Point::from(Vector::x()),
+            Point::from(Vector::x() * 2.0),
+            Point::from(Vector::x() * 3.0),
+            #[cfg(feature = "dim3")]
+            Point::new(0.0, 0.0, 1.0),
+        ];
+        #[cfg(feature = "dim2")]
+        let indices = vec![na::Point3::new(0, 1, 2), na::Point3::new(1, 2, 3)];
+        #[cfg(feature = "dim3")]
+        let indices = vec![na::Point3::new(0, 1, 4), na::Point3::new(1, 3, 4)];
+
+        let mesh = Trimesh::new(vertices, indices);
+        assert!(MassProperties::from_shape(&mesh, 2.0).is_zero());
+    }
+
+    #[test]
+    fn mass_properties_round_cone_matches_hull() {
+        use crate::geometry::RoundCone;
+
+        // Compare with the mass properties of the convex hull of a sampling of both balls.
+        let (a, b, radius_a, radius_b) =
+            (Point::origin(), Point::from(Vector::repeat(1.0)), 0.5, 0.2);
+        let mut points = Vec::new();
+        for (center, radius) in [(a, radius_a), (b, radius_b)].iter() {
+            #[cfg(feature = "dim2")]
+            let ball = ncollide::procedural::circle(&(radius * 2.0), 128)
+                .coords()
+                .to_vec();
+            #[cfg(feature = "dim3")]
+            let ball = ncollide::procedural::sphere(radius * 2.0, 64, 32, false).coords;
+            points.extend(ball.iter().map(|pt| center + pt.coords));
+        }
+
+        let hull = ncollide::transformation::convex_hull(&points);
+        #[cfg(feature = "dim2")]
+        let expected = MassProperties::from_polygon(2.0, hull.coords());
+        #[cfg(feature = "dim3")]
+        let expected = MassProperties::from_convex_polyhedron(
+            2.0,
+            &hull.coords,
+            &hull.indices.unwrap_unified(),
+        );
+        let result = MassProperties::from_shape(&RoundCone::new(a, b, radius_a, radius_b), 2.0);
+
+        assert_relative_eq!(result.local_com, expected.local_com, epsilon = 1.0e-2);
+        assert_relative_eq!(result.inv_mass, expected.inv_mass, max_relative = 1.0e-2);
+        #[cfg(feature = "dim2")]
+        assert_relative_eq!(
+            result.inv_principal_inertia_sqrt,
+            expected.inv_principal_inertia_sqrt,
+            max_relative = 1.0e-2
+        );
+        #[cfg(feature = "dim3")]
+        assert_relative_eq!(
+            result.reconstruct_inertia_matrix(),
+            expected.reconstruct_inertia_matrix(),
+            max_relative = 1.0e-2
+        );
+    }
+
+    #[test]
+    fn rigid_body_world_mass_properties() {
+        use crate::dynamics::{RigidBodyBuilder, RigidBodySet};
+        use crate::geometry::ColliderSet;
+        use crate::math::Isometry;
+        #[cfg(feature = "dim3")]
+        use crate::utils::WAngularInertia;
+
+        let mut bodies = RigidBodySet::new();
+        let mut colliders = ColliderSet::new();
+
+        #[cfg(feature = "dim2")]
+        let (cuboid, angle) = (ColliderBuilder::cuboid(1.0, 2.0), 1.0);
+        #[cfg(feature = "dim3")]
+        let (cuboid, angle) = (ColliderBuilder::cuboid(1.0, 2.0, 3.0), Vector::z());
+
+        let pos = Isometry::new(Vector::x() * 3.0, angle);
+        let handle = bodies.insert(RigidBodyBuilder::new_dynamic().position(pos).build());
+        let co = cuboid
+            .position(Isometry::new(Vector::y(), na::zero()))
+            .build();
+        let local_props = co.mass_properties();
+        colliders.insert(co, handle, &mut bodies);
+
+        let rb = &bodies[handle];
+        assert_relative_eq!(*rb.local_center_of_mass(), Point::from(Vector::y()));
+        assert_relative_eq!(*rb.center_of_mass(), pos * Point::from(Vector::y()));
+
+        #[cfg(feature = "dim2")]
+        assert_relative_eq!(
+            rb.world_inertia_tensor(),
+            1.0 / (local_props.inv_principal_inertia_sqrt.powi(2)),
+            epsilon = 1.0e-4
+        );
+        #[cfg(feature = "dim3")]
+        {
+            // The inertia tensor is rotated with the rigid-body.
+            let rot = pos.rotation.to_rotation_matrix();
+            let expected = rot * local_props.reconstruct_inertia_matrix() * rot.transpose();
+            assert_relative_eq!(rb.world_inertia_tensor(), expected, epsilon = 1.0e-4);
+
+            let inv_inertia = rb.world_inv_inertia_sqrt.squared().into_matrix();
+            assert_relative_eq!(
+                rb.world_inertia_tensor() * inv_inertia,
+                na::Matrix3::identity(),
+                epsilon = 1.0e-4
+            );
+        }
+    }
 }
@@ -22,7 +22,8 @@ impl MassProperties {
         }
     }
 
-    pub(crate) fn from_ball(density: f32, radius: f32) -> Self {
+    /// Computes the mass properties of a ball with the given radius, centered at the origin.
+    pub fn from_ball(density: f32, radius: f32) -> Self {
         let (vol, unit_i) = Self::ball_volume_unit_angular_inertia(radius);
         let mass = vol * density;
         Self::new(Point::origin(), mass, unit_i * mass)
@@ -4,7 +4,8 @@ use crate::geometry::Capsule;
 use crate::math::Point;
 
 impl MassProperties {
-    pub(crate) fn from_capsule(density: f32, a: Point<f32>, b: Point<f32>, radius: f32) -> Self {
+    /// Computes the mass properties of a capsule with the segment `[a, b]` as its principal axis.
+    pub fn from_capsule(density: f32, a: Point<f32>, b: Point<f32>, radius: f32) -> Self {
         let half_height = (b - a).norm() / 2.0;
         let (cyl_vol, cyl_unit_i) = Self::cylinder_y_volume_unit_inertia(half_height, radius);
         let (ball_vol, ball_unit_i) = Self::ball_volume_unit_angular_inertia(radius);
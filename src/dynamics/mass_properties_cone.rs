@@ -15,7 +15,8 @@ impl MassProperties {
         (volume, Vector::new(off_principal, principal, off_principal))
     }
 
-    pub(crate) fn from_cone(density: f32, half_height: f32, radius: f32) -> Self {
+    /// Computes the mass properties of a cone aligned with the `y` axis, centered at the origin.
+    pub fn from_cone(density: f32, half_height: f32, radius: f32) -> Self {
         let (cyl_vol, cyl_unit_i) = Self::cone_y_volume_unit_inertia(half_height, radius);
         let cyl_mass = cyl_vol * density;
 
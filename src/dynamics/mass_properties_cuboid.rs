@@ -25,7 +25,8 @@ impl MassProperties {
         }
     }
 
-    pub(crate) fn from_cuboid(density: f32, half_extents: Vector<f32>) -> Self {
+    /// Computes the mass properties of a cuboid with the given half-extents, centered at the origin.
+    pub fn from_cuboid(density: f32, half_extents: Vector<f32>) -> Self {
         let (vol, unit_i) = Self::cuboid_volume_unit_inertia(half_extents);
         let mass = vol * density;
         Self::new(Point::origin(), mass, unit_i * mass)
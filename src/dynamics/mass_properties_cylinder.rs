@@ -26,7 +26,8 @@ impl MassProperties {
     }
 
     #[cfg(feature = "dim3")]
-    pub(crate) fn from_cylinder(density: f32, half_height: f32, radius: f32) -> Self {
+    /// Computes the mass properties of a cylinder aligned with the `y` axis, centered at the origin.
+    pub fn from_cylinder(density: f32, half_height: f32, radius: f32) -> Self {
         let (cyl_vol, cyl_unit_i) = Self::cylinder_y_volume_unit_inertia(half_height, radius);
         let cyl_mass = cyl_vol * density;
 
@@ -1,10 +1,9 @@
-#![allow(dead_code)] // TODO: remove this
-
 use crate::dynamics::MassProperties;
 use crate::math::Point;
 
 impl MassProperties {
-    pub(crate) fn from_polygon(density: f32, vertices: &[Point<f32>]) -> MassProperties {
+    /// Computes the mass properties of a convex polygon.
+    pub fn from_polygon(density: f32, vertices: &[Point<f32>]) -> MassProperties {
         let (area, com) = convex_polygon_area_and_center_of_mass(vertices);
 
         if area == 0.0 {
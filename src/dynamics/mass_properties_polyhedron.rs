@@ -0,0 +1,71 @@
+use crate::dynamics::MassProperties;
+use crate::math::{Point, Rotation, Vector};
+use na::{Matrix3, Point3};
+use num::Zero;
+
+impl MassProperties {
+    /// Computes the mass properties of a closed convex polyhedron.
+    ///
+    /// The triangles of the polyhedron boundary must be oriented consistently, either all
+    /// clockwise or all counter-clockwise.
+    pub fn from_convex_polyhedron(
+        density: f32,
+        vertices: &[Point<f32>],
+        indices: &[Point3<u32>],
+    ) -> MassProperties {
+        if vertices.is_empty() {
+            return MassProperties::zero();
+        }
+
+        // Decompose the polyhedron into tetrahedra sharing the vertex `reference`,
+        // and integrate their volumes and covariance matrices.
+        let reference = vertices
+            .iter()
+            .fold(Point::origin(), |e1, e2| e1 + e2.coords)
+            / vertices.len() as f32;
+        let canonical_covariance =
+            Matrix3::new(2.0, 1.0, 1.0, 1.0, 2.0, 1.0, 1.0, 1.0, 2.0) / 120.0;
+        let mut volume = 0.0;
+        let mut first_moment = Vector::zeros();
+        let mut covariance = Matrix3::zeros();
+
+        for idx in indices {
+            let a = vertices[idx.x as usize] - reference;
+            let b = vertices[idx.y as usize] - reference;
+            let c = vertices[idx.z as usize] - reference;
+            let basis = Matrix3::from_columns(&[a, b, c]);
+            let det = basis.determinant();
+
+            volume += det / 6.0;
+            first_moment += (a + b + c) * (det / 24.0);
+            covariance += basis * canonical_covariance * basis.transpose() * det;
+        }
+
+        // The orientation of the triangles only affects the sign of the results.
+        if volume < 0.0 {
+            volume = -volume;
+            first_moment = -first_moment;
+            covariance = -covariance;
+        }
+
+        if volume == 0.0 {
+            return MassProperties::new(reference, 0.0, Vector::zeros());
+        }
+
+        let shift = first_moment / volume;
+        let covariance = covariance - shift * shift.transpose() * volume;
+        let inertia = (Matrix3::identity() * covariance.trace() - covariance) * density;
+        let eigen = inertia.symmetric_eigen();
+        let principal_inertia_local_frame =
+            Rotation::from_matrix_eps(&eigen.eigenvectors, 1.0e-6, 10, na::one());
+        // NOTE: we drop the negative eigenvalues that may result from rounding errors.
+        let principal_inertia = eigen.eigenvalues.map(|e| e.max(0.0));
+
+        MassProperties::with_principal_inertia_frame(
+            reference + shift,
+            volume * density,
+            principal_inertia,
+            principal_inertia_local_frame,
+        )
+    }
+}
@@ -0,0 +1,116 @@
+use crate::dynamics::MassProperties;
+#[cfg(feature = "dim3")]
+use crate::geometry::Capsule;
+use crate::math::Point;
+#[cfg(feature = "dim3")]
+use crate::math::Vector;
+
+// The number of slices used for the numerical integration of the mass properties.
+const NUM_SLICES: usize = 100;
+
+impl MassProperties {
+    /// Computes the mass properties of a round cone with the segment `[a, b]` as its principal axis.
+    ///
+    /// The round cone is the convex hull of the ball of radius `radius_a` centered at `a`, and of
+    /// the ball of radius `radius_b` centered at `b`. Its mass properties are integrated numerically
+    /// by decomposing it into thin slices orthogonal to its principal axis.
+    pub fn from_round_cone(
+        density: f32,
+        a: Point<f32>,
+        b: Point<f32>,
+        radius_a: f32,
+        radius_b: f32,
+    ) -> Self {
+        let length = (b - a).norm();
+
+        // If one ball contains the other, the round cone is just the biggest ball.
+        if length <= (radius_a - radius_b).abs() {
+            return if radius_a >= radius_b {
+                shifted_ball(density, a, radius_a)
+            } else {
+                shifted_ball(density, b, radius_b)
+            };
+        }
+
+        // The coordinates of the points where the lateral surface touches the balls,
+        // along the principal axis starting from `a`.
+        let sin = (radius_a - radius_b) / length;
+        let cos = (1.0 - sin * sin).sqrt();
+        let y_tangent_a = radius_a * sin;
+        let y_tangent_b = length + radius_b * sin;
+
+        let y_min = -radius_a;
+        let y_max = length + radius_b;
+        let dy = (y_max - y_min) / NUM_SLICES as f32;
+
+        let mut measure = 0.0; // Area in 2D, volume in 3D.
+        let mut first_moment = 0.0;
+        let mut second_moment = 0.0;
+        let mut slice_inertia = 0.0;
+        #[cfg(feature = "dim3")]
+        let mut axial_inertia = 0.0;
+
+        for i in 0..NUM_SLICES {
+            let y = y_min + (i as f32 + 0.5) * dy;
+            let r = if y < y_tangent_a {
+                (radius_a * radius_a - y * y).max(0.0).sqrt()
+            } else if y > y_tangent_b {
+                (radius_b * radius_b - (y - length) * (y - length))
+                    .max(0.0)
+                    .sqrt()
+            } else {
+                let t = (y - y_tangent_a) / (y_tangent_b - y_tangent_a);
+                (radius_a + (radius_b - radius_a) * t) * cos
+            };
+
+            #[cfg(feature = "dim2")]
+            {
+                // A strip of length `2r`.
+                let m = 2.0 * r * dy;
+                measure += m;
+                first_moment += m * y;
+                second_moment += m * y * y;
+                slice_inertia += m * r * r / 3.0;
+            }
+
+            #[cfg(feature = "dim3")]
+            {
+                // A disk of radius `r`.
+                let m = std::f32::consts::PI * r * r * dy;
+                measure += m;
+                first_moment += m * y;
+                second_moment += m * y * y;
+                slice_inertia += m * r * r / 4.0;
+                axial_inertia += m * r * r / 2.0;
+            }
+        }
+
+        let mass = measure * density;
+        let y_com = first_moment / measure;
+        let transverse_inertia =
+            (slice_inertia + second_moment - measure * y_com * y_com) * density;
+        let local_com = a + (b - a) * (y_com / length);
+
+        #[cfg(feature = "dim2")]
+        {
+            Self::new(local_com, mass, transverse_inertia)
+        }
+
+        #[cfg(feature = "dim3")]
+        {
+            let principal_inertia = Vector::new(
+                transverse_inertia,
+                axial_inertia * density,
+                transverse_inertia,
+            );
+            let local_frame = Capsule::new(a, b, radius_a).rotation_wrt_y();
+            Self::with_principal_inertia_frame(local_com, mass, principal_inertia, local_frame)
+        }
+    }
+}
+
+fn shifted_ball(density: f32, center: Point<f32>, radius: f32) -> MassProperties {
+    let mut props = MassProperties::from_ball(density, radius);
+    props.local_com = center;
+    props
+}
@@ -1,13 +1,21 @@
 //! Structures related to dynamics: bodies, joints, etc.
 
+pub use self::coefficient_combine_rule::CoefficientCombineRule;
+#[cfg(feature = "dim2")]
+pub use self::ground_friction::GroundFriction;
 pub use self::integration_parameters::IntegrationParameters;
+pub use self::island_manager::IslandManager;
 pub(crate) use self::joint::JointIndex;
 #[cfg(feature = "dim3")]
 pub use self::joint::RevoluteJoint;
+#[cfg(feature = "dim2")]
+pub use self::joint::{AngularJoint, GearCoordinate, GearJoint, LinearJoint};
 pub use self::joint::{
-    BallJoint, FixedJoint, Joint, JointHandle, JointParams, JointSet, PrismaticJoint,
+    BallJoint, CustomConstraint, CustomConstraintBody, CustomConstraintRow, CustomJoint,
+    FixedJoint, Joint, JointHandle, JointParams, JointSet, PrismaticJoint, SpringJoint,
 };
 pub use self::mass_properties::MassProperties;
+pub use self::physics_state::{BodyState, PhysicsState, PhysicsStateDelta, SnapshotId};
 pub use self::rigid_body::{ActivationStatus, BodyStatus, RigidBody, RigidBodyBuilder};
 pub use self::rigid_body_set::{BodyPair, RigidBodyHandle, RigidBodySet};
 // #[cfg(not(feature = "parallel"))]
@@ -18,7 +26,11 @@ pub(crate) use self::solver::IslandSolver;
 #[cfg(feature = "parallel")]
 pub(crate) use self::solver::ParallelIslandSolver;
 
+mod coefficient_combine_rule;
+#[cfg(feature = "dim2")]
+mod ground_friction;
 mod integration_parameters;
+mod island_manager;
 mod joint;
 mod mass_properties;
 mod mass_properties_ball;
@@ -29,6 +41,10 @@ mod mass_properties_cuboid;
 mod mass_properties_cylinder;
 #[cfg(feature = "dim2")]
 mod mass_properties_polygon;
+#[cfg(feature = "dim3")]
+mod mass_properties_polyhedron;
+mod mass_properties_round_cone;
+mod physics_state;
 mod rigid_body;
 mod rigid_body_set;
 mod solver;
@@ -0,0 +1,252 @@
+use crate::dynamics::{RigidBodyHandle, RigidBodySet};
+use crate::math::{AngVector, Isometry, Vector};
+
+/// The identifier of a snapshot recorded by a `PhysicsState`.
+pub type SnapshotId = u64;
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde-serialize", derive(Serialize, Deserialize))]
+/// The position and velocities of a rigid-body.
+pub struct BodyState {
+    /// The world-space position of the rigid-body.
+    pub position: Isometry<f32>,
+    /// The linear velocity of the rigid-body.
+    pub linvel: Vector<f32>,
+    /// The angular velocity of the rigid-body.
+    pub angvel: AngVector<f32>,
+}
+
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde-serialize", derive(Serialize, Deserialize))]
+/// The states of the rigid-bodies that changed since a baseline snapshot.
+///
+/// This is typically serialized and sent by a server to clients that already applied
+/// the baseline snapshot.
+pub struct PhysicsStateDelta {
+    /// The snapshot this delta is relative to.
+    pub baseline: SnapshotId,
+    /// The snapshot this delta leads to, if it was recorded by the sender.
+    pub snapshot: Option<SnapshotId>,
+    /// The new states of the rigid-bodies that changed since the baseline snapshot.
+    pub bodies: Vec<(RigidBodyHandle, BodyState)>,
+}
+
+#[derive(Clone)]
+#[cfg_attr(feature = "serde-serialize", derive(Serialize, Deserialize))]
+/// A set of snapshots of the states of rigid-bodies, used to compute compact state deltas
+/// for networked replication.
+///
+/// A server records a snapshot with `PhysicsState::snapshot` each time it sends its state.
+/// Once a client acknowledged a snapshot, the server sends it only the rigid-bodies that
+/// changed since, with `PhysicsState::delta_since`. The client applies it with
+/// `PhysicsState::apply_delta`.
+///
+/// The insertion and removal of rigid-bodies are not replicated by the deltas: they must
+/// be replicated separately so the rigid-body handles are the same on both sides.
+pub struct PhysicsState {
+    /// The translation above which a rigid-body is considered moved (default: `1.0e-3`).
+    pub translation_threshold: f32,
+    /// The rotation angle above which a rigid-body is considered rotated (default: `1.0e-3`).
+    pub rotation_threshold: f32,
+    /// The change of linear or angular velocity above which a rigid-body is considered
+    /// to have a different velocity (default: `1.0e-3`).
+    pub velocity_threshold: f32,
+    next_snapshot_id: SnapshotId,
+    // Sorted by handle, following the iteration order of the rigid-body set.
+    snapshots: Vec<(SnapshotId, Vec<(RigidBodyHandle, BodyState)>)>,
+}
+
+impl BodyState {
+    fn differs_from(&self, other: &BodyState, state: &PhysicsState) -> bool {
+        let delta_translation =
+            (self.position.translation.vector - other.position.translation.vector).norm();
+        let delta_rotation = self.position.rotation.angle_to(&other.position.rotation);
+        let delta_linvel = (self.linvel - other.linvel).norm();
+        #[cfg(feature = "dim2")]
+        let delta_angvel = (self.angvel - other.angvel).abs();
+        #[cfg(feature = "dim3")]
+        let delta_angvel = (self.angvel - other.angvel).norm();
+
+        delta_translation > state.translation_threshold
+            || delta_rotation > state.rotation_threshold
+            || delta_linvel > state.velocity_threshold
+            || delta_angvel > state.velocity_threshold
+    }
+}
+
+impl PhysicsState {
+    /// Creates a new physics state without any snapshot.
+    pub fn new() -> Self {
+        Self {
+            translation_threshold: 1.0e-3,
+            rotation_threshold: 1.0e-3,
+            velocity_threshold: 1.0e-3,
+            next_snapshot_id: 0,
+            snapshots: Vec::new(),
+        }
+    }
+
+    fn body_states(bodies: &RigidBodySet) -> Vec<(RigidBodyHandle, BodyState)> {
+        bodies
+            .iter()
+            .map(|(handle, rb)| {
+                let state = BodyState {
+                    position: *rb.position(),
+                    linvel: *rb.linvel(),
+                    angvel: rb.angvel,
+                };
+                (handle, state)
+            })
+            .collect()
+    }
+
+    /// Records the current states of all the rigid-bodies and returns the identifier of this snapshot.
+    pub fn snapshot(&mut self, bodies: &RigidBodySet) -> SnapshotId {
+        let id = self.next_snapshot_id;
+        self.next_snapshot_id += 1;
+        self.snapshots.push((id, Self::body_states(bodies)));
+        id
+    }
+
+    /// Is the snapshot with the given identifier still recorded?
+    pub fn contains_snapshot(&self, snapshot_id: SnapshotId) -> bool {
+        self.snapshot_states(snapshot_id).is_some()
+    }
+
+    /// Removes all the snapshots older than the given snapshot.
+    ///
+    /// This should be called once all the clients acknowledged `snapshot_id` in order
+    /// to bound the memory used by the recorded snapshots.
+    pub fn forget_snapshots_before(&mut self, snapshot_id: SnapshotId) {
+        self.snapshots.retain(|(id, _)| *id >= snapshot_id)
+    }
+
+    fn snapshot_states(&self, snapshot_id: SnapshotId) -> Option<&[(RigidBodyHandle, BodyState)]> {
+        self.snapshots
+            .iter()
+            .find(|(id, _)| *id == snapshot_id)
+            .map(|(_, states)| &states[..])
+    }
+
+    /// Computes the states of the rigid-bodies that changed beyond the thresholds of `self`
+    /// since the given baseline snapshot.
+    ///
+    /// The rigid-bodies inserted after the baseline snapshot are always part of the delta.
+    /// Returns `None` if the baseline snapshot isn't recorded.
+    pub fn delta_since(
+        &self,
+        snapshot_id: SnapshotId,
+        bodies: &RigidBodySet,
+    ) -> Option<PhysicsStateDelta> {
+        let baseline = self.snapshot_states(snapshot_id)?;
+        let changed = Self::body_states(bodies)
+            .into_iter()
+            .filter(
+                |(handle, state)| match baseline.binary_search_by_key(handle, |(h, _)| *h) {
+                    Ok(i) => state.differs_from(&baseline[i].1, self),
+                    Err(_) => true,
+                },
+            )
+            .collect();
+
+        Some(PhysicsStateDelta {
+            baseline: snapshot_id,
+            snapshot: None,
+            bodies: changed,
+        })
+    }
+
+    /// Records a snapshot of the current states of all the rigid-bodies, and computes
+    /// the delta leading to it from the given baseline snapshot.
+    ///
+    /// Returns `None`, without recording any snapshot, if the baseline snapshot isn't recorded.
+    pub fn snapshot_delta_since(
+        &mut self,
+        snapshot_id: SnapshotId,
+        bodies: &RigidBodySet,
+    ) -> Option<PhysicsStateDelta> {
+        let mut delta = self.delta_since(snapshot_id, bodies)?;
+        delta.snapshot = Some(self.snapshot(bodies));
+        Some(delta)
+    }
+
+    /// Sets the positions and velocities of the rigid-bodies changed by the given delta.
+    ///
+    /// The modified rigid-bodies are woken up. The rigid-bodies in the delta that don't
+    /// exist in `bodies` are ignored.
+    pub fn apply_delta(delta: &PhysicsStateDelta, bodies: &mut RigidBodySet) {
+        for (handle, state) in &delta.bodies {
+            if let Some(rb) = bodies.get_mut(*handle) {
+                rb.set_position(state.position, true);
+                rb.set_linvel(state.linvel, true);
+                rb.set_angvel(state.angvel, true);
+            }
+        }
+    }
+}
+
+impl Default for PhysicsState {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::PhysicsState;
+    use crate::dynamics::{RigidBodyBuilder, RigidBodySet};
+    use crate::math::{Isometry, Vector};
+
+    #[test]
+    fn delta_contains_only_changed_bodies() {
+        let build_bodies = || {
+            let mut bodies = RigidBodySet::new();
+            let handles: Vec<_> = (0..3)
+                .map(|i| {
+                    let pos = Isometry::new(Vector::x() * i as f32, na::zero());
+                    bodies.insert(RigidBodyBuilder::new_dynamic().position(pos).build())
+                })
+                .collect();
+            (bodies, handles)
+        };
+
+        let (mut server, handles) = build_bodies();
+        let (mut client, _) = build_bodies();
+        let mut state = PhysicsState::new();
+        let baseline = state.snapshot(&server);
+
+        let moved = Isometry::new(Vector::y() * 2.0, na::zero());
+        server
+            .get_mut(handles[0])
+            .unwrap()
+            .set_position(moved, true);
+        server
+            .get_mut(handles[1])
+            .unwrap()
+            .set_linvel(Vector::x() * 1.0e-5, true);
+        let inserted = server.insert(RigidBodyBuilder::new_dynamic().build());
+
+        let delta = state.snapshot_delta_since(baseline, &server).unwrap();
+        let changed: Vec<_> = delta.bodies.iter().map(|(h, _)| *h).collect();
+        assert_eq!(changed, vec![handles[0], inserted]);
+
+        #[cfg(feature = "serde-serialize")]
+        let delta: super::PhysicsStateDelta =
+            bincode::deserialize(&bincode::serialize(&delta).unwrap()).unwrap();
+
+        PhysicsState::apply_delta(&delta, &mut client);
+        assert_eq!(*client[handles[0]].position(), moved);
+
+        // Nothing changed since the new snapshot.
+        let snapshot = delta.snapshot.unwrap();
+        assert!(state
+            .delta_since(snapshot, &server)
+            .unwrap()
+            .bodies
+            .is_empty());
+
+        state.forget_snapshots_before(snapshot);
+        assert!(!state.contains_snapshot(baseline));
+        assert!(state.delta_since(baseline, &server).is_none());
+    }
+}
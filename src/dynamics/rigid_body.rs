@@ -1,6 +1,9 @@
+#[cfg(feature = "dim2")]
+use crate::dynamics::GroundFriction;
 use crate::dynamics::MassProperties;
 use crate::geometry::{
-    Collider, ColliderHandle, ColliderSet, InteractionGraph, RigidBodyGraphIndex,
+    Collider, ColliderHandle, ColliderSet, InteractionGraph, Ray, RayIntersection,
+    RigidBodyGraphIndex, Shape,
 };
 use crate::math::{AngVector, AngularInertia, Isometry, Point, Rotation, Translation, Vector};
 use crate::utils::{self, WCross, WDot};
@@ -33,6 +36,7 @@ bitflags::bitflags! {
         const IGNORE_COLLIDER_ANGULAR_INERTIA_X = 1 << 1;
         const IGNORE_COLLIDER_ANGULAR_INERTIA_Y = 1 << 2;
         const IGNORE_COLLIDER_ANGULAR_INERTIA_Z = 1 << 3;
+        const CCD_ENABLED                       = 1 << 4;
     }
 }
 
@@ -44,6 +48,8 @@ bitflags::bitflags! {
         const POSITION  = 1 << 1;
         const SLEEP     = 1 << 2;
         const COLLIDERS = 1 << 3;
+        const BODY_STATUS = 1 << 4;
+        const ENABLED     = 1 << 5;
     }
 }
 
@@ -58,6 +64,14 @@ pub struct RigidBody {
     pub(crate) predicted_position: Isometry<f32>,
     /// The local mass properties of the rigid-body.
     pub(crate) mass_properties: MassProperties,
+    /// The mass properties added to the contributions of the colliders attached to this rigid-body.
+    additional_mass_properties: MassProperties,
+    /// The sum of the mass properties of the colliders attached to this rigid-body.
+    colliders_mass_properties: MassProperties,
+    /// The contribution of each collider of `self.colliders` to `self.colliders_mass_properties`.
+    colliders_mass_contributions: Vec<MassProperties>,
+    /// The user-defined local center-of-mass replacing the computed one.
+    local_com_override: Option<Point<f32>>,
     /// The world-space center of mass of the rigid-body.
     pub world_com: Point<f32>,
     /// The square-root of the inverse angular inertia tensor of the rigid-body.
@@ -70,8 +84,27 @@ pub struct RigidBody {
     pub linear_damping: f32,
     /// Damping factor for gradually slowing down the angular motion of the rigid-body.
     pub angular_damping: f32,
+    /// The maximum norm of the linear velocity of the rigid-body.
+    ///
+    /// If set, the linear velocity is scaled down to this norm at each timestep, after the
+    /// resolution of the velocity constraints and before the integration of the position.
+    pub max_linear_velocity: Option<f32>,
+    /// The maximum norm of the angular velocity of the rigid-body.
+    ///
+    /// If set, the angular velocity is scaled down to this norm at each timestep, after the
+    /// resolution of the velocity constraints and before the integration of the position.
+    pub max_angular_velocity: Option<f32>,
     pub(crate) linacc: Vector<f32>,
     pub(crate) angacc: AngVector<f32>,
+    /// The force applied to this rigid-body at each timestep until it is reset.
+    pub(crate) force: Vector<f32>,
+    /// The torque applied to this rigid-body at each timestep until it is reset.
+    pub(crate) torque: AngVector<f32>,
+    /// The gravity applied to this rigid-body instead of the global gravity.
+    gravity_override: Option<Vector<f32>>,
+    /// The friction with an implicit ground plane, for top-down simulations.
+    #[cfg(feature = "dim2")]
+    ground_friction: Option<GroundFriction>,
     pub(crate) colliders: Vec<ColliderHandle>,
     /// Whether or not this rigid-body is sleeping.
     pub activation: ActivationStatus,
@@ -83,7 +116,11 @@ pub struct RigidBody {
     flags: RigidBodyFlags,
     pub(crate) changes: RigidBodyChanges,
     /// The status of the body, governing how it is affected by external forces.
+    ///
+    /// Use `RigidBody::set_body_status` to modify the status of a rigid-body that has
+    /// already been added to a `RigidBodySet`.
     pub body_status: BodyStatus,
+    enabled: bool,
     /// User-defined data associated to this rigid-body.
     pub user_data: u128,
 }
@@ -94,14 +131,25 @@ impl RigidBody {
             position: Isometry::identity(),
             predicted_position: Isometry::identity(),
             mass_properties: MassProperties::zero(),
+            additional_mass_properties: MassProperties::zero(),
+            colliders_mass_properties: MassProperties::zero(),
+            colliders_mass_contributions: Vec::new(),
+            local_com_override: None,
             world_com: Point::origin(),
             world_inv_inertia_sqrt: AngularInertia::zero(),
             linvel: Vector::zeros(),
             angvel: na::zero(),
             linacc: Vector::zeros(),
             angacc: na::zero(),
+            force: Vector::zeros(),
+            torque: na::zero(),
+            gravity_override: None,
+            #[cfg(feature = "dim2")]
+            ground_friction: None,
             linear_damping: 0.0,
             angular_damping: 0.0,
+            max_linear_velocity: None,
+            max_angular_velocity: None,
             colliders: Vec::new(),
             activation: ActivationStatus::new_active(),
             joint_graph_index: InteractionGraph::<()>::invalid_graph_index(),
@@ -112,6 +160,7 @@ impl RigidBody {
             flags: RigidBodyFlags::empty(),
             changes: RigidBodyChanges::all(),
             body_status: BodyStatus::Dynamic,
+            enabled: true,
             user_data: 0,
         }
     }
@@ -127,8 +176,26 @@ impl RigidBody {
 
     pub(crate) fn integrate_accelerations(&mut self, dt: f32, gravity: Vector<f32>) {
         if self.mass_properties.inv_mass != 0.0 {
-            self.linvel += (gravity + self.linacc) * dt;
-            self.angvel += self.angacc * dt;
+            let gravity = self.gravity_override.unwrap_or(gravity);
+            let linacc = self.linacc + self.force * self.mass_properties.inv_mass;
+            let angacc = self.angacc
+                + self.world_inv_inertia_sqrt * (self.world_inv_inertia_sqrt * self.torque);
+            self.linvel += (gravity + linacc) * dt;
+            self.angvel += angacc * dt;
+
+            #[cfg(feature = "dim2")]
+            {
+                if let Some(friction) = &self.ground_friction {
+                    friction.apply(
+                        dt,
+                        &self.position.rotation,
+                        self.mass_properties.inv_mass,
+                        self.mass_properties.inv_principal_inertia_sqrt,
+                        &mut self.linvel,
+                        &mut self.angvel,
+                    );
+                }
+            }
 
             // Reset the accelerations.
             self.linacc = na::zero();
@@ -144,6 +211,10 @@ impl RigidBody {
 
     /// Sets the rigid-body's mass properties.
     ///
+    /// This replaces the mass properties of this rigid-body: the contributions of its colliders
+    /// are ignored from now on, including these of the colliders attached or modified afterwards.
+    /// Use `self.set_additional_mass_properties` to keep the contributions of the colliders.
+    ///
     /// If `wake_up` is `true` then the rigid-body will be woken up if it was
     /// put to sleep because it did not move for a while.
     #[inline]
@@ -152,7 +223,134 @@ impl RigidBody {
             self.wake_up(true);
         }
 
-        self.mass_properties = props;
+        self.flags.insert(
+            RigidBodyFlags::IGNORE_COLLIDER_MASS
+                | RigidBodyFlags::IGNORE_COLLIDER_ANGULAR_INERTIA_X
+                | RigidBodyFlags::IGNORE_COLLIDER_ANGULAR_INERTIA_Y
+                | RigidBodyFlags::IGNORE_COLLIDER_ANGULAR_INERTIA_Z,
+        );
+        self.additional_mass_properties = props;
+        self.colliders_mass_properties = MassProperties::zero();
+        self.colliders_mass_contributions
+            .iter_mut()
+            .for_each(|contribution| *contribution = MassProperties::zero());
+        self.update_mass_properties();
+    }
+
+    /// The mass properties added to the contributions of the colliders attached to this rigid-body.
+    #[inline]
+    pub fn additional_mass_properties(&self) -> &MassProperties {
+        &self.additional_mass_properties
+    }
+
+    /// Sets the mass properties added to the contributions of the colliders attached to this rigid-body.
+    ///
+    /// The final mass properties of this rigid-body are the sum of `props` and of the mass properties
+    /// of its colliders. To make `props` the only mass properties of this rigid-body, attach colliders
+    /// with a density equal to zero, or disable the collider contributions with
+    /// [`RigidBodyBuilder::mass`] and [`RigidBodyBuilder::principal_angular_inertia`].
+    ///
+    /// If `wake_up` is `true` then the rigid-body will be woken up if it was
+    /// put to sleep because it did not move for a while.
+    pub fn set_additional_mass_properties(&mut self, props: MassProperties, wake_up: bool) {
+        if self.is_dynamic() && wake_up {
+            self.wake_up(true);
+        }
+
+        self.additional_mass_properties = props;
+        self.update_mass_properties();
+    }
+
+    /// The user-defined center-of-mass of this rigid-body, expressed in its local-space, if any.
+    #[inline]
+    pub fn local_center_of_mass_override(&self) -> Option<&Point<f32>> {
+        self.local_com_override.as_ref()
+    }
+
+    /// Overrides the center-of-mass of this rigid-body, expressed in its local-space.
+    ///
+    /// The angular inertia of the rigid-body is left unchanged. Set this to `None` to go back
+    /// to the center-of-mass computed from the additional mass properties and the colliders.
+    ///
+    /// If `wake_up` is `true` then the rigid-body will be woken up if it was
+    /// put to sleep because it did not move for a while.
+    pub fn set_local_center_of_mass(&mut self, local_com: Option<Point<f32>>, wake_up: bool) {
+        if self.is_dynamic() && wake_up {
+            self.wake_up(true);
+        }
+
+        self.local_com_override = local_com;
+        self.update_mass_properties();
+    }
+
+    /// The gravity applied to this rigid-body instead of the global gravity, if any.
+    pub fn gravity_override(&self) -> Option<Vector<f32>> {
+        self.gravity_override
+    }
+
+    /// Sets the gravity applied to this rigid-body instead of the global gravity passed to
+    /// `PhysicsPipeline::step`.
+    ///
+    /// This can be modified before each timestep to simulate a gravity field that depends on the
+    /// position of the rigid-body, e.g., to attract it toward the center of a planet. Set this to
+    /// `None` to go back to the global gravity.
+    ///
+    /// If `wake_up` is `true` then the rigid-body will be woken up if it was
+    /// put to sleep because it did not move for a while.
+    pub fn set_gravity_override(&mut self, gravity: Option<Vector<f32>>, wake_up: bool) {
+        if self.is_dynamic() && wake_up && gravity != self.gravity_override {
+            self.wake_up(true);
+        }
+
+        self.gravity_override = gravity;
+    }
+
+    /// The friction between this rigid-body and an implicit ground plane, if any.
+    #[cfg(feature = "dim2")]
+    pub fn ground_friction(&self) -> Option<&GroundFriction> {
+        self.ground_friction.as_ref()
+    }
+
+    /// Sets the friction between this rigid-body and an implicit ground plane, for top-down
+    /// simulations.
+    ///
+    /// This friction is applied at each timestep in addition to the global gravity, which is
+    /// usually zero in top-down simulations. See `GroundFriction` for details.
+    ///
+    /// If `wake_up` is `true` then the rigid-body will be woken up if it was
+    /// put to sleep because it did not move for a while.
+    #[cfg(feature = "dim2")]
+    pub fn set_ground_friction(&mut self, friction: Option<GroundFriction>, wake_up: bool) {
+        if self.is_dynamic() && wake_up && friction != self.ground_friction {
+            self.wake_up(true);
+        }
+
+        self.ground_friction = friction;
+    }
+
+    /// Is the continuous collision detection enabled for this rigid-body?
+    pub fn is_ccd_enabled(&self) -> bool {
+        self.flags.contains(RigidBodyFlags::CCD_ENABLED)
+    }
+
+    /// Enables or disables the continuous collision detection for this rigid-body.
+    ///
+    /// The broad-phase enlarges the AABBs of the colliders of a CCD-enabled rigid-body so they
+    /// contain the whole motion of the colliders during the next timestep. Therefore, a fast
+    /// rigid-body generates collision pairs with all the obstacles it may reach during this timestep,
+    /// instead of only these it touches at the beginning of the timestep.
+    pub fn enable_ccd(&mut self, enabled: bool) {
+        self.flags.set(RigidBodyFlags::CCD_ENABLED, enabled);
+    }
+
+    pub(crate) fn update_mass_properties(&mut self) {
+        self.mass_properties = self.additional_mass_properties + self.colliders_mass_properties;
+
+        if let Some(local_com) = self.local_com_override {
+            self.mass_properties.local_com = local_com;
+        }
+
+        self.update_world_mass_properties();
     }
 
     /// The handles of colliders attached to this rigid body.
@@ -160,6 +358,82 @@ impl RigidBody {
         &self.colliders[..]
     }
 
+    /// The world-space positions of the enabled colliders attached to this rigid body,
+    /// given the current position of this rigid body.
+    fn enabled_colliders_with_positions<'a>(
+        &'a self,
+        colliders: &'a ColliderSet,
+    ) -> impl Iterator<Item = (ColliderHandle, &'a Collider, Isometry<f32>)> {
+        self.colliders.iter().filter_map(move |handle| {
+            let collider = colliders.get(*handle)?;
+            if collider.is_enabled() {
+                let position = self.position * collider.position_wrt_parent();
+                Some((*handle, collider, position))
+            } else {
+                None
+            }
+        })
+    }
+
+    /// Find the closest intersection between a ray and the colliders attached to this rigid body.
+    ///
+    /// Only the colliders of this rigid body are tested, at their positions relative to the current
+    /// position of this rigid body. This is useful to find which part of a body is hit by a ray
+    /// without performing a query on the whole scene. Disabled colliders are ignored, and sensors
+    /// are tested like the other colliders.
+    ///
+    /// # Parameters
+    /// - `colliders`: the set containing the colliders attached to this rigid body.
+    /// - `ray`: the ray to cast.
+    /// - `max_toi`: the maximum time-of-impact that can be reported by this cast. This effectively
+    ///   limits the length of the ray to `ray.dir.norm() * max_toi`. Use `f32::MAX` for an unbounded ray.
+    pub fn cast_ray(
+        &self,
+        colliders: &ColliderSet,
+        ray: &Ray,
+        max_toi: f32,
+    ) -> Option<(ColliderHandle, RayIntersection)> {
+        let mut result: Option<(ColliderHandle, RayIntersection)> = None;
+
+        for (handle, collider, position) in self.enabled_colliders_with_positions(colliders) {
+            if let Some(inter) = collider
+                .shape()
+                .toi_and_normal_with_ray(&position, ray, max_toi, true)
+            {
+                if result.map(|(_, best)| inter.toi < best.toi).unwrap_or(true) {
+                    result = Some((handle, inter));
+                }
+            }
+        }
+
+        result
+    }
+
+    /// Find the first collider attached to this rigid body intersecting the given shape.
+    ///
+    /// Only the colliders of this rigid body are tested, at their positions relative to the current
+    /// position of this rigid body. Disabled colliders are ignored, and sensors are tested like the
+    /// other colliders. See [`intersection_test`](crate::geometry::intersection_test) for the
+    /// supported shape combinations: the colliders that can't be tested against `shape` are ignored.
+    ///
+    /// # Parameters
+    /// - `colliders`: the set containing the colliders attached to this rigid body.
+    /// - `shape_pos`: the world-space position of the shape to test.
+    /// - `shape`: the shape to test.
+    pub fn intersects_shape(
+        &self,
+        colliders: &ColliderSet,
+        shape_pos: &Isometry<f32>,
+        shape: &dyn Shape,
+    ) -> Option<ColliderHandle> {
+        self.enabled_colliders_with_positions(colliders)
+            .find(|(_, collider, position)| {
+                crate::geometry::intersection_test(collider.shape(), position, shape, shape_pos)
+                    .unwrap_or(false)
+            })
+            .map(|(handle, _, _)| handle)
+    }
+
     /// Is this rigid body dynamic?
     ///
     /// A dynamic body can move freely and is affected by forces.
@@ -181,6 +455,68 @@ impl RigidBody {
         self.body_status == BodyStatus::Static
     }
 
+    /// Sets the status of this rigid-body.
+    ///
+    /// The rigid-body is moved to the relevant active sets at the beginning of the next timestep,
+    /// and the dynamic bodies touching it or attached to it by a joint are woken up. Making a
+    /// rigid-body static or kinematic resets its velocities and its next kinematic position.
+    ///
+    /// The rigid-body must be accessed with `RigidBodySet::get_mut` for this change to be
+    /// taken into account.
+    pub fn set_body_status(&mut self, status: BodyStatus) {
+        if status == self.body_status {
+            return;
+        }
+
+        self.body_status = status;
+        self.changes
+            .insert(RigidBodyChanges::BODY_STATUS | RigidBodyChanges::POSITION);
+
+        if self.is_dynamic() {
+            // Make sure the body is added to the active dynamic set.
+            self.changes.insert(RigidBodyChanges::SLEEP);
+        } else {
+            self.linvel = na::zero();
+            self.angvel = na::zero();
+            self.predicted_position = self.position;
+        }
+
+        self.wake_up(true);
+    }
+
+    /// Is this rigid-body enabled?
+    ///
+    /// A disabled rigid-body is not simulated, and its colliders do not interact with any other
+    /// collider. It still retains all its state and its handle remains valid.
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    /// Enables or disables this rigid-body.
+    ///
+    /// Disabling a rigid-body removes it from the active sets and removes all the contacts and
+    /// proximities involving its colliders, without removing it from the `RigidBodySet`. The
+    /// joints attached to a disabled rigid-body are ignored by the solver. Its velocities and
+    /// forces are kept unchanged, so it resumes its motion once it is enabled again.
+    ///
+    /// The rigid-body must be accessed with `RigidBodySet::get_mut` for this change to be
+    /// taken into account.
+    pub fn set_enabled(&mut self, enabled: bool) {
+        if enabled == self.enabled {
+            return;
+        }
+
+        self.enabled = enabled;
+        self.changes
+            .insert(RigidBodyChanges::ENABLED | RigidBodyChanges::COLLIDERS);
+
+        if enabled && self.is_dynamic() {
+            // Make sure the body is added back to the active dynamic set.
+            self.changes.insert(RigidBodyChanges::SLEEP);
+            self.wake_up(true);
+        }
+    }
+
     /// The mass of this rigid body.
     ///
     /// Returns zero if this rigid body has an infinite mass.
@@ -188,6 +524,35 @@ impl RigidBody {
         utils::inv(self.mass_properties.inv_mass)
     }
 
+    /// The center-of-mass of this rigid-body, expressed in world-space.
+    pub fn center_of_mass(&self) -> &Point<f32> {
+        &self.world_com
+    }
+
+    /// The center-of-mass of this rigid-body, expressed in its local-space.
+    pub fn local_center_of_mass(&self) -> &Point<f32> {
+        &self.mass_properties.local_com
+    }
+
+    /// The angular inertia of this rigid-body, at its center-of-mass.
+    ///
+    /// Returns zero if this rigid body has an infinite angular inertia.
+    #[cfg(feature = "dim2")]
+    pub fn world_inertia_tensor(&self) -> f32 {
+        let inv_inertia_sqrt = self.mass_properties.inv_principal_inertia_sqrt;
+        utils::inv(inv_inertia_sqrt * inv_inertia_sqrt)
+    }
+
+    /// The angular inertia tensor of this rigid-body, at its center-of-mass and expressed in world-space.
+    ///
+    /// The principal inertia along the axes with an infinite angular inertia, e.g., the
+    /// axes with locked rotations, is zero.
+    #[cfg(feature = "dim3")]
+    pub fn world_inertia_tensor(&self) -> na::Matrix3<f32> {
+        let rot = self.position.rotation.to_rotation_matrix();
+        rot * self.mass_properties.reconstruct_inertia_matrix() * rot.inverse()
+    }
+
     /// The predicted position of this rigid-body.
     ///
     /// If this rigid-body is kinematic this value is set by the `set_next_kinematic_position`
@@ -199,17 +564,31 @@ impl RigidBody {
 
     /// Adds a collider to this rigid-body.
     pub(crate) fn add_collider(&mut self, handle: ColliderHandle, coll: &Collider) {
+        let mass_properties = coll
+            .mass_properties()
+            .transform_by(coll.position_wrt_parent());
+        self.add_collider_without_update(handle, mass_properties);
+        self.update_mass_properties();
+    }
+
+    /// Adds a collider with the given mass properties, expressed in the local-space of this
+    /// rigid-body, without updating the mass properties of this rigid-body.
+    ///
+    /// `update_mass_properties` must be called after all the colliders have been added.
+    pub(crate) fn add_collider_without_update(
+        &mut self,
+        handle: ColliderHandle,
+        mass_properties: MassProperties,
+    ) {
         self.changes.set(
             RigidBodyChanges::MODIFIED | RigidBodyChanges::COLLIDERS,
             true,
         );
 
-        let mass_properties = coll
-            .mass_properties()
-            .transform_by(coll.position_wrt_parent());
+        let contribution = Self::filter_collider_mass_props(mass_properties, self.flags);
         self.colliders.push(handle);
-        self.mass_properties += Self::filter_collider_mass_props(mass_properties, self.flags);
-        self.update_world_mass_properties();
+        self.colliders_mass_contributions.push(contribution);
+        self.colliders_mass_properties += contribution;
     }
 
     fn filter_collider_mass_props(
@@ -242,24 +621,65 @@ impl RigidBody {
         props
     }
 
+    pub(crate) fn update_colliders_predicted_positions(&self, colliders: &mut ColliderSet) {
+        for handle in &self.colliders {
+            let collider = &mut colliders[*handle];
+            collider.predicted_position = self.predicted_position * collider.delta;
+        }
+    }
+
     pub(crate) fn update_colliders_positions(&mut self, colliders: &mut ColliderSet) {
         for handle in &self.colliders {
             let collider = &mut colliders[*handle];
             collider.position = self.position * collider.delta;
             collider.predicted_position = self.predicted_position * collider.delta;
+            colliders.mark_collider_moved(*handle);
         }
     }
 
     /// Removes a collider from this rigid-body.
-    pub(crate) fn remove_collider_internal(&mut self, handle: ColliderHandle, coll: &Collider) {
+    pub(crate) fn remove_collider_internal(&mut self, handle: ColliderHandle) {
         if let Some(i) = self.colliders.iter().position(|e| *e == handle) {
             self.changes.set(RigidBodyChanges::COLLIDERS, true);
             self.colliders.swap_remove(i);
+            let contribution = self.colliders_mass_contributions.swap_remove(i);
+
+            if self.colliders.is_empty() {
+                // NOTE: reset explicitly so rounding errors don't leave a residual mass.
+                self.colliders_mass_properties = MassProperties::zero();
+            } else {
+                self.colliders_mass_properties -= contribution;
+            }
+
+            self.update_mass_properties();
+        }
+    }
+
+    /// Replaces the contribution of the given collider to the mass properties of this rigid-body.
+    ///
+    /// This is done automatically whenever one of the colliders of this rigid-body has its mass
+    /// properties modified.
+    pub(crate) fn update_collider_mass_properties(
+        &mut self,
+        handle: ColliderHandle,
+        coll: &Collider,
+    ) {
+        if let Some(i) = self.colliders.iter().position(|e| *e == handle) {
             let mass_properties = coll
                 .mass_properties()
                 .transform_by(coll.position_wrt_parent());
-            self.mass_properties -= Self::filter_collider_mass_props(mass_properties, self.flags);
-            self.update_world_mass_properties();
+            let contribution = Self::filter_collider_mass_props(mass_properties, self.flags);
+            let old_contribution =
+                std::mem::replace(&mut self.colliders_mass_contributions[i], contribution);
+
+            if self.colliders.len() == 1 {
+                self.colliders_mass_properties = contribution;
+            } else {
+                self.colliders_mass_properties =
+                    self.colliders_mass_properties - old_contribution + contribution;
+            }
+
+            self.update_mass_properties();
         }
     }
 
@@ -321,6 +741,12 @@ impl RigidBody {
         // TODO: do we want to apply damping before or after the velocity integration?
         self.linvel *= 1.0 / (1.0 + dt * self.linear_damping);
         self.angvel *= 1.0 / (1.0 + dt * self.angular_damping);
+        utils::clamp_velocities(
+            &mut self.linvel,
+            &mut self.angvel,
+            self.max_linear_velocity.unwrap_or(f32::MAX),
+            self.max_angular_velocity.unwrap_or(f32::MAX),
+        );
 
         self.position = self.integrate_velocity(dt) * self.position;
     }
@@ -394,6 +820,11 @@ impl RigidBody {
     ///
     /// If `wake_up` is `true` then the rigid-body will be woken up if it was
     /// put to sleep because it did not move for a while.
+    ///
+    /// The positions of the colliders attached to this rigid-body are updated at the
+    /// beginning of the next timestep, and the impulses of the contacts involving them are
+    /// reset so they are not used for warm-starting. Use `RigidBody::teleport` to update
+    /// the colliders positions immediately.
     pub fn set_position(&mut self, pos: Isometry<f32>, wake_up: bool) {
         self.changes.insert(RigidBodyChanges::POSITION);
         self.set_position_internal(pos);
@@ -404,6 +835,34 @@ impl RigidBody {
         }
     }
 
+    /// Teleports this rigid-body to the specified position/orientation, and wakes it up.
+    ///
+    /// Unlike `RigidBody::set_position`, the positions, and thus the AABBs, of the colliders
+    /// attached to this rigid-body are updated immediately. If `reset_velocities` is `true`,
+    /// the linear and angular velocities of this rigid-body are set to zero. In any case, the
+    /// impulses of the contacts involving this rigid-body are reset at the next timestep so
+    /// that the leftover impulses from its previous position are not applied again.
+    ///
+    /// The rigid-body must be accessed with `RigidBodySet::get_mut` for this change to be
+    /// taken into account.
+    pub fn teleport(
+        &mut self,
+        pos: Isometry<f32>,
+        reset_velocities: bool,
+        colliders: &mut ColliderSet,
+    ) {
+        self.set_position(pos, true);
+        self.predicted_position = pos;
+
+        if reset_velocities {
+            self.linvel = na::zero();
+            self.angvel = na::zero();
+        }
+
+        self.update_world_mass_properties();
+        self.update_colliders_positions(colliders);
+    }
+
     pub(crate) fn set_position_internal(&mut self, pos: Isometry<f32>) {
         self.position = pos;
 
@@ -414,6 +873,10 @@ impl RigidBody {
     }
 
     /// If this rigid body is kinematic, sets its future position after the next timestep integration.
+    ///
+    /// The velocity of the kinematic body, estimated from this position, is used by the contacts
+    /// involving this body. Bodies resting on a moving or rotating kinematic platform are therefore
+    /// carried along by friction.
     pub fn set_next_kinematic_position(&mut self, pos: Isometry<f32>) {
         if self.is_kinematic() {
             self.predicted_position = pos;
@@ -430,7 +893,13 @@ impl RigidBody {
         {
             self.angvel = dpos.rotation.scaled_axis() * inv_dt;
         }
-        self.linvel = dpos.translation.vector * inv_dt;
+
+        // The linear velocity is the velocity of the center-of-mass. This way, contacts
+        // with the surface of this body see the velocity of the surface itself,
+        // `linvel + angvel × (point - world_com)`, even if the body rotates.
+        self.world_com = self.mass_properties.world_com(&self.position);
+        let next_com = self.mass_properties.world_com(&self.predicted_position);
+        self.linvel = (next_com - self.world_com) * inv_dt;
     }
 
     pub(crate) fn update_predicted_position(&mut self, dt: f32) {
@@ -446,8 +915,108 @@ impl RigidBody {
 
     /*
      * Application of forces/impulses.
+     *
+     * At each timestep, the velocity of every awake dynamic rigid-body is first updated by
+     * integrating the gravity (or its gravity override), the persistent forces added with `add_force`, `add_torque`,
+     * or `add_force_at_point` and the one-shot forces applied with `apply_force`,
+     * `apply_torque`, or `apply_force_at_point`. The one-shot forces are then reset, while
+     * the persistent forces remain until `reset_forces` or `reset_torques` is called.
+     * Only then are the contacts and joints solved.
+     *
+     * Impulses, applied with `apply_impulse`, `apply_torque_impulse`, or `apply_impulse_at_point`,
+     * modify the velocity of the rigid-body immediately.
      */
+    /// The persistent force applied to this rigid-body at each timestep.
+    pub fn user_force(&self) -> Vector<f32> {
+        self.force
+    }
+
+    /// The persistent torque applied to this rigid-body at each timestep.
+    #[cfg(feature = "dim2")]
+    pub fn user_torque(&self) -> f32 {
+        self.torque
+    }
+
+    /// The persistent torque applied to this rigid-body at each timestep.
+    #[cfg(feature = "dim3")]
+    pub fn user_torque(&self) -> Vector<f32> {
+        self.torque
+    }
+
+    /// Adds a force at the center-of-mass of this rigid-body.
+    ///
+    /// This force is applied at each timestep until `self.reset_forces` is called.
+    pub fn add_force(&mut self, force: Vector<f32>, wake_up: bool) {
+        if self.body_status == BodyStatus::Dynamic {
+            self.force += force;
+
+            if wake_up {
+                self.wake_up(true);
+            }
+        }
+    }
+
+    /// Adds a torque at the center-of-mass of this rigid-body.
+    ///
+    /// This torque is applied at each timestep until `self.reset_torques` is called.
+    #[cfg(feature = "dim2")]
+    pub fn add_torque(&mut self, torque: f32, wake_up: bool) {
+        if self.body_status == BodyStatus::Dynamic {
+            self.torque += torque;
+
+            if wake_up {
+                self.wake_up(true);
+            }
+        }
+    }
+
+    /// Adds a torque at the center-of-mass of this rigid-body.
+    ///
+    /// This torque is applied at each timestep until `self.reset_torques` is called.
+    #[cfg(feature = "dim3")]
+    pub fn add_torque(&mut self, torque: Vector<f32>, wake_up: bool) {
+        if self.body_status == BodyStatus::Dynamic {
+            self.torque += torque;
+
+            if wake_up {
+                self.wake_up(true);
+            }
+        }
+    }
+
+    /// Adds a force at the given world-space point of this rigid-body.
+    ///
+    /// This is converted to a force and a torque at the center-of-mass of this rigid-body,
+    /// computed from its current position. They are applied at each timestep until
+    /// `self.reset_forces` and `self.reset_torques` are called.
+    pub fn add_force_at_point(&mut self, force: Vector<f32>, point: Point<f32>, wake_up: bool) {
+        let torque = (point - self.world_com).gcross(force);
+        self.add_force(force, wake_up);
+        self.add_torque(torque, wake_up);
+    }
+
+    /// Resets to zero the persistent force applied to this rigid-body.
+    pub fn reset_forces(&mut self, wake_up: bool) {
+        if self.force != Vector::zeros() && wake_up {
+            self.wake_up(true);
+        }
+
+        self.force = Vector::zeros();
+    }
+
+    /// Resets to zero the persistent torque applied to this rigid-body.
+    pub fn reset_torques(&mut self, wake_up: bool) {
+        if !self.torque.is_zero() && wake_up {
+            self.wake_up(true);
+        }
+
+        self.torque = na::zero();
+    }
+
     /// Applies a force at the center-of-mass of this rigid-body.
+    ///
+    /// This force is only applied during the next timestep. Use `self.add_force`
+    /// for a force applied continuously.
     pub fn apply_force(&mut self, force: Vector<f32>, wake_up: bool) {
         if self.body_status == BodyStatus::Dynamic {
             self.linacc += force * self.mass_properties.inv_mass;
@@ -470,6 +1039,9 @@ impl RigidBody {
     }
 
     /// Applies a torque at the center-of-mass of this rigid-body.
+    ///
+    /// This torque is only applied during the next timestep. Use `self.add_torque`
+    /// for a torque applied continuously.
     #[cfg(feature = "dim2")]
     pub fn apply_torque(&mut self, torque: f32, wake_up: bool) {
         if self.body_status == BodyStatus::Dynamic {
@@ -482,6 +1054,9 @@ impl RigidBody {
     }
 
     /// Applies a torque at the center-of-mass of this rigid-body.
+    ///
+    /// This torque is only applied during the next timestep. Use `self.add_torque`
+    /// for a torque applied continuously.
     #[cfg(feature = "dim3")]
     pub fn apply_torque(&mut self, torque: Vector<f32>, wake_up: bool) {
         if self.body_status == BodyStatus::Dynamic {
@@ -520,6 +1095,9 @@ impl RigidBody {
     }
 
     /// Applies a force at the given world-space point of this rigid-body.
+    ///
+    /// This force is only applied during the next timestep. Use `self.add_force_at_point`
+    /// for a force applied continuously.
     pub fn apply_force_at_point(&mut self, force: Vector<f32>, point: Point<f32>, wake_up: bool) {
         let torque = (point - self.world_com).gcross(force);
         self.apply_force(force, wake_up);
@@ -539,24 +1117,45 @@ impl RigidBody {
     }
 
     /// The velocity of the given world-space point on this rigid-body.
+    ///
+    /// This is the linear velocity of the center-of-mass, plus the velocity induced by the
+    /// rotation of the rigid-body around its center-of-mass at its current position.
     pub fn velocity_at_point(&self, point: &Point<f32>) -> Vector<f32> {
-        let dpt = point - self.world_com;
+        let dpt = point - self.mass_properties.world_com(&self.position);
         self.linvel + self.angvel.gcross(dpt)
     }
+
+    /// The position this rigid-body would reach after `dt` seconds if it kept its current
+    /// linear and angular velocities.
+    ///
+    /// The rigid-body rotates around its center-of-mass, like during the timestep integration.
+    /// Forces, gravity, damping, and contacts are not taken into account.
+    pub fn predict_position_after(&self, dt: f32) -> Isometry<f32> {
+        self.integrate_velocity(dt) * self.position
+    }
 }
 
 /// A builder for rigid-bodies.
+#[derive(Clone)]
+#[cfg_attr(feature = "serde-serialize", derive(Serialize, Deserialize))]
 pub struct RigidBodyBuilder {
     position: Isometry<f32>,
     linvel: Vector<f32>,
     angvel: AngVector<f32>,
     linear_damping: f32,
     angular_damping: f32,
+    max_linear_velocity: Option<f32>,
+    max_angular_velocity: Option<f32>,
     body_status: BodyStatus,
     flags: RigidBodyFlags,
     mass_properties: MassProperties,
+    local_com: Option<Point<f32>>,
+    gravity_override: Option<Vector<f32>>,
+    #[cfg(feature = "dim2")]
+    ground_friction: Option<GroundFriction>,
     can_sleep: bool,
     sleeping: bool,
+    enabled: bool,
     user_data: u128,
 }
 
@@ -569,11 +1168,18 @@ impl RigidBodyBuilder {
             angvel: na::zero(),
             linear_damping: 0.0,
             angular_damping: 0.0,
+            max_linear_velocity: None,
+            max_angular_velocity: None,
             body_status,
             flags: RigidBodyFlags::empty(),
             mass_properties: MassProperties::zero(),
+            local_com: None,
+            gravity_override: None,
+            #[cfg(feature = "dim2")]
+            ground_friction: None,
             can_sleep: true,
             sleeping: false,
+            enabled: true,
             user_data: 0,
         }
     }
@@ -638,11 +1244,21 @@ impl RigidBodyBuilder {
     /// Therefore, if you want your provided mass properties to be the final
     /// mass properties of your rigid-body, don't attach colliders to it, or
     /// only attach colliders with densities equal to zero.
+    ///
+    /// This is the builder equivalent of [`RigidBody::set_additional_mass_properties`].
     pub fn mass_properties(mut self, props: MassProperties) -> Self {
         self.mass_properties = props;
         self
     }
 
+    /// Overrides the center-of-mass of the rigid-body being built, expressed in its local-space.
+    ///
+    /// See the documentation of [`RigidBody::set_local_center_of_mass`] for more details.
+    pub fn local_center_of_mass(mut self, local_com: Point<f32>) -> Self {
+        self.local_com = Some(local_com);
+        self
+    }
+
     /// Prevents this rigid-body from translating because of forces.
     ///
     /// This is equivalent to `self.mass(0.0, false)`. See the
@@ -761,6 +1377,23 @@ impl RigidBodyBuilder {
         self.principal_angular_inertia(inertia, colliders_contribution_enabled)
     }
 
+    /// Sets the gravity applied to the rigid-body to be created instead of the global gravity.
+    ///
+    /// See `RigidBody::set_gravity_override` for details.
+    pub fn gravity_override(mut self, gravity: Vector<f32>) -> Self {
+        self.gravity_override = Some(gravity);
+        self
+    }
+
+    /// Sets the friction between the rigid-body to be created and an implicit ground plane.
+    ///
+    /// See `RigidBody::set_ground_friction` for details.
+    #[cfg(feature = "dim2")]
+    pub fn ground_friction(mut self, friction: GroundFriction) -> Self {
+        self.ground_friction = Some(friction);
+        self
+    }
+
     /// Sets the damping factor for the linear part of the rigid-body motion.
     ///
     /// The higher the linear damping factor is, the more quickly the rigid-body
@@ -779,6 +1412,22 @@ impl RigidBodyBuilder {
         self
     }
 
+    /// Sets the maximum norm of the linear velocity of the rigid-body to be created.
+    ///
+    /// See `RigidBody::max_linear_velocity` for details.
+    pub fn max_linear_velocity(mut self, max_linear_velocity: f32) -> Self {
+        self.max_linear_velocity = Some(max_linear_velocity);
+        self
+    }
+
+    /// Sets the maximum norm of the angular velocity of the rigid-body to be created.
+    ///
+    /// See `RigidBody::max_angular_velocity` for details.
+    pub fn max_angular_velocity(mut self, max_angular_velocity: f32) -> Self {
+        self.max_angular_velocity = Some(max_angular_velocity);
+        self
+    }
+
     /// Sets the initial linear velocity of the rigid-body to be created.
     #[cfg(feature = "dim2")]
     pub fn linvel(mut self, x: f32, y: f32) -> Self {
@@ -811,6 +1460,18 @@ impl RigidBodyBuilder {
         self
     }
 
+    /// Sets whether or not the rigid-body is to be created enabled.
+    pub fn enabled(mut self, enabled: bool) -> Self {
+        self.enabled = enabled;
+        self
+    }
+
+    /// Enables or disables the continuous collision detection for the rigid-body to be created.
+    pub fn ccd_enabled(mut self, enabled: bool) -> Self {
+        self.flags.set(RigidBodyFlags::CCD_ENABLED, enabled);
+        self
+    }
+
     /// Build a new rigid-body with the parameters configured with this builder.
     pub fn build(&self) -> RigidBody {
         let mut rb = RigidBody::new();
@@ -819,10 +1480,20 @@ impl RigidBodyBuilder {
         rb.linvel = self.linvel;
         rb.angvel = self.angvel;
         rb.body_status = self.body_status;
+        rb.enabled = self.enabled;
         rb.user_data = self.user_data;
-        rb.mass_properties = self.mass_properties;
+        rb.additional_mass_properties = self.mass_properties;
+        rb.local_com_override = self.local_com;
+        rb.update_mass_properties();
+        rb.gravity_override = self.gravity_override;
+        #[cfg(feature = "dim2")]
+        {
+            rb.ground_friction = self.ground_friction;
+        }
         rb.linear_damping = self.linear_damping;
         rb.angular_damping = self.angular_damping;
+        rb.max_linear_velocity = self.max_linear_velocity;
+        rb.max_angular_velocity = self.max_angular_velocity;
         rb.flags = self.flags;
 
         if self.can_sleep && self.sleeping {
@@ -882,3 +1553,293 @@ impl ActivationStatus {
         self.energy != 0.0
     }
 }
+
+#[cfg(test)]
+mod test {
+    use crate::dynamics::{MassProperties, RigidBodyBuilder};
+    use crate::geometry::{Collider, ColliderBuilder};
+    use crate::math::{Isometry, Point, Vector};
+    use crate::pipeline::TestWorld;
+    use approx::assert_relative_eq;
+    use num::Zero;
+
+    #[cfg(feature = "dim2")]
+    fn point_mass(local_com: Point<f32>, mass: f32) -> MassProperties {
+        MassProperties::new(local_com, mass, 1.0)
+    }
+
+    #[cfg(feature = "dim3")]
+    fn point_mass(local_com: Point<f32>, mass: f32) -> MassProperties {
+        MassProperties::new(local_com, mass, Vector::repeat(1.0))
+    }
+
+    #[test]
+    fn additional_mass_properties_and_center_of_mass_override() {
+        let mut world = TestWorld::new(Vector::zeros());
+        let extra = point_mass(Point::from(Vector::y()), 2.0);
+        let rb = RigidBodyBuilder::new_dynamic()
+            .mass_properties(extra)
+            .build();
+        let ball = ColliderBuilder::ball(0.5).mass(1.0).build();
+        let (handle, _) = world.insert(rb, ball);
+
+        // The additional mass properties are added to the contributions of the colliders.
+        let rb = &world.bodies[handle];
+        assert_relative_eq!(rb.mass(), 3.0, epsilon = 1.0e-5);
+        assert_relative_eq!(
+            *rb.local_center_of_mass(),
+            Point::from(Vector::y() * 2.0 / 3.0),
+            epsilon = 1.0e-5
+        );
+
+        world.attach(ColliderBuilder::ball(0.5).mass(1.0).build(), handle);
+        assert_relative_eq!(world.bodies[handle].mass(), 4.0, epsilon = 1.0e-5);
+
+        let rb = &mut world.bodies[handle];
+        rb.set_additional_mass_properties(point_mass(Point::origin(), 1.0), true);
+        assert_relative_eq!(rb.mass(), 3.0, epsilon = 1.0e-5);
+
+        // The center-of-mass override survives mass properties updates.
+        let com = Point::from(-Vector::y());
+        rb.set_local_center_of_mass(Some(com), true);
+        assert_eq!(*rb.local_center_of_mass(), com);
+        assert_eq!(rb.local_center_of_mass_override(), Some(&com));
+
+        world.attach(ColliderBuilder::ball(0.5).mass(1.0).build(), handle);
+        assert_relative_eq!(world.bodies[handle].mass(), 4.0, epsilon = 1.0e-5);
+        assert_eq!(*world.bodies[handle].local_center_of_mass(), com);
+    }
+
+    #[test]
+    fn set_mass_properties_ignores_colliders() {
+        let mut world = TestWorld::new(Vector::zeros());
+        let rb = RigidBodyBuilder::new_dynamic().build();
+        let ball = ColliderBuilder::ball(0.5).mass(1.0).build();
+        let (handle, collider) = world.insert(rb, ball);
+
+        let props = point_mass(Point::origin(), 5.0);
+        world.bodies[handle].set_mass_properties(props, true);
+        assert_relative_eq!(world.bodies[handle].mass(), 5.0, epsilon = 1.0e-5);
+
+        // Neither new colliders, nor modified colliders, contribute to the mass properties.
+        world.attach(ColliderBuilder::ball(0.5).mass(1.0).build(), handle);
+        world.colliders.get_mut(collider).unwrap().set_mass(10.0);
+        world.step();
+
+        assert_relative_eq!(world.bodies[handle].mass(), 5.0, epsilon = 1.0e-5);
+        assert_eq!(
+            *world.bodies[handle].local_center_of_mass(),
+            Point::origin()
+        );
+    }
+
+    fn ball_at(x: f32, mass: f32) -> Collider {
+        let mut position = Isometry::identity();
+        position.translation.vector.x = x;
+        ColliderBuilder::ball(0.5)
+            .position(position)
+            .mass(mass)
+            .build()
+    }
+
+    #[test]
+    fn modified_and_removed_colliders_update_mass_properties() {
+        let mut world = TestWorld::new(Vector::zeros());
+        let rb = RigidBodyBuilder::new_dynamic().build();
+        let (handle, collider1) = world.insert(rb, ball_at(-1.0, 1.0));
+        let collider2 = world.attach(ball_at(1.0, 1.0), handle);
+        assert_relative_eq!(world.bodies[handle].mass(), 2.0, epsilon = 1.0e-5);
+
+        // Only the contribution of the modified collider changes.
+        world.colliders.get_mut(collider2).unwrap().set_mass(3.0);
+        world.step();
+
+        let mut expected_world = TestWorld::new(Vector::zeros());
+        let rb = RigidBodyBuilder::new_dynamic().build();
+        let (expected, _) = expected_world.insert(rb, ball_at(-1.0, 1.0));
+        expected_world.attach(ball_at(1.0, 3.0), expected);
+
+        let rb = &world.bodies[handle];
+        let expected = &expected_world.bodies[expected];
+        assert_relative_eq!(rb.mass(), 4.0, epsilon = 1.0e-5);
+        assert_relative_eq!(
+            *rb.local_center_of_mass(),
+            Point::from(Vector::x() * 0.5),
+            epsilon = 1.0e-5
+        );
+        assert_relative_eq!(
+            rb.mass_properties().inv_principal_inertia_sqrt,
+            expected.mass_properties().inv_principal_inertia_sqrt,
+            epsilon = 1.0e-4
+        );
+
+        // Removing the colliders subtracts their contributions.
+        world.colliders.remove(collider2, &mut world.bodies, true);
+        let rb = &world.bodies[handle];
+        assert_relative_eq!(rb.mass(), 1.0, epsilon = 1.0e-5);
+        assert_relative_eq!(
+            *rb.local_center_of_mass(),
+            Point::from(-Vector::x()),
+            epsilon = 1.0e-4
+        );
+
+        world.colliders.remove(collider1, &mut world.bodies, true);
+        assert!(world.bodies[handle].mass_properties().is_zero());
+    }
+
+    #[test]
+    fn persistent_forces() {
+        let mut world = TestWorld::new(Vector::zeros());
+        let dt = world.params.dt();
+        let rb = RigidBodyBuilder::new_dynamic().build();
+        let (handle, _) = world.insert(rb, ColliderBuilder::ball(0.5).build());
+
+        world.bodies[handle].add_force(Vector::x() * 2.0, true);
+        world.bodies[handle].apply_force(Vector::y() * 2.0, true);
+        world.steps(10);
+
+        // The persistent force is applied at each step, the one-shot force only once.
+        let inv_mass = world.bodies[handle].mass_properties().inv_mass;
+        let linvel = *world.bodies[handle].linvel();
+        assert!((linvel.x - 2.0 * inv_mass * 10.0 * dt).abs() < 1.0e-5);
+        assert!((linvel.y - 2.0 * inv_mass * dt).abs() < 1.0e-5);
+
+        world.bodies[handle].reset_forces(true);
+        world.step();
+        assert_eq!(world.bodies[handle].linvel(), &linvel);
+    }
+
+    #[test]
+    fn gravity_override() {
+        let mut world = TestWorld::new(-Vector::y());
+        let dt = world.params.dt();
+        let rb1 = RigidBodyBuilder::new_dynamic().build();
+        let rb2 = RigidBodyBuilder::new_dynamic()
+            .gravity_override(Vector::x())
+            .build();
+        let (h1, _) = world.insert(rb1, ColliderBuilder::ball(0.5).build());
+        let (h2, _) = world.insert(rb2, ColliderBuilder::ball(0.5).build());
+
+        world.step();
+
+        assert_eq!(*world.bodies[h1].linvel(), -Vector::y() * dt);
+        assert_eq!(*world.bodies[h2].linvel(), Vector::x() * dt);
+    }
+
+    #[test]
+    fn velocity_at_point_and_predicted_position() {
+        let mut world = TestWorld::new(Vector::zeros());
+        let dt = world.params.dt();
+
+        // The center-of-mass is away from the origin of the rigid-body.
+        let mut delta = Isometry::identity();
+        delta.translation.vector.x = 2.0;
+        let rb = RigidBodyBuilder::new_dynamic().build();
+        let co = ColliderBuilder::ball(0.5).position(delta).build();
+        let (handle, _) = world.insert(rb, co);
+
+        let body = world.bodies.get_mut(handle).unwrap();
+        body.set_linvel(Vector::y() * 3.0, true);
+        #[cfg(feature = "dim2")]
+        body.set_angvel(1.5, true);
+        #[cfg(feature = "dim3")]
+        body.set_angvel(Vector::z() * 1.5, true);
+
+        for _ in 0..3 {
+            let body = &world.bodies[handle];
+            let predicted = body.predict_position_after(dt);
+            let local_point = Point::from(Vector::y() * 1.0);
+            let point = body.position() * local_point;
+            let com = body.position() * body.local_center_of_mass();
+            let velocity = body.velocity_at_point(&point);
+            assert!((body.velocity_at_point(&com) - body.linvel()).norm() < 1.0e-5);
+
+            world.step();
+
+            let body = &world.bodies[handle];
+            let position = body.position();
+            assert!((position.translation.vector - predicted.translation.vector).norm() < 1.0e-4);
+            assert!(position.rotation.angle_to(&predicted.rotation) < 1.0e-4);
+
+            // The velocity of a point matches its displacement during the timestep, up to the
+            // curvature of its trajectory.
+            let displacement = (position * local_point - point) / dt;
+            assert!((displacement - velocity).norm() < 0.1);
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "dim2")]
+    fn ground_friction_stops_top_down_bodies() {
+        use crate::dynamics::GroundFriction;
+
+        let mut world = TestWorld::new(Vector::zeros());
+
+        // A puck sliding isotropically, and a car rolling along its local `x` axis while
+        // drifting and spinning.
+        let puck = RigidBodyBuilder::new_dynamic()
+            .linvel(5.0, 0.0)
+            .ground_friction(GroundFriction::new(0.5))
+            .build();
+        let (puck, _) = world.insert(puck, ColliderBuilder::ball(0.5).build());
+        let car = RigidBodyBuilder::new_dynamic()
+            .position(Isometry::new(
+                Vector::y() * 10.0,
+                std::f32::consts::FRAC_PI_2,
+            ))
+            .linvel(-2.0, 5.0)
+            .angvel(3.0)
+            .ground_friction(GroundFriction::anisotropic(0.01, 2.0))
+            .build();
+        let (car, _) = world.insert(car, ColliderBuilder::cuboid(1.0, 0.5).build());
+
+        world.steps(120);
+
+        // The puck stopped after sliding by `v^2 / (2 * friction * gravity)`.
+        let puck = &world.bodies[puck];
+        assert_eq!(*puck.linvel(), Vector::zeros());
+        let distance = 25.0 / (2.0 * 0.5 * 9.81);
+        assert!((puck.position().translation.x - distance).abs() < 0.05);
+
+        // The car stopped drifting and spinning, but still rolls forward.
+        let car = &world.bodies[car];
+        let local_linvel = car
+            .position()
+            .rotation
+            .inverse_transform_vector(car.linvel());
+        assert_eq!(local_linvel.y, 0.0);
+        assert_eq!(car.angvel(), 0.0);
+        assert!(local_linvel.x > 4.0);
+    }
+
+    #[test]
+    fn velocities_are_clamped() {
+        let mut world = TestWorld::falling();
+        let dt = world.params.dt();
+
+        #[cfg(feature = "dim2")]
+        let angvel = 10.0;
+        #[cfg(feature = "dim3")]
+        let angvel = Vector::z() * 10.0;
+        let rb = RigidBodyBuilder::new_dynamic()
+            .angvel(angvel)
+            .max_linear_velocity(2.0)
+            .max_angular_velocity(1.0)
+            .build();
+        let (body, _) = world.insert(rb, ColliderBuilder::ball(0.5).build());
+
+        let num_steps = 60;
+        world.steps(num_steps);
+
+        let rb = &world.bodies[body];
+        assert!((rb.linvel().norm() - 2.0).abs() < 1.0e-5);
+        #[cfg(feature = "dim2")]
+        assert!((rb.angvel().abs() - 1.0).abs() < 1.0e-5);
+        #[cfg(feature = "dim3")]
+        assert!((rb.angvel().norm() - 1.0).abs() < 1.0e-5);
+
+        // The positions are integrated with the clamped velocities.
+        let fall = dt * num_steps as f32 * 2.0;
+        assert!(rb.position().translation.vector.y > -fall - 1.0e-4);
+    }
+}
@@ -2,11 +2,16 @@
 use rayon::prelude::*;
 
 use crate::data::arena::Arena;
-use crate::dynamics::{Joint, JointSet, RigidBody, RigidBodyChanges};
-use crate::geometry::{ColliderHandle, ColliderSet, InteractionGraph, NarrowPhase};
+use crate::dynamics::{IslandManager, Joint, JointSet, RigidBody, RigidBodyChanges};
+use crate::geometry::{
+    ColliderChanges, ColliderHandle, ColliderSet, InteractionGraph, NarrowPhase,
+};
 use std::ops::{Index, IndexMut};
 
 /// The unique handle of a rigid body added to a `RigidBodySet`.
+///
+/// Handles are generational: the handle of a removed rigid-body is never valid again, even if
+/// its storage slot is reused by another rigid-body.
 pub type RigidBodyHandle = crate::data::arena::Index;
 
 #[derive(Copy, Clone, Debug, PartialEq, Eq)]
@@ -38,19 +43,16 @@ pub struct RigidBodySet {
     // parallelism because the `Receiver` breaks the Sync impl.
     // Could we avoid this?
     pub(crate) bodies: Arena<RigidBody>,
-    pub(crate) active_dynamic_set: Vec<RigidBodyHandle>,
-    pub(crate) active_kinematic_set: Vec<RigidBodyHandle>,
+    pub(crate) islands: IslandManager,
     // Set of inactive bodies which have been modified.
     // This typically include static bodies which have been modified.
     pub(crate) modified_inactive_set: Vec<RigidBodyHandle>,
-    pub(crate) active_islands: Vec<usize>,
-    active_set_timestamp: u32,
     pub(crate) modified_bodies: Vec<RigidBodyHandle>,
     pub(crate) modified_all_bodies: bool,
+    // Bodies with a modified status, which have to wake up the bodies they interact with.
+    pub(crate) modified_status_bodies: Vec<RigidBodyHandle>,
     #[cfg_attr(feature = "serde-serialize", serde(skip))]
-    can_sleep: Vec<RigidBodyHandle>, // Workspace.
-    #[cfg_attr(feature = "serde-serialize", serde(skip))]
-    stack: Vec<RigidBodyHandle>, // Workspace.
+    modified_colliders_parents: Vec<(ColliderHandle, RigidBodyHandle, ColliderChanges)>, // Workspace.
 }
 
 impl RigidBodySet {
@@ -58,15 +60,34 @@ impl RigidBodySet {
     pub fn new() -> Self {
         RigidBodySet {
             bodies: Arena::new(),
-            active_dynamic_set: Vec::new(),
-            active_kinematic_set: Vec::new(),
+            islands: IslandManager::new(),
             modified_inactive_set: Vec::new(),
-            active_islands: Vec::new(),
-            active_set_timestamp: 0,
             modified_bodies: Vec::new(),
             modified_all_bodies: false,
-            can_sleep: Vec::new(),
-            stack: Vec::new(),
+            modified_status_bodies: Vec::new(),
+            modified_colliders_parents: Vec::new(),
+        }
+    }
+
+    /// Create a new empty set of rigid bodies, able to hold `capacity` rigid-bodies
+    /// without reallocating.
+    pub fn with_capacity(capacity: usize) -> Self {
+        let mut result = Self::new();
+        result.bodies = Arena::with_capacity(capacity);
+        result
+    }
+
+    /// The number of rigid-bodies this set can hold without reallocating.
+    pub fn capacity(&self) -> usize {
+        self.bodies.capacity()
+    }
+
+    /// Reserves enough capacity for inserting at least `additional` more rigid-bodies
+    /// into this set without reallocating.
+    pub fn reserve(&mut self, additional: usize) {
+        let available = self.bodies.capacity() - self.bodies.len();
+        if additional > available {
+            self.bodies.reserve(additional - available);
         }
     }
 
@@ -98,13 +119,27 @@ impl RigidBodySet {
         let rb = &mut self.bodies[handle];
 
         if rb.is_kinematic() {
-            rb.active_set_id = self.active_kinematic_set.len();
-            self.active_kinematic_set.push(handle);
+            rb.active_set_id = self.islands.active_kinematic_set.len();
+            self.islands.active_kinematic_set.push(handle);
         }
 
         handle
     }
 
+    /// Inserts many rigid-bodies at once, and retrieve their handles in the same order.
+    ///
+    /// This is equivalent to inserting each rigid-body with `insert`, after reserving enough
+    /// capacity for all of them. Use it with `ColliderSet::insert_batch` to load large scenes.
+    pub fn insert_batch(
+        &mut self,
+        bodies: impl IntoIterator<Item = RigidBody>,
+    ) -> Vec<RigidBodyHandle> {
+        let bodies = bodies.into_iter();
+        self.reserve(bodies.size_hint().0);
+        self.modified_bodies.reserve(bodies.size_hint().0);
+        bodies.map(|rb| self.insert(rb)).collect()
+    }
+
     /// Removes a rigid-body, and all its attached colliders and joints, from these sets.
     pub fn remove(
         &mut self,
@@ -116,17 +151,18 @@ impl RigidBodySet {
         /*
          * Update active sets.
          */
-        let mut active_sets = [&mut self.active_kinematic_set, &mut self.active_dynamic_set];
-
-        for active_set in &mut active_sets {
-            if active_set.get(rb.active_set_id) == Some(&handle) {
-                active_set.swap_remove(rb.active_set_id);
-
-                if let Some(replacement) = active_set.get(rb.active_set_id) {
-                    self.bodies[*replacement].active_set_id = rb.active_set_id;
-                }
-            }
-        }
+        Self::remove_from_active_set(
+            &mut self.bodies,
+            &mut self.islands.active_kinematic_set,
+            handle,
+            rb.active_set_id,
+        );
+        Self::remove_from_active_set(
+            &mut self.bodies,
+            &mut self.islands.active_dynamic_set,
+            handle,
+            rb.active_set_id,
+        );
 
         /*
          * Remove colliders attached to this rigid-body.
@@ -143,8 +179,28 @@ impl RigidBodySet {
         Some(rb)
     }
 
+    fn remove_from_active_set(
+        bodies: &mut Arena<RigidBody>,
+        active_set: &mut Vec<RigidBodyHandle>,
+        handle: RigidBodyHandle,
+        active_set_id: usize,
+    ) {
+        if active_set.get(active_set_id) == Some(&handle) {
+            active_set.swap_remove(active_set_id);
+
+            if let Some(replacement) = active_set.get(active_set_id) {
+                bodies[*replacement].active_set_id = active_set_id;
+            }
+        }
+    }
+
+    /// The active rigid-bodies of this set, and their simulation islands.
+    pub fn islands(&self) -> &IslandManager {
+        &self.islands
+    }
+
     pub(crate) fn num_islands(&self) -> usize {
-        self.active_islands.len() - 1
+        self.islands.num_active_islands()
     }
 
     /// Forces the specified rigid-body to wake up if it is dynamic.
@@ -154,12 +210,12 @@ impl RigidBodySet {
     pub fn wake_up(&mut self, handle: RigidBodyHandle, strong: bool) {
         if let Some(rb) = self.bodies.get_mut(handle) {
             // TODO: what about kinematic bodies?
-            if rb.is_dynamic() {
+            if rb.is_dynamic() && rb.is_enabled() {
                 rb.wake_up(strong);
 
-                if self.active_dynamic_set.get(rb.active_set_id) != Some(&handle) {
-                    rb.active_set_id = self.active_dynamic_set.len();
-                    self.active_dynamic_set.push(handle);
+                if self.islands.active_dynamic_set.get(rb.active_set_id) != Some(&handle) {
+                    rb.active_set_id = self.islands.active_dynamic_set.len();
+                    self.islands.active_dynamic_set.push(handle);
                 }
             }
         }
@@ -224,6 +280,10 @@ impl RigidBodySet {
     }
 
     /// Iterates through all the rigid-bodies on this set.
+    ///
+    /// The iteration order only depends on the sequence of insertions and removals performed
+    /// on this set, so it is the same for two sets built the same way, and for a set restored
+    /// from a snapshot.
     pub fn iter(&self) -> impl Iterator<Item = (RigidBodyHandle, &RigidBody)> {
         self.bodies.iter()
     }
@@ -240,7 +300,8 @@ impl RigidBodySet {
         &'a self,
     ) -> impl Iterator<Item = (RigidBodyHandle, &'a RigidBody)> {
         let bodies: &'a _ = &self.bodies;
-        self.active_kinematic_set
+        self.islands
+            .active_kinematic_set
             .iter()
             .filter_map(move |h| Some((*h, bodies.get(*h)?)))
     }
@@ -250,7 +311,8 @@ impl RigidBodySet {
         &'a self,
     ) -> impl Iterator<Item = (RigidBodyHandle, &'a RigidBody)> {
         let bodies: &'a _ = &self.bodies;
-        self.active_dynamic_set
+        self.islands
+            .active_dynamic_set
             .iter()
             .filter_map(move |h| Some((*h, bodies.get(*h)?)))
     }
@@ -260,9 +322,9 @@ impl RigidBodySet {
         &'a self,
         island_id: usize,
     ) -> impl Iterator<Item = (RigidBodyHandle, &'a RigidBody)> {
-        let island_range = self.active_islands[island_id]..self.active_islands[island_id + 1];
+        let island_range = self.islands.active_island_range(island_id);
         let bodies: &'a _ = &self.bodies;
-        self.active_dynamic_set[island_range]
+        self.islands.active_dynamic_set[island_range]
             .iter()
             .filter_map(move |h| Some((*h, bodies.get(*h)?)))
     }
@@ -272,13 +334,13 @@ impl RigidBodySet {
         &mut self,
         mut f: impl FnMut(RigidBodyHandle, &mut RigidBody),
     ) {
-        for handle in &self.active_dynamic_set {
+        for handle in &self.islands.active_dynamic_set {
             if let Some(rb) = self.bodies.get_mut(*handle) {
                 f(*handle, rb)
             }
         }
 
-        for handle in &self.active_kinematic_set {
+        for handle in &self.islands.active_kinematic_set {
             if let Some(rb) = self.bodies.get_mut(*handle) {
                 f(*handle, rb)
             }
@@ -290,7 +352,7 @@ impl RigidBodySet {
         &mut self,
         mut f: impl FnMut(RigidBodyHandle, &mut RigidBody),
     ) {
-        for handle in &self.active_dynamic_set {
+        for handle in &self.islands.active_dynamic_set {
             if let Some(rb) = self.bodies.get_mut(*handle) {
                 f(*handle, rb)
             }
@@ -302,7 +364,7 @@ impl RigidBodySet {
         &mut self,
         mut f: impl FnMut(RigidBodyHandle, &mut RigidBody),
     ) {
-        for handle in &self.active_kinematic_set {
+        for handle in &self.islands.active_kinematic_set {
             if let Some(rb) = self.bodies.get_mut(*handle) {
                 f(*handle, rb)
             }
@@ -316,8 +378,8 @@ impl RigidBodySet {
         island_id: usize,
         mut f: impl FnMut(RigidBodyHandle, &mut RigidBody),
     ) {
-        let island_range = self.active_islands[island_id]..self.active_islands[island_id + 1];
-        for handle in &self.active_dynamic_set[island_range] {
+        let island_range = self.islands.active_island_range(island_id);
+        for handle in &self.islands.active_dynamic_set[island_range] {
             if let Some(rb) = self.bodies.get_mut(*handle) {
                 f(*handle, rb)
             }
@@ -334,9 +396,9 @@ impl RigidBodySet {
     ) {
         use std::sync::atomic::Ordering;
 
-        let island_range = self.active_islands[island_id]..self.active_islands[island_id + 1];
+        let island_range = self.islands.active_island_range(island_id);
         let bodies = std::sync::atomic::AtomicPtr::new(&mut self.bodies as *mut _);
-        self.active_dynamic_set[island_range]
+        self.islands.active_dynamic_set[island_range]
             .par_iter()
             .for_each_init(
                 || bodies.load(Ordering::Relaxed),
@@ -350,15 +412,11 @@ impl RigidBodySet {
     }
 
     // pub(crate) fn active_dynamic_set(&self) -> &[RigidBodyHandle] {
-    //     &self.active_dynamic_set
+    //     &self.islands.active_dynamic_set
     // }
 
-    pub(crate) fn active_island_range(&self, island_id: usize) -> std::ops::Range<usize> {
-        self.active_islands[island_id]..self.active_islands[island_id + 1]
-    }
-
     pub(crate) fn active_island(&self, island_id: usize) -> &[RigidBodyHandle] {
-        &self.active_dynamic_set[self.active_island_range(island_id)]
+        self.islands.active_island(island_id)
     }
 
     // Utility function to avoid some borrowing issue in the `maintain` method.
@@ -370,17 +428,42 @@ impl RigidBodySet {
         active_kinematic_set: &mut Vec<RigidBodyHandle>,
         active_dynamic_set: &mut Vec<RigidBodyHandle>,
     ) {
+        // Propagate the enabled state of the body to its colliders, so that
+        // the broad-phase and the narrow-phase take it into account.
+        if rb.changes.contains(RigidBodyChanges::ENABLED) {
+            for handle in &rb.colliders {
+                if let Some(collider) = colliders.get_mut(*handle) {
+                    collider.parent_enabled = rb.is_enabled();
+                    collider.changes.insert(ColliderChanges::ENABLED);
+                }
+            }
+        }
+
         // Update the positions of the colliders.
         if rb.changes.contains(RigidBodyChanges::POSITION)
             || rb.changes.contains(RigidBodyChanges::COLLIDERS)
         {
             rb.update_colliders_positions(colliders);
 
-            if rb.is_static() {
+            if rb.changes.contains(RigidBodyChanges::POSITION) {
+                // Let the narrow-phase know the colliders have been teleported.
+                for handle in &rb.colliders {
+                    if let Some(collider) = colliders.get_mut(*handle) {
+                        collider.changes.insert(ColliderChanges::POSITION);
+                    }
+                }
+            }
+
+            // Static and sleeping bodies are not part of the active sets, so the broad-phase
+            // only updates the AABBs of their colliders if they are in the modified inactive set.
+            if rb.is_static() || (rb.is_dynamic() && rb.is_sleeping()) {
                 modified_inactive_set.push(handle);
             }
 
-            if rb.is_kinematic() && active_kinematic_set.get(rb.active_set_id) != Some(&handle) {
+            if rb.is_kinematic()
+                && rb.is_enabled()
+                && active_kinematic_set.get(rb.active_set_id) != Some(&handle)
+            {
                 rb.active_set_id = active_kinematic_set.len();
                 active_kinematic_set.push(handle);
             }
@@ -391,6 +474,7 @@ impl RigidBodySet {
         if rb.changes.contains(RigidBodyChanges::SLEEP)
             && !rb.is_sleeping() // May happen if the body was put to sleep manually.
             && rb.is_dynamic() // Only dynamic bodies are in the active dynamic set.
+            && rb.is_enabled() // Disabled bodies are not simulated.
             && active_dynamic_set.get(rb.active_set_id) != Some(&handle)
         {
             rb.active_set_id = active_dynamic_set.len(); // This will handle the case where the activation_channel contains duplicates.
@@ -400,7 +484,114 @@ impl RigidBodySet {
         rb.changes = RigidBodyChanges::empty();
     }
 
+    fn handle_modified_colliders(&mut self, colliders: &ColliderSet) {
+        // NOTE: the modification flags of the colliders are reset by the
+        // pipeline once the narrow-phase also took them into account.
+        for handle in &colliders.modified_colliders {
+            if let Some(collider) = colliders.get(*handle) {
+                let changes = collider.changes;
+
+                if changes.intersects(
+                    ColliderChanges::MASS_PROPERTIES
+                        | ColliderChanges::POSITION_WRT_PARENT
+                        | ColliderChanges::SHAPE
+                        | ColliderChanges::SHAPE_DEFORMED
+                        | ColliderChanges::PREDICTION_DISTANCE
+                        | ColliderChanges::CONTACT_SKIN
+                        | ColliderChanges::ENABLED,
+                ) {
+                    self.modified_colliders_parents
+                        .push((*handle, collider.parent, changes));
+                }
+            }
+        }
+
+        for (handle, parent, changes) in self.modified_colliders_parents.drain(..) {
+            if let Some(rb) = self.bodies.get_mut(parent) {
+                if !self.modified_all_bodies && !rb.changes.contains(RigidBodyChanges::MODIFIED) {
+                    rb.changes = RigidBodyChanges::MODIFIED;
+                    self.modified_bodies.push(parent);
+                }
+
+                if changes.contains(ColliderChanges::MASS_PROPERTIES) {
+                    rb.update_collider_mass_properties(handle, &colliders[handle]);
+                }
+
+                if changes.intersects(
+                    ColliderChanges::POSITION_WRT_PARENT
+                        | ColliderChanges::SHAPE
+                        | ColliderChanges::SHAPE_DEFORMED
+                        | ColliderChanges::PREDICTION_DISTANCE
+                        | ColliderChanges::CONTACT_SKIN
+                        | ColliderChanges::ENABLED,
+                ) {
+                    // This will trigger the update of the colliders positions and AABBs.
+                    rb.changes.insert(RigidBodyChanges::COLLIDERS);
+
+                    if rb.is_dynamic() {
+                        rb.wake_up(true);
+                    }
+                }
+            }
+        }
+    }
+
+    fn handle_modified_statuses(&mut self) {
+        let status_changes = RigidBodyChanges::BODY_STATUS | RigidBodyChanges::ENABLED;
+        let modified_status_bodies = &mut self.modified_status_bodies;
+        let bodies = &self.bodies;
+
+        if self.modified_all_bodies {
+            modified_status_bodies.extend(
+                bodies
+                    .iter()
+                    .filter(|(_, rb)| rb.changes.intersects(status_changes))
+                    .map(|(handle, _)| handle),
+            );
+        } else {
+            modified_status_bodies.extend(self.modified_bodies.iter().copied().filter(|handle| {
+                bodies
+                    .get(*handle)
+                    .map(|rb| rb.changes.intersects(status_changes))
+                    == Some(true)
+            }));
+        }
+
+        // Remove the bodies from the active sets they no longer belong to. They
+        // are added to their new active set by `Self::maintain_one`.
+        for handle in modified_status_bodies.iter() {
+            let rb = &self.bodies[*handle];
+            let (active_set_id, is_dynamic, is_kinematic, is_enabled) = (
+                rb.active_set_id,
+                rb.is_dynamic(),
+                rb.is_kinematic(),
+                rb.is_enabled(),
+            );
+
+            if !is_kinematic || !is_enabled {
+                Self::remove_from_active_set(
+                    &mut self.bodies,
+                    &mut self.islands.active_kinematic_set,
+                    *handle,
+                    active_set_id,
+                );
+            }
+
+            if !is_dynamic || !is_enabled {
+                Self::remove_from_active_set(
+                    &mut self.bodies,
+                    &mut self.islands.active_dynamic_set,
+                    *handle,
+                    active_set_id,
+                );
+            }
+        }
+    }
+
     pub(crate) fn maintain(&mut self, colliders: &mut ColliderSet) {
+        self.handle_modified_colliders(colliders);
+        self.handle_modified_statuses();
+
         if self.modified_all_bodies {
             for (handle, rb) in self.bodies.iter_mut() {
                 Self::maintain_one(
@@ -408,8 +599,8 @@ impl RigidBodySet {
                     handle,
                     rb,
                     &mut self.modified_inactive_set,
-                    &mut self.active_kinematic_set,
-                    &mut self.active_dynamic_set,
+                    &mut self.islands.active_kinematic_set,
+                    &mut self.islands.active_dynamic_set,
                 )
             }
 
@@ -422,8 +613,8 @@ impl RigidBodySet {
                         handle,
                         rb,
                         &mut self.modified_inactive_set,
-                        &mut self.active_kinematic_set,
-                        &mut self.active_dynamic_set,
+                        &mut self.islands.active_kinematic_set,
+                        &mut self.islands.active_dynamic_set,
                     )
                 }
             }
@@ -445,15 +636,17 @@ impl RigidBodySet {
         // Update the energy of every rigid body and
         // keep only those that may not sleep.
         //        let t = instant::now();
-        self.active_set_timestamp += 1;
-        self.stack.clear();
-        self.can_sleep.clear();
+        self.islands.active_set_timestamp += 1;
+        self.islands.stack.clear();
+        self.islands.can_sleep.clear();
+        self.islands.activated_bodies.clear();
+        self.islands.deactivated_bodies.clear();
 
         // NOTE: the `.rev()` is here so that two successive timesteps preserve
         // the order of the bodies in the `active_dynamic_set` vec. This reversal
         // does not seem to affect performances nor stability. However it makes
         // debugging slightly nicer so we keep this rev.
-        for h in self.active_dynamic_set.drain(..).rev() {
+        for h in self.islands.active_dynamic_set.drain(..).rev() {
             let rb = &mut self.bodies[h];
             rb.update_energy();
             if rb.activation.energy <= rb.activation.threshold {
@@ -461,9 +654,9 @@ impl RigidBodySet {
                 // be set to false during the graph traversal
                 // if it should not be put to sleep.
                 rb.activation.sleeping = true;
-                self.can_sleep.push(h);
+                self.islands.can_sleep.push(h);
             } else {
-                self.stack.push(h);
+                self.islands.stack.push(h);
             }
         }
 
@@ -496,7 +689,7 @@ impl RigidBodySet {
 
         // Now iterate on all active kinematic bodies and push all the bodies
         // touching them to the stack so they can be woken up.
-        for h in self.active_kinematic_set.iter() {
+        for h in self.islands.active_kinematic_set.iter() {
             let rb = &self.bodies[*h];
 
             if !rb.is_moving() {
@@ -505,7 +698,19 @@ impl RigidBodySet {
                 continue;
             }
 
-            push_contacting_colliders(rb, colliders, narrow_phase, &mut self.stack);
+            push_contacting_colliders(rb, colliders, narrow_phase, &mut self.islands.stack);
+        }
+
+        // Wake up all the bodies interacting with a rigid-body with a modified status.
+        for h in self.modified_status_bodies.drain(..) {
+            if let Some(rb) = self.bodies.get(h) {
+                push_contacting_colliders(rb, colliders, narrow_phase, &mut self.islands.stack);
+
+                for inter in joint_graph.interactions_with(rb.joint_graph_index) {
+                    let other = crate::utils::other_handle((inter.0, inter.1), h);
+                    self.islands.stack.push(other);
+                }
+            }
         }
 
         //        println!("Selection: {}", instant::now() - t);
@@ -513,62 +718,87 @@ impl RigidBodySet {
         //        let t = instant::now();
         // Propagation of awake state and awake island computation through the
         // traversal of the interaction graph.
-        self.active_islands.clear();
-        self.active_islands.push(0);
+        self.islands.active_islands.clear();
+        self.islands.active_islands.push(0);
+        self.islands.activated_islands.clear();
+        self.islands.activated_islands.push(false);
 
         // The max avoid underflow when the stack is empty.
-        let mut island_marker = self.stack.len().max(1) - 1;
+        let mut island_marker = self.islands.stack.len().max(1) - 1;
 
-        while let Some(handle) = self.stack.pop() {
+        while let Some(handle) = self.islands.stack.pop() {
             let rb = &mut self.bodies[handle];
 
-            if rb.active_set_timestamp == self.active_set_timestamp || !rb.is_dynamic() {
+            if rb.active_set_timestamp == self.islands.active_set_timestamp
+                || !rb.is_dynamic()
+                || !rb.is_enabled()
+            {
                 // We already visited this body and its neighbors.
-                // Also, we don't propagate awake state through static bodies.
+                // Also, we don't propagate awake state through static or disabled bodies.
                 continue;
             }
 
-            if self.stack.len() < island_marker {
-                if self.active_dynamic_set.len() - *self.active_islands.last().unwrap()
+            if self.islands.stack.len() < island_marker {
+                if self.islands.active_dynamic_set.len()
+                    - *self.islands.active_islands.last().unwrap()
                     >= min_island_size
                 {
                     // We are starting a new island.
-                    self.active_islands.push(self.active_dynamic_set.len());
+                    self.islands
+                        .active_islands
+                        .push(self.islands.active_dynamic_set.len());
+                    self.islands.activated_islands.push(false);
                 }
 
-                island_marker = self.stack.len();
+                island_marker = self.islands.stack.len();
+            }
+
+            // The body was awake during the previous timestep iff it was
+            // visited by the previous traversal.
+            if rb.active_set_timestamp != self.islands.active_set_timestamp - 1 {
+                *self.islands.activated_islands.last_mut().unwrap() = true;
+                self.islands.activated_bodies.push(handle);
             }
 
             rb.wake_up(false);
-            rb.active_island_id = self.active_islands.len() - 1;
-            rb.active_set_id = self.active_dynamic_set.len();
-            rb.active_set_offset = rb.active_set_id - self.active_islands[rb.active_island_id];
-            rb.active_set_timestamp = self.active_set_timestamp;
-            self.active_dynamic_set.push(handle);
+            rb.active_island_id = self.islands.active_islands.len() - 1;
+            rb.active_set_id = self.islands.active_dynamic_set.len();
+            rb.active_set_offset =
+                rb.active_set_id - self.islands.active_islands[rb.active_island_id];
+            rb.active_set_timestamp = self.islands.active_set_timestamp;
+            self.islands.active_dynamic_set.push(handle);
 
             // Transmit the active state to all the rigid-bodies with colliders
             // in contact or joined with this collider.
-            push_contacting_colliders(rb, colliders, narrow_phase, &mut self.stack);
+            push_contacting_colliders(rb, colliders, narrow_phase, &mut self.islands.stack);
 
             for inter in joint_graph.interactions_with(rb.joint_graph_index) {
                 let other = crate::utils::other_handle((inter.0, inter.1), handle);
-                self.stack.push(other);
+                self.islands.stack.push(other);
             }
         }
 
-        self.active_islands.push(self.active_dynamic_set.len());
+        if self.islands.active_dynamic_set.is_empty() {
+            // Don't report an empty island if all the bodies are sleeping.
+            self.islands.activated_islands.clear();
+        } else {
+            self.islands
+                .active_islands
+                .push(self.islands.active_dynamic_set.len());
+        }
         //        println!(
         //            "Extraction: {}, num islands: {}",
         //            instant::now() - t,
-        //            self.active_islands.len() - 1
+        //            self.islands.active_islands.len() - 1
         //        );
 
         // Actually put to sleep bodies which have not been detected as awake.
         //        let t = instant::now();
-        for h in &self.can_sleep {
+        for h in &self.islands.can_sleep {
             let b = &mut self.bodies[*h];
             if b.activation.sleeping {
                 b.sleep();
+                self.islands.deactivated_bodies.push(*h);
             }
         }
         //        println!("Activation: {}", instant::now() - t);
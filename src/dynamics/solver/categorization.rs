@@ -49,6 +49,22 @@ pub(crate) fn categorize_velocity_contacts(
     }
 }
 
+/// The largest numbers of extra velocity and position iterations among the given joints.
+pub(crate) fn extra_joint_iterations<'a>(
+    joints: &[JointGraphEdge],
+    joint_indices: impl IntoIterator<Item = &'a JointIndex>,
+) -> (usize, usize) {
+    joint_indices
+        .into_iter()
+        .fold((0, 0), |(vel_iters, pos_iters), joint_i| {
+            let joint = &joints[*joint_i].weight;
+            (
+                vel_iters.max(joint.extra_velocity_iterations),
+                pos_iters.max(joint.extra_position_iterations),
+            )
+        })
+}
+
 pub(crate) fn categorize_joints(
     bodies: &RigidBodySet,
     joints: &[JointGraphEdge],
@@ -27,6 +27,7 @@ pub(crate) struct ParallelInteractionGroups {
     bodies_color: Vec<u128>,         // Workspace.
     interaction_indices: Vec<usize>, // Workspace.
     interaction_colors: Vec<usize>,  // Workspace.
+    uncolored: Vec<usize>,           // Workspace.
     sorted_interactions: Vec<usize>,
     groups: Vec<usize>,
 }
@@ -38,6 +39,7 @@ impl ParallelInteractionGroups {
             bodies_color: Vec::new(),
             interaction_indices: Vec::new(),
             interaction_colors: Vec::new(),
+            uncolored: Vec::new(),
             sorted_interactions: Vec::new(),
             groups: Vec::new(),
         }
@@ -51,6 +53,14 @@ impl ParallelInteractionGroups {
         self.groups.len() - 1
     }
 
+    /// Partitions the given interactions into groups such that no two interactions of the
+    /// same group involve the same dynamic rigid-body.
+    ///
+    /// The interactions of each group can then be solved in parallel. This is a greedy graph
+    /// coloring where each group is a color. The colors are assigned by blocks of 128 colors
+    /// tracked with bitmasks: the interactions that don't fit in the current block because their
+    /// bodies are already involved in interactions of all its colors are colored with the next
+    /// block.
     pub fn group_interactions<Interaction: PairInteraction>(
         &mut self,
         island_id: usize,
@@ -59,64 +69,79 @@ impl ParallelInteractionGroups {
         interaction_indices: &[usize],
     ) {
         let num_island_bodies = bodies.active_island(island_id).len();
-        self.bodies_color.clear();
         self.interaction_indices.clear();
         self.groups.clear();
         self.sorted_interactions.clear();
         self.interaction_colors.clear();
 
-        let mut color_len = [0; 128];
-        self.bodies_color.resize(num_island_bodies, 0u128);
+        let mut color_len = Vec::new();
         self.interaction_indices
             .extend_from_slice(interaction_indices);
         self.interaction_colors.resize(interaction_indices.len(), 0);
-        let bcolors = &mut self.bodies_color;
 
-        for (interaction_id, color) in self
-            .interaction_indices
-            .iter()
-            .zip(self.interaction_colors.iter_mut())
-        {
-            let body_pair = interactions[*interaction_id].body_pair();
-            let rb1 = &bodies[body_pair.body1];
-            let rb2 = &bodies[body_pair.body2];
-
-            match (rb1.is_static(), rb2.is_static()) {
-                (false, false) => {
-                    let color_mask =
-                        bcolors[rb1.active_set_offset] | bcolors[rb2.active_set_offset];
-                    *color = (!color_mask).trailing_zeros() as usize;
-                    color_len[*color] += 1;
-                    bcolors[rb1.active_set_offset] |= 1 << *color;
-                    bcolors[rb2.active_set_offset] |= 1 << *color;
+        // The interactions still to be colored, identified by their position
+        // in `interaction_indices`.
+        self.uncolored.clear();
+        self.uncolored.extend(0..interaction_indices.len());
+        let mut color_block = 0;
+
+        while !self.uncolored.is_empty() {
+            self.bodies_color.clear();
+            self.bodies_color.resize(num_island_bodies, 0u128);
+            color_len.resize(color_block + 128, 0);
+            let bcolors = &mut self.bodies_color;
+            let interaction_indices = &self.interaction_indices;
+            let interaction_colors = &mut self.interaction_colors;
+
+            self.uncolored.retain(|i| {
+                let body_pair = interactions[interaction_indices[*i]].body_pair();
+                let rb1 = &bodies[body_pair.body1];
+                let rb2 = &bodies[body_pair.body2];
+
+                let color_mask = match (rb1.is_static(), rb2.is_static()) {
+                    (false, false) => {
+                        bcolors[rb1.active_set_offset] | bcolors[rb2.active_set_offset]
+                    }
+                    (true, false) => bcolors[rb2.active_set_offset],
+                    (false, true) => bcolors[rb1.active_set_offset],
+                    (true, true) => unreachable!(),
+                };
+
+                if color_mask == u128::MAX {
+                    // All the colors of this block are already used by these bodies.
+                    return true;
                 }
-                (true, false) => {
-                    let color_mask = bcolors[rb2.active_set_offset];
-                    *color = (!color_mask).trailing_zeros() as usize;
-                    color_len[*color] += 1;
-                    bcolors[rb2.active_set_offset] |= 1 << *color;
+
+                let color = (!color_mask).trailing_zeros() as usize;
+                interaction_colors[*i] = color_block + color;
+                color_len[color_block + color] += 1;
+
+                if !rb1.is_static() {
+                    bcolors[rb1.active_set_offset] |= 1 << color;
                 }
-                (false, true) => {
-                    let color_mask = bcolors[rb1.active_set_offset];
-                    *color = (!color_mask).trailing_zeros() as usize;
-                    color_len[*color] += 1;
-                    bcolors[rb1.active_set_offset] |= 1 << *color;
+                if !rb2.is_static() {
+                    bcolors[rb2.active_set_offset] |= 1 << color;
                 }
-                (true, true) => unreachable!(),
-            }
+
+                false
+            });
+
+            color_block += 128;
         }
 
-        let mut sort_offsets = [0; 128];
+        let mut sort_offsets = vec![0; color_len.len()];
         let mut last_offset = 0;
 
-        for i in 0..128 {
-            if color_len[i] == 0 {
-                break;
+        for (len, sort_offset) in color_len.iter().zip(sort_offsets.iter_mut()) {
+            if *len == 0 {
+                // NOTE: the colors of a block are assigned in increasing order, so empty colors
+                // may only appear at the end of a block.
+                continue;
             }
 
             self.groups.push(last_offset);
-            sort_offsets[i] = last_offset;
-            last_offset += color_len[i];
+            *sort_offset = last_offset;
+            last_offset += *len;
         }
 
         self.sorted_interactions
@@ -214,6 +239,12 @@ impl InteractionGroups {
                 continue;
             }
 
+            if !interaction.params.supports_simd_constraints() {
+                // This joint does not have any SIMD constraint implementation.
+                self.nongrouped_interactions.push(*interaction_i);
+                continue;
+            }
+
             let ijoint = interaction.params.type_id();
             let i1 = body1.active_set_offset;
             let i2 = body2.active_set_offset;
@@ -0,0 +1,104 @@
+use crate::dynamics::{AngularJoint, IntegrationParameters, RigidBody};
+use crate::math::{Isometry, Point, Rotation};
+
+// Rotates `position` by `angle` around its center of mass.
+fn rotate_wrt_com(position: &mut Isometry<f32>, local_com: &Point<f32>, angle: f32) {
+    let com = *position * local_com;
+    let rotation = Rotation::new(angle);
+    position.rotation = rotation * position.rotation;
+    position.translation.vector =
+        com.coords + rotation * (position.translation.vector - com.coords);
+}
+
+#[derive(Debug)]
+pub(crate) struct AngularPositionConstraint {
+    position1: usize,
+    position2: usize,
+
+    local_com1: Point<f32>,
+    local_com2: Point<f32>,
+
+    ii1: f32,
+    ii2: f32,
+
+    angle: f32,
+}
+
+impl AngularPositionConstraint {
+    pub fn from_params(rb1: &RigidBody, rb2: &RigidBody, cparams: &AngularJoint) -> Self {
+        Self {
+            local_com1: rb1.mass_properties.local_com,
+            local_com2: rb2.mass_properties.local_com,
+            ii1: rb1.world_inv_inertia_sqrt * rb1.world_inv_inertia_sqrt,
+            ii2: rb2.world_inv_inertia_sqrt * rb2.world_inv_inertia_sqrt,
+            angle: cparams.angle,
+            position1: rb1.active_set_offset,
+            position2: rb2.active_set_offset,
+        }
+    }
+
+    pub fn solve(&self, params: &IntegrationParameters, positions: &mut [Isometry<f32>]) {
+        let total_ii = self.ii1 + self.ii2;
+
+        if total_ii == 0.0 {
+            return;
+        }
+
+        let mut position1 = positions[self.position1 as usize];
+        let mut position2 = positions[self.position2 as usize];
+
+        let target2 = position1.rotation * Rotation::new(self.angle);
+        let err = target2.rotation_to(&position2.rotation).angle();
+        let correction = err * params.joint_erp / total_ii;
+
+        rotate_wrt_com(&mut position1, &self.local_com1, correction * self.ii1);
+        rotate_wrt_com(&mut position2, &self.local_com2, -correction * self.ii2);
+
+        positions[self.position1 as usize] = position1;
+        positions[self.position2 as usize] = position2;
+    }
+}
+
+#[derive(Debug)]
+pub(crate) struct AngularPositionGroundConstraint {
+    position2: usize,
+    // The rotation the second body should have to satisfy the joint.
+    target2: Rotation<f32>,
+    local_com2: Point<f32>,
+    ii2: f32,
+}
+
+impl AngularPositionGroundConstraint {
+    pub fn from_params(
+        rb1: &RigidBody,
+        rb2: &RigidBody,
+        cparams: &AngularJoint,
+        flipped: bool,
+    ) -> Self {
+        // NOTE: if the bodies are flipped, the joint angle relative
+        // to the non-dynamic body rb1 is `-cparams.angle`.
+        let angle = if flipped {
+            -cparams.angle
+        } else {
+            cparams.angle
+        };
+
+        Self {
+            position2: rb2.active_set_offset,
+            target2: rb1.predicted_position.rotation * Rotation::new(angle),
+            local_com2: rb2.mass_properties.local_com,
+            ii2: rb2.world_inv_inertia_sqrt * rb2.world_inv_inertia_sqrt,
+        }
+    }
+
+    pub fn solve(&self, params: &IntegrationParameters, positions: &mut [Isometry<f32>]) {
+        if self.ii2 == 0.0 {
+            return;
+        }
+
+        let mut position2 = positions[self.position2 as usize];
+        let err = self.target2.rotation_to(&position2.rotation).angle();
+        rotate_wrt_com(&mut position2, &self.local_com2, -err * params.joint_erp);
+        positions[self.position2 as usize] = position2;
+    }
+}
@@ -0,0 +1,138 @@
+use crate::dynamics::solver::DeltaVel;
+use crate::dynamics::{
+    AngularJoint, IntegrationParameters, JointGraphEdge, JointIndex, JointParams, RigidBody,
+};
+
+#[derive(Debug)]
+pub(crate) struct AngularVelocityConstraint {
+    mj_lambda1: usize,
+    mj_lambda2: usize,
+
+    joint_id: JointIndex,
+
+    rhs: f32,
+    impulse: f32,
+    inv_lhs: f32,
+
+    ii1_sqrt: f32,
+    ii2_sqrt: f32,
+}
+
+impl AngularVelocityConstraint {
+    pub fn from_params(
+        params: &IntegrationParameters,
+        joint_id: JointIndex,
+        rb1: &RigidBody,
+        rb2: &RigidBody,
+        cparams: &AngularJoint,
+    ) -> Self {
+        let ii1_sqrt = rb1.world_inv_inertia_sqrt;
+        let ii2_sqrt = rb2.world_inv_inertia_sqrt;
+        let lhs = ii1_sqrt * ii1_sqrt + ii2_sqrt * ii2_sqrt;
+        let inv_lhs = if lhs > 0.0 { 1.0 / lhs } else { 0.0 };
+
+        AngularVelocityConstraint {
+            joint_id,
+            mj_lambda1: rb1.active_set_offset,
+            mj_lambda2: rb2.active_set_offset,
+            rhs: rb2.angvel - rb1.angvel,
+            impulse: cparams.impulse * params.effective_warmstart_coeff(),
+            inv_lhs,
+            ii1_sqrt,
+            ii2_sqrt,
+        }
+    }
+
+    fn apply_impulse(&self, impulse: f32, mj_lambdas: &mut [DeltaVel<f32>]) {
+        mj_lambdas[self.mj_lambda1 as usize].angular += self.ii1_sqrt * impulse;
+        mj_lambdas[self.mj_lambda2 as usize].angular -= self.ii2_sqrt * impulse;
+    }
+
+    pub fn warmstart(&self, mj_lambdas: &mut [DeltaVel<f32>]) {
+        self.apply_impulse(self.impulse, mj_lambdas)
+    }
+
+    pub fn solve(&mut self, mj_lambdas: &mut [DeltaVel<f32>]) {
+        let dvel = self.ii2_sqrt * mj_lambdas[self.mj_lambda2 as usize].angular
+            - self.ii1_sqrt * mj_lambdas[self.mj_lambda1 as usize].angular
+            + self.rhs;
+
+        let impulse = self.inv_lhs * dvel;
+        self.impulse += impulse;
+        self.apply_impulse(impulse, mj_lambdas)
+    }
+
+    pub fn writeback_impulses(&self, joints_all: &mut [JointGraphEdge]) {
+        let joint = &mut joints_all[self.joint_id].weight;
+        if let JointParams::AngularJoint(angular) = &mut joint.params {
+            angular.impulse = self.impulse
+        }
+    }
+}
+
+#[derive(Debug)]
+pub(crate) struct AngularVelocityGroundConstraint {
+    mj_lambda2: usize,
+    joint_id: JointIndex,
+    rhs: f32,
+    impulse: f32,
+    inv_lhs: f32,
+    ii2_sqrt: f32,
+    flipped: bool,
+}
+
+impl AngularVelocityGroundConstraint {
+    pub fn from_params(
+        params: &IntegrationParameters,
+        joint_id: JointIndex,
+        rb1: &RigidBody,
+        rb2: &RigidBody,
+        cparams: &AngularJoint,
+        flipped: bool,
+    ) -> Self {
+        let ii2_sqrt = rb2.world_inv_inertia_sqrt;
+        let lhs = ii2_sqrt * ii2_sqrt;
+        let inv_lhs = if lhs > 0.0 { 1.0 / lhs } else { 0.0 };
+        // NOTE: if the bodies are flipped, the impulse applied to the
+        // first body (which is the static one here) is `-cparams.impulse`.
+        let impulse = if flipped {
+            -cparams.impulse
+        } else {
+            cparams.impulse
+        };
+
+        AngularVelocityGroundConstraint {
+            joint_id,
+            mj_lambda2: rb2.active_set_offset,
+            rhs: rb2.angvel - rb1.angvel,
+            impulse: impulse * params.effective_warmstart_coeff(),
+            inv_lhs,
+            ii2_sqrt,
+            flipped,
+        }
+    }
+
+    pub fn warmstart(&self, mj_lambdas: &mut [DeltaVel<f32>]) {
+        mj_lambdas[self.mj_lambda2 as usize].angular -= self.ii2_sqrt * self.impulse;
+    }
+
+    pub fn solve(&mut self, mj_lambdas: &mut [DeltaVel<f32>]) {
+        let mj_lambda2 = &mut mj_lambdas[self.mj_lambda2 as usize];
+        let dvel = self.ii2_sqrt * mj_lambda2.angular + self.rhs;
+
+        let impulse = self.inv_lhs * dvel;
+        self.impulse += impulse;
+        mj_lambda2.angular -= self.ii2_sqrt * impulse;
+    }
+
+    pub fn writeback_impulses(&self, joints_all: &mut [JointGraphEdge]) {
+        let joint = &mut joints_all[self.joint_id].weight;
+        if let JointParams::AngularJoint(angular) = &mut joint.params {
+            angular.impulse = if self.flipped {
+                -self.impulse
+            } else {
+                self.impulse
+            };
+        }
+    }
+}
@@ -83,7 +83,7 @@ impl BallVelocityConstraint {
             mj_lambda2: rb2.active_set_offset,
             im1,
             im2,
-            impulse: cparams.impulse * params.warmstart_coeff,
+            impulse: cparams.impulse * params.effective_warmstart_coeff(),
             gcross1,
             gcross2,
             rhs,
@@ -199,7 +199,7 @@ impl BallVelocityGroundConstraint {
             joint_id,
             mj_lambda2: rb2.active_set_offset,
             im2,
-            impulse: cparams.impulse * params.warmstart_coeff,
+            impulse: cparams.impulse * params.effective_warmstart_coeff(),
             gcross2,
             rhs,
             inv_lhs,
@@ -99,7 +99,7 @@ impl WBallVelocityConstraint {
             mj_lambda2,
             im1,
             im2,
-            impulse: impulse * SimdFloat::splat(params.warmstart_coeff),
+            impulse: impulse * SimdFloat::splat(params.effective_warmstart_coeff()),
             gcross1,
             gcross2,
             rhs,
@@ -267,7 +267,7 @@ impl WBallVelocityGroundConstraint {
             joint_id,
             mj_lambda2,
             im2,
-            impulse: impulse * SimdFloat::splat(params.warmstart_coeff),
+            impulse: impulse * SimdFloat::splat(params.effective_warmstart_coeff()),
             gcross2,
             rhs,
             inv_lhs,
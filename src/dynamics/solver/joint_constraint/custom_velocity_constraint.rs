@@ -0,0 +1,180 @@
+use crate::dynamics::solver::DeltaVel;
+use crate::dynamics::{
+    CustomConstraint, CustomConstraintBody, CustomJoint, IntegrationParameters, JointGraphEdge,
+    JointIndex, JointParams, RigidBody,
+};
+use crate::math::{AngVector, Vector};
+use crate::utils::WDot;
+use std::sync::Arc;
+
+#[derive(Debug)]
+struct CustomRowPart {
+    linear1: Vector<f32>,
+    ii_angular1: AngVector<f32>,
+    linear2: Vector<f32>,
+    ii_angular2: AngVector<f32>,
+    rhs: f32,
+    inv_lhs: f32,
+    impulse: f32,
+    min_impulse: f32,
+    max_impulse: f32,
+}
+
+impl CustomRowPart {
+    fn apply(
+        &self,
+        body1: &mut CustomConstraintBody,
+        body2: &mut CustomConstraintBody,
+        impulse: f32,
+    ) {
+        body1.delta.linear += self.linear1 * (body1.inv_mass() * impulse);
+        body1.delta.angular += self.ii_angular1 * impulse;
+        body2.delta.linear += self.linear2 * (body2.inv_mass() * impulse);
+        body2.delta.angular += self.ii_angular2 * impulse;
+    }
+
+    fn solve(&mut self, body1: &mut CustomConstraintBody, body2: &mut CustomConstraintBody) {
+        let dvel = self.rhs
+            + self.linear1.dot(&body1.delta.linear)
+            + self.ii_angular1.gdot(body1.delta.angular)
+            + self.linear2.dot(&body2.delta.linear)
+            + self.ii_angular2.gdot(body2.delta.angular);
+        let new_impulse = (self.impulse - dvel * self.inv_lhs)
+            .max(self.min_impulse)
+            .min(self.max_impulse);
+        let dimpulse = new_impulse - self.impulse;
+        self.impulse = new_impulse;
+        self.apply(body1, body2, dimpulse);
+    }
+}
+
+// NOTE: unlike the other joints, the same constraint is used whether or not one of the bodies
+// is static, and the bodies are never swapped, so the user-defined rows and callbacks always
+// see the bodies in the order they were attached to the joint.
+pub(crate) struct CustomVelocityConstraint {
+    mj_lambda1: Option<usize>,
+    mj_lambda2: Option<usize>,
+
+    joint_id: JointIndex,
+
+    constraint: Option<Arc<dyn CustomConstraint>>,
+    rows: Vec<CustomRowPart>,
+    body1: CustomConstraintBody,
+    body2: CustomConstraintBody,
+    dt: f32,
+}
+
+impl CustomVelocityConstraint {
+    pub fn from_params(
+        params: &IntegrationParameters,
+        joint_id: JointIndex,
+        rb1: &RigidBody,
+        rb2: &RigidBody,
+        cparams: &CustomJoint,
+    ) -> Self {
+        let body1 = CustomConstraintBody::new(rb1);
+        let body2 = CustomConstraintBody::new(rb2);
+        let mut rows = Vec::new();
+
+        if let Some(constraint) = cparams.constraint() {
+            constraint.rows(params, rb1, rb2, &mut rows);
+        }
+
+        let warmstart = rows.len() == cparams.impulses.len();
+        let rows = rows
+            .iter()
+            .enumerate()
+            .map(|(i, row)| {
+                let (vel1, ii_angular1) = body1.jacobian_velocity(&row.linear1, &row.angular1);
+                let (vel2, ii_angular2) = body2.jacobian_velocity(&row.linear2, &row.angular2);
+                let lhs = body1.inv_mass() * row.linear1.norm_squared()
+                    + ii_angular1.gdot(ii_angular1)
+                    + body2.inv_mass() * row.linear2.norm_squared()
+                    + ii_angular2.gdot(ii_angular2);
+                let impulse = if warmstart {
+                    cparams.impulses[i] * params.warmstart_coeff
+                } else {
+                    0.0
+                };
+
+                CustomRowPart {
+                    linear1: row.linear1,
+                    ii_angular1,
+                    linear2: row.linear2,
+                    ii_angular2,
+                    rhs: vel1 + vel2 - row.target_velocity,
+                    inv_lhs: if lhs != 0.0 { 1.0 / lhs } else { 0.0 },
+                    impulse: impulse.max(row.min_impulse).min(row.max_impulse),
+                    min_impulse: row.min_impulse,
+                    max_impulse: row.max_impulse,
+                }
+            })
+            .collect();
+
+        CustomVelocityConstraint {
+            mj_lambda1: Some(rb1.active_set_offset).filter(|_| rb1.is_dynamic()),
+            mj_lambda2: Some(rb2.active_set_offset).filter(|_| rb2.is_dynamic()),
+            joint_id,
+            constraint: cparams.constraint().cloned(),
+            rows,
+            body1,
+            body2,
+            dt: params.dt(),
+        }
+    }
+
+    fn load(&self, mj_lambdas: &[DeltaVel<f32>]) -> (CustomConstraintBody, CustomConstraintBody) {
+        let mut body1 = self.body1;
+        let mut body2 = self.body2;
+        if let Some(i) = self.mj_lambda1 {
+            body1.delta = mj_lambdas[i];
+        }
+        if let Some(i) = self.mj_lambda2 {
+            body2.delta = mj_lambdas[i];
+        }
+        (body1, body2)
+    }
+
+    fn store(
+        &self,
+        body1: &CustomConstraintBody,
+        body2: &CustomConstraintBody,
+        mj_lambdas: &mut [DeltaVel<f32>],
+    ) {
+        if let Some(i) = self.mj_lambda1 {
+            mj_lambdas[i] = body1.delta;
+        }
+        if let Some(i) = self.mj_lambda2 {
+            mj_lambdas[i] = body2.delta;
+        }
+    }
+
+    pub fn warmstart(&self, mj_lambdas: &mut [DeltaVel<f32>]) {
+        let (mut body1, mut body2) = self.load(mj_lambdas);
+        for row in &self.rows {
+            row.apply(&mut body1, &mut body2, row.impulse);
+        }
+        self.store(&body1, &body2, mj_lambdas);
+    }
+
+    pub fn solve(&mut self, mj_lambdas: &mut [DeltaVel<f32>]) {
+        let (mut body1, mut body2) = self.load(mj_lambdas);
+        for row in &mut self.rows {
+            row.solve(&mut body1, &mut body2);
+        }
+        if let Some(constraint) = &self.constraint {
+            constraint.solve_velocities(self.dt, &mut body1, &mut body2);
+        }
+        self.store(&body1, &body2, mj_lambdas);
+    }
+
+    pub fn writeback_impulses(&self, joints_all: &mut [JointGraphEdge]) {
+        let joint = &mut joints_all[self.joint_id].weight;
+        if let JointParams::CustomJoint(custom) = &mut joint.params {
+            custom.impulses.clear();
+            custom
+                .impulses
+                .extend(self.rows.iter().map(|row| row.impulse));
+        }
+    }
+}
@@ -120,7 +120,7 @@ impl FixedVelocityConstraint {
             ii2,
             ii1_sqrt: rb1.world_inv_inertia_sqrt,
             ii2_sqrt: rb2.world_inv_inertia_sqrt,
-            impulse: cparams.impulse * params.warmstart_coeff,
+            impulse: cparams.impulse * params.effective_warmstart_coeff(),
             inv_lhs,
             r1,
             r2,
@@ -305,7 +305,7 @@ impl FixedVelocityGroundConstraint {
             im2,
             ii2,
             ii2_sqrt: rb2.world_inv_inertia_sqrt,
-            impulse: cparams.impulse * params.warmstart_coeff,
+            impulse: cparams.impulse * params.effective_warmstart_coeff(),
             inv_lhs,
             r2,
             rhs,
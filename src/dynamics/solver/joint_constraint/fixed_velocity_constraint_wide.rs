@@ -150,7 +150,7 @@ impl WFixedVelocityConstraint {
             ii2,
             ii1_sqrt,
             ii2_sqrt,
-            impulse: impulse * SimdFloat::splat(params.warmstart_coeff),
+            impulse: impulse * SimdFloat::splat(params.effective_warmstart_coeff()),
             inv_lhs,
             r1,
             r2,
@@ -386,7 +386,7 @@ impl WFixedVelocityGroundConstraint {
             im2,
             ii2,
             ii2_sqrt,
-            impulse: impulse * SimdFloat::splat(params.warmstart_coeff),
+            impulse: impulse * SimdFloat::splat(params.effective_warmstart_coeff()),
             inv_lhs,
             r2,
             rhs,
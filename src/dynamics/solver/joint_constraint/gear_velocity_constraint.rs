@@ -0,0 +1,204 @@
+use crate::dynamics::solver::DeltaVel;
+use crate::dynamics::{
+    GearCoordinate, GearJoint, IntegrationParameters, JointGraphEdge, JointIndex, JointParams,
+    RigidBody,
+};
+use crate::math::Vector;
+
+/// The linear and angular parts of the jacobian of `coeff * coordinate` for the given body.
+fn gear_jacobian(rb: &RigidBody, coordinate: &GearCoordinate, coeff: f32) -> (Vector<f32>, f32) {
+    match coordinate {
+        GearCoordinate::Angle => (Vector::zeros(), coeff),
+        GearCoordinate::Translation(axis) => {
+            (rb.position.rotation * axis.into_inner() * coeff, 0.0)
+        }
+    }
+}
+
+#[derive(Debug)]
+pub(crate) struct GearVelocityConstraint {
+    mj_lambda1: usize,
+    mj_lambda2: usize,
+
+    joint_id: JointIndex,
+
+    rhs: f32,
+    impulse: f32,
+    inv_lhs: f32,
+
+    lin_jac1: Vector<f32>,
+    lin_jac2: Vector<f32>,
+    // The angular parts of the jacobian, multiplied by the square root of the inverse inertia.
+    ang_jac1: f32,
+    ang_jac2: f32,
+
+    im1: f32,
+    im2: f32,
+}
+
+impl GearVelocityConstraint {
+    pub fn from_params(
+        params: &IntegrationParameters,
+        joint_id: JointIndex,
+        rb1: &RigidBody,
+        rb2: &RigidBody,
+        cparams: &GearJoint,
+    ) -> Self {
+        let (lin_jac1, ang_jac1) = gear_jacobian(rb1, &cparams.coordinate1, 1.0);
+        let (lin_jac2, ang_jac2) = gear_jacobian(rb2, &cparams.coordinate2, cparams.ratio);
+        let im1 = rb1.mass_properties.inv_mass;
+        let im2 = rb2.mass_properties.inv_mass;
+
+        let rhs = lin_jac1.dot(&rb1.linvel)
+            + ang_jac1 * rb1.angvel
+            + lin_jac2.dot(&rb2.linvel)
+            + ang_jac2 * rb2.angvel;
+
+        let ang_jac1 = rb1.world_inv_inertia_sqrt * ang_jac1;
+        let ang_jac2 = rb2.world_inv_inertia_sqrt * ang_jac2;
+        let lhs = im1 * lin_jac1.norm_squared()
+            + ang_jac1 * ang_jac1
+            + im2 * lin_jac2.norm_squared()
+            + ang_jac2 * ang_jac2;
+        let inv_lhs = if lhs > 0.0 { 1.0 / lhs } else { 0.0 };
+
+        GearVelocityConstraint {
+            joint_id,
+            mj_lambda1: rb1.active_set_offset,
+            mj_lambda2: rb2.active_set_offset,
+            rhs,
+            impulse: cparams.impulse * params.effective_warmstart_coeff(),
+            inv_lhs,
+            lin_jac1,
+            lin_jac2,
+            ang_jac1,
+            ang_jac2,
+            im1,
+            im2,
+        }
+    }
+
+    fn apply_impulse(&self, impulse: f32, mj_lambdas: &mut [DeltaVel<f32>]) {
+        let mut mj_lambda1 = mj_lambdas[self.mj_lambda1 as usize];
+        let mut mj_lambda2 = mj_lambdas[self.mj_lambda2 as usize];
+
+        mj_lambda1.linear += self.lin_jac1 * (self.im1 * impulse);
+        mj_lambda1.angular += self.ang_jac1 * impulse;
+        mj_lambda2.linear += self.lin_jac2 * (self.im2 * impulse);
+        mj_lambda2.angular += self.ang_jac2 * impulse;
+
+        mj_lambdas[self.mj_lambda1 as usize] = mj_lambda1;
+        mj_lambdas[self.mj_lambda2 as usize] = mj_lambda2;
+    }
+
+    pub fn warmstart(&self, mj_lambdas: &mut [DeltaVel<f32>]) {
+        self.apply_impulse(self.impulse, mj_lambdas)
+    }
+
+    pub fn solve(&mut self, mj_lambdas: &mut [DeltaVel<f32>]) {
+        let mj_lambda1 = mj_lambdas[self.mj_lambda1 as usize];
+        let mj_lambda2 = mj_lambdas[self.mj_lambda2 as usize];
+
+        let dvel = self.lin_jac1.dot(&mj_lambda1.linear)
+            + self.ang_jac1 * mj_lambda1.angular
+            + self.lin_jac2.dot(&mj_lambda2.linear)
+            + self.ang_jac2 * mj_lambda2.angular
+            + self.rhs;
+
+        let impulse = -self.inv_lhs * dvel;
+        self.impulse += impulse;
+        self.apply_impulse(impulse, mj_lambdas)
+    }
+
+    pub fn writeback_impulses(&self, joints_all: &mut [JointGraphEdge]) {
+        let joint = &mut joints_all[self.joint_id].weight;
+        if let JointParams::GearJoint(gear) = &mut joint.params {
+            gear.impulse = self.impulse
+        }
+    }
+}
+
+#[derive(Debug)]
+pub(crate) struct GearVelocityGroundConstraint {
+    mj_lambda2: usize,
+    joint_id: JointIndex,
+    rhs: f32,
+    impulse: f32,
+    inv_lhs: f32,
+    lin_jac2: Vector<f32>,
+    ang_jac2: f32,
+    im2: f32,
+}
+
+impl GearVelocityGroundConstraint {
+    pub fn from_params(
+        params: &IntegrationParameters,
+        joint_id: JointIndex,
+        rb1: &RigidBody,
+        rb2: &RigidBody,
+        cparams: &GearJoint,
+        flipped: bool,
+    ) -> Self {
+        let ((lin_jac1, ang_jac1), (lin_jac2, ang_jac2)) = if flipped {
+            (
+                gear_jacobian(rb1, &cparams.coordinate2, cparams.ratio),
+                gear_jacobian(rb2, &cparams.coordinate1, 1.0),
+            )
+        } else {
+            (
+                gear_jacobian(rb1, &cparams.coordinate1, 1.0),
+                gear_jacobian(rb2, &cparams.coordinate2, cparams.ratio),
+            )
+        };
+        let im2 = rb2.mass_properties.inv_mass;
+
+        let rhs = lin_jac1.dot(&rb1.linvel)
+            + ang_jac1 * rb1.angvel
+            + lin_jac2.dot(&rb2.linvel)
+            + ang_jac2 * rb2.angvel;
+
+        let ang_jac2 = rb2.world_inv_inertia_sqrt * ang_jac2;
+        let lhs = im2 * lin_jac2.norm_squared() + ang_jac2 * ang_jac2;
+        let inv_lhs = if lhs > 0.0 { 1.0 / lhs } else { 0.0 };
+
+        GearVelocityGroundConstraint {
+            joint_id,
+            mj_lambda2: rb2.active_set_offset,
+            rhs,
+            impulse: cparams.impulse * params.effective_warmstart_coeff(),
+            inv_lhs,
+            lin_jac2,
+            ang_jac2,
+            im2,
+        }
+    }
+
+    fn apply_impulse(&self, impulse: f32, mj_lambdas: &mut [DeltaVel<f32>]) {
+        let mut mj_lambda2 = mj_lambdas[self.mj_lambda2 as usize];
+        mj_lambda2.linear += self.lin_jac2 * (self.im2 * impulse);
+        mj_lambda2.angular += self.ang_jac2 * impulse;
+        mj_lambdas[self.mj_lambda2 as usize] = mj_lambda2;
+    }
+
+    pub fn warmstart(&self, mj_lambdas: &mut [DeltaVel<f32>]) {
+        self.apply_impulse(self.impulse, mj_lambdas)
+    }
+
+    pub fn solve(&mut self, mj_lambdas: &mut [DeltaVel<f32>]) {
+        let mj_lambda2 = mj_lambdas[self.mj_lambda2 as usize];
+
+        let dvel =
+            self.lin_jac2.dot(&mj_lambda2.linear) + self.ang_jac2 * mj_lambda2.angular + self.rhs;
+
+        let impulse = -self.inv_lhs * dvel;
+        self.impulse += impulse;
+        self.apply_impulse(impulse, mj_lambdas)
+    }
+
+    pub fn writeback_impulses(&self, joints_all: &mut [JointGraphEdge]) {
+        let joint = &mut joints_all[self.joint_id].weight;
+        if let JointParams::GearJoint(gear) = &mut joint.params {
+            gear.impulse = self.impulse
+        }
+    }
+}
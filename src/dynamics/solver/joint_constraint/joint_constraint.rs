@@ -1,6 +1,12 @@
+#[cfg(feature = "dim2")]
 use super::{
-    BallVelocityConstraint, BallVelocityGroundConstraint, FixedVelocityConstraint,
-    FixedVelocityGroundConstraint, PrismaticVelocityConstraint, PrismaticVelocityGroundConstraint,
+    AngularVelocityConstraint, AngularVelocityGroundConstraint, GearVelocityConstraint,
+    GearVelocityGroundConstraint, LinearVelocityConstraint, LinearVelocityGroundConstraint,
+};
+use super::{
+    BallVelocityConstraint, BallVelocityGroundConstraint, CustomVelocityConstraint,
+    FixedVelocityConstraint, FixedVelocityGroundConstraint, PrismaticVelocityConstraint,
+    PrismaticVelocityGroundConstraint, SpringVelocityConstraint, SpringVelocityGroundConstraint,
 };
 #[cfg(feature = "dim3")]
 use super::{RevoluteVelocityConstraint, RevoluteVelocityGroundConstraint};
@@ -49,6 +55,21 @@ pub(crate) enum AnyJointVelocityConstraint {
     #[cfg(feature = "dim3")]
     #[cfg(feature = "simd-is-enabled")]
     WRevoluteGroundConstraint(WRevoluteVelocityGroundConstraint),
+    SpringConstraint(SpringVelocityConstraint),
+    SpringGroundConstraint(SpringVelocityGroundConstraint),
+    #[cfg(feature = "dim2")]
+    GearConstraint(GearVelocityConstraint),
+    #[cfg(feature = "dim2")]
+    GearGroundConstraint(GearVelocityGroundConstraint),
+    #[cfg(feature = "dim2")]
+    AngularConstraint(AngularVelocityConstraint),
+    #[cfg(feature = "dim2")]
+    AngularGroundConstraint(AngularVelocityGroundConstraint),
+    #[cfg(feature = "dim2")]
+    LinearConstraint(LinearVelocityConstraint),
+    #[cfg(feature = "dim2")]
+    LinearGroundConstraint(LinearVelocityGroundConstraint),
+    CustomConstraint(CustomVelocityConstraint),
     #[allow(dead_code)] // The Empty variant is only used with parallel code.
     Empty,
 }
@@ -82,6 +103,24 @@ impl AnyJointVelocityConstraint {
             JointParams::RevoluteJoint(p) => AnyJointVelocityConstraint::RevoluteConstraint(
                 RevoluteVelocityConstraint::from_params(params, joint_id, rb1, rb2, p),
             ),
+            JointParams::SpringJoint(p) => AnyJointVelocityConstraint::SpringConstraint(
+                SpringVelocityConstraint::from_params(params, joint_id, rb1, rb2, p),
+            ),
+            #[cfg(feature = "dim2")]
+            JointParams::GearJoint(p) => AnyJointVelocityConstraint::GearConstraint(
+                GearVelocityConstraint::from_params(params, joint_id, rb1, rb2, p),
+            ),
+            #[cfg(feature = "dim2")]
+            JointParams::AngularJoint(p) => AnyJointVelocityConstraint::AngularConstraint(
+                AngularVelocityConstraint::from_params(params, joint_id, rb1, rb2, p),
+            ),
+            #[cfg(feature = "dim2")]
+            JointParams::LinearJoint(p) => AnyJointVelocityConstraint::LinearConstraint(
+                LinearVelocityConstraint::from_params(params, joint_id, rb1, rb2, p),
+            ),
+            JointParams::CustomJoint(p) => AnyJointVelocityConstraint::CustomConstraint(
+                CustomVelocityConstraint::from_params(params, joint_id, rb1, rb2, p),
+            ),
         }
     }
 
@@ -123,6 +162,16 @@ impl AnyJointVelocityConstraint {
                     WRevoluteVelocityConstraint::from_params(params, joint_id, rbs1, rbs2, joints),
                 )
             }
+            JointParams::SpringJoint(_) => unreachable!("Springs don't support SIMD constraints."),
+            #[cfg(feature = "dim2")]
+            JointParams::GearJoint(_) => unreachable!("Gears don't support SIMD constraints."),
+            #[cfg(feature = "dim2")]
+            JointParams::AngularJoint(_) | JointParams::LinearJoint(_) => {
+                unreachable!("Angular and linear joints don't support SIMD constraints.")
+            }
+            JointParams::CustomJoint(_) => {
+                unreachable!("Custom joints don't support SIMD constraints.")
+            }
         }
     }
 
@@ -160,6 +209,33 @@ impl AnyJointVelocityConstraint {
                     params, joint_id, rb1, rb2, p, flipped,
                 ),
             ),
+            JointParams::SpringJoint(p) => AnyJointVelocityConstraint::SpringGroundConstraint(
+                SpringVelocityGroundConstraint::from_params(params, joint_id, rb1, rb2, p, flipped),
+            ),
+            #[cfg(feature = "dim2")]
+            JointParams::GearJoint(p) => AnyJointVelocityConstraint::GearGroundConstraint(
+                GearVelocityGroundConstraint::from_params(params, joint_id, rb1, rb2, p, flipped),
+            ),
+            #[cfg(feature = "dim2")]
+            JointParams::AngularJoint(p) => AnyJointVelocityConstraint::AngularGroundConstraint(
+                AngularVelocityGroundConstraint::from_params(
+                    params, joint_id, rb1, rb2, p, flipped,
+                ),
+            ),
+            #[cfg(feature = "dim2")]
+            JointParams::LinearJoint(p) => AnyJointVelocityConstraint::LinearGroundConstraint(
+                LinearVelocityGroundConstraint::from_params(params, joint_id, rb1, rb2, p, flipped),
+            ),
+            // The custom constraint handles static bodies itself, and must not see them flipped.
+            JointParams::CustomJoint(p) => {
+                AnyJointVelocityConstraint::CustomConstraint(CustomVelocityConstraint::from_params(
+                    params,
+                    joint_id,
+                    &bodies[joint.body1],
+                    &bodies[joint.body2],
+                    p,
+                ))
+            }
         }
     }
 
@@ -217,6 +293,16 @@ impl AnyJointVelocityConstraint {
                     ),
                 )
             }
+            JointParams::SpringJoint(_) => unreachable!("Springs don't support SIMD constraints."),
+            #[cfg(feature = "dim2")]
+            JointParams::GearJoint(_) => unreachable!("Gears don't support SIMD constraints."),
+            #[cfg(feature = "dim2")]
+            JointParams::AngularJoint(_) | JointParams::LinearJoint(_) => {
+                unreachable!("Angular and linear joints don't support SIMD constraints.")
+            }
+            JointParams::CustomJoint(_) => {
+                unreachable!("Custom joints don't support SIMD constraints.")
+            }
         }
     }
 
@@ -250,6 +336,21 @@ impl AnyJointVelocityConstraint {
             #[cfg(feature = "dim3")]
             #[cfg(feature = "simd-is-enabled")]
             AnyJointVelocityConstraint::WRevoluteGroundConstraint(c) => c.warmstart(mj_lambdas),
+            AnyJointVelocityConstraint::SpringConstraint(c) => c.warmstart(mj_lambdas),
+            AnyJointVelocityConstraint::SpringGroundConstraint(c) => c.warmstart(mj_lambdas),
+            #[cfg(feature = "dim2")]
+            AnyJointVelocityConstraint::GearConstraint(c) => c.warmstart(mj_lambdas),
+            #[cfg(feature = "dim2")]
+            AnyJointVelocityConstraint::GearGroundConstraint(c) => c.warmstart(mj_lambdas),
+            #[cfg(feature = "dim2")]
+            AnyJointVelocityConstraint::AngularConstraint(c) => c.warmstart(mj_lambdas),
+            #[cfg(feature = "dim2")]
+            AnyJointVelocityConstraint::AngularGroundConstraint(c) => c.warmstart(mj_lambdas),
+            #[cfg(feature = "dim2")]
+            AnyJointVelocityConstraint::LinearConstraint(c) => c.warmstart(mj_lambdas),
+            #[cfg(feature = "dim2")]
+            AnyJointVelocityConstraint::LinearGroundConstraint(c) => c.warmstart(mj_lambdas),
+            AnyJointVelocityConstraint::CustomConstraint(c) => c.warmstart(mj_lambdas),
             AnyJointVelocityConstraint::Empty => unreachable!(),
         }
     }
@@ -284,6 +385,21 @@ impl AnyJointVelocityConstraint {
             #[cfg(feature = "dim3")]
             #[cfg(feature = "simd-is-enabled")]
             AnyJointVelocityConstraint::WRevoluteGroundConstraint(c) => c.solve(mj_lambdas),
+            AnyJointVelocityConstraint::SpringConstraint(c) => c.solve(mj_lambdas),
+            AnyJointVelocityConstraint::SpringGroundConstraint(c) => c.solve(mj_lambdas),
+            #[cfg(feature = "dim2")]
+            AnyJointVelocityConstraint::GearConstraint(c) => c.solve(mj_lambdas),
+            #[cfg(feature = "dim2")]
+            AnyJointVelocityConstraint::GearGroundConstraint(c) => c.solve(mj_lambdas),
+            #[cfg(feature = "dim2")]
+            AnyJointVelocityConstraint::AngularConstraint(c) => c.solve(mj_lambdas),
+            #[cfg(feature = "dim2")]
+            AnyJointVelocityConstraint::AngularGroundConstraint(c) => c.solve(mj_lambdas),
+            #[cfg(feature = "dim2")]
+            AnyJointVelocityConstraint::LinearConstraint(c) => c.solve(mj_lambdas),
+            #[cfg(feature = "dim2")]
+            AnyJointVelocityConstraint::LinearGroundConstraint(c) => c.solve(mj_lambdas),
+            AnyJointVelocityConstraint::CustomConstraint(c) => c.solve(mj_lambdas),
             AnyJointVelocityConstraint::Empty => unreachable!(),
         }
     }
@@ -334,6 +450,27 @@ impl AnyJointVelocityConstraint {
             AnyJointVelocityConstraint::WRevoluteGroundConstraint(c) => {
                 c.writeback_impulses(joints_all)
             }
+            AnyJointVelocityConstraint::SpringConstraint(c) => c.writeback_impulses(joints_all),
+            AnyJointVelocityConstraint::SpringGroundConstraint(c) => {
+                c.writeback_impulses(joints_all)
+            }
+            #[cfg(feature = "dim2")]
+            AnyJointVelocityConstraint::GearConstraint(c) => c.writeback_impulses(joints_all),
+            #[cfg(feature = "dim2")]
+            AnyJointVelocityConstraint::GearGroundConstraint(c) => c.writeback_impulses(joints_all),
+            #[cfg(feature = "dim2")]
+            AnyJointVelocityConstraint::AngularConstraint(c) => c.writeback_impulses(joints_all),
+            #[cfg(feature = "dim2")]
+            AnyJointVelocityConstraint::AngularGroundConstraint(c) => {
+                c.writeback_impulses(joints_all)
+            }
+            #[cfg(feature = "dim2")]
+            AnyJointVelocityConstraint::LinearConstraint(c) => c.writeback_impulses(joints_all),
+            #[cfg(feature = "dim2")]
+            AnyJointVelocityConstraint::LinearGroundConstraint(c) => {
+                c.writeback_impulses(joints_all)
+            }
+            AnyJointVelocityConstraint::CustomConstraint(c) => c.writeback_impulses(joints_all),
             AnyJointVelocityConstraint::Empty => unreachable!(),
         }
     }
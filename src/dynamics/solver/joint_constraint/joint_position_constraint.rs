@@ -1,3 +1,8 @@
+#[cfg(feature = "dim2")]
+use super::{
+    AngularPositionConstraint, AngularPositionGroundConstraint, LinearPositionConstraint,
+    LinearPositionGroundConstraint,
+};
 use super::{
     BallPositionConstraint, BallPositionGroundConstraint, FixedPositionConstraint,
     FixedPositionGroundConstraint, PrismaticPositionConstraint, PrismaticPositionGroundConstraint,
@@ -26,6 +31,21 @@ pub(crate) enum AnyJointPositionConstraint {
     RevoluteJoint(RevolutePositionConstraint),
     #[cfg(feature = "dim3")]
     RevoluteGroundConstraint(RevolutePositionGroundConstraint),
+    // Springs are soft constraints without any position correction.
+    SpringJoint,
+    // Gears are only enforced at the velocity level.
+    #[cfg(feature = "dim2")]
+    GearJoint,
+    #[cfg(feature = "dim2")]
+    AngularJoint(AngularPositionConstraint),
+    #[cfg(feature = "dim2")]
+    AngularGroundConstraint(AngularPositionGroundConstraint),
+    #[cfg(feature = "dim2")]
+    LinearJoint(LinearPositionConstraint),
+    #[cfg(feature = "dim2")]
+    LinearGroundConstraint(LinearPositionGroundConstraint),
+    // Custom joints are only enforced at the velocity level.
+    CustomJoint,
     #[allow(dead_code)] // The Empty variant is only used with parallel code.
     Empty,
 }
@@ -67,6 +87,18 @@ impl AnyJointPositionConstraint {
             JointParams::RevoluteJoint(p) => AnyJointPositionConstraint::RevoluteJoint(
                 RevolutePositionConstraint::from_params(rb1, rb2, p),
             ),
+            JointParams::SpringJoint(_) => AnyJointPositionConstraint::SpringJoint,
+            #[cfg(feature = "dim2")]
+            JointParams::GearJoint(_) => AnyJointPositionConstraint::GearJoint,
+            #[cfg(feature = "dim2")]
+            JointParams::AngularJoint(p) => AnyJointPositionConstraint::AngularJoint(
+                AngularPositionConstraint::from_params(rb1, rb2, p),
+            ),
+            #[cfg(feature = "dim2")]
+            JointParams::LinearJoint(p) => AnyJointPositionConstraint::LinearJoint(
+                LinearPositionConstraint::from_params(rb1, rb2, p),
+            ),
+            JointParams::CustomJoint(_) => AnyJointPositionConstraint::CustomJoint,
         }
     }
 
@@ -86,6 +118,12 @@ impl AnyJointPositionConstraint {
             JointParams::PrismaticJoint(_) => None,
             #[cfg(feature = "dim3")]
             JointParams::RevoluteJoint(_) => None,
+            JointParams::SpringJoint(_) => None,
+            #[cfg(feature = "dim2")]
+            JointParams::GearJoint(_) => None,
+            #[cfg(feature = "dim2")]
+            JointParams::AngularJoint(_) | JointParams::LinearJoint(_) => None,
+            JointParams::CustomJoint(_) => None,
         }
     }
 
@@ -114,6 +152,18 @@ impl AnyJointPositionConstraint {
             JointParams::RevoluteJoint(p) => AnyJointPositionConstraint::RevoluteGroundConstraint(
                 RevolutePositionGroundConstraint::from_params(rb1, rb2, p, flipped),
             ),
+            JointParams::SpringJoint(_) => AnyJointPositionConstraint::SpringJoint,
+            #[cfg(feature = "dim2")]
+            JointParams::GearJoint(_) => AnyJointPositionConstraint::GearJoint,
+            #[cfg(feature = "dim2")]
+            JointParams::AngularJoint(p) => AnyJointPositionConstraint::AngularGroundConstraint(
+                AngularPositionGroundConstraint::from_params(rb1, rb2, p, flipped),
+            ),
+            #[cfg(feature = "dim2")]
+            JointParams::LinearJoint(p) => AnyJointPositionConstraint::LinearGroundConstraint(
+                LinearPositionGroundConstraint::from_params(rb1, rb2, p, flipped),
+            ),
+            JointParams::CustomJoint(_) => AnyJointPositionConstraint::CustomJoint,
         }
     }
 
@@ -144,6 +194,12 @@ impl AnyJointPositionConstraint {
             JointParams::PrismaticJoint(_) => None,
             #[cfg(feature = "dim3")]
             JointParams::RevoluteJoint(_) => None,
+            JointParams::SpringJoint(_) => None,
+            #[cfg(feature = "dim2")]
+            JointParams::GearJoint(_) => None,
+            #[cfg(feature = "dim2")]
+            JointParams::AngularJoint(_) | JointParams::LinearJoint(_) => None,
+            JointParams::CustomJoint(_) => None,
         }
     }
 
@@ -163,6 +219,18 @@ impl AnyJointPositionConstraint {
             AnyJointPositionConstraint::RevoluteJoint(c) => c.solve(params, positions),
             #[cfg(feature = "dim3")]
             AnyJointPositionConstraint::RevoluteGroundConstraint(c) => c.solve(params, positions),
+            AnyJointPositionConstraint::SpringJoint => {}
+            #[cfg(feature = "dim2")]
+            AnyJointPositionConstraint::GearJoint => {}
+            #[cfg(feature = "dim2")]
+            AnyJointPositionConstraint::AngularJoint(c) => c.solve(params, positions),
+            #[cfg(feature = "dim2")]
+            AnyJointPositionConstraint::AngularGroundConstraint(c) => c.solve(params, positions),
+            #[cfg(feature = "dim2")]
+            AnyJointPositionConstraint::LinearJoint(c) => c.solve(params, positions),
+            #[cfg(feature = "dim2")]
+            AnyJointPositionConstraint::LinearGroundConstraint(c) => c.solve(params, positions),
+            AnyJointPositionConstraint::CustomJoint => {}
             AnyJointPositionConstraint::Empty => unreachable!(),
         }
     }
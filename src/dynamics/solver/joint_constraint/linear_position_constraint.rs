@@ -0,0 +1,96 @@
+use crate::dynamics::{IntegrationParameters, LinearJoint, RigidBody};
+use crate::math::{Isometry, Point, Vector};
+
+#[derive(Debug)]
+pub(crate) struct LinearPositionConstraint {
+    position1: usize,
+    position2: usize,
+
+    local_com1: Point<f32>,
+    local_com2: Point<f32>,
+
+    im1: f32,
+    im2: f32,
+
+    offset: Vector<f32>,
+}
+
+impl LinearPositionConstraint {
+    pub fn from_params(rb1: &RigidBody, rb2: &RigidBody, cparams: &LinearJoint) -> Self {
+        Self {
+            local_com1: rb1.mass_properties.local_com,
+            local_com2: rb2.mass_properties.local_com,
+            im1: rb1.mass_properties.inv_mass,
+            im2: rb2.mass_properties.inv_mass,
+            offset: cparams.offset,
+            position1: rb1.active_set_offset,
+            position2: rb2.active_set_offset,
+        }
+    }
+
+    pub fn solve(&self, params: &IntegrationParameters, positions: &mut [Isometry<f32>]) {
+        let total_im = self.im1 + self.im2;
+
+        if total_im == 0.0 {
+            return;
+        }
+
+        let mut position1 = positions[self.position1 as usize];
+        let mut position2 = positions[self.position2 as usize];
+
+        let com1 = position1 * self.local_com1;
+        let com2 = position2 * self.local_com2;
+        let err = com2 - com1 - self.offset;
+        let correction = err * (params.joint_erp / total_im);
+
+        position1.translation.vector += correction * self.im1;
+        position2.translation.vector -= correction * self.im2;
+
+        positions[self.position1 as usize] = position1;
+        positions[self.position2 as usize] = position2;
+    }
+}
+
+#[derive(Debug)]
+pub(crate) struct LinearPositionGroundConstraint {
+    position2: usize,
+    // The position the center of mass of the second body should have to satisfy the joint.
+    target_com2: Point<f32>,
+    local_com2: Point<f32>,
+    im2: f32,
+}
+
+impl LinearPositionGroundConstraint {
+    pub fn from_params(
+        rb1: &RigidBody,
+        rb2: &RigidBody,
+        cparams: &LinearJoint,
+        flipped: bool,
+    ) -> Self {
+        // NOTE: if the bodies are flipped, the joint offset relative
+        // to the non-dynamic body rb1 is `-cparams.offset`.
+        let offset = if flipped {
+            -cparams.offset
+        } else {
+            cparams.offset
+        };
+
+        Self {
+            position2: rb2.active_set_offset,
+            target_com2: rb1.predicted_position * rb1.mass_properties.local_com + offset,
+            local_com2: rb2.mass_properties.local_com,
+            im2: rb2.mass_properties.inv_mass,
+        }
+    }
+
+    pub fn solve(&self, params: &IntegrationParameters, positions: &mut [Isometry<f32>]) {
+        if self.im2 == 0.0 {
+            return;
+        }
+
+        let mut position2 = positions[self.position2 as usize];
+        let err = position2 * self.local_com2 - self.target_com2;
+        position2.translation.vector -= err * params.joint_erp;
+        positions[self.position2 as usize] = position2;
+    }
+}
@@ -0,0 +1,138 @@
+use crate::dynamics::solver::DeltaVel;
+use crate::dynamics::{
+    IntegrationParameters, JointGraphEdge, JointIndex, JointParams, LinearJoint, RigidBody,
+};
+use crate::math::Vector;
+
+#[derive(Debug)]
+pub(crate) struct LinearVelocityConstraint {
+    mj_lambda1: usize,
+    mj_lambda2: usize,
+
+    joint_id: JointIndex,
+
+    rhs: Vector<f32>,
+    impulse: Vector<f32>,
+    inv_lhs: f32,
+
+    im1: f32,
+    im2: f32,
+}
+
+impl LinearVelocityConstraint {
+    pub fn from_params(
+        params: &IntegrationParameters,
+        joint_id: JointIndex,
+        rb1: &RigidBody,
+        rb2: &RigidBody,
+        cparams: &LinearJoint,
+    ) -> Self {
+        let im1 = rb1.mass_properties.inv_mass;
+        let im2 = rb2.mass_properties.inv_mass;
+        let lhs = im1 + im2;
+        let inv_lhs = if lhs > 0.0 { 1.0 / lhs } else { 0.0 };
+
+        LinearVelocityConstraint {
+            joint_id,
+            mj_lambda1: rb1.active_set_offset,
+            mj_lambda2: rb2.active_set_offset,
+            rhs: rb2.linvel - rb1.linvel,
+            impulse: cparams.impulse * params.effective_warmstart_coeff(),
+            inv_lhs,
+            im1,
+            im2,
+        }
+    }
+
+    fn apply_impulse(&self, impulse: Vector<f32>, mj_lambdas: &mut [DeltaVel<f32>]) {
+        mj_lambdas[self.mj_lambda1 as usize].linear += impulse * self.im1;
+        mj_lambdas[self.mj_lambda2 as usize].linear -= impulse * self.im2;
+    }
+
+    pub fn warmstart(&self, mj_lambdas: &mut [DeltaVel<f32>]) {
+        self.apply_impulse(self.impulse, mj_lambdas)
+    }
+
+    pub fn solve(&mut self, mj_lambdas: &mut [DeltaVel<f32>]) {
+        let dvel = mj_lambdas[self.mj_lambda2 as usize].linear
+            - mj_lambdas[self.mj_lambda1 as usize].linear
+            + self.rhs;
+
+        let impulse = dvel * self.inv_lhs;
+        self.impulse += impulse;
+        self.apply_impulse(impulse, mj_lambdas)
+    }
+
+    pub fn writeback_impulses(&self, joints_all: &mut [JointGraphEdge]) {
+        let joint = &mut joints_all[self.joint_id].weight;
+        if let JointParams::LinearJoint(linear) = &mut joint.params {
+            linear.impulse = self.impulse
+        }
+    }
+}
+
+#[derive(Debug)]
+pub(crate) struct LinearVelocityGroundConstraint {
+    mj_lambda2: usize,
+    joint_id: JointIndex,
+    rhs: Vector<f32>,
+    impulse: Vector<f32>,
+    im2: f32,
+    flipped: bool,
+}
+
+impl LinearVelocityGroundConstraint {
+    pub fn from_params(
+        params: &IntegrationParameters,
+        joint_id: JointIndex,
+        rb1: &RigidBody,
+        rb2: &RigidBody,
+        cparams: &LinearJoint,
+        flipped: bool,
+    ) -> Self {
+        // NOTE: if the bodies are flipped, the impulse applied to the
+        // first body (which is the static one here) is `-cparams.impulse`.
+        let impulse = if flipped {
+            -cparams.impulse
+        } else {
+            cparams.impulse
+        };
+
+        LinearVelocityGroundConstraint {
+            joint_id,
+            mj_lambda2: rb2.active_set_offset,
+            rhs: rb2.linvel - rb1.linvel,
+            impulse: impulse * params.effective_warmstart_coeff(),
+            im2: rb2.mass_properties.inv_mass,
+            flipped,
+        }
+    }
+
+    pub fn warmstart(&self, mj_lambdas: &mut [DeltaVel<f32>]) {
+        mj_lambdas[self.mj_lambda2 as usize].linear -= self.impulse * self.im2;
+    }
+
+    pub fn solve(&mut self, mj_lambdas: &mut [DeltaVel<f32>]) {
+        if self.im2 == 0.0 {
+            return;
+        }
+
+        let mj_lambda2 = &mut mj_lambdas[self.mj_lambda2 as usize];
+        let dvel = mj_lambda2.linear + self.rhs;
+
+        let impulse = dvel / self.im2;
+        self.impulse += impulse;
+        mj_lambda2.linear -= impulse * self.im2;
+    }
+
+    pub fn writeback_impulses(&self, joints_all: &mut [JointGraphEdge]) {
+        let joint = &mut joints_all[self.joint_id].weight;
+        if let JointParams::LinearJoint(linear) = &mut joint.params {
+            linear.impulse = if self.flipped {
+                -self.impulse
+            } else {
+                self.impulse
+            };
+        }
+    }
+}
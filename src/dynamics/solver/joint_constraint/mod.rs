@@ -1,3 +1,11 @@
+#[cfg(feature = "dim2")]
+pub(self) use angular_position_constraint::{
+    AngularPositionConstraint, AngularPositionGroundConstraint,
+};
+#[cfg(feature = "dim2")]
+pub(self) use angular_velocity_constraint::{
+    AngularVelocityConstraint, AngularVelocityGroundConstraint,
+};
 pub(self) use ball_position_constraint::{BallPositionConstraint, BallPositionGroundConstraint};
 #[cfg(feature = "simd-is-enabled")]
 pub(self) use ball_position_constraint_wide::{
@@ -8,14 +16,26 @@ pub(self) use ball_velocity_constraint::{BallVelocityConstraint, BallVelocityGro
 pub(self) use ball_velocity_constraint_wide::{
     WBallVelocityConstraint, WBallVelocityGroundConstraint,
 };
+pub(self) use custom_velocity_constraint::CustomVelocityConstraint;
 pub(self) use fixed_position_constraint::{FixedPositionConstraint, FixedPositionGroundConstraint};
 pub(self) use fixed_velocity_constraint::{FixedVelocityConstraint, FixedVelocityGroundConstraint};
 #[cfg(feature = "simd-is-enabled")]
 pub(self) use fixed_velocity_constraint_wide::{
     WFixedVelocityConstraint, WFixedVelocityGroundConstraint,
 };
+#[cfg(feature = "dim2")]
+pub(self) use gear_velocity_constraint::{GearVelocityConstraint, GearVelocityGroundConstraint};
 pub(crate) use joint_constraint::AnyJointVelocityConstraint;
 pub(crate) use joint_position_constraint::AnyJointPositionConstraint;
+#[cfg(feature = "dim2")]
+pub(self) use linear_position_constraint::{
+    LinearPositionConstraint, LinearPositionGroundConstraint,
+};
+#[cfg(feature = "dim2")]
+pub(self) use linear_velocity_constraint::{
+    LinearVelocityConstraint, LinearVelocityGroundConstraint,
+};
+pub(self) use motor_part::MotorPart;
 pub(self) use prismatic_position_constraint::{
     PrismaticPositionConstraint, PrismaticPositionGroundConstraint,
 };
@@ -39,19 +59,34 @@ pub(self) use revolute_velocity_constraint::{
 pub(self) use revolute_velocity_constraint_wide::{
     WRevoluteVelocityConstraint, WRevoluteVelocityGroundConstraint,
 };
+pub(self) use spring_velocity_constraint::{
+    SpringVelocityConstraint, SpringVelocityGroundConstraint,
+};
 
+#[cfg(feature = "dim2")]
+mod angular_position_constraint;
+#[cfg(feature = "dim2")]
+mod angular_velocity_constraint;
 mod ball_position_constraint;
 #[cfg(feature = "simd-is-enabled")]
 mod ball_position_constraint_wide;
 mod ball_velocity_constraint;
 #[cfg(feature = "simd-is-enabled")]
 mod ball_velocity_constraint_wide;
+mod custom_velocity_constraint;
 mod fixed_position_constraint;
 mod fixed_velocity_constraint;
 #[cfg(feature = "simd-is-enabled")]
 mod fixed_velocity_constraint_wide;
+#[cfg(feature = "dim2")]
+mod gear_velocity_constraint;
 mod joint_constraint;
 mod joint_position_constraint;
+#[cfg(feature = "dim2")]
+mod linear_position_constraint;
+#[cfg(feature = "dim2")]
+mod linear_velocity_constraint;
+mod motor_part;
 mod prismatic_position_constraint;
 mod prismatic_velocity_constraint;
 #[cfg(feature = "simd-is-enabled")]
@@ -63,3 +98,4 @@ mod revolute_velocity_constraint;
 #[cfg(feature = "dim3")]
 #[cfg(feature = "simd-is-enabled")]
 mod revolute_velocity_constraint_wide;
+mod spring_velocity_constraint;
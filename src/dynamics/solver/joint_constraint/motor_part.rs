@@ -0,0 +1,71 @@
+use crate::dynamics::IntegrationParameters;
+
+/// The one-dimensional part of a joint velocity constraint responsible for its motor.
+///
+/// The motor is modeled as a spring-damper driving the joint toward its target position and
+/// velocity. It is solved implicitly as a soft constraint so large gains remain stable.
+#[derive(Debug, Copy, Clone)]
+pub(crate) struct MotorPart {
+    /// The initial velocity error, including the position-based bias.
+    pub rhs: f32,
+    /// The effective mass of this soft constraint.
+    pub inv_lhs: f32,
+    /// The constraint force mixing term of this soft constraint.
+    pub gamma: f32,
+    pub impulse: f32,
+    pub max_impulse: f32,
+}
+
+impl MotorPart {
+    /// Initializes the motor part of a joint constraint.
+    ///
+    /// Returns `None` if the motor is disabled, i.e., if both the stiffness and the damping are zero.
+    ///
+    /// # Parameters
+    /// - `target_pos_error`: the current joint coordinate minus its target.
+    /// - `curr_vel`: the time derivative of the current joint coordinate.
+    /// - `proj_inv_mass`: the inverse mass of the attached bodies, projected on the motor axis.
+    pub fn new(
+        params: &IntegrationParameters,
+        stiffness: f32,
+        damping: f32,
+        target_pos_error: f32,
+        target_vel: f32,
+        curr_vel: f32,
+        proj_inv_mass: f32,
+        impulse: f32,
+        max_impulse: f32,
+    ) -> Option<Self> {
+        let dt = params.dt();
+        let denom = damping + dt * stiffness;
+
+        if denom <= 0.0 || dt == 0.0 {
+            return None;
+        }
+
+        let gamma = 1.0 / (dt * denom);
+        let erp_inv_dt = stiffness / denom;
+        let rhs = curr_vel - target_vel + erp_inv_dt * target_pos_error;
+        let inv_lhs = 1.0 / (proj_inv_mass + gamma);
+        let impulse =
+            (impulse * params.effective_warmstart_coeff()).clamp(-max_impulse, max_impulse);
+
+        Some(Self {
+            rhs,
+            inv_lhs,
+            gamma,
+            impulse,
+            max_impulse,
+        })
+    }
+
+    /// Computes the impulse increment for the given relative velocity, in addition to
+    /// the velocity already accounted for by `self.rhs`.
+    pub fn solve(&mut self, dvel: f32) -> f32 {
+        let dimpulse = self.inv_lhs * (dvel + self.rhs - self.gamma * self.impulse);
+        let new_impulse = (self.impulse + dimpulse).clamp(-self.max_impulse, self.max_impulse);
+        let dimpulse = new_impulse - self.impulse;
+        self.impulse = new_impulse;
+        dimpulse
+    }
+}
@@ -1,9 +1,10 @@
+use super::MotorPart;
 use crate::dynamics::solver::DeltaVel;
 use crate::dynamics::{
     IntegrationParameters, JointGraphEdge, JointIndex, JointParams, PrismaticJoint, RigidBody,
 };
-use crate::math::{AngularInertia, Vector};
-use crate::utils::{WAngularInertia, WCross, WCrossMatrix};
+use crate::math::{AngularInertia, Isometry, Vector};
+use crate::utils::{WAngularInertia, WCross, WCrossMatrix, WDot};
 #[cfg(feature = "dim3")]
 use na::{Cholesky, Matrix3x2, Matrix5, Vector5, U2, U3};
 #[cfg(feature = "dim2")]
@@ -44,6 +45,10 @@ pub(crate) struct PrismaticVelocityConstraint {
     limits_impulse: f32,
     limits_forcedirs: Option<(Vector<f32>, Vector<f32>)>,
     limits_rhs: f32,
+    active_limit: Option<usize>,
+
+    motor: Option<MotorPart>,
+    motor_axis: Vector<f32>,
 
     #[cfg(feature = "dim2")]
     basis1: Vector2<f32>,
@@ -153,6 +158,7 @@ impl PrismaticVelocityConstraint {
         let mut limits_forcedirs = None;
         let mut limits_rhs = 0.0;
         let mut limits_impulse = 0.0;
+        let mut active_limit = None;
 
         if cparams.limits_enabled {
             let danchor = anchor2 - anchor1;
@@ -164,13 +170,30 @@ impl PrismaticVelocityConstraint {
                 limits_forcedirs = Some((-axis1.into_inner(), axis2.into_inner()));
                 limits_rhs = anchor_linvel2.dot(&axis2) - anchor_linvel1.dot(&axis1);
                 limits_impulse = cparams.limits_impulse;
+                active_limit = Some(0);
             } else if dist > cparams.limits[1] {
                 limits_forcedirs = Some((axis1.into_inner(), -axis2.into_inner()));
                 limits_rhs = -anchor_linvel2.dot(&axis2) + anchor_linvel1.dot(&axis1);
                 limits_impulse = cparams.limits_impulse;
+                active_limit = Some(1);
             }
         }
 
+        // Setup the motor.
+        let motor_axis = axis1.into_inner();
+        let motor = MotorPart::new(
+            params,
+            cparams.motor_stiffness,
+            cparams.motor_damping,
+            translation_error(cparams, &rb1.position, &rb2.position),
+            cparams.motor_target_vel,
+            motor_axis.dot(&(anchor_linvel2 - anchor_linvel1)),
+            proj_inv_mass(im1, &rb1.world_inv_inertia_sqrt, &r1, &motor_axis)
+                + proj_inv_mass(im2, &rb2.world_inv_inertia_sqrt, &r2, &motor_axis),
+            cparams.motor_impulse,
+            cparams.motor_max_impulse,
+        );
+
         PrismaticVelocityConstraint {
             joint_id,
             mj_lambda1: rb1.active_set_offset,
@@ -179,10 +202,13 @@ impl PrismaticVelocityConstraint {
             ii1_sqrt: rb1.world_inv_inertia_sqrt,
             im2,
             ii2_sqrt: rb2.world_inv_inertia_sqrt,
-            impulse: cparams.impulse * params.warmstart_coeff,
-            limits_impulse: limits_impulse * params.warmstart_coeff,
+            impulse: cparams.impulse * params.effective_warmstart_coeff(),
+            limits_impulse: limits_impulse * params.effective_warmstart_coeff(),
             limits_forcedirs,
             limits_rhs,
+            active_limit,
+            motor,
+            motor_axis,
             basis1,
             inv_lhs,
             rhs,
@@ -216,6 +242,18 @@ impl PrismaticVelocityConstraint {
             mj_lambda2.linear += limits_forcedir2 * (self.im2 * self.limits_impulse);
         }
 
+        if let Some(motor) = &self.motor {
+            let motor_impulse = self.motor_axis * motor.impulse;
+            mj_lambda1.linear += self.im1 * motor_impulse;
+            mj_lambda1.angular += self
+                .ii1_sqrt
+                .transform_vector(self.r1.gcross(motor_impulse));
+            mj_lambda2.linear -= self.im2 * motor_impulse;
+            mj_lambda2.angular -= self
+                .ii2_sqrt
+                .transform_vector(self.r2.gcross(motor_impulse));
+        }
+
         mj_lambdas[self.mj_lambda1 as usize] = mj_lambda1;
         mj_lambdas[self.mj_lambda2 as usize] = mj_lambda2;
     }
@@ -276,6 +314,27 @@ impl PrismaticVelocityConstraint {
             mj_lambda2.linear += limits_forcedir2 * (self.im2 * dimpulse);
         }
 
+        /*
+         * Motor.
+         */
+        if let Some(motor) = &mut self.motor {
+            let ang_vel1 = self.ii1_sqrt.transform_vector(mj_lambda1.angular);
+            let ang_vel2 = self.ii2_sqrt.transform_vector(mj_lambda2.angular);
+            let lin_vel1 = mj_lambda1.linear + ang_vel1.gcross(self.r1);
+            let lin_vel2 = mj_lambda2.linear + ang_vel2.gcross(self.r2);
+            let dvel = self.motor_axis.dot(&(lin_vel2 - lin_vel1));
+            let motor_impulse = self.motor_axis * motor.solve(dvel);
+
+            mj_lambda1.linear += self.im1 * motor_impulse;
+            mj_lambda1.angular += self
+                .ii1_sqrt
+                .transform_vector(self.r1.gcross(motor_impulse));
+            mj_lambda2.linear -= self.im2 * motor_impulse;
+            mj_lambda2.angular -= self
+                .ii2_sqrt
+                .transform_vector(self.r2.gcross(motor_impulse));
+        }
+
         mj_lambdas[self.mj_lambda1 as usize] = mj_lambda1;
         mj_lambdas[self.mj_lambda2 as usize] = mj_lambda2;
     }
@@ -285,6 +344,8 @@ impl PrismaticVelocityConstraint {
         if let JointParams::PrismaticJoint(revolute) = &mut joint.params {
             revolute.impulse = self.impulse;
             revolute.limits_impulse = self.limits_impulse;
+            revolute.active_limit = self.active_limit;
+            revolute.motor_impulse = self.motor.map(|m| m.impulse).unwrap_or(0.0);
         }
     }
 }
@@ -313,6 +374,7 @@ pub(crate) struct PrismaticVelocityGroundConstraint {
 
     limits_impulse: f32,
     limits_rhs: f32,
+    active_limit: Option<usize>,
 
     axis2: Vector<f32>,
     #[cfg(feature = "dim2")]
@@ -321,6 +383,9 @@ pub(crate) struct PrismaticVelocityGroundConstraint {
     basis1: Matrix3x2<f32>,
     limits_forcedir2: Option<Vector<f32>>,
 
+    motor: Option<MotorPart>,
+    motor_axis: Vector<f32>,
+
     im2: f32,
     ii2_sqrt: AngularInertia<f32>,
 }
@@ -442,6 +507,7 @@ impl PrismaticVelocityGroundConstraint {
         let mut limits_forcedir2 = None;
         let mut limits_rhs = 0.0;
         let mut limits_impulse = 0.0;
+        let mut active_limit = None;
 
         if cparams.limits_enabled {
             let danchor = anchor2 - anchor1;
@@ -454,20 +520,49 @@ impl PrismaticVelocityGroundConstraint {
                 limits_forcedir2 = Some(axis2.into_inner());
                 limits_rhs = anchor_linvel2.dot(&axis2) - anchor_linvel1.dot(&axis1);
                 limits_impulse = cparams.limits_impulse;
+                active_limit = Some(0);
             } else if dist > cparams.limits[1] {
                 limits_forcedir2 = Some(-axis2.into_inner());
                 limits_rhs = -anchor_linvel2.dot(&axis2) + anchor_linvel1.dot(&axis1);
                 limits_impulse = cparams.limits_impulse;
+                active_limit = Some(1);
             }
         }
 
+        // Setup the motor.
+        // NOTE: the motor axis is flipped if the bodies are flipped so that
+        // its velocity is still the one of the second attached body relative
+        // to the first one.
+        let (motor_axis, translation_error) = if flipped {
+            (
+                -axis2.into_inner(),
+                translation_error(cparams, &rb2.position, &rb1.position),
+            )
+        } else {
+            (
+                axis1.into_inner(),
+                translation_error(cparams, &rb1.position, &rb2.position),
+            )
+        };
+        let motor = MotorPart::new(
+            params,
+            cparams.motor_stiffness,
+            cparams.motor_damping,
+            translation_error,
+            cparams.motor_target_vel,
+            motor_axis.dot(&(anchor_linvel2 - anchor_linvel1)),
+            proj_inv_mass(im2, &rb2.world_inv_inertia_sqrt, &r2, &motor_axis),
+            cparams.motor_impulse,
+            cparams.motor_max_impulse,
+        );
+
         PrismaticVelocityGroundConstraint {
             joint_id,
             mj_lambda2: rb2.active_set_offset,
             im2,
             ii2_sqrt: rb2.world_inv_inertia_sqrt,
-            impulse: cparams.impulse * params.warmstart_coeff,
-            limits_impulse: limits_impulse * params.warmstart_coeff,
+            impulse: cparams.impulse * params.effective_warmstart_coeff(),
+            limits_impulse: limits_impulse * params.effective_warmstart_coeff(),
             basis1,
             inv_lhs,
             rhs,
@@ -475,6 +570,9 @@ impl PrismaticVelocityGroundConstraint {
             axis2: axis2.into_inner(),
             limits_forcedir2,
             limits_rhs,
+            active_limit,
+            motor,
+            motor_axis,
         }
     }
 
@@ -496,6 +594,14 @@ impl PrismaticVelocityGroundConstraint {
             mj_lambda2.linear += limits_forcedir2 * (self.im2 * self.limits_impulse);
         }
 
+        if let Some(motor) = &self.motor {
+            let motor_impulse = self.motor_axis * motor.impulse;
+            mj_lambda2.linear -= self.im2 * motor_impulse;
+            mj_lambda2.angular -= self
+                .ii2_sqrt
+                .transform_vector(self.r2.gcross(motor_impulse));
+        }
+
         mj_lambdas[self.mj_lambda2 as usize] = mj_lambda2;
     }
 
@@ -544,6 +650,21 @@ impl PrismaticVelocityGroundConstraint {
             mj_lambda2.linear += limits_forcedir2 * (self.im2 * dimpulse);
         }
 
+        /*
+         * Motor.
+         */
+        if let Some(motor) = &mut self.motor {
+            let ang_vel2 = self.ii2_sqrt.transform_vector(mj_lambda2.angular);
+            let lin_vel2 = mj_lambda2.linear + ang_vel2.gcross(self.r2);
+            let dvel = self.motor_axis.dot(&lin_vel2);
+            let motor_impulse = self.motor_axis * motor.solve(dvel);
+
+            mj_lambda2.linear -= self.im2 * motor_impulse;
+            mj_lambda2.angular -= self
+                .ii2_sqrt
+                .transform_vector(self.r2.gcross(motor_impulse));
+        }
+
         mj_lambdas[self.mj_lambda2 as usize] = mj_lambda2;
     }
 
@@ -553,6 +674,28 @@ impl PrismaticVelocityGroundConstraint {
         if let JointParams::PrismaticJoint(revolute) = &mut joint.params {
             revolute.impulse = self.impulse;
             revolute.limits_impulse = self.limits_impulse;
+            revolute.active_limit = self.active_limit;
+            revolute.motor_impulse = self.motor.map(|m| m.impulse).unwrap_or(0.0);
         }
     }
 }
+
+/// The difference between the current translation of the joint and its motor target translation.
+fn translation_error(
+    cparams: &PrismaticJoint,
+    position1: &Isometry<f32>,
+    position2: &Isometry<f32>,
+) -> f32 {
+    cparams.translation_along_axis(position1, position2) - cparams.motor_target_pos
+}
+
+/// The inverse mass of a body at the point `r` relative to its center of mass, projected on the given axis.
+fn proj_inv_mass(
+    im: f32,
+    ii_sqrt: &AngularInertia<f32>,
+    r: &Vector<f32>,
+    axis: &Vector<f32>,
+) -> f32 {
+    let ii_sqrt_gcross = ii_sqrt.transform_vector(r.gcross(*axis));
+    im + ii_sqrt_gcross.gdot(ii_sqrt_gcross)
+}
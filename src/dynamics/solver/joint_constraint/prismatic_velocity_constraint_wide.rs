@@ -48,6 +48,7 @@ pub(crate) struct WPrismaticVelocityConstraint {
     limits_impulse: SimdFloat,
     limits_forcedirs: Option<(Vector<SimdFloat>, Vector<SimdFloat>)>,
     limits_rhs: SimdFloat,
+    active_limits: [Option<usize>; SIMD_WIDTH],
 
     #[cfg(feature = "dim2")]
     basis1: Vector2<SimdFloat>,
@@ -191,6 +192,7 @@ impl WPrismaticVelocityConstraint {
         let mut limits_forcedirs = None;
         let mut limits_rhs = na::zero();
         let mut limits_impulse = na::zero();
+        let mut active_limits = [None; SIMD_WIDTH];
         let limits_enabled = SimdBool::from(array![|ii| cparams[ii].limits_enabled; SIMD_WIDTH]);
 
         if limits_enabled.any() {
@@ -214,6 +216,16 @@ impl WPrismaticVelocityConstraint {
                 limits_rhs = (anchor_linvel2.dot(&axis2) - anchor_linvel1.dot(&axis1)) * sign;
                 limits_impulse = lim_impulse.select(min_enabled | max_enabled, _0);
             }
+
+            for ii in 0..SIMD_WIDTH {
+                if cparams[ii].limits_enabled {
+                    if min_enabled.extract(ii) {
+                        active_limits[ii] = Some(0);
+                    } else if max_enabled.extract(ii) {
+                        active_limits[ii] = Some(1);
+                    }
+                }
+            }
         }
 
         WPrismaticVelocityConstraint {
@@ -224,10 +236,11 @@ impl WPrismaticVelocityConstraint {
             ii1_sqrt,
             im2,
             ii2_sqrt,
-            impulse: impulse * SimdFloat::splat(params.warmstart_coeff),
-            limits_impulse: limits_impulse * SimdFloat::splat(params.warmstart_coeff),
+            impulse: impulse * SimdFloat::splat(params.effective_warmstart_coeff()),
+            limits_impulse: limits_impulse * SimdFloat::splat(params.effective_warmstart_coeff()),
             limits_forcedirs,
             limits_rhs,
+            active_limits,
             basis1,
             inv_lhs,
             rhs,
@@ -372,6 +385,7 @@ impl WPrismaticVelocityConstraint {
             if let JointParams::PrismaticJoint(rev) = &mut joint.params {
                 rev.impulse = self.impulse.extract(ii);
                 rev.limits_impulse = self.limits_impulse.extract(ii);
+                rev.active_limit = self.active_limits[ii];
             }
         }
     }
@@ -401,6 +415,7 @@ pub(crate) struct WPrismaticVelocityGroundConstraint {
 
     limits_impulse: SimdFloat,
     limits_rhs: SimdFloat,
+    active_limits: [Option<usize>; SIMD_WIDTH],
 
     axis2: Vector<SimdFloat>,
     #[cfg(feature = "dim2")]
@@ -543,6 +558,7 @@ impl WPrismaticVelocityGroundConstraint {
         let mut limits_forcedir2 = None;
         let mut limits_rhs = na::zero();
         let mut limits_impulse = na::zero();
+        let mut active_limits = [None; SIMD_WIDTH];
         let limits_enabled = SimdBool::from(array![|ii| cparams[ii].limits_enabled; SIMD_WIDTH]);
 
         if limits_enabled.any() {
@@ -566,6 +582,16 @@ impl WPrismaticVelocityGroundConstraint {
                 limits_rhs = anchor_linvel2.dot(&axis2) * sign - anchor_linvel1.dot(&axis1) * sign;
                 limits_impulse = lim_impulse.select(use_min | use_max, _0);
             }
+
+            for ii in 0..SIMD_WIDTH {
+                if cparams[ii].limits_enabled {
+                    if use_min.extract(ii) {
+                        active_limits[ii] = Some(0);
+                    } else if use_max.extract(ii) {
+                        active_limits[ii] = Some(1);
+                    }
+                }
+            }
         }
 
         WPrismaticVelocityGroundConstraint {
@@ -573,8 +599,8 @@ impl WPrismaticVelocityGroundConstraint {
             mj_lambda2,
             im2,
             ii2_sqrt,
-            impulse: impulse * SimdFloat::splat(params.warmstart_coeff),
-            limits_impulse: limits_impulse * SimdFloat::splat(params.warmstart_coeff),
+            impulse: impulse * SimdFloat::splat(params.effective_warmstart_coeff()),
+            limits_impulse: limits_impulse * SimdFloat::splat(params.effective_warmstart_coeff()),
             basis1,
             inv_lhs,
             rhs,
@@ -582,6 +608,7 @@ impl WPrismaticVelocityGroundConstraint {
             axis2,
             limits_forcedir2,
             limits_rhs,
+            active_limits,
         }
     }
 
@@ -681,6 +708,7 @@ impl WPrismaticVelocityGroundConstraint {
             if let JointParams::PrismaticJoint(rev) = &mut joint.params {
                 rev.impulse = self.impulse.extract(ii);
                 rev.limits_impulse = self.limits_impulse.extract(ii);
+                rev.active_limit = self.active_limits[ii];
             }
         }
     }
@@ -1,9 +1,10 @@
+use super::MotorPart;
 use crate::dynamics::solver::DeltaVel;
 use crate::dynamics::{
     IntegrationParameters, JointGraphEdge, JointIndex, JointParams, RevoluteJoint, RigidBody,
 };
-use crate::math::{AngularInertia, Vector};
-use crate::utils::{WAngularInertia, WCross, WCrossMatrix};
+use crate::math::{AngularInertia, Isometry, Vector};
+use crate::utils::{WAngularInertia, WCross, WCrossMatrix, WDot};
 use na::{Cholesky, Matrix3x2, Matrix5, Vector5, U2, U3};
 
 #[derive(Debug)]
@@ -22,6 +23,9 @@ pub(crate) struct RevoluteVelocityConstraint {
 
     basis1: Matrix3x2<f32>,
 
+    motor: Option<MotorPart>,
+    motor_axis: Vector<f32>,
+
     im1: f32,
     im2: f32,
 
@@ -82,6 +86,21 @@ impl RevoluteVelocityConstraint {
         let ang_rhs = basis1.tr_mul(&(rb2.angvel - rb1.angvel));
         let rhs = Vector5::new(lin_rhs.x, lin_rhs.y, lin_rhs.z, ang_rhs.x, ang_rhs.y);
 
+        // Setup the motor.
+        let motor_axis = rb1.position * cparams.local_axis1.into_inner();
+        let motor = MotorPart::new(
+            params,
+            cparams.motor_stiffness,
+            cparams.motor_damping,
+            angle_error(cparams, &rb1.position, &rb2.position),
+            cparams.motor_target_vel,
+            motor_axis.dot(&(rb2.angvel - rb1.angvel)),
+            proj_inv_inertia(&rb1.world_inv_inertia_sqrt, &motor_axis)
+                + proj_inv_inertia(&rb2.world_inv_inertia_sqrt, &motor_axis),
+            cparams.motor_impulse,
+            cparams.motor_max_impulse,
+        );
+
         RevoluteVelocityConstraint {
             joint_id,
             mj_lambda1: rb1.active_set_offset,
@@ -91,7 +110,9 @@ impl RevoluteVelocityConstraint {
             basis1,
             im2,
             ii2_sqrt: rb2.world_inv_inertia_sqrt,
-            impulse: cparams.impulse * params.warmstart_coeff,
+            impulse: cparams.impulse * params.effective_warmstart_coeff(),
+            motor,
+            motor_axis,
             inv_lhs,
             rhs,
             r1,
@@ -116,6 +137,12 @@ impl RevoluteVelocityConstraint {
             .ii2_sqrt
             .transform_vector(ang_impulse + self.r2.gcross(lin_impulse));
 
+        if let Some(motor) = &self.motor {
+            let motor_impulse = self.motor_axis * motor.impulse;
+            mj_lambda1.angular += self.ii1_sqrt.transform_vector(motor_impulse);
+            mj_lambda2.angular -= self.ii2_sqrt.transform_vector(motor_impulse);
+        }
+
         mj_lambdas[self.mj_lambda1 as usize] = mj_lambda1;
         mj_lambdas[self.mj_lambda2 as usize] = mj_lambda2;
     }
@@ -147,6 +174,19 @@ impl RevoluteVelocityConstraint {
             .ii2_sqrt
             .transform_vector(ang_impulse + self.r2.gcross(lin_impulse));
 
+        /*
+         * Motor.
+         */
+        if let Some(motor) = &mut self.motor {
+            let ang_vel1 = self.ii1_sqrt.transform_vector(mj_lambda1.angular);
+            let ang_vel2 = self.ii2_sqrt.transform_vector(mj_lambda2.angular);
+            let dvel = self.motor_axis.dot(&(ang_vel2 - ang_vel1));
+            let motor_impulse = self.motor_axis * motor.solve(dvel);
+
+            mj_lambda1.angular += self.ii1_sqrt.transform_vector(motor_impulse);
+            mj_lambda2.angular -= self.ii2_sqrt.transform_vector(motor_impulse);
+        }
+
         mj_lambdas[self.mj_lambda1 as usize] = mj_lambda1;
         mj_lambdas[self.mj_lambda2 as usize] = mj_lambda2;
     }
@@ -155,6 +195,7 @@ impl RevoluteVelocityConstraint {
         let joint = &mut joints_all[self.joint_id].weight;
         if let JointParams::RevoluteJoint(revolute) = &mut joint.params {
             revolute.impulse = self.impulse;
+            revolute.motor_impulse = self.motor.map(|m| m.impulse).unwrap_or(0.0);
         }
     }
 }
@@ -173,6 +214,9 @@ pub(crate) struct RevoluteVelocityGroundConstraint {
 
     basis1: Matrix3x2<f32>,
 
+    motor: Option<MotorPart>,
+    motor_axis: Vector<f32>,
+
     im2: f32,
 
     ii2_sqrt: AngularInertia<f32>,
@@ -236,13 +280,42 @@ impl RevoluteVelocityGroundConstraint {
         let ang_rhs = basis1.tr_mul(&(rb2.angvel - rb1.angvel));
         let rhs = Vector5::new(lin_rhs.x, lin_rhs.y, lin_rhs.z, ang_rhs.x, ang_rhs.y);
 
+        // Setup the motor.
+        // NOTE: the motor axis is flipped if the bodies are flipped so that
+        // its velocity is still the one of the second attached body relative
+        // to the first one.
+        let (motor_axis, angle_error) = if flipped {
+            (
+                -(rb2.position * cparams.local_axis1.into_inner()),
+                angle_error(cparams, &rb2.position, &rb1.position),
+            )
+        } else {
+            (
+                rb1.position * cparams.local_axis1.into_inner(),
+                angle_error(cparams, &rb1.position, &rb2.position),
+            )
+        };
+        let motor = MotorPart::new(
+            params,
+            cparams.motor_stiffness,
+            cparams.motor_damping,
+            angle_error,
+            cparams.motor_target_vel,
+            motor_axis.dot(&(rb2.angvel - rb1.angvel)),
+            proj_inv_inertia(&rb2.world_inv_inertia_sqrt, &motor_axis),
+            cparams.motor_impulse,
+            cparams.motor_max_impulse,
+        );
+
         RevoluteVelocityGroundConstraint {
             joint_id,
             mj_lambda2: rb2.active_set_offset,
             im2,
             ii2_sqrt: rb2.world_inv_inertia_sqrt,
-            impulse: cparams.impulse * params.warmstart_coeff,
+            impulse: cparams.impulse * params.effective_warmstart_coeff(),
             basis1,
+            motor,
+            motor_axis,
             inv_lhs,
             rhs,
             r2,
@@ -260,6 +333,12 @@ impl RevoluteVelocityGroundConstraint {
             .ii2_sqrt
             .transform_vector(ang_impulse + self.r2.gcross(lin_impulse));
 
+        if let Some(motor) = &self.motor {
+            mj_lambda2.angular -= self
+                .ii2_sqrt
+                .transform_vector(self.motor_axis * motor.impulse);
+        }
+
         mj_lambdas[self.mj_lambda2 as usize] = mj_lambda2;
     }
 
@@ -281,6 +360,17 @@ impl RevoluteVelocityGroundConstraint {
             .ii2_sqrt
             .transform_vector(ang_impulse + self.r2.gcross(lin_impulse));
 
+        /*
+         * Motor.
+         */
+        if let Some(motor) = &mut self.motor {
+            let ang_vel2 = self.ii2_sqrt.transform_vector(mj_lambda2.angular);
+            let dvel = self.motor_axis.dot(&ang_vel2);
+            let motor_impulse = self.motor_axis * motor.solve(dvel);
+
+            mj_lambda2.angular -= self.ii2_sqrt.transform_vector(motor_impulse);
+        }
+
         mj_lambdas[self.mj_lambda2 as usize] = mj_lambda2;
     }
 
@@ -289,6 +379,24 @@ impl RevoluteVelocityGroundConstraint {
         let joint = &mut joints_all[self.joint_id].weight;
         if let JointParams::RevoluteJoint(revolute) = &mut joint.params {
             revolute.impulse = self.impulse;
+            revolute.motor_impulse = self.motor.map(|m| m.impulse).unwrap_or(0.0);
         }
     }
 }
+
+/// The difference between the current angle of the joint and its motor target angle, in `[-pi, pi]`.
+fn angle_error(
+    cparams: &RevoluteJoint,
+    position1: &Isometry<f32>,
+    position2: &Isometry<f32>,
+) -> f32 {
+    let error = cparams.angle_between(position1, position2) - cparams.motor_target_pos;
+    // Wrap the error so the motor always takes the shortest path to its target.
+    error - (error / std::f32::consts::TAU).round() * std::f32::consts::TAU
+}
+
+/// The inverse angular inertia of a body, projected on the given axis.
+fn proj_inv_inertia(ii_sqrt: &AngularInertia<f32>, axis: &Vector<f32>) -> f32 {
+    let ii_sqrt_axis = ii_sqrt.transform_vector(*axis);
+    ii_sqrt_axis.gdot(ii_sqrt_axis)
+}
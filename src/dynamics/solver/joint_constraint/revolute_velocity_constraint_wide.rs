@@ -115,7 +115,7 @@ impl WRevoluteVelocityConstraint {
             basis1,
             im2,
             ii2_sqrt,
-            impulse: impulse * SimdFloat::splat(params.warmstart_coeff),
+            impulse: impulse * SimdFloat::splat(params.effective_warmstart_coeff()),
             inv_lhs,
             rhs,
             r1,
@@ -322,7 +322,7 @@ impl WRevoluteVelocityGroundConstraint {
             mj_lambda2,
             im2,
             ii2_sqrt,
-            impulse: impulse * SimdFloat::splat(params.warmstart_coeff),
+            impulse: impulse * SimdFloat::splat(params.effective_warmstart_coeff()),
             basis1,
             inv_lhs,
             rhs,
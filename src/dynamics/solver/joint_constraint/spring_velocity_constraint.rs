@@ -0,0 +1,215 @@
+use super::MotorPart;
+use crate::dynamics::solver::DeltaVel;
+use crate::dynamics::{
+    IntegrationParameters, JointGraphEdge, JointIndex, JointParams, RigidBody, SpringJoint,
+};
+use crate::math::{AngVector, Point, Vector};
+use crate::utils::{WAngularInertia, WCross, WDot};
+use na::Unit;
+
+/// The unit direction from `anchor1` to `anchor2`, and the distance between both anchors.
+fn spring_dir_and_length(anchor1: &Point<f32>, anchor2: &Point<f32>) -> (Vector<f32>, f32) {
+    // NOTE: if both anchors coincide, the spring direction is arbitrary.
+    Unit::try_new_and_get(anchor2 - anchor1, 1.0e-6)
+        .map(|(dir, length)| (dir.into_inner(), length))
+        .unwrap_or((Vector::y(), 0.0))
+}
+
+#[derive(Debug)]
+pub(crate) struct SpringVelocityConstraint {
+    mj_lambda1: usize,
+    mj_lambda2: usize,
+
+    joint_id: JointIndex,
+
+    spring: Option<MotorPart>,
+    dir: Vector<f32>,
+    ang_dir1: AngVector<f32>,
+    ang_dir2: AngVector<f32>,
+
+    im1: f32,
+    im2: f32,
+}
+
+impl SpringVelocityConstraint {
+    pub fn from_params(
+        params: &IntegrationParameters,
+        joint_id: JointIndex,
+        rb1: &RigidBody,
+        rb2: &RigidBody,
+        cparams: &SpringJoint,
+    ) -> Self {
+        let anchor1 = rb1.position * cparams.local_anchor1;
+        let anchor2 = rb2.position * cparams.local_anchor2;
+        let r1 = anchor1 - rb1.world_com;
+        let r2 = anchor2 - rb2.world_com;
+        let (dir, length) = spring_dir_and_length(&anchor1, &anchor2);
+
+        let vel1 = rb1.linvel + rb1.angvel.gcross(r1);
+        let vel2 = rb2.linvel + rb2.angvel.gcross(r2);
+        let im1 = rb1.mass_properties.inv_mass;
+        let im2 = rb2.mass_properties.inv_mass;
+        let ang_dir1 = rb1.world_inv_inertia_sqrt.transform_vector(r1.gcross(dir));
+        let ang_dir2 = rb2.world_inv_inertia_sqrt.transform_vector(r2.gcross(dir));
+
+        let spring = MotorPart::new(
+            params,
+            cparams.stiffness,
+            cparams.damping,
+            length - cparams.rest_length,
+            0.0,
+            dir.dot(&(vel2 - vel1)),
+            im1 + ang_dir1.gdot(ang_dir1) + im2 + ang_dir2.gdot(ang_dir2),
+            cparams.impulse,
+            f32::MAX,
+        );
+
+        SpringVelocityConstraint {
+            joint_id,
+            mj_lambda1: rb1.active_set_offset,
+            mj_lambda2: rb2.active_set_offset,
+            spring,
+            dir,
+            ang_dir1,
+            ang_dir2,
+            im1,
+            im2,
+        }
+    }
+
+    pub fn warmstart(&self, mj_lambdas: &mut [DeltaVel<f32>]) {
+        if let Some(spring) = &self.spring {
+            let mut mj_lambda1 = mj_lambdas[self.mj_lambda1 as usize];
+            let mut mj_lambda2 = mj_lambdas[self.mj_lambda2 as usize];
+
+            mj_lambda1.linear += self.dir * (self.im1 * spring.impulse);
+            mj_lambda1.angular += self.ang_dir1 * spring.impulse;
+            mj_lambda2.linear -= self.dir * (self.im2 * spring.impulse);
+            mj_lambda2.angular -= self.ang_dir2 * spring.impulse;
+
+            mj_lambdas[self.mj_lambda1 as usize] = mj_lambda1;
+            mj_lambdas[self.mj_lambda2 as usize] = mj_lambda2;
+        }
+    }
+
+    pub fn solve(&mut self, mj_lambdas: &mut [DeltaVel<f32>]) {
+        if let Some(spring) = &mut self.spring {
+            let mut mj_lambda1 = mj_lambdas[self.mj_lambda1 as usize];
+            let mut mj_lambda2 = mj_lambdas[self.mj_lambda2 as usize];
+
+            let dvel = self.dir.dot(&(mj_lambda2.linear - mj_lambda1.linear))
+                + mj_lambda2.angular.gdot(self.ang_dir2)
+                - mj_lambda1.angular.gdot(self.ang_dir1);
+            let dimpulse = spring.solve(dvel);
+
+            mj_lambda1.linear += self.dir * (self.im1 * dimpulse);
+            mj_lambda1.angular += self.ang_dir1 * dimpulse;
+            mj_lambda2.linear -= self.dir * (self.im2 * dimpulse);
+            mj_lambda2.angular -= self.ang_dir2 * dimpulse;
+
+            mj_lambdas[self.mj_lambda1 as usize] = mj_lambda1;
+            mj_lambdas[self.mj_lambda2 as usize] = mj_lambda2;
+        }
+    }
+
+    pub fn writeback_impulses(&self, joints_all: &mut [JointGraphEdge]) {
+        let joint = &mut joints_all[self.joint_id].weight;
+        if let JointParams::SpringJoint(spring) = &mut joint.params {
+            spring.impulse = self.spring.map(|s| s.impulse).unwrap_or(0.0);
+        }
+    }
+}
+
+#[derive(Debug)]
+pub(crate) struct SpringVelocityGroundConstraint {
+    mj_lambda2: usize,
+    joint_id: JointIndex,
+    spring: Option<MotorPart>,
+    dir: Vector<f32>,
+    ang_dir2: AngVector<f32>,
+    im2: f32,
+}
+
+impl SpringVelocityGroundConstraint {
+    pub fn from_params(
+        params: &IntegrationParameters,
+        joint_id: JointIndex,
+        rb1: &RigidBody,
+        rb2: &RigidBody,
+        cparams: &SpringJoint,
+        flipped: bool,
+    ) -> Self {
+        // NOTE: the spring direction is flipped if the bodies are flipped, so the
+        // impulse has the same sign as with the non-ground constraint.
+        let (anchor1, anchor2) = if flipped {
+            (
+                rb1.position * cparams.local_anchor2,
+                rb2.position * cparams.local_anchor1,
+            )
+        } else {
+            (
+                rb1.position * cparams.local_anchor1,
+                rb2.position * cparams.local_anchor2,
+            )
+        };
+        let r1 = anchor1 - rb1.world_com;
+        let r2 = anchor2 - rb2.world_com;
+        let (dir, length) = spring_dir_and_length(&anchor1, &anchor2);
+
+        let vel1 = rb1.linvel + rb1.angvel.gcross(r1);
+        let vel2 = rb2.linvel + rb2.angvel.gcross(r2);
+        let im2 = rb2.mass_properties.inv_mass;
+        let ang_dir2 = rb2.world_inv_inertia_sqrt.transform_vector(r2.gcross(dir));
+
+        let spring = MotorPart::new(
+            params,
+            cparams.stiffness,
+            cparams.damping,
+            length - cparams.rest_length,
+            0.0,
+            dir.dot(&(vel2 - vel1)),
+            im2 + ang_dir2.gdot(ang_dir2),
+            cparams.impulse,
+            f32::MAX,
+        );
+
+        SpringVelocityGroundConstraint {
+            joint_id,
+            mj_lambda2: rb2.active_set_offset,
+            spring,
+            dir,
+            ang_dir2,
+            im2,
+        }
+    }
+
+    pub fn warmstart(&self, mj_lambdas: &mut [DeltaVel<f32>]) {
+        if let Some(spring) = &self.spring {
+            let mut mj_lambda2 = mj_lambdas[self.mj_lambda2 as usize];
+            mj_lambda2.linear -= self.dir * (self.im2 * spring.impulse);
+            mj_lambda2.angular -= self.ang_dir2 * spring.impulse;
+            mj_lambdas[self.mj_lambda2 as usize] = mj_lambda2;
+        }
+    }
+
+    pub fn solve(&mut self, mj_lambdas: &mut [DeltaVel<f32>]) {
+        if let Some(spring) = &mut self.spring {
+            let mut mj_lambda2 = mj_lambdas[self.mj_lambda2 as usize];
+
+            let dvel = self.dir.dot(&mj_lambda2.linear) + mj_lambda2.angular.gdot(self.ang_dir2);
+            let dimpulse = spring.solve(dvel);
+
+            mj_lambda2.linear -= self.dir * (self.im2 * dimpulse);
+            mj_lambda2.angular -= self.ang_dir2 * dimpulse;
+
+            mj_lambdas[self.mj_lambda2 as usize] = mj_lambda2;
+        }
+    }
+
+    pub fn writeback_impulses(&self, joints_all: &mut [JointGraphEdge]) {
+        let joint = &mut joints_all[self.joint_id].weight;
+        if let JointParams::SpringJoint(spring) = &mut joint.params {
+            spring.impulse = self.spring.map(|s| s.impulse).unwrap_or(0.0);
+        }
+    }
+}
@@ -10,7 +10,7 @@ pub(self) use self::parallel_velocity_solver::ParallelVelocitySolver;
 pub(self) use self::position_solver::PositionSolver;
 #[cfg(not(feature = "parallel"))]
 pub(self) use self::velocity_solver::VelocitySolver;
-pub(self) use delta_vel::DeltaVel;
+pub(crate) use delta_vel::DeltaVel;
 pub(self) use interaction_groups::*;
 pub(self) use joint_constraint::*;
 pub(self) use position_constraint::*;
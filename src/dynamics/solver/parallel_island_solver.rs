@@ -223,8 +223,8 @@ impl ParallelIslandSolver {
                     .solve_constraints(&thread, params, manifolds, joints, mj_lambdas);
 
                 // Write results back to rigid bodies and integrate velocities.
-                let island_range = bodies.active_island_range(island_id);
-                let active_bodies = &bodies.active_dynamic_set[island_range];
+                let island_range = bodies.islands.active_island_range(island_id);
+                let active_bodies = &bodies.islands.active_dynamic_set[island_range];
                 let bodies = &mut bodies.bodies;
 
                 concurrent_loop! {
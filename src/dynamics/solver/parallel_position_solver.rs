@@ -1,6 +1,8 @@
 use super::ParallelInteractionGroups;
 use super::{AnyJointPositionConstraint, AnyPositionConstraint, ThreadContext};
-use crate::dynamics::solver::categorization::{categorize_joints, categorize_position_contacts};
+use crate::dynamics::solver::categorization::{
+    categorize_joints, categorize_position_contacts, extra_joint_iterations,
+};
 use crate::dynamics::solver::{InteractionGroups, PositionConstraint, PositionGroundConstraint};
 use crate::dynamics::{IntegrationParameters, JointGraphEdge, RigidBodySet};
 use crate::geometry::ContactManifold;
@@ -450,6 +452,7 @@ impl ParallelPositionSolverContactPart {
 pub(crate) struct ParallelPositionSolver {
     part: ParallelPositionSolverContactPart,
     joint_part: ParallelPositionSolverJointPart,
+    extra_joint_iterations: usize,
 }
 
 impl ParallelPositionSolver {
@@ -457,6 +460,7 @@ impl ParallelPositionSolver {
         Self {
             part: ParallelPositionSolverContactPart::new(),
             joint_part: ParallelPositionSolverJointPart::new(),
+            extra_joint_iterations: 0,
         }
     }
 
@@ -473,6 +477,8 @@ impl ParallelPositionSolver {
             .init_constraints_groups(island_id, bodies, manifolds, manifold_groups);
         self.joint_part
             .init_constraints_groups(island_id, bodies, joints, joint_groups);
+        let joint_indices = (0..joint_groups.num_groups()).flat_map(|i| joint_groups.group(i));
+        self.extra_joint_iterations = extra_joint_iterations(joints, joint_indices).1;
     }
 
     pub fn fill_constraints(
@@ -502,7 +508,7 @@ impl ParallelPositionSolver {
         params: &IntegrationParameters,
         positions: &mut [Isometry<f32>],
     ) {
-        if self.part.constraint_descs.len() == 0 {
+        if self.part.constraint_descs.len() == 0 && self.joint_part.constraint_descs.len() == 0 {
             return;
         }
 
@@ -523,7 +529,7 @@ impl ParallelPositionSolver {
             let mut target_num_desc = 0;
             let mut shift = 0;
 
-            for _ in 0..params.max_position_iterations {
+            for i in 0..params.max_position_iterations + self.extra_joint_iterations {
                 macro_rules! solve {
                     ($part: expr) => {
                         // Joint groups.
@@ -573,9 +579,13 @@ impl ParallelPositionSolver {
                 solve!(self.joint_part);
                 shift += joint_descs.len();
                 start_index -= joint_descs.len();
-                solve!(self.part);
-                shift += contact_descs.len();
-                start_index -= contact_descs.len();
+
+                // The extra joint iterations don't solve contacts.
+                if i < params.max_position_iterations {
+                    solve!(self.part);
+                    shift += contact_descs.len();
+                    start_index -= contact_descs.len();
+                }
             }
         }
     }
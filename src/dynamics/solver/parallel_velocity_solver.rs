@@ -1,6 +1,8 @@
 use super::ParallelInteractionGroups;
 use super::{AnyJointVelocityConstraint, AnyVelocityConstraint, DeltaVel, ThreadContext};
-use crate::dynamics::solver::categorization::{categorize_joints, categorize_velocity_contacts};
+use crate::dynamics::solver::categorization::{
+    categorize_joints, categorize_velocity_contacts, extra_joint_iterations,
+};
 use crate::dynamics::solver::{InteractionGroups, VelocityConstraint, VelocityGroundConstraint};
 use crate::dynamics::{IntegrationParameters, JointGraphEdge, RigidBodySet};
 use crate::geometry::ContactManifold;
@@ -264,6 +266,7 @@ impl ParallelVelocitySolverPart<AnyJointVelocityConstraint> {
 pub(crate) struct ParallelVelocitySolver {
     part: ParallelVelocitySolverPart<AnyVelocityConstraint>,
     joint_part: ParallelVelocitySolverPart<AnyJointVelocityConstraint>,
+    extra_joint_iterations: usize,
 }
 
 impl ParallelVelocitySolver {
@@ -271,6 +274,7 @@ impl ParallelVelocitySolver {
         Self {
             part: ParallelVelocitySolverPart::new(),
             joint_part: ParallelVelocitySolverPart::new(),
+            extra_joint_iterations: 0,
         }
     }
 
@@ -287,6 +291,8 @@ impl ParallelVelocitySolver {
             .init_constraints_groups(island_id, bodies, manifolds, manifold_groups);
         self.joint_part
             .init_constraints_groups(island_id, bodies, joints, joint_groups);
+        let joint_indices = (0..joint_groups.num_groups()).flat_map(|i| joint_groups.group(i));
+        self.extra_joint_iterations = extra_joint_iterations(joints, joint_indices).0;
     }
 
     pub fn fill_constraints(
@@ -403,7 +409,7 @@ impl ParallelVelocitySolver {
             let mut target_num_desc = 0;
             let mut shift = 0;
 
-            for _ in 0..params.max_velocity_iterations {
+            for i in 0..params.max_velocity_iterations + self.extra_joint_iterations {
                 macro_rules! solve {
                     ($part: expr) => {
                         // Joint groups.
@@ -458,9 +464,13 @@ impl ParallelVelocitySolver {
                 solve!(self.joint_part);
                 shift += joint_descs.len();
                 start_index -= joint_descs.len();
-                solve!(self.part);
-                shift += contact_descs.len();
-                start_index -= contact_descs.len();
+
+                // The extra joint iterations don't solve contacts.
+                if i < params.max_velocity_iterations {
+                    solve!(self.part);
+                    shift += contact_descs.len();
+                    start_index -= contact_descs.len();
+                }
             }
         }
 
@@ -121,7 +121,7 @@ impl PositionConstraint {
                 ii2: rb2.world_inv_inertia_sqrt.squared(),
                 num_contacts: manifold_points.len() as u8,
                 erp: params.erp,
-                max_linear_correction: params.max_linear_correction,
+                max_linear_correction: params.max_contact_correction(),
             };
 
             if push {
@@ -75,7 +75,7 @@ impl WPositionConstraint {
                 ii1: sqrt_ii1.squared(),
                 ii2: sqrt_ii2.squared(),
                 erp: SimdFloat::splat(params.erp),
-                max_linear_correction: SimdFloat::splat(params.max_linear_correction),
+                max_linear_correction: SimdFloat::splat(params.max_contact_correction()),
                 num_contacts: num_points as u8,
             };
 
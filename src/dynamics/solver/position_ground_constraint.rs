@@ -90,7 +90,7 @@ impl PositionGroundConstraint {
                 ii2: rb2.world_inv_inertia_sqrt.squared(),
                 num_contacts: manifold_contacts.len() as u8,
                 erp: params.erp,
-                max_linear_correction: params.max_linear_correction,
+                max_linear_correction: params.max_contact_correction(),
             };
 
             if push {
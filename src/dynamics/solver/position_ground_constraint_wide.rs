@@ -86,7 +86,7 @@ impl WPositionGroundConstraint {
                 im2,
                 ii2: sqrt_ii2.squared(),
                 erp: SimdFloat::splat(params.erp),
-                max_linear_correction: SimdFloat::splat(params.max_linear_correction),
+                max_linear_correction: SimdFloat::splat(params.max_contact_correction()),
                 num_contacts: num_points as u8,
             };
 
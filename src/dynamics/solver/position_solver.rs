@@ -3,7 +3,9 @@ use super::{
 };
 #[cfg(feature = "simd-is-enabled")]
 use super::{WPositionConstraint, WPositionGroundConstraint};
-use crate::dynamics::solver::categorization::{categorize_joints, categorize_position_contacts};
+use crate::dynamics::solver::categorization::{
+    categorize_joints, categorize_position_contacts, extra_joint_iterations,
+};
 use crate::dynamics::{
     solver::AnyPositionConstraint, IntegrationParameters, JointGraphEdge, JointIndex, RigidBodySet,
 };
@@ -64,6 +66,7 @@ pub(crate) struct PositionSolver {
     positions: Vec<Isometry<f32>>,
     part: PositionSolverPart,
     joint_part: PositionSolverJointPart,
+    extra_joint_iterations: usize,
 }
 
 impl PositionSolver {
@@ -72,6 +75,7 @@ impl PositionSolver {
             positions: Vec::new(),
             part: PositionSolverPart::new(),
             joint_part: PositionSolverJointPart::new(),
+            extra_joint_iterations: 0,
         }
     }
 
@@ -94,6 +98,7 @@ impl PositionSolver {
             joints,
             joint_constraint_indices,
         );
+        self.extra_joint_iterations = extra_joint_iterations(joints, joint_constraint_indices).1;
     }
 
     pub fn solve_constraints(
@@ -109,13 +114,16 @@ impl PositionSolver {
                 .map(|(_, b)| b.position),
         );
 
-        for _ in 0..params.max_position_iterations {
+        for i in 0..params.max_position_iterations + self.extra_joint_iterations {
             for constraint in &self.joint_part.constraints {
                 constraint.solve(params, &mut self.positions)
             }
 
-            for constraint in &self.part.constraints {
-                constraint.solve(params, &mut self.positions)
+            // The extra joint iterations don't solve contacts.
+            if i < params.max_position_iterations {
+                for constraint in &self.part.constraints {
+                    constraint.solve(params, &mut self.positions)
+                }
             }
         }
 
@@ -151,7 +151,7 @@ impl VelocityConstraint {
         let pos_coll1 = rb1.position * manifold.delta1;
         let pos_coll2 = rb2.position * manifold.delta2;
         let force_dir1 = pos_coll1 * (-manifold.local_n1);
-        let warmstart_coeff = manifold.warmstart_multiplier * params.warmstart_coeff;
+        let warmstart_coeff = manifold.warmstart_multiplier * params.effective_warmstart_coeff();
 
         for (l, manifold_points) in manifold
             .active_contacts()
@@ -222,6 +222,8 @@ impl VelocityConstraint {
 
                 let vel1 = rb1.linvel + rb1.angvel.gcross(dp1);
                 let vel2 = rb2.linvel + rb2.angvel.gcross(dp2);
+                let surface_vel =
+                    manifold.relative_surface_velocity(manifold_point, &pos_coll1, &pos_coll2);
 
                 // Normal part.
                 {
@@ -273,7 +275,7 @@ impl VelocityConstraint {
                                 + rb2.mass_properties.inv_mass
                                 + gcross1.gdot(gcross1)
                                 + gcross2.gdot(gcross2));
-                        let rhs = (vel1 - vel2).dot(&tangents1[j]);
+                        let rhs = (vel1 - vel2 + surface_vel).dot(&tangents1[j]);
                         #[cfg(feature = "dim2")]
                         let impulse = manifold_points[k].tangent_impulse * warmstart_coeff;
                         #[cfg(feature = "dim3")]
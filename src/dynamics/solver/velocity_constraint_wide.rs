@@ -97,6 +97,10 @@ impl WVelocityConstraint {
 
         let coll_pos1 = pos1 * delta1;
         let coll_pos2 = pos2 * delta2;
+        let manifold_coll_pos = array![|ii| (
+            rbs1[ii].position * manifolds[ii].delta1,
+            rbs2[ii].position * manifolds[ii].delta2,
+        ); SIMD_WIDTH];
 
         let force_dir1 = coll_pos1 * -Vector::from(array![|ii| manifolds[ii].local_n1; SIMD_WIDTH]);
 
@@ -110,7 +114,8 @@ impl WVelocityConstraint {
 
         let warmstart_multiplier =
             SimdFloat::from(array![|ii| manifolds[ii].warmstart_multiplier; SIMD_WIDTH]);
-        let warmstart_coeff = warmstart_multiplier * SimdFloat::splat(params.warmstart_coeff);
+        let warmstart_coeff =
+            warmstart_multiplier * SimdFloat::splat(params.effective_warmstart_coeff());
 
         for l in (0..manifolds[0].num_active_contacts()).step_by(MAX_MANIFOLD_POINTS) {
             let manifold_points = array![|ii| &manifolds[ii].active_contacts()[l..]; SIMD_WIDTH];
@@ -147,6 +152,12 @@ impl WVelocityConstraint {
 
                 let vel1 = linvel1 + angvel1.gcross(dp1);
                 let vel2 = linvel2 + angvel2.gcross(dp2);
+                let surface_vel =
+                    Vector::from(array![|ii| manifolds[ii].relative_surface_velocity(
+                    &manifold_points[ii][k],
+                    &manifold_coll_pos[ii].0,
+                    &manifold_coll_pos[ii].1,
+                ); SIMD_WIDTH]);
 
                 // Normal part.
                 {
@@ -187,7 +198,7 @@ impl WVelocityConstraint {
                     let gcross2 = ii2.transform_vector(dp2.gcross(-tangents1[j]));
                     let r = SimdFloat::splat(1.0)
                         / (im1 + im2 + gcross1.gdot(gcross1) + gcross2.gdot(gcross2));
-                    let rhs = (vel1 - vel2).dot(&tangents1[j]);
+                    let rhs = (vel1 - vel2 + surface_vel).dot(&tangents1[j]);
 
                     constraint.elements[k].tangent_parts[j] = WVelocityConstraintElementPart {
                         gcross1,
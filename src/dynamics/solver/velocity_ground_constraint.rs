@@ -82,7 +82,7 @@ impl VelocityGroundConstraint {
         }
 
         let mj_lambda2 = rb2.active_set_offset;
-        let warmstart_coeff = manifold.warmstart_multiplier * params.warmstart_coeff;
+        let warmstart_coeff = manifold.warmstart_multiplier * params.effective_warmstart_coeff();
 
         for (l, manifold_points) in manifold
             .active_contacts()
@@ -161,6 +161,11 @@ impl VelocityGroundConstraint {
                 let dp1 = p1 - rb1.world_com;
                 let vel1 = rb1.linvel + rb1.angvel.gcross(dp1);
                 let vel2 = rb2.linvel + rb2.angvel.gcross(dp2);
+                let surface_vel = if flipped {
+                    -manifold.relative_surface_velocity(manifold_point, &coll_pos2, &coll_pos1)
+                } else {
+                    manifold.relative_surface_velocity(manifold_point, &coll_pos1, &coll_pos2)
+                };
 
                 // Normal part.
                 {
@@ -197,7 +202,7 @@ impl VelocityGroundConstraint {
                             .world_inv_inertia_sqrt
                             .transform_vector(dp2.gcross(-tangents1[j]));
                         let r = 1.0 / (rb2.mass_properties.inv_mass + gcross2.gdot(gcross2));
-                        let rhs = -vel2.dot(&tangents1[j]) + vel1.dot(&tangents1[j]);
+                        let rhs = (vel1 - vel2 + surface_vel).dot(&tangents1[j]);
                         #[cfg(feature = "dim2")]
                         let impulse = manifold_points[k].tangent_impulse * warmstart_coeff;
                         #[cfg(feature = "dim3")]
@@ -98,6 +98,12 @@ impl WVelocityGroundConstraint {
 
         let coll_pos1 = pos1 * delta1;
         let coll_pos2 = pos2 * delta2;
+        // The collider positions, in the order of the manifold colliders.
+        let manifold_coll_pos = array![|ii| if flipped[ii] {
+            (rbs2[ii].position * manifolds[ii].delta1, rbs1[ii].position * manifolds[ii].delta2)
+        } else {
+            (rbs1[ii].position * manifolds[ii].delta1, rbs2[ii].position * manifolds[ii].delta2)
+        }; SIMD_WIDTH];
 
         let world_com1 = Point::from(array![|ii| rbs1[ii].world_com; SIMD_WIDTH]);
         let world_com2 = Point::from(array![|ii| rbs2[ii].world_com; SIMD_WIDTH]);
@@ -116,7 +122,8 @@ impl WVelocityGroundConstraint {
 
         let warmstart_multiplier =
             SimdFloat::from(array![|ii| manifolds[ii].warmstart_multiplier; SIMD_WIDTH]);
-        let warmstart_coeff = warmstart_multiplier * SimdFloat::splat(params.warmstart_coeff);
+        let warmstart_coeff =
+            warmstart_multiplier * SimdFloat::splat(params.effective_warmstart_coeff());
 
         for l in (0..manifolds[0].num_active_contacts()).step_by(MAX_MANIFOLD_POINTS) {
             let manifold_points = array![|ii| &manifolds[ii].active_contacts()[l..]; SIMD_WIDTH];
@@ -152,6 +159,15 @@ impl WVelocityGroundConstraint {
 
                 let vel1 = linvel1 + angvel1.gcross(dp1);
                 let vel2 = linvel2 + angvel2.gcross(dp2);
+                let surface_vel = Vector::from(array![|ii| {
+                    let (coll_pos1, coll_pos2) = &manifold_coll_pos[ii];
+                    let vel = manifolds[ii].relative_surface_velocity(
+                        &manifold_points[ii][k],
+                        coll_pos1,
+                        coll_pos2,
+                    );
+                    if flipped[ii] { -vel } else { vel }
+                }; SIMD_WIDTH]);
 
                 // Normal part.
                 {
@@ -187,7 +203,7 @@ impl WVelocityGroundConstraint {
 
                     let gcross2 = ii2.transform_vector(dp2.gcross(-tangents1[j]));
                     let r = SimdFloat::splat(1.0) / (im2 + gcross2.gdot(gcross2));
-                    let rhs = -vel2.dot(&tangents1[j]) + vel1.dot(&tangents1[j]);
+                    let rhs = (vel1 - vel2 + surface_vel).dot(&tangents1[j]);
 
                     constraint.elements[k].tangent_parts[j] =
                         WVelocityGroundConstraintElementPart {
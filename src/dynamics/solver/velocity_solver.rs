@@ -3,7 +3,9 @@ use super::{
 };
 #[cfg(feature = "simd-is-enabled")]
 use super::{WVelocityConstraint, WVelocityGroundConstraint};
-use crate::dynamics::solver::categorization::{categorize_joints, categorize_velocity_contacts};
+use crate::dynamics::solver::categorization::{
+    categorize_joints, categorize_velocity_contacts, extra_joint_iterations,
+};
 use crate::dynamics::{
     solver::{AnyVelocityConstraint, DeltaVel},
     IntegrationParameters, JointGraphEdge, JointIndex, RigidBodySet,
@@ -17,6 +19,7 @@ pub(crate) struct VelocitySolver {
     pub mj_lambdas: Vec<DeltaVel<f32>>,
     pub contact_part: VelocitySolverPart<AnyVelocityConstraint>,
     pub joint_part: VelocitySolverPart<AnyJointVelocityConstraint>,
+    pub extra_joint_iterations: usize,
 }
 
 impl VelocitySolver {
@@ -25,6 +28,7 @@ impl VelocitySolver {
             mj_lambdas: Vec::new(),
             contact_part: VelocitySolverPart::new(),
             joint_part: VelocitySolverPart::new(),
+            extra_joint_iterations: 0,
         }
     }
 
@@ -46,7 +50,8 @@ impl VelocitySolver {
             bodies,
             joints,
             joint_constraint_indices,
-        )
+        );
+        self.extra_joint_iterations = extra_joint_iterations(joints, joint_constraint_indices).0;
     }
 
     pub fn solve_constraints(
@@ -75,13 +80,16 @@ impl VelocitySolver {
         /*
          * Solve constraints.
          */
-        for _ in 0..params.max_velocity_iterations {
+        for i in 0..params.max_velocity_iterations + self.extra_joint_iterations {
             for constraint in &mut self.joint_part.constraints {
                 constraint.solve(&mut self.mj_lambdas[..]);
             }
 
-            for constraint in &mut self.contact_part.constraints {
-                constraint.solve(&mut self.mj_lambdas[..]);
+            // The extra joint iterations don't solve contacts.
+            if i < params.max_velocity_iterations {
+                for constraint in &mut self.contact_part.constraints {
+                    constraint.solve(&mut self.mj_lambdas[..]);
+                }
             }
         }
 
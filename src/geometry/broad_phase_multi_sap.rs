@@ -1,7 +1,9 @@
 use crate::data::hashmap::HashMap;
 use crate::data::pubsub::Subscription;
-use crate::dynamics::RigidBodySet;
-use crate::geometry::{ColliderHandle, ColliderSet, RemovedCollider};
+use crate::dynamics::{RigidBody, RigidBodyHandle, RigidBodySet};
+use crate::geometry::{
+    Collider, ColliderChanges, ColliderHandle, ColliderSet, RemovedCollider, WQuadtree,
+};
 use crate::math::{Point, Vector, DIM};
 use bit_vec::BitVec;
 use ncollide::bounding_volume::{BoundingVolume, AABB};
@@ -139,6 +141,10 @@ struct SAPAxis {
     endpoints: Vec<Endpoint>,
     #[cfg_attr(feature = "serde-serialize", serde(skip))]
     new_endpoints: Vec<(Endpoint, usize)>, // Workspace
+    #[cfg_attr(feature = "serde-serialize", serde(skip))]
+    active_new_proxies: Vec<u32>, // Workspace
+    #[cfg_attr(feature = "serde-serialize", serde(skip))]
+    active_old_proxies: Vec<u32>, // Workspace
 }
 
 impl SAPAxis {
@@ -150,6 +156,8 @@ impl SAPAxis {
             max_bound,
             endpoints: vec![Endpoint::start_sentinel(), Endpoint::end_sentinel()],
             new_endpoints: Vec::new(),
+            active_new_proxies: Vec::new(),
+            active_old_proxies: Vec::new(),
         }
     }
 
@@ -207,35 +215,68 @@ impl SAPAxis {
             curr_shift_index -= 1;
         }
 
-        // Report pairs using a single mbp pass on each new endpoint.
-        let endpoints_wo_last_sentinel = &self.endpoints[..self.endpoints.len() - 1];
+        // Report pairs with a single sweep over the endpoints, keeping track of the proxies
+        // overlapping the current endpoint. Only the pairs involving at least one new proxy are
+        // reported: the other pairs were already reported by previous updates. This is linear
+        // in the number of endpoints and overlaps, so inserting many proxies at once, e.g., when
+        // loading a level, doesn't have a quadratic cost.
         if let Some(reporting) = reporting {
-            for (endpoint, endpoint_id) in self.new_endpoints.drain(..).filter(|e| e.0.is_start()) {
-                let proxy1 = &proxies[endpoint.proxy() as usize];
-                let min = endpoint.value;
-                let max = proxy1.aabb.maxs[dim];
+            let active_new = &mut self.active_new_proxies;
+            let active_old = &mut self.active_old_proxies;
+            let mut new_endpoint_ids = self.new_endpoints.iter().map(|e| e.1).peekable();
+            active_new.clear();
+            active_old.clear();
+
+            for (endpoint_id, endpoint) in self.endpoints.iter().enumerate() {
+                if new_endpoint_ids.peek().is_none() && active_new.is_empty() {
+                    // All the remaining pairs involve old proxies only.
+                    break;
+                }
 
-                for endpoint2 in &endpoints_wo_last_sentinel[endpoint_id + 1..] {
-                    if endpoint2.proxy() == endpoint.proxy() {
-                        continue;
-                    }
+                if endpoint.is_sentinel() {
+                    continue;
+                }
 
-                    let proxy2 = &proxies[endpoint2.proxy() as usize];
+                let is_new = new_endpoint_ids.peek() == Some(&endpoint_id);
+                if is_new {
+                    let _ = new_endpoint_ids.next();
+                }
 
-                    // NOTE: some pairs with equal aabb.mins[dim] may end up being reported twice.
-                    if (endpoint2.is_start() && endpoint2.value < max)
-                        || (endpoint2.is_end() && proxy2.aabb.mins[dim] <= min)
-                    {
-                        // Report pair.
-                        if proxy1.aabb.intersects(&proxy2.aabb) {
-                            // Report pair.
-                            let pair = sort2(endpoint.proxy(), endpoint2.proxy());
-                            reporting.insert(pair, true);
+                let proxy1 = endpoint.proxy();
+
+                if endpoint.is_start() {
+                    let aabb1 = &proxies[proxy1 as usize].aabb;
+                    let others = if is_new {
+                        active_new.iter().chain(active_old.iter())
+                    } else {
+                        active_new.iter().chain([].iter())
+                    };
+
+                    for proxy2 in others {
+                        if aabb1.intersects(&proxies[*proxy2 as usize].aabb) {
+                            reporting.insert(sort2(proxy1, *proxy2), true);
                         }
                     }
+
+                    if is_new {
+                        active_new.push(proxy1);
+                    } else {
+                        active_old.push(proxy1);
+                    }
+                } else {
+                    let active = if is_new {
+                        &mut *active_new
+                    } else {
+                        &mut *active_old
+                    };
+                    if let Some(i) = active.iter().position(|p| *p == proxy1) {
+                        let _ = active.swap_remove(i);
+                    }
                 }
             }
         }
+
+        self.new_endpoints.clear();
     }
 
     fn delete_out_of_bounds_proxies(&self, existing_proxies: &mut BitVec) -> usize {
@@ -452,6 +493,10 @@ impl SAPRegion {
 }
 
 /// A broad-phase based on multiple Sweep-and-Prune instances running of disjoint region of the 3D world.
+///
+/// The broad-phase doesn't need to be restored with the other parts of a snapshot of a simulation.
+/// A new broad-phase rebuilds its state from all the colliders of the collider set at its first
+/// timestep.
 #[cfg_attr(feature = "serde-serialize", derive(Serialize, Deserialize))]
 #[derive(Clone)]
 pub struct BroadPhase {
@@ -459,21 +504,44 @@ pub struct BroadPhase {
     regions: HashMap<Point<i32>, SAPRegion>,
     removed_colliders: Option<Subscription<RemovedCollider>>,
     deleted_any: bool,
+    // Set if the proxies of all the colliders, and not only those of the active bodies,
+    // must be updated at the next call to `update_aabbs`.
+    update_all_colliders: bool,
+    // The proxies baked by `bake_static_colliders`. They are stored in `baked_tree` instead of
+    // the SAP regions, so they are never re-sorted.
+    baked_proxies: BitVec,
+    baked_tree: WQuadtree<usize>,
+    // For each proxy that isn't baked, the sorted list of the baked proxies it overlaps.
+    baked_pairs: HashMap<u32, Vec<u32>>,
+    // Set if the colliders attached to static bodies must be baked at the next call
+    // to `update_aabbs`.
+    bake_requested: bool,
+    #[cfg_attr(feature = "serde-serialize", serde(skip))]
+    baked_queries: Vec<usize>, // Workspace
+    #[cfg_attr(feature = "serde-serialize", serde(skip))]
+    baked_reports: Vec<((u32, u32), bool)>, // Workspace
+    #[cfg_attr(feature = "serde-serialize", serde(skip))]
+    baked_candidates: Vec<usize>, // Workspace
+    #[cfg_attr(feature = "serde-serialize", serde(skip))]
+    updated_aabbs: Vec<(RigidBodyHandle, ColliderHandle, AABB<f32>)>, // Workspace
     #[cfg_attr(feature = "serde-serialize", serde(skip))]
     region_pool: Vec<SAPRegion>, // To avoid repeated allocations.
     #[cfg_attr(feature = "serde-serialize", serde(skip))]
     regions_to_remove: Vec<Point<i32>>, // Workspace
-    // We could think serializing this workspace is useless.
-    // It turns out is is important to serialize at least its capacity
-    // and restore this capacity when deserializing the hashmap.
-    // This is because the order of future elements inserted into the
-    // hashmap depends on its capacity (because the internal bucket indices
-    // depend on this capacity). So not restoring this capacity may alter
-    // the order at which future elements are reported. This will in turn
-    // alter the order at which the pairs are registered in the narrow-phase,
-    // thus altering the order of the contact manifold. In the end, this
-    // alters the order of the resolution of contacts, resulting in
-    // diverging simulation after restoration of a snapshot.
+    #[cfg_attr(feature = "serde-serialize", serde(skip))]
+    regions_to_update: Vec<Point<i32>>, // Workspace
+    #[cfg_attr(feature = "serde-serialize", serde(skip))]
+    sorted_reports: Vec<((u32, u32), bool)>, // Workspace
+    // The order at which the elements of a hashmap are iterated on depends
+    // on its capacity and on its insertion/removal history. Because the
+    // registration order of the pairs in the narrow-phase determines the order
+    // of the resolution of contacts, we never rely on the iteration order of
+    // `self.regions` or `self.reporting`: the regions are updated in the
+    // lexicographic order of their keys, and the reported pairs are sorted
+    // before being converted to events. This way, a simulation restored from
+    // a snapshot won't diverge from the original one.
+    // We still serialize the capacity of this workspace to avoid reallocations
+    // after restoring a snapshot.
     #[cfg_attr(
         feature = "serde-serialize",
         serde(
@@ -557,8 +625,127 @@ impl BroadPhase {
             regions: HashMap::default(),
             region_pool: Vec::new(),
             regions_to_remove: Vec::new(),
+            regions_to_update: Vec::new(),
+            sorted_reports: Vec::new(),
             reporting: HashMap::default(),
             deleted_any: false,
+            update_all_colliders: false,
+            baked_proxies: BitVec::new(),
+            baked_tree: WQuadtree::new(),
+            baked_pairs: HashMap::default(),
+            bake_requested: false,
+            baked_queries: Vec::new(),
+            baked_reports: Vec::new(),
+            baked_candidates: Vec::new(),
+            updated_aabbs: Vec::new(),
+        }
+    }
+
+    /// Bakes the colliders attached to static rigid-bodies at the next timestep.
+    ///
+    /// The baked colliders are moved out of the Sweep-and-Prune regions into a bounding volume
+    /// tree built once. Because they are never re-sorted, this drastically reduces the cost of
+    /// the broad-phase in scenes with many static colliders, e.g., tens of thousands of level
+    /// tiles, and comparatively few moving colliders: only the colliders of the awake rigid-bodies
+    /// query this tree at each timestep. The pairs involving a baked collider are still reported,
+    /// except the pairs of two baked colliders which are only reported once while baking.
+    ///
+    /// A baked collider is moved back to the Sweep-and-Prune regions as soon as its AABB changes,
+    /// e.g., if its rigid-body is moved. The colliders attached to static rigid-bodies after this
+    /// call are handled as usual until this method is called again.
+    pub fn bake_static_colliders(&mut self) {
+        self.bake_requested = true;
+    }
+
+    /// Finds all the colliders with a broad-phase proxy intersecting the given AABB and passes them
+    /// to a callback.
+    ///
+    /// This only tests the AABBs of the broad-phase proxies computed during the last timestep,
+    /// without any narrow-phase test. These AABBs are enlarged by the prediction distance and
+    /// contact skin of the colliders, so the colliders yielded by this method don't necessarily
+    /// intersect `aabb`. Disabled colliders, and colliders inserted since the last timestep, are
+    /// not yielded.
+    ///
+    /// # Parameters
+    /// - `aabb`: the AABB tested for intersection with the broad-phase proxies.
+    /// - `callback`: function executed on each collider with a proxy intersecting `aabb`.
+    ///   There is no guarantees on the order the results will be yielded. If this callback returns `false`,
+    ///   this method will exit early, ignoring any further collider.
+    pub fn colliders_with_aabb_intersecting_aabb(
+        &self,
+        aabb: &AABB<f32>,
+        mut callback: impl FnMut(&ColliderHandle) -> bool,
+    ) {
+        if self.baked_proxies.any() {
+            // TODO: avoid allocation?
+            let mut candidates = Vec::new();
+            self.baked_tree.intersect_aabb(aabb, &mut candidates);
+
+            for proxy_id in candidates {
+                let proxy = &self.proxies[proxy_id];
+                if self.is_baked(proxy_id)
+                    && proxy.aabb.intersects(aabb)
+                    && !callback(&proxy.handle)
+                {
+                    return;
+                }
+            }
+        }
+
+        let start = point_key(aabb.mins);
+        let end = point_key(aabb.maxs);
+
+        let mut visit_region = |key: Point<i32>| {
+            let region = match self.regions.get(&key) {
+                Some(region) => region,
+                None => return true,
+            };
+
+            for endpoint in &region.axes[0].endpoints {
+                if endpoint.value > aabb.maxs.x {
+                    break;
+                }
+
+                if !endpoint.is_start() || endpoint.is_sentinel() {
+                    continue;
+                }
+
+                let proxy_id = endpoint.proxy() as usize;
+                let proxy = &self.proxies[proxy_id];
+
+                // NOTE: a proxy is registered in all the regions it overlaps, so it is
+                // only yielded by the first region overlapped by both the proxy and `aabb`.
+                let first_key = point_key(proxy.aabb.mins).sup(&start);
+                if first_key == key
+                    && region.existing_proxies.get(proxy_id).unwrap_or(false)
+                    && proxy.aabb.intersects(aabb)
+                    && !callback(&proxy.handle)
+                {
+                    return false;
+                }
+            }
+
+            true
+        };
+
+        #[cfg(feature = "dim2")]
+        for i in start.x..=end.x {
+            for j in start.y..=end.y {
+                if !visit_region(Point::new(i, j)) {
+                    return;
+                }
+            }
+        }
+
+        #[cfg(feature = "dim3")]
+        for i in start.x..=end.x {
+            for j in start.y..=end.y {
+                for k in start.z..=end.z {
+                    if !visit_region(Point::new(i, j, k)) {
+                        return;
+                    }
+                }
+            }
         }
     }
 
@@ -567,6 +754,9 @@ impl BroadPhase {
         // Ensure we already subscribed.
         if self.removed_colliders.is_none() {
             self.removed_colliders = Some(colliders.removed_colliders.subscribe());
+            // This broad-phase never saw these colliders, e.g., because only the collider set
+            // was restored from a snapshot. Their proxy indices refer to another broad-phase.
+            self.invalidate_proxies(colliders);
         }
 
         let mut cursor = self.removed_colliders.take().unwrap();
@@ -576,6 +766,49 @@ impl BroadPhase {
 
         colliders.removed_colliders.ack(&mut cursor);
         self.removed_colliders = Some(cursor);
+
+        // Remove the proxies of the colliders that have been disabled. The
+        // proxy will be re-created by `self.update_aabbs` once re-enabled.
+        for handle in &colliders.modified_colliders {
+            if let Some(collider) = colliders.colliders.get_mut(*handle) {
+                if collider.changes.contains(ColliderChanges::ENABLED)
+                    && !collider.is_enabled_with_parent()
+                {
+                    self.remove_collider(collider.proxy_index);
+                    collider.proxy_index = crate::INVALID_USIZE;
+                }
+            }
+        }
+    }
+
+    /// Removes all the proxies and regions of this broad-phase.
+    ///
+    /// The proxies are re-created from all the colliders of the collider set at the next
+    /// call to `self.update_aabbs`, and all the pairs of overlapping proxies are reported again.
+    pub(crate) fn reset(&mut self, colliders: &mut ColliderSet) {
+        let removed_colliders = self.removed_colliders.take();
+        let bake_requested = self.bake_requested || self.baked_proxies.any();
+        *self = BroadPhase::new();
+        self.removed_colliders = removed_colliders;
+        self.bake_requested = bake_requested;
+        self.invalidate_proxies(colliders);
+    }
+
+    fn invalidate_proxies(&mut self, colliders: &mut ColliderSet) {
+        for (_, collider) in colliders.colliders.iter_mut() {
+            collider.proxy_index = crate::INVALID_USIZE;
+        }
+
+        // The static colliders will be baked again once their proxies are re-created.
+        self.bake_requested |= self.baked_proxies.any();
+        self.baked_proxies.clear();
+        self.baked_tree = WQuadtree::new();
+        self.baked_pairs.clear();
+        self.update_all_colliders = true;
+    }
+
+    fn is_baked(&self, proxy_index: usize) -> bool {
+        self.baked_proxies.get(proxy_index).unwrap_or(false)
     }
 
     fn remove_collider<'a>(&mut self, proxy_index: usize) {
@@ -584,7 +817,26 @@ impl BroadPhase {
             return;
         }
 
+        if self.is_baked(proxy_index) {
+            self.unbake_proxy(proxy_index, false);
+        } else {
+            let _ = self.baked_pairs.remove(&(proxy_index as u32));
+            self.predelete_from_regions(proxy_index);
+        }
+
+        // Push the proxy to infinity, but not beyond the sentinels.
         let proxy = &mut self.proxies[proxy_index];
+        proxy.aabb.mins.coords.fill(SENTINEL_VALUE / 2.0);
+        proxy.aabb.maxs.coords.fill(SENTINEL_VALUE / 2.0);
+        self.proxies.remove(proxy_index);
+    }
+
+    /// Marks the proxy as deleted in all the regions it overlaps.
+    ///
+    /// The proxy is removed from these regions at their next update, once its AABB is
+    /// pushed out of their bounds.
+    fn predelete_from_regions(&mut self, proxy_index: usize) {
+        let proxy = &self.proxies[proxy_index];
 
         // Discretize the AABB to find the regions that need to be invalidated.
         let start = point_key(proxy.aabb.mins);
@@ -611,60 +863,252 @@ impl BroadPhase {
                 }
             }
         }
+    }
 
-        // Push the proxy to infinity, but not beyond the sentinels.
-        proxy.aabb.mins.coords.fill(SENTINEL_VALUE / 2.0);
-        proxy.aabb.maxs.coords.fill(SENTINEL_VALUE / 2.0);
-        self.proxies.remove(proxy_index);
+    /// Moves a baked proxy out of the baked tree.
+    ///
+    /// If `report` is `true`, the pairs involving this proxy are reported as deleted at the
+    /// next call to `find_pairs`. The pairs that still exist are reported again once the proxy
+    /// is inserted into the SAP regions.
+    fn unbake_proxy(&mut self, proxy_index: usize, report: bool) {
+        self.baked_proxies.set(proxy_index, false);
+        let proxy_id = proxy_index as u32;
+
+        if report {
+            let aabb = self.proxies[proxy_index].aabb;
+            self.baked_candidates.clear();
+            self.baked_tree
+                .intersect_aabb(&aabb, &mut self.baked_candidates);
+
+            for other in &self.baked_candidates {
+                if self.baked_proxies.get(*other).unwrap_or(false)
+                    && self.proxies[*other].aabb.intersects(&aabb)
+                {
+                    let pair = sort2(proxy_id, *other as u32);
+                    self.baked_reports.push((pair, false));
+                }
+            }
+        }
+
+        for (other, pairs) in self.baked_pairs.iter_mut() {
+            if let Ok(i) = pairs.binary_search(&proxy_id) {
+                let _ = pairs.remove(i);
+
+                if report {
+                    self.baked_reports.push((sort2(*other, proxy_id), false));
+                }
+            }
+        }
     }
 
-    pub(crate) fn update_aabbs(
+    fn bake_static_colliders_now(
         &mut self,
         prediction_distance: f32,
         bodies: &RigidBodySet,
         colliders: &mut ColliderSet,
     ) {
-        // First, if we have any pending removals we have
-        // to deal with them now because otherwise we will
-        // end up with an ABA problems when reusing proxy
-        // ids.
-        self.complete_removals();
+        // Pull the proxies of the static colliders out of the SAP regions. Their pairs with
+        // the other proxies are found again by querying the baked tree.
+        let mut to_bake = Vec::new();
 
-        for body_handle in bodies
-            .modified_inactive_set
-            .iter()
-            .chain(bodies.active_dynamic_set.iter())
-            .chain(bodies.active_kinematic_set.iter())
-        {
-            for handle in &bodies[*body_handle].colliders {
+        for (_, body) in bodies.iter().filter(|(_, body)| body.is_static()) {
+            for handle in &body.colliders {
                 let collider = &mut colliders[*handle];
-                let aabb = collider.compute_aabb().loosened(prediction_distance / 2.0);
+
+                if !collider.is_enabled_with_parent() || self.is_baked(collider.proxy_index) {
+                    continue;
+                }
 
                 if let Some(proxy) = self.proxies.get_mut(collider.proxy_index) {
-                    proxy.aabb = aabb;
+                    let aabb = proxy.aabb;
+                    self.predelete_from_regions(collider.proxy_index);
+                    let proxy = &mut self.proxies[collider.proxy_index];
+                    proxy.aabb.mins.coords.fill(SENTINEL_VALUE / 2.0);
+                    proxy.aabb.maxs.coords.fill(SENTINEL_VALUE / 2.0);
+                    to_bake.push((collider.proxy_index, aabb));
                 } else {
+                    let aabb = proxy_aabb(prediction_distance, body, collider);
                     let proxy = BroadPhaseProxy {
                         handle: *handle,
                         aabb,
                         next_free: NEXT_FREE_SENTINEL,
                     };
                     collider.proxy_index = self.proxies.insert(proxy);
+                    to_bake.push((collider.proxy_index, aabb));
+                }
+            }
+        }
+
+        // NOTE: we don't care about the pairs reported while removing
+        // the proxies from the SAP regions.
+        self.complete_removals();
+
+        for (proxy_id, aabb) in &to_bake {
+            self.proxies[*proxy_id].aabb = *aabb;
+            let mask_len = self.baked_proxies.len();
+            if *proxy_id >= mask_len {
+                self.baked_proxies.grow(*proxy_id + 1 - mask_len, false);
+            }
+            self.baked_proxies.set(*proxy_id, true);
+        }
+
+        let proxies = &self.proxies;
+        let baked: Vec<_> = self
+            .baked_proxies
+            .iter()
+            .enumerate()
+            .filter(|(_, baked)| *baked)
+            .map(|(id, _)| (id, proxies[id].aabb))
+            .collect();
+        self.baked_tree = WQuadtree::new();
+        if !baked.is_empty() {
+            self.baked_tree.clear_and_rebuild(baked.into_iter(), 0.0);
+        }
+
+        // Report the pairs of new baked proxies. These pairs won't be updated anymore.
+        for (proxy_id, aabb) in &to_bake {
+            self.baked_candidates.clear();
+            self.baked_tree
+                .intersect_aabb(aabb, &mut self.baked_candidates);
+
+            for other in &self.baked_candidates {
+                if *other != *proxy_id
+                    && self.baked_proxies.get(*other).unwrap_or(false)
+                    && self.proxies[*other].aabb.intersects(aabb)
+                {
+                    let pair = sort2(*proxy_id as u32, *other as u32);
+                    self.baked_reports.push((pair, true));
+                }
+            }
+        }
+
+        // All the proxies that aren't baked must look for their baked neighbors, including the
+        // proxies of sleeping bodies that won't be updated by `update_aabbs`.
+        for (_, collider) in colliders.colliders.iter() {
+            if self.proxies.get_mut(collider.proxy_index).is_some()
+                && !self.is_baked(collider.proxy_index)
+            {
+                self.baked_queries.push(collider.proxy_index);
+            }
+        }
+    }
+
+    pub(crate) fn update_aabbs(
+        &mut self,
+        prediction_distance: f32,
+        bodies: &RigidBodySet,
+        colliders: &mut ColliderSet,
+    ) {
+        if std::mem::replace(&mut self.bake_requested, false) {
+            self.bake_static_colliders_now(prediction_distance, bodies, colliders);
+        }
+
+        // First, if we have any pending removals we have
+        // to deal with them now because otherwise we will
+        // end up with an ABA problems when reusing proxy
+        // ids.
+        self.complete_removals();
+        let has_baked_proxies = self.baked_proxies.any();
+
+        let mut active_bodies = bodies
+            .modified_inactive_set
+            .iter()
+            .chain(bodies.islands.active_dynamic_set.iter())
+            .chain(bodies.islands.active_kinematic_set.iter())
+            .copied();
+        let mut all_bodies = bodies.iter().map(|(handle, _)| handle);
+        let body_handles: &mut dyn Iterator<Item = RigidBodyHandle> =
+            if std::mem::replace(&mut self.update_all_colliders, false) {
+                &mut all_bodies
+            } else {
+                &mut active_bodies
+            };
+
+        let mut updated_aabbs = std::mem::take(&mut self.updated_aabbs);
+
+        for body_handle in body_handles {
+            for handle in &bodies[body_handle].colliders {
+                if colliders[*handle].is_enabled_with_parent() {
+                    updated_aabbs.push((body_handle, *handle, AABB::new_invalid()));
                 }
+            }
+        }
 
-                // Discretize the aabb.
-                let proxy_id = collider.proxy_index;
-                // let start = Point::origin();
-                // let end = Point::origin();
-                let start = point_key(aabb.mins);
-                let end = point_key(aabb.maxs);
+        // Computing the AABBs is the most expensive part of this update when many colliders
+        // were just inserted, e.g., when a level is loaded, so it is done in parallel if possible.
+        {
+            let colliders = &*colliders;
+            let compute_aabb = |(body_handle, handle, aabb): &mut (_, _, AABB<f32>)| {
+                *aabb = proxy_aabb(
+                    prediction_distance,
+                    &bodies[*body_handle],
+                    &colliders[*handle],
+                );
+            };
+
+            #[cfg(not(feature = "parallel"))]
+            updated_aabbs.iter_mut().for_each(compute_aabb);
+            #[cfg(feature = "parallel")]
+            {
+                use rayon::prelude::*;
+                updated_aabbs.par_iter_mut().for_each(compute_aabb);
+            }
+        }
 
-                let regions = &mut self.regions;
-                let pool = &mut self.region_pool;
+        for (_, handle, aabb) in updated_aabbs.drain(..) {
+            let collider = &mut colliders[handle];
+
+            if self.proxies.get_mut(collider.proxy_index).is_some() {
+                if self.is_baked(collider.proxy_index) {
+                    if self.proxies[collider.proxy_index].aabb == aabb {
+                        // The baked collider didn't move, e.g., only other colliders of
+                        // its rigid-body were modified.
+                        continue;
+                    }
+
+                    self.unbake_proxy(collider.proxy_index, true);
+                }
 
-                #[cfg(feature = "dim2")]
-                for i in start.x..=end.x {
-                    for j in start.y..=end.y {
-                        let region_key = Point::new(i, j);
+                self.proxies[collider.proxy_index].aabb = aabb;
+            } else {
+                let proxy = BroadPhaseProxy {
+                    handle,
+                    aabb,
+                    next_free: NEXT_FREE_SENTINEL,
+                };
+                collider.proxy_index = self.proxies.insert(proxy);
+            }
+
+            // Discretize the aabb.
+            let proxy_id = collider.proxy_index;
+            if has_baked_proxies {
+                self.baked_queries.push(proxy_id);
+            }
+            // let start = Point::origin();
+            // let end = Point::origin();
+            let start = point_key(aabb.mins);
+            let end = point_key(aabb.maxs);
+
+            let regions = &mut self.regions;
+            let pool = &mut self.region_pool;
+
+            #[cfg(feature = "dim2")]
+            for i in start.x..=end.x {
+                for j in start.y..=end.y {
+                    let region_key = Point::new(i, j);
+                    let region_bounds = region_aabb(region_key);
+                    let region = regions
+                        .entry(region_key)
+                        .or_insert_with(|| SAPRegion::recycle_or_new(region_bounds, pool));
+                    let _ = region.preupdate_proxy(proxy_id);
+                }
+            }
+
+            #[cfg(feature = "dim3")]
+            for i in start.x..=end.x {
+                for j in start.y..=end.y {
+                    for k in start.z..=end.z {
+                        let region_key = Point::new(i, j, k);
                         let region_bounds = region_aabb(region_key);
                         let region = regions
                             .entry(region_key)
@@ -672,26 +1116,70 @@ impl BroadPhase {
                         let _ = region.preupdate_proxy(proxy_id);
                     }
                 }
+            }
+        }
 
-                #[cfg(feature = "dim3")]
-                for i in start.x..=end.x {
-                    for j in start.y..=end.y {
-                        for k in start.z..=end.z {
-                            let region_key = Point::new(i, j, k);
-                            let region_bounds = region_aabb(region_key);
-                            let region = regions
-                                .entry(region_key)
-                                .or_insert_with(|| SAPRegion::recycle_or_new(region_bounds, pool));
-                            let _ = region.preupdate_proxy(proxy_id);
-                        }
-                    }
+        self.updated_aabbs = updated_aabbs;
+    }
+
+    fn update_baked_pairs(&mut self) {
+        for proxy_id in self.baked_queries.drain(..) {
+            let aabb = self.proxies[proxy_id].aabb;
+            self.baked_candidates.clear();
+            self.baked_tree
+                .intersect_aabb(&aabb, &mut self.baked_candidates);
+
+            let baked_proxies = &self.baked_proxies;
+            let proxies = &self.proxies;
+            self.baked_candidates.retain(|other| {
+                baked_proxies.get(*other).unwrap_or(false) && proxies[*other].aabb.intersects(&aabb)
+            });
+
+            let proxy_id = proxy_id as u32;
+            let pairs = match self.baked_pairs.get_mut(&proxy_id) {
+                Some(pairs) => pairs,
+                None if self.baked_candidates.is_empty() => continue,
+                None => self.baked_pairs.entry(proxy_id).or_default(),
+            };
+            self.baked_candidates.sort_unstable();
+
+            // Report the difference between the old and new sorted lists of baked neighbors.
+            let (mut i, mut j) = (0, 0);
+            while i < pairs.len() || j < self.baked_candidates.len() {
+                let old = pairs.get(i).copied().unwrap_or(u32::MAX);
+                let new = self
+                    .baked_candidates
+                    .get(j)
+                    .map(|id| *id as u32)
+                    .unwrap_or(u32::MAX);
+
+                if old == new {
+                    i += 1;
+                    j += 1;
+                } else if old < new {
+                    let _ = self.reporting.insert(sort2(proxy_id, old), false);
+                    i += 1;
+                } else {
+                    let _ = self.reporting.insert(sort2(proxy_id, new), true);
+                    j += 1;
                 }
             }
+
+            pairs.clear();
+            pairs.extend(self.baked_candidates.iter().map(|id| *id as u32));
         }
     }
 
     fn update_regions(&mut self) {
-        for (point, region) in &mut self.regions {
+        // A pair may be reported by several regions, so the regions must be updated in
+        // an order that doesn't depend on the internal layout of the hashmap.
+        self.regions_to_update.clear();
+        self.regions_to_update.extend(self.regions.keys().copied());
+        self.regions_to_update
+            .sort_unstable_by(|a, b| a.coords.as_slice().cmp(b.coords.as_slice()));
+
+        for point in &self.regions_to_update {
+            let region = self.regions.get_mut(point).unwrap();
             region.update(&self.proxies, &mut self.reporting);
             if region.proxy_count == 0 {
                 self.regions_to_remove.push(*point);
@@ -721,14 +1209,24 @@ impl BroadPhase {
         // println!("num regions: {}", self.regions.len());
 
         self.reporting.clear();
+        // The reports of the baked proxies are overwritten by the reports of the SAP
+        // regions, e.g., if an unbaked proxy still overlaps its former baked neighbors.
+        self.reporting.extend(self.baked_reports.drain(..));
         self.update_regions();
+        self.update_baked_pairs();
 
         // Convert reports to broad phase events.
         // let t = instant::now();
         // let mut num_add_events = 0;
         // let mut num_delete_events = 0;
 
-        for ((proxy1, proxy2), colliding) in &self.reporting {
+        // Sort the reported pairs so the events order doesn't depend on the hashmap layout.
+        self.sorted_reports.clear();
+        self.sorted_reports
+            .extend(self.reporting.iter().map(|(k, v)| (*k, *v)));
+        self.sorted_reports.sort_unstable_by_key(|report| report.0);
+
+        for ((proxy1, proxy2), colliding) in &self.sorted_reports {
             let proxy1 = &self.proxies[*proxy1 as usize];
             let proxy2 = &self.proxies[*proxy2 as usize];
 
@@ -759,10 +1257,28 @@ impl BroadPhase {
     }
 }
 
+/// The AABB of the proxy of a collider, loosened by its prediction distance and contact skin.
+fn proxy_aabb(prediction_distance: f32, body: &RigidBody, collider: &Collider) -> AABB<f32> {
+    // NOTE: two AABBs loosened this way overlap whenever their colliders are
+    // closer than the largest prediction distance of both colliders, plus
+    // their contact skins.
+    let margin = collider
+        .prediction_distance()
+        .map(|d| d.max(prediction_distance / 2.0))
+        .unwrap_or(prediction_distance / 2.0)
+        + collider.contact_skin();
+    let aabb = if body.is_ccd_enabled() {
+        collider.compute_swept_aabb()
+    } else {
+        collider.compute_aabb()
+    };
+    aabb.loosened(margin)
+}
+
 #[cfg(test)]
 mod test {
     use crate::dynamics::{JointSet, RigidBodyBuilder, RigidBodySet};
-    use crate::geometry::{BroadPhase, ColliderBuilder, ColliderSet, NarrowPhase};
+    use crate::geometry::{BroadPhase, ColliderBuilder, ColliderSet};
 
     #[test]
     fn test_add_update_remove() {
@@ -791,4 +1307,243 @@ mod test {
         // Make sure the proxy handles is recycled properly.
         broad_phase.update_aabbs(0.0, &bodies, &mut colliders);
     }
+
+    #[test]
+    fn colliders_with_aabb_intersecting_aabb() {
+        use crate::math::{Isometry, Point, Vector};
+        use crate::pipeline::TestWorld;
+        use ncollide::bounding_volume::AABB;
+
+        let mut world = TestWorld::new(Vector::zeros());
+
+        // A row of balls spanning several SAP regions, half of them baked.
+        let ground = world.bodies.insert(RigidBodyBuilder::new_static().build());
+        let mut balls = Vec::new();
+        for i in -30..30 {
+            let mut position = Isometry::identity();
+            position.translation.vector.x = i as f32 * 2.0;
+            let ball = ColliderBuilder::ball(0.5).position(position).build();
+
+            if i % 2 == 0 {
+                balls.push(world.attach(ball, ground));
+            } else {
+                let rb = RigidBodyBuilder::new_dynamic().position(position).build();
+                let (_, handle) = world.insert(rb, ColliderBuilder::ball(0.5).build());
+                balls.push(handle);
+            }
+        }
+
+        world.broad_phase.bake_static_colliders();
+        world.step();
+        assert!(world.broad_phase.baked_proxies.any());
+
+        let query = |world: &TestWorld, mins: Point<f32>, maxs: Point<f32>, max_hits: usize| {
+            let mut hits = Vec::new();
+            world.broad_phase.colliders_with_aabb_intersecting_aabb(
+                &AABB::new(mins, maxs),
+                |handle| {
+                    hits.push(*handle);
+                    hits.len() < max_hits
+                },
+            );
+            hits.sort_unstable_by_key(|handle| handle.into_raw_parts());
+            hits
+        };
+
+        // The AABB overlaps the balls located in [-42, 42] across the boundaries of the SAP
+        // regions, and each ball is only yielded once.
+        let mins = Point::from(Vector::repeat(-1.0) - Vector::x() * 40.5);
+        let maxs = Point::from(Vector::repeat(1.0) + Vector::x() * 40.5);
+        let mut expected = balls[9..52].to_vec();
+        expected.sort_unstable_by_key(|handle| handle.into_raw_parts());
+        assert_eq!(query(&world, mins, maxs, usize::MAX), expected);
+
+        // The search stops as soon as the callback returns `false`.
+        assert_eq!(query(&world, mins, maxs, 3).len(), 3);
+
+        // No ball is between two balls.
+        let mins = Point::from(Vector::repeat(-0.2) + Vector::x());
+        let maxs = Point::from(Vector::repeat(0.2) + Vector::x());
+        assert!(query(&world, mins, maxs, usize::MAX).is_empty());
+    }
+
+    #[test]
+    fn baked_static_colliders() {
+        use crate::math::{Isometry, Vector};
+        use crate::pipeline::TestWorld;
+
+        let mut world = TestWorld::falling();
+
+        // A floor made of many tiles, spanning several SAP regions.
+        let ground = world.bodies.insert(RigidBodyBuilder::new_static().build());
+        let mut num_tiles = 0;
+        for i in -30..30 {
+            #[cfg(feature = "dim2")]
+            let tile = ColliderBuilder::cuboid(0.5, 0.5).translation(i as f32, 0.0);
+            #[cfg(feature = "dim3")]
+            let tile = ColliderBuilder::cuboid(0.5, 0.5, 0.5).translation(i as f32, 0.0, 0.0);
+            world.attach(tile.build(), ground);
+            num_tiles += 1;
+        }
+
+        let rb = RigidBodyBuilder::new_dynamic()
+            .position(Isometry::new(Vector::y() * 2.0, na::zero()))
+            .build();
+        let (ball, ball_collider) = world.insert(rb, ColliderBuilder::ball(0.5).build());
+
+        let step = |world: &mut TestWorld| {
+            world.step();
+            world
+                .narrow_phase
+                .contacts_with(ball_collider)
+                .into_iter()
+                .flatten()
+                .filter(|pair| pair.2.has_any_active_contact())
+                .count()
+        };
+        // The number of proxies in at least one SAP region.
+        let num_sap_proxies = |bf: &BroadPhase| {
+            let mut ids: Vec<_> = bf
+                .regions
+                .values()
+                .flat_map(|r| r.existing_proxies.iter().enumerate().filter(|(_, e)| *e))
+                .map(|(id, _)| id)
+                .collect();
+            ids.sort_unstable();
+            ids.dedup();
+            ids.len()
+        };
+
+        // Bake the tiles after they have been registered in the SAP regions.
+        let _ = step(&mut world);
+        world.broad_phase.bake_static_colliders();
+
+        let mut num_contacts = 0;
+        for _ in 0..100 {
+            num_contacts = step(&mut world);
+        }
+
+        // Only the ball is left in the SAP regions, and it still lands on the baked floor.
+        let bf = &world.broad_phase;
+        assert_eq!(bf.baked_proxies.iter().filter(|b| *b).count(), num_tiles);
+        assert_eq!(num_sap_proxies(bf), 1);
+        assert!(num_contacts > 0);
+        assert!((world.bodies[ball].position().translation.vector.y - 1.0).abs() < 0.05);
+
+        // Moving the floor unbakes its tiles, and the ball falls.
+        world
+            .bodies
+            .get_mut(ground)
+            .unwrap()
+            .set_position(Isometry::new(Vector::x() * 100.0, na::zero()), true);
+        for _ in 0..10 {
+            num_contacts = step(&mut world);
+        }
+
+        let bf = &world.broad_phase;
+        assert!(!bf.baked_proxies.any());
+        assert_eq!(num_sap_proxies(bf), num_tiles + 1);
+        assert_eq!(num_contacts, 0);
+        assert_eq!(
+            world
+                .narrow_phase
+                .contacts_with(ball_collider)
+                .into_iter()
+                .flatten()
+                .count(),
+            0
+        );
+        assert!(world.bodies[ball].position().translation.vector.y < 0.9);
+    }
+
+    #[test]
+    fn batch_insertion() {
+        use super::{BroadPhasePairEvent, ColliderPair};
+        use crate::math::{Isometry, Vector};
+        use ncollide::bounding_volume::BoundingVolume;
+
+        let mut broad_phase = BroadPhase::new();
+        let mut bodies = RigidBodySet::new();
+        let mut colliders = ColliderSet::new();
+        let mut pairs = Vec::new();
+
+        // Pseudo-random overlapping balls, inserted in two batches so that both the pairs among
+        // new proxies and the pairs between new and old proxies are reported.
+        let mut seed = 1u32;
+        let mut rand = move || {
+            seed = seed.wrapping_mul(1_103_515_245).wrapping_add(12_345);
+            (seed >> 8) as f32 / (1u32 << 24) as f32
+        };
+
+        #[cfg(feature = "dim2")]
+        let extent = 30.0;
+        #[cfg(feature = "dim3")]
+        let extent = 12.0;
+
+        for _ in 0..2 {
+            let positions: Vec<_> = (0..300)
+                .map(|_| Isometry::new(Vector::from_fn(|_, _| rand() * extent), na::zero()))
+                .collect();
+            let radii: Vec<_> = (0..300).map(|_| 0.2 + rand()).collect();
+            let new_bodies = positions
+                .iter()
+                .map(|pos| RigidBodyBuilder::new_dynamic().position(*pos).build());
+            let handles = bodies.insert_batch(new_bodies);
+            let new_colliders = handles
+                .iter()
+                .zip(radii.iter())
+                .map(|(h, r)| (ColliderBuilder::ball(*r).build(), *h));
+            let _ = colliders.insert_batch(new_colliders, &mut bodies);
+
+            // The new bodies are not part of the active sets before the next timestep.
+            let mut events = Vec::new();
+            broad_phase.update_all_colliders = true;
+            broad_phase.update_aabbs(0.0, &bodies, &mut colliders);
+            broad_phase.find_pairs(&mut events);
+            for event in events {
+                match event {
+                    BroadPhasePairEvent::AddPair(pair) => pairs.push(pair),
+                    BroadPhasePairEvent::DeletePair(_) => panic!("No pair should be removed."),
+                }
+            }
+        }
+
+        let mut expected = Vec::new();
+        let all: Vec<_> = colliders.iter().collect();
+        for (i, (h1, c1)) in all.iter().enumerate() {
+            for (h2, c2) in &all[i + 1..] {
+                if c1.compute_aabb().intersects(&c2.compute_aabb()) {
+                    expected.push(ColliderPair::new_sorted(*h1, *h2));
+                }
+            }
+        }
+
+        let key = |p: &ColliderPair| (p.collider1.into_raw_parts(), p.collider2.into_raw_parts());
+        let mut pairs: Vec<_> = pairs
+            .iter()
+            .map(|p| ColliderPair::new_sorted(p.collider1, p.collider2))
+            .collect();
+        pairs.sort_by_key(key);
+        pairs.dedup();
+        expected.sort_by_key(key);
+        assert!(expected.len() > 300);
+        assert!(pairs == expected);
+
+        // The mass properties of a rigid-body are the same as with individual insertions.
+        let ball = |x: f32| {
+            let mut position = Isometry::identity();
+            position.translation.vector.x = x;
+            ColliderBuilder::ball(0.5).position(position).build()
+        };
+        let rb1 = bodies.insert(RigidBodyBuilder::new_dynamic().build());
+        let rb2 = bodies.insert(RigidBodyBuilder::new_dynamic().build());
+        for x in &[0.0, 1.0, 3.0] {
+            colliders.insert(ball(*x), rb1, &mut bodies);
+        }
+        let _ =
+            colliders.insert_batch([0.0, 1.0, 3.0].iter().map(|x| (ball(*x), rb2)), &mut bodies);
+        assert_eq!(bodies[rb1].mass(), bodies[rb2].mass());
+        assert_eq!(bodies[rb1].center_of_mass(), bodies[rb2].center_of_mass());
+        assert_eq!(bodies[rb2].colliders().len(), 3);
+    }
 }
@@ -35,6 +35,30 @@ impl Capsule {
         Self::new(-b, b, radius)
     }
 
+    /// Creates a new capsule centered at the origin, aligned with the given axis, and with the
+    /// given half-height and radius.
+    ///
+    /// The segment of the capsule goes from `-axis * half_height` to `axis * half_height`.
+    pub fn new_along(axis: Unit<Vector<f32>>, half_height: f32, radius: f32) -> Self {
+        Self::new_centered(Point::origin(), axis, half_height, radius)
+    }
+
+    /// Creates a new capsule from its center, the direction of its segment, its half-height,
+    /// and its radius.
+    ///
+    /// This rebuilds a capsule from its `center`, `axis`, and `half_height`: for any capsule `c`,
+    /// `Capsule::new_centered(c.center(), c.axis(), c.half_height(), c.radius)` is equal to `c`,
+    /// up to rounding errors.
+    pub fn new_centered(
+        center: Point<f32>,
+        axis: Unit<Vector<f32>>,
+        half_height: f32,
+        radius: f32,
+    ) -> Self {
+        let b = axis.into_inner() * half_height;
+        Self::new(center - b, center + b, radius)
+    }
+
     /// Creates a new capsule defined as the segment between `a` and `b` and with the given `radius`.
     pub fn new(a: Point<f32>, b: Point<f32>, radius: f32) -> Self {
         let segment = Segment::new(a, b);
@@ -65,11 +89,51 @@ impl Capsule {
         na::center(&self.segment.a, &self.segment.b)
     }
 
+    /// The unit direction of the segment of this capsule, from `segment.a` to `segment.b`.
+    ///
+    /// This is the `y` axis if both endpoints of the segment coincide.
+    pub fn axis(&self) -> Unit<Vector<f32>> {
+        Unit::try_new(self.segment.b - self.segment.a, f32::EPSILON).unwrap_or(Vector::y_axis())
+    }
+
     /// Creates a new capsule equal to `self` with all its endpoints transformed by `pos`.
     pub fn transform_by(&self, pos: &Isometry<f32>) -> Self {
         Self::new(pos * self.segment.a, pos * self.segment.b, self.radius)
     }
 
+    /// The feature of this capsule closest to the given local-space point.
+    ///
+    /// The features of a capsule are the features of its segment: `FeatureId::Vertex(0)` and
+    /// `FeatureId::Vertex(1)` identify the hemispherical caps centered at `self.segment.a` and
+    /// `self.segment.b`. In 2D, `FeatureId::Face(0)` and `FeatureId::Face(1)` identify the
+    /// flat sides with the outward normals `(ab.y, -ab.x)` and `(-ab.y, ab.x)`, where `ab` is
+    /// `b - a`. In 3D, `FeatureId::Edge(0)` identifies the cylindrical side.
+    pub fn local_feature_at(&self, pt: &Point<f32>) -> FeatureId {
+        let ab = self.segment.b - self.segment.a;
+        let ap = pt - self.segment.a;
+        let ab_ap = ab.dot(&ap);
+
+        if ab_ap <= 0.0 {
+            FeatureId::Vertex(0)
+        } else if ab_ap >= ab.norm_squared() {
+            FeatureId::Vertex(1)
+        } else {
+            #[cfg(feature = "dim2")]
+            {
+                if ap.perp(&ab) >= 0.0 {
+                    FeatureId::Face(0)
+                } else {
+                    FeatureId::Face(1)
+                }
+            }
+
+            #[cfg(feature = "dim3")]
+            {
+                FeatureId::Edge(0)
+            }
+        }
+    }
+
     /// The rotation `r` such that `r * Y` is collinear with `b - a`.
     pub fn rotation_wrt_y(&self) -> Rotation<f32> {
         let mut dir = self.segment.b - self.segment.a;
@@ -187,6 +251,80 @@ impl PointQuery<f32> for Capsule {
         m: &Isometry<f32>,
         pt: &Point<f32>,
     ) -> (PointProjection<f32>, FeatureId) {
-        (self.project_point(m, pt, false), FeatureId::Face(0))
+        let proj = self.project_point(m, pt, false);
+        let local_pt = m.inverse_transform_point(pt);
+        (proj, self.local_feature_at(&local_pt))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Capsule;
+    use crate::math::{Isometry, Point, Vector};
+    use ncollide::query::PointQuery;
+    use ncollide::shape::FeatureId;
+
+    #[test]
+    fn capsule_features() {
+        // A capsule along the local `y` axis, rotated such that this axis points toward `-x`.
+        let capsule = Capsule::new_y(1.0, 0.5);
+        #[cfg(feature = "dim2")]
+        let pos = Isometry::new(Vector::zeros(), std::f32::consts::FRAC_PI_2);
+        #[cfg(feature = "dim3")]
+        let pos = Isometry::new(Vector::zeros(), Vector::z() * std::f32::consts::FRAC_PI_2);
+
+        let feature = |pt: Point<f32>| capsule.project_point_with_feature(&pos, &pt).1;
+        assert_eq!(
+            feature(Point::from(Vector::x() * 3.0)),
+            FeatureId::Vertex(0)
+        );
+        assert_eq!(
+            feature(Point::from(Vector::x() * -3.0)),
+            FeatureId::Vertex(1)
+        );
+
+        #[cfg(feature = "dim2")]
+        {
+            assert_eq!(feature(Point::from(Vector::y() * 2.0)), FeatureId::Face(0));
+            assert_eq!(feature(Point::from(Vector::y() * -2.0)), FeatureId::Face(1));
+        }
+        #[cfg(feature = "dim3")]
+        {
+            assert_eq!(feature(Point::from(Vector::y() * 2.0)), FeatureId::Edge(0));
+            assert_eq!(feature(Point::from(Vector::z() * -2.0)), FeatureId::Edge(0));
+        }
+    }
+
+    #[test]
+    fn capsule_along_axis() {
+        let along_x = Capsule::new_along(Vector::x_axis(), 1.5, 0.5);
+        let along_y = Capsule::new_along(Vector::y_axis(), 1.5, 0.5);
+        assert_eq!(along_x.segment, Capsule::new_x(1.5, 0.5).segment);
+        assert_eq!(along_y.segment, Capsule::new_y(1.5, 0.5).segment);
+        #[cfg(feature = "dim3")]
+        assert_eq!(
+            Capsule::new_along(Vector::z_axis(), 1.5, 0.5).segment,
+            Capsule::new_z(1.5, 0.5).segment
+        );
+
+        // Rebuild an arbitrary capsule from its center, axis, and half-height.
+        let capsule = Capsule::new(
+            Point::from(Vector::repeat(1.0)),
+            Point::from(Vector::x() * -2.0 + Vector::y() * 3.0),
+            0.3,
+        );
+        let rebuilt = Capsule::new_centered(
+            capsule.center(),
+            capsule.axis(),
+            capsule.half_height(),
+            capsule.radius,
+        );
+        assert!((rebuilt.segment.a - capsule.segment.a).norm() < 1.0e-6);
+        assert!((rebuilt.segment.b - capsule.segment.b).norm() < 1.0e-6);
+        assert_eq!(rebuilt.radius, capsule.radius);
+
+        // A degenerate capsule is a ball, aligned with `y` by convention.
+        let ball = Capsule::new(Point::origin(), Point::origin(), 1.0);
+        assert_eq!(ball.axis(), Vector::y_axis());
     }
 }
@@ -1,190 +1,36 @@
-use crate::dynamics::{MassProperties, RigidBodyHandle, RigidBodySet};
+use crate::dynamics::{CoefficientCombineRule, MassProperties, RigidBodyHandle, RigidBodySet};
 use crate::geometry::{
-    Ball, Capsule, Cuboid, HeightField, InteractionGroups, Segment, Shape, ShapeType, Triangle,
-    Trimesh,
+    ColliderMaterial, HeightField, InteractionGroups, Shape, SharedShape, Trimesh,
 };
-#[cfg(feature = "dim3")]
-use crate::geometry::{Cone, Cylinder, RoundCylinder};
 use crate::math::{AngVector, Isometry, Point, Rotation, Vector};
-use na::Point3;
-use ncollide::bounding_volume::AABB;
-use std::ops::Deref;
+use crate::utils;
+use na::{Point3, Unit};
+use ncollide::bounding_volume::{BoundingVolume, AABB};
 use std::sync::Arc;
 
-// TODO: move this to its own file.
-/// The shape of a collider.
-#[derive(Clone)]
-pub struct ColliderShape(pub Arc<dyn Shape>);
-
-impl Deref for ColliderShape {
-    type Target = dyn Shape;
-    fn deref(&self) -> &dyn Shape {
-        &*self.0
-    }
-}
-
-impl ColliderShape {
-    /// Initialize a ball shape defined by its radius.
-    pub fn ball(radius: f32) -> Self {
-        ColliderShape(Arc::new(Ball::new(radius)))
-    }
-
-    /// Initialize a cylindrical shape defined by its half-height
-    /// (along along the y axis) and its radius.
-    #[cfg(feature = "dim3")]
-    pub fn cylinder(half_height: f32, radius: f32) -> Self {
-        ColliderShape(Arc::new(Cylinder::new(half_height, radius)))
-    }
-
-    /// Initialize a rounded cylindrical shape defined by its half-height
-    /// (along along the y axis), its radius, and its roundedness (the
-    /// radius of the sphere used for dilating the cylinder).
-    #[cfg(feature = "dim3")]
-    pub fn round_cylinder(half_height: f32, radius: f32, border_radius: f32) -> Self {
-        ColliderShape(Arc::new(RoundCylinder::new(
-            half_height,
-            radius,
-            border_radius,
-        )))
-    }
-
-    /// Initialize a cone shape defined by its half-height
-    /// (along along the y axis) and its basis radius.
-    #[cfg(feature = "dim3")]
-    pub fn cone(half_height: f32, radius: f32) -> Self {
-        ColliderShape(Arc::new(Cone::new(half_height, radius)))
-    }
-
-    /// Initialize a cuboid shape defined by its half-extents.
-    pub fn cuboid(half_extents: Vector<f32>) -> Self {
-        ColliderShape(Arc::new(Cuboid::new(half_extents)))
-    }
-
-    /// Initialize a capsule shape from its endpoints and radius.
-    pub fn capsule(a: Point<f32>, b: Point<f32>, radius: f32) -> Self {
-        ColliderShape(Arc::new(Capsule::new(a, b, radius)))
-    }
-
-    /// Initialize a segment shape from its endpoints.
-    pub fn segment(a: Point<f32>, b: Point<f32>) -> Self {
-        ColliderShape(Arc::new(Segment::new(a, b)))
-    }
-
-    /// Initializes a triangle shape.
-    pub fn triangle(a: Point<f32>, b: Point<f32>, c: Point<f32>) -> Self {
-        ColliderShape(Arc::new(Triangle::new(a, b, c)))
-    }
-
-    /// Initializes a triangle mesh shape defined by its vertex and index buffers.
-    pub fn trimesh(vertices: Vec<Point<f32>>, indices: Vec<Point3<u32>>) -> Self {
-        ColliderShape(Arc::new(Trimesh::new(vertices, indices)))
-    }
-
-    /// Initializes an heightfield shape defined by its set of height and a scale
-    /// factor along each coordinate axis.
-    #[cfg(feature = "dim2")]
-    pub fn heightfield(heights: na::DVector<f32>, scale: Vector<f32>) -> Self {
-        ColliderShape(Arc::new(HeightField::new(heights, scale)))
-    }
-
-    /// Initializes an heightfield shape on the x-z plane defined by its set of height and a scale
-    /// factor along each coordinate axis.
-    #[cfg(feature = "dim3")]
-    pub fn heightfield(heights: na::DMatrix<f32>, scale: Vector<f32>) -> Self {
-        ColliderShape(Arc::new(HeightField::new(heights, scale)))
-    }
-}
-
-#[cfg(feature = "serde-serialize")]
-impl serde::Serialize for ColliderShape {
-    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
-    where
-        S: serde::Serializer,
-    {
-        use crate::serde::ser::SerializeStruct;
-
-        if let Some(ser) = self.0.as_serialize() {
-            let typ = self.0.shape_type();
-            let mut state = serializer.serialize_struct("ColliderShape", 2)?;
-            state.serialize_field("tag", &(typ as i32))?;
-            state.serialize_field("inner", ser)?;
-            state.end()
-        } else {
-            Err(serde::ser::Error::custom(
-                "Found a non-serializable custom shape.",
-            ))
-        }
-    }
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde-serialize", derive(Serialize, Deserialize))]
+/// The way the mass properties of a collider are computed from its shape.
+pub enum ColliderMassProperties {
+    /// The mass properties are computed from the collider's shape and this density.
+    Density(f32),
+    /// The mass properties are computed from the collider's shape with a density
+    /// such that the total mass of the collider is equal to this mass.
+    Mass(f32),
 }
 
-#[cfg(feature = "serde-serialize")]
-impl<'de> serde::Deserialize<'de> for ColliderShape {
-    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
-    where
-        D: serde::Deserializer<'de>,
-    {
-        struct Visitor {};
-        impl<'de> serde::de::Visitor<'de> for Visitor {
-            type Value = ColliderShape;
-            fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
-                write!(formatter, "one shape type tag and the inner shape data")
-            }
-
-            fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
-            where
-                A: serde::de::SeqAccess<'de>,
-            {
-                use num::cast::FromPrimitive;
-
-                let tag: i32 = seq
-                    .next_element()?
-                    .ok_or_else(|| serde::de::Error::invalid_length(0, &self))?;
-
-                fn deser<'de, A, S: Shape + serde::Deserialize<'de>>(
-                    seq: &mut A,
-                ) -> Result<Arc<dyn Shape>, A::Error>
-                where
-                    A: serde::de::SeqAccess<'de>,
-                {
-                    let shape: S = seq.next_element()?.ok_or_else(|| {
-                        serde::de::Error::custom("Failed to deserialize builtin shape.")
-                    })?;
-                    Ok(Arc::new(shape) as Arc<dyn Shape>)
-                }
-
-                let shape = match ShapeType::from_i32(tag) {
-                    Some(ShapeType::Ball) => deser::<A, Ball>(&mut seq)?,
-                    Some(ShapeType::Polygon) => {
-                        unimplemented!()
-                        // let shape: Polygon = seq
-                        //     .next_element()?
-                        //     .ok_or_else(|| serde::de::Error::invalid_length(0, &self))?;
-                        // Arc::new(shape) as Arc<dyn Shape>
-                    }
-                    Some(ShapeType::Cuboid) => deser::<A, Cuboid>(&mut seq)?,
-                    Some(ShapeType::Capsule) => deser::<A, Capsule>(&mut seq)?,
-                    Some(ShapeType::Triangle) => deser::<A, Triangle>(&mut seq)?,
-                    Some(ShapeType::Segment) => deser::<A, Segment>(&mut seq)?,
-                    Some(ShapeType::Trimesh) => deser::<A, Trimesh>(&mut seq)?,
-                    Some(ShapeType::HeightField) => deser::<A, HeightField>(&mut seq)?,
-                    #[cfg(feature = "dim3")]
-                    Some(ShapeType::Cylinder) => deser::<A, Cylinder>(&mut seq)?,
-                    #[cfg(feature = "dim3")]
-                    Some(ShapeType::Cone) => deser::<A, Cone>(&mut seq)?,
-                    #[cfg(feature = "dim3")]
-                    Some(ShapeType::RoundCylinder) => deser::<A, RoundCylinder>(&mut seq)?,
-                    None => {
-                        return Err(serde::de::Error::custom(
-                            "found invalid shape type to deserialize",
-                        ))
-                    }
-                };
-
-                Ok(ColliderShape(shape))
-            }
-        }
-
-        deserializer.deserialize_struct("ColliderShape", &["tag", "inner"], Visitor {})
+bitflags::bitflags! {
+    /// Flags describing how the collider has been modified by the user.
+    pub(crate) struct ColliderChanges: u32 {
+        const MODIFIED            = 1 << 0;
+        const MASS_PROPERTIES     = 1 << 1;
+        const POSITION_WRT_PARENT = 1 << 2;
+        const SHAPE               = 1 << 3;
+        const ENABLED             = 1 << 4;
+        const POSITION            = 1 << 5;
+        const SHAPE_DEFORMED      = 1 << 6;
+        const PREDICTION_DISTANCE = 1 << 7;
+        const CONTACT_SKIN        = 1 << 8;
     }
 }
 
@@ -194,20 +40,36 @@ impl<'de> serde::Deserialize<'de> for ColliderShape {
 ///
 /// To build a new collider, use the `ColliderBuilder` structure.
 pub struct Collider {
-    shape: ColliderShape,
-    density: f32,
+    shape: SharedShape,
+    // The mass properties of the shape with a density of 1.
+    unit_mass_properties: MassProperties,
+    mass_props: ColliderMassProperties,
     is_sensor: bool,
+    enabled: bool,
+    pub(crate) parent_enabled: bool,
     pub(crate) parent: RigidBodyHandle,
     pub(crate) delta: Isometry<f32>,
     pub(crate) position: Isometry<f32>,
     pub(crate) predicted_position: Isometry<f32>,
-    /// The friction coefficient of this collider.
-    pub friction: f32,
-    /// The restitution coefficient of this collider.
-    pub restitution: f32,
+    material: Arc<ColliderMaterial>,
+    /// The total contact force magnitude above which contact force events are emitted for
+    /// the contacts involving this collider.
+    ///
+    /// Set to `f32::MAX` by default, meaning that no contact force event is emitted.
+    /// See [`ContactForceEvent`](crate::pipeline::ContactForceEvent) for details.
+    pub contact_force_event_threshold: f32,
+    prediction_distance: Option<f32>,
+    contact_skin: f32,
     pub(crate) collision_groups: InteractionGroups,
     pub(crate) solver_groups: InteractionGroups,
     pub(crate) proxy_index: usize,
+    #[cfg_attr(
+        feature = "serde-serialize",
+        serde(skip, default = "ColliderChanges::empty")
+    )]
+    pub(crate) changes: ColliderChanges,
+    // The local-space AABB of the parts of the shape modified in-place since the last timestep.
+    pub(crate) deformed_local_aabb: AABB<f32>,
     /// User-defined data associated to this rigid-body.
     pub user_data: u128,
 }
@@ -216,6 +78,8 @@ impl Collider {
     pub(crate) fn reset_internal_references(&mut self) {
         self.parent = RigidBodySet::invalid_handle();
         self.proxy_index = crate::INVALID_USIZE;
+        self.changes = ColliderChanges::empty();
+        self.parent_enabled = true;
     }
 
     /// The rigid body this collider is attached to.
@@ -228,6 +92,89 @@ impl Collider {
         self.is_sensor
     }
 
+    /// Is this collider enabled?
+    ///
+    /// This only reflects the flag set by `Collider::set_enabled`. A collider attached
+    /// to a disabled rigid-body does not interact with any other collider either.
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    /// Enables or disables this collider.
+    ///
+    /// A disabled collider is removed from the broad-phase, and all the contacts and proximities
+    /// involving it are removed from the narrow-phase. It keeps all its other properties, and still
+    /// contributes to the mass properties of the rigid-body it is attached to. No contact or
+    /// proximity event is generated for the interactions removed this way.
+    ///
+    /// The collider must be accessed with `ColliderSet::get_mut` for this change to be
+    /// taken into account.
+    pub fn set_enabled(&mut self, enabled: bool) {
+        if enabled != self.enabled {
+            self.enabled = enabled;
+            self.changes.insert(ColliderChanges::ENABLED);
+        }
+    }
+
+    /// The maximal distance separating this collider from another collider that will generate
+    /// predictive contacts, if it overrides `IntegrationParameters::prediction_distance`.
+    pub fn prediction_distance(&self) -> Option<f32> {
+        self.prediction_distance
+    }
+
+    /// Sets the maximal distance separating this collider from another collider that will
+    /// generate predictive contacts.
+    ///
+    /// Predictive contacts let the solver slow down objects before they touch, which
+    /// prevents medium-speed objects from tunneling through thin colliders without
+    /// enabling CCD. Larger distances generate more contacts. If `None`, the global
+    /// `IntegrationParameters::prediction_distance` is used. The prediction distance of
+    /// a pair of colliders is the largest prediction distance of both colliders.
+    ///
+    /// The collider must be accessed with `ColliderSet::get_mut` for this change to be
+    /// taken into account.
+    pub fn set_prediction_distance(&mut self, distance: Option<f32>) {
+        if distance != self.prediction_distance {
+            self.prediction_distance = distance;
+            self.changes.insert(ColliderChanges::PREDICTION_DISTANCE);
+        }
+    }
+
+    /// The prediction distance of this collider, given the global prediction distance.
+    pub(crate) fn effective_prediction_distance(&self, default: f32) -> f32 {
+        self.prediction_distance.unwrap_or(default)
+    }
+
+    /// The thickness of the contact skin of this collider.
+    pub fn contact_skin(&self) -> f32 {
+        self.contact_skin
+    }
+
+    /// Sets the thickness of the contact skin of this collider.
+    ///
+    /// The contacts involving this collider are computed as if its shape was dilated by this
+    /// skin: they are generated while the colliders are still separated by the sum of their
+    /// skins, and the contact distances are reduced by these skins. Therefore, the solver keeps
+    /// the shapes slightly apart instead of slightly penetrating, which reduces jitter with low
+    /// numbers of solver iterations, and makes thin colliders less prone to tunneling.
+    ///
+    /// The skin only affects contacts, not proximities, scene queries, nor mass properties.
+    /// It is primarily meant for convex shapes. Set to `0.0` by default.
+    ///
+    /// The collider must be accessed with `ColliderSet::get_mut` for this change to be
+    /// taken into account.
+    pub fn set_contact_skin(&mut self, skin: f32) {
+        if skin != self.contact_skin {
+            self.contact_skin = skin;
+            self.changes.insert(ColliderChanges::CONTACT_SKIN);
+        }
+    }
+
+    /// Is this collider enabled, and attached to an enabled rigid-body?
+    pub(crate) fn is_enabled_with_parent(&self) -> bool {
+        self.enabled && self.parent_enabled
+    }
+
     #[doc(hidden)]
     pub fn set_position_debug(&mut self, position: Isometry<f32>) {
         self.position = position;
@@ -249,6 +196,18 @@ impl Collider {
         &self.delta
     }
 
+    /// Sets the position of this collider wrt the body it is attached to.
+    ///
+    /// The world-space position of this collider, its broad-phase proxy, and the mass
+    /// properties of its parent rigid-body will be updated automatically at the next
+    /// timestep if this collider was modified through
+    /// [`ColliderSet::get_mut`](crate::geometry::ColliderSet::get_mut).
+    pub fn set_position_wrt_parent(&mut self, position: Isometry<f32>) {
+        self.delta = position;
+        self.changes
+            .insert(ColliderChanges::POSITION_WRT_PARENT | ColliderChanges::MASS_PROPERTIES);
+    }
+
     /// The collision groups used by this collider.
     pub fn collision_groups(&self) -> InteractionGroups {
         self.collision_groups
@@ -260,8 +219,48 @@ impl Collider {
     }
 
     /// The density of this collider.
+    ///
+    /// If the mass of this collider has been set explicitly, this is the density
+    /// resulting in that mass.
     pub fn density(&self) -> f32 {
-        self.density
+        match self.mass_props {
+            ColliderMassProperties::Density(density) => density,
+            ColliderMassProperties::Mass(mass) => {
+                let unit_mass = utils::inv(self.unit_mass_properties.inv_mass);
+                mass * utils::inv(unit_mass)
+            }
+        }
+    }
+
+    /// The mass of this collider.
+    pub fn mass(&self) -> f32 {
+        utils::inv(self.mass_properties().inv_mass)
+    }
+
+    /// The way the mass properties of this collider are computed.
+    pub fn mass_properties_source(&self) -> ColliderMassProperties {
+        self.mass_props
+    }
+
+    /// Sets the density of this collider.
+    ///
+    /// The mass properties of the rigid-body this collider is attached to will be updated
+    /// automatically at the next timestep if this collider was modified through
+    /// [`ColliderSet::get_mut`](crate::geometry::ColliderSet::get_mut).
+    pub fn set_density(&mut self, density: f32) {
+        self.mass_props = ColliderMassProperties::Density(density);
+        self.changes.insert(ColliderChanges::MASS_PROPERTIES);
+    }
+
+    /// Sets the mass of this collider.
+    ///
+    /// The density of this collider will be adjusted so that its total mass is equal to `mass`.
+    /// The mass properties of the rigid-body this collider is attached to will be updated
+    /// automatically at the next timestep if this collider was modified through
+    /// [`ColliderSet::get_mut`](crate::geometry::ColliderSet::get_mut).
+    pub fn set_mass(&mut self, mass: f32) {
+        self.mass_props = ColliderMassProperties::Mass(mass);
+        self.changes.insert(ColliderChanges::MASS_PROPERTIES);
     }
 
     /// The geometric shape of this collider.
@@ -269,20 +268,172 @@ impl Collider {
         &*self.shape.0
     }
 
+    /// The shared geometric shape of this collider.
+    ///
+    /// Clone it to build other colliders sharing the same shape allocation.
+    pub fn shared_shape(&self) -> &SharedShape {
+        &self.shape
+    }
+
+    /// Replaces the geometric shape of this collider.
+    ///
+    /// The broad-phase proxy of this collider, its contacts, and the mass properties of its
+    /// parent rigid-body will be updated automatically at the next timestep if this collider
+    /// was modified through [`ColliderSet::get_mut`](crate::geometry::ColliderSet::get_mut).
+    /// Contacts between two colliders with simple shapes retain their warm-starting
+    /// information.
+    pub fn set_shape(&mut self, shape: SharedShape) {
+        self.unit_mass_properties = shape.mass_properties(1.0);
+        self.shape = shape;
+        self.changes
+            .insert(ColliderChanges::SHAPE | ColliderChanges::MASS_PROPERTIES);
+    }
+
+    /// The material of this collider, i.e., its friction, restitution, and surface velocity.
+    pub fn material(&self) -> &ColliderMaterial {
+        &self.material
+    }
+
+    /// The shared material of this collider.
+    ///
+    /// Clone it to build other colliders sharing the same material.
+    pub fn shared_material(&self) -> &Arc<ColliderMaterial> {
+        &self.material
+    }
+
+    /// Gets a mutable reference to the material of this collider.
+    ///
+    /// If the material is shared with other colliders, it is cloned first so the other colliders
+    /// are not affected. Use [`ColliderSet::replace_material`](crate::geometry::ColliderSet::replace_material)
+    /// to modify the material of all the colliders sharing it.
+    pub fn material_mut(&mut self) -> &mut ColliderMaterial {
+        Arc::make_mut(&mut self.material)
+    }
+
+    /// Replaces the material of this collider.
+    ///
+    /// The friction and restitution coefficients, and the surface velocities, of the contacts
+    /// involving this collider are updated at the next timestep. The contacts between sleeping
+    /// rigid-bodies are updated once they wake up.
+    pub fn set_material(&mut self, material: Arc<ColliderMaterial>) {
+        self.material = material;
+    }
+
+    /// Gets a mutable reference to the shape of this collider if it has the type `S`.
+    ///
+    /// If the shape is shared with other colliders, it is cloned first so the other colliders
+    /// are not affected.
+    fn unshared_shape_mut<S: Shape + Clone>(&mut self) -> Option<&mut S> {
+        if !self.shape.is::<S>() {
+            return None;
+        }
+
+        if Arc::get_mut(&mut self.shape.0).is_none() {
+            let cloned = self.shape.downcast_ref::<S>()?.clone();
+            self.shape = SharedShape::new(cloned);
+        }
+
+        Arc::get_mut(&mut self.shape.0)?.downcast_mut::<S>()
+    }
+
+    /// Modifies in-place the positions of the vertices of the triangle mesh of this collider.
+    ///
+    /// See [`Trimesh::update_vertices`] for details. If the mesh is shared with other colliders, it is
+    /// cloned first so the other colliders are not affected. The broad-phase proxy of this collider
+    /// and its contacts will be updated automatically at the next timestep if this collider was
+    /// modified through [`ColliderSet::get_mut`](crate::geometry::ColliderSet::get_mut). Unlike
+    /// [`Collider::set_shape`], the contacts retain their warm-starting information and the mass
+    /// properties of the parent rigid-body are not recomputed.
+    ///
+    /// Returns `false` without calling `f` if the shape of this collider is not a triangle mesh.
+    pub fn update_trimesh_vertices(&mut self, f: impl FnOnce(&mut [Point<f32>])) -> bool {
+        let trimesh = match self.unshared_shape_mut::<Trimesh>() {
+            Some(trimesh) => trimesh,
+            None => return false,
+        };
+
+        let old_aabb = *trimesh.local_aabb();
+        trimesh.update_vertices(f);
+        let deformed_aabb = old_aabb.merged(trimesh.local_aabb());
+        self.mark_deformed(&deformed_aabb);
+        true
+    }
+
+    /// Modifies in-place the heights of the heightfield of this collider.
+    ///
+    /// See [`HeightField::update_heights`] for details. If the heightfield is shared with other
+    /// colliders, it is cloned first so the other colliders are not affected. The broad-phase proxy
+    /// of this collider will be updated automatically at the next timestep if this collider was
+    /// modified through [`ColliderSet::get_mut`](crate::geometry::ColliderSet::get_mut), and only
+    /// the rigid-bodies in contact with the modified cells are woken up.
+    ///
+    /// Returns `false` if the shape of this collider is not a heightfield.
+    #[cfg(feature = "dim2")]
+    pub fn update_heightfield_heights(
+        &mut self,
+        heights: impl IntoIterator<Item = (usize, f32)>,
+    ) -> bool {
+        let heightfield = match self.unshared_shape_mut::<HeightField>() {
+            Some(heightfield) => heightfield,
+            None => return false,
+        };
+
+        let deformed_aabb = heightfield.update_heights(heights);
+        self.mark_deformed(&deformed_aabb);
+        true
+    }
+
+    /// Modifies in-place the heights of the heightfield of this collider.
+    ///
+    /// See [`HeightField::update_heights`] for details. If the heightfield is shared with other
+    /// colliders, it is cloned first so the other colliders are not affected. The broad-phase proxy
+    /// of this collider will be updated automatically at the next timestep if this collider was
+    /// modified through [`ColliderSet::get_mut`](crate::geometry::ColliderSet::get_mut), and only
+    /// the rigid-bodies in contact with the modified cells are woken up.
+    ///
+    /// Returns `false` if the shape of this collider is not a heightfield.
+    #[cfg(feature = "dim3")]
+    pub fn update_heightfield_heights(
+        &mut self,
+        heights: impl IntoIterator<Item = ((usize, usize), f32)>,
+    ) -> bool {
+        let heightfield = match self.unshared_shape_mut::<HeightField>() {
+            Some(heightfield) => heightfield,
+            None => return false,
+        };
+
+        let deformed_aabb = heightfield.update_heights(heights);
+        self.mark_deformed(&deformed_aabb);
+        true
+    }
+
+    fn mark_deformed(&mut self, deformed_local_aabb: &AABB<f32>) {
+        if !self.changes.contains(ColliderChanges::SHAPE_DEFORMED) {
+            self.changes.insert(ColliderChanges::SHAPE_DEFORMED);
+            self.deformed_local_aabb = AABB::new_invalid();
+        }
+
+        self.deformed_local_aabb.merge(deformed_local_aabb);
+    }
+
     /// Compute the axis-aligned bounding box of this collider.
     pub fn compute_aabb(&self) -> AABB<f32> {
         self.shape.compute_aabb(&self.position)
     }
 
-    // pub(crate) fn compute_aabb_with_prediction(&self) -> AABB<f32> {
-    //     let aabb1 = self.shape.compute_aabb(&self.position);
-    //     let aabb2 = self.shape.compute_aabb(&self.predicted_position);
-    //     aabb1.merged(&aabb2)
-    // }
+    /// Compute the axis-aligned bounding box of this collider moving from its current position
+    /// to its position predicted at the end of the timestep.
+    ///
+    /// This AABB ignores the rotational motion in-between both positions.
+    pub fn compute_swept_aabb(&self) -> AABB<f32> {
+        let aabb1 = self.shape.compute_aabb(&self.position);
+        let aabb2 = self.shape.compute_aabb(&self.predicted_position);
+        aabb1.merged(&aabb2)
+    }
 
     /// Compute the local-space mass properties of this collider.
     pub fn mass_properties(&self) -> MassProperties {
-        self.shape.mass_properties(self.density)
+        self.shape.mass_properties(self.density())
     }
 }
 
@@ -291,17 +442,25 @@ impl Collider {
 #[cfg_attr(feature = "serde-serialize", derive(Serialize, Deserialize))]
 pub struct ColliderBuilder {
     /// The shape of the collider to be built.
-    pub shape: ColliderShape,
+    pub shape: SharedShape,
     /// The density of the collider to be built.
     density: Option<f32>,
-    /// The friction coefficient of the collider to be built.
-    pub friction: f32,
-    /// The restitution coefficient of the collider to be built.
-    pub restitution: f32,
+    /// The mass of the collider to be built.
+    mass: Option<f32>,
+    /// The material of the collider to be built.
+    pub material: Arc<ColliderMaterial>,
+    /// The total contact force magnitude above which contact force events are emitted.
+    pub contact_force_event_threshold: f32,
+    /// The prediction distance of the collider to be built, if it overrides the global one.
+    pub prediction_distance: Option<f32>,
+    /// The contact skin of the collider to be built.
+    pub contact_skin: f32,
     /// The position of this collider relative to the local frame of the rigid-body it is attached to.
     pub delta: Isometry<f32>,
     /// Is this collider a sensor?
     pub is_sensor: bool,
+    /// Is the collider to be built enabled?
+    pub enabled: bool,
     /// The user-data of the collider being built.
     pub user_data: u128,
     /// The collision groups for the collider being built.
@@ -312,14 +471,18 @@ pub struct ColliderBuilder {
 
 impl ColliderBuilder {
     /// Initialize a new collider builder with the given shape.
-    pub fn new(shape: ColliderShape) -> Self {
+    pub fn new(shape: SharedShape) -> Self {
         Self {
             shape,
             density: None,
-            friction: Self::default_friction(),
-            restitution: 0.0,
+            mass: None,
+            material: Arc::new(ColliderMaterial::new(Self::default_friction(), 0.0)),
+            contact_force_event_threshold: f32::MAX,
+            prediction_distance: None,
+            contact_skin: 0.0,
             delta: Isometry::identity(),
             is_sensor: false,
+            enabled: true,
             user_data: 0,
             collision_groups: InteractionGroups::all(),
             solver_groups: InteractionGroups::all(),
@@ -334,14 +497,14 @@ impl ColliderBuilder {
 
     /// Initialize a new collider builder with a ball shape defined by its radius.
     pub fn ball(radius: f32) -> Self {
-        Self::new(ColliderShape::ball(radius))
+        Self::new(SharedShape::ball(radius))
     }
 
     /// Initialize a new collider builder with a cylindrical shape defined by its half-height
     /// (along along the y axis) and its radius.
     #[cfg(feature = "dim3")]
     pub fn cylinder(half_height: f32, radius: f32) -> Self {
-        Self::new(ColliderShape::cylinder(half_height, radius))
+        Self::new(SharedShape::cylinder(half_height, radius))
     }
 
     /// Initialize a new collider builder with a rounded cylindrical shape defined by its half-height
@@ -349,7 +512,7 @@ impl ColliderBuilder {
     /// radius of the sphere used for dilating the cylinder).
     #[cfg(feature = "dim3")]
     pub fn round_cylinder(half_height: f32, radius: f32, border_radius: f32) -> Self {
-        Self::new(ColliderShape::round_cylinder(
+        Self::new(SharedShape::round_cylinder(
             half_height,
             radius,
             border_radius,
@@ -360,67 +523,160 @@ impl ColliderBuilder {
     /// (along along the y axis) and its basis radius.
     #[cfg(feature = "dim3")]
     pub fn cone(half_height: f32, radius: f32) -> Self {
-        Self::new(ColliderShape::cone(half_height, radius))
+        Self::new(SharedShape::cone(half_height, radius))
+    }
+
+    /// Initialize a new collider builder with a cylindrical shape aligned with the given axis.
+    ///
+    /// The cylinder is aligned by setting the initial orientation of the collider, relative to
+    /// the rigid-body it is attached to. This orientation is overwritten by `.rotation`
+    /// and `.position`, but not by `.translation`.
+    #[cfg(feature = "dim3")]
+    pub fn cylinder_along(axis: Unit<Vector<f32>>, half_height: f32, radius: f32) -> Self {
+        Self::cylinder(half_height, radius).align_y_with(axis)
+    }
+
+    /// Initialize a new collider builder with a rounded cylindrical shape aligned with the
+    /// given axis.
+    ///
+    /// See `cylinder_along` for details.
+    #[cfg(feature = "dim3")]
+    pub fn round_cylinder_along(
+        axis: Unit<Vector<f32>>,
+        half_height: f32,
+        radius: f32,
+        border_radius: f32,
+    ) -> Self {
+        Self::round_cylinder(half_height, radius, border_radius).align_y_with(axis)
+    }
+
+    /// Initialize a new collider builder with a cone shape with its apex toward the given axis.
+    ///
+    /// See `cylinder_along` for details.
+    #[cfg(feature = "dim3")]
+    pub fn cone_along(axis: Unit<Vector<f32>>, half_height: f32, radius: f32) -> Self {
+        Self::cone(half_height, radius).align_y_with(axis)
+    }
+
+    /// Sets the initial orientation of the collider to be created, such that its local `y` axis
+    /// points toward `axis`.
+    #[cfg(feature = "dim3")]
+    fn align_y_with(mut self, axis: Unit<Vector<f32>>) -> Self {
+        // NOTE: the rotation between two opposite directions is not unique.
+        self.delta.rotation = Rotation::rotation_between_axis(&Vector::y_axis(), &axis)
+            .unwrap_or_else(|| Rotation::from_axis_angle(&Vector::x_axis(), std::f32::consts::PI));
+        self
     }
 
     /// Initialize a new collider builder with a cuboid shape defined by its half-extents.
     #[cfg(feature = "dim2")]
     pub fn cuboid(hx: f32, hy: f32) -> Self {
-        Self::new(ColliderShape::cuboid(Vector::new(hx, hy)))
+        Self::new(SharedShape::cuboid(Vector::new(hx, hy)))
+    }
+
+    /// Initialize a new collider builder with a cuboid shape with rounded corners, defined by
+    /// its half-extents and the radius of the circle used for dilating the cuboid.
+    ///
+    /// The half-extents of the resulting shape are `hx + border_radius` and `hy + border_radius`.
+    #[cfg(feature = "dim2")]
+    pub fn round_cuboid(hx: f32, hy: f32, border_radius: f32) -> Self {
+        Self::new(SharedShape::round_cuboid(
+            Vector::new(hx, hy),
+            border_radius,
+        ))
     }
 
     /// Initialize a new collider builder with a capsule shape aligned with the `x` axis.
     pub fn capsule_x(half_height: f32, radius: f32) -> Self {
         let p = Point::from(Vector::x() * half_height);
-        Self::new(ColliderShape::capsule(-p, p, radius))
+        Self::new(SharedShape::capsule(-p, p, radius))
     }
 
     /// Initialize a new collider builder with a capsule shape aligned with the `y` axis.
     pub fn capsule_y(half_height: f32, radius: f32) -> Self {
         let p = Point::from(Vector::y() * half_height);
-        Self::new(ColliderShape::capsule(-p, p, radius))
+        Self::new(SharedShape::capsule(-p, p, radius))
     }
 
     /// Initialize a new collider builder with a capsule shape aligned with the `z` axis.
     #[cfg(feature = "dim3")]
     pub fn capsule_z(half_height: f32, radius: f32) -> Self {
         let p = Point::from(Vector::z() * half_height);
-        Self::new(ColliderShape::capsule(-p, p, radius))
+        Self::new(SharedShape::capsule(-p, p, radius))
+    }
+
+    /// Initialize a new collider builder with a capsule shape aligned with the given axis.
+    ///
+    /// Unlike `cylinder_along`, this doesn't modify the initial orientation of the collider,
+    /// because the segment of a capsule can have any direction.
+    pub fn capsule_along(axis: Unit<Vector<f32>>, half_height: f32, radius: f32) -> Self {
+        Self::new(SharedShape::capsule_along(axis, half_height, radius))
+    }
+
+    /// Initialize a new collider builder with a round cone shape defined by its endpoints
+    /// and the radius at each endpoint.
+    pub fn round_cone(a: Point<f32>, b: Point<f32>, radius_a: f32, radius_b: f32) -> Self {
+        Self::new(SharedShape::round_cone(a, b, radius_a, radius_b))
     }
 
     /// Initialize a new collider builder with a cuboid shape defined by its half-extents.
     #[cfg(feature = "dim3")]
     pub fn cuboid(hx: f32, hy: f32, hz: f32) -> Self {
-        Self::new(ColliderShape::cuboid(Vector::new(hx, hy, hz)))
+        Self::new(SharedShape::cuboid(Vector::new(hx, hy, hz)))
+    }
+
+    /// Initialize a new collider builder with a cuboid shape with rounded corners, defined by
+    /// its half-extents and the radius of the sphere used for dilating the cuboid.
+    ///
+    /// The half-extents of the resulting shape are `hx + border_radius`, `hy + border_radius`,
+    /// and `hz + border_radius`.
+    #[cfg(feature = "dim3")]
+    pub fn round_cuboid(hx: f32, hy: f32, hz: f32, border_radius: f32) -> Self {
+        Self::new(SharedShape::round_cuboid(
+            Vector::new(hx, hy, hz),
+            border_radius,
+        ))
     }
 
     /// Initializes a collider builder with a segment shape.
     pub fn segment(a: Point<f32>, b: Point<f32>) -> Self {
-        Self::new(ColliderShape::segment(a, b))
+        Self::new(SharedShape::segment(a, b))
+    }
+
+    /// Initializes a collider builder with a segment shape with rounded corners.
+    ///
+    /// This is the same as a capsule with the segment `[a, b]` as its principal axis.
+    pub fn round_segment(a: Point<f32>, b: Point<f32>, border_radius: f32) -> Self {
+        Self::new(SharedShape::capsule(a, b, border_radius))
     }
 
     /// Initializes a collider builder with a triangle shape.
     pub fn triangle(a: Point<f32>, b: Point<f32>, c: Point<f32>) -> Self {
-        Self::new(ColliderShape::triangle(a, b, c))
+        Self::new(SharedShape::triangle(a, b, c))
+    }
+
+    /// Initializes a collider builder with a triangle shape with rounded corners.
+    pub fn round_triangle(a: Point<f32>, b: Point<f32>, c: Point<f32>, border_radius: f32) -> Self {
+        Self::new(SharedShape::round_triangle(a, b, c, border_radius))
     }
 
     /// Initializes a collider builder with a triangle mesh shape defined by its vertex and index buffers.
     pub fn trimesh(vertices: Vec<Point<f32>>, indices: Vec<Point3<u32>>) -> Self {
-        Self::new(ColliderShape::trimesh(vertices, indices))
+        Self::new(SharedShape::trimesh(vertices, indices))
     }
 
     /// Initializes a collider builder with a heightfield shape defined by its set of height and a scale
     /// factor along each coordinate axis.
     #[cfg(feature = "dim2")]
     pub fn heightfield(heights: na::DVector<f32>, scale: Vector<f32>) -> Self {
-        Self::new(ColliderShape::heightfield(heights, scale))
+        Self::new(SharedShape::heightfield(heights, scale))
     }
 
     /// Initializes a collider builder with a heightfield shape defined by its set of height and a scale
     /// factor along each coordinate axis.
     #[cfg(feature = "dim3")]
     pub fn heightfield(heights: na::DMatrix<f32>, scale: Vector<f32>) -> Self {
-        Self::new(ColliderShape::heightfield(heights, scale))
+        Self::new(SharedShape::heightfield(heights, scale))
     }
 
     /// The default friction coefficient used by the collider builder.
@@ -458,21 +714,97 @@ impl ColliderBuilder {
         self
     }
 
+    /// Sets whether or not the collider built by this builder is enabled.
+    pub fn enabled(mut self, enabled: bool) -> Self {
+        self.enabled = enabled;
+        self
+    }
+
+    /// Sets the total contact force magnitude above which contact force events are emitted
+    /// for the contacts involving the collider this builder will build.
+    pub fn contact_force_event_threshold(mut self, threshold: f32) -> Self {
+        self.contact_force_event_threshold = threshold;
+        self
+    }
+
+    /// Sets the linear and angular velocities, expressed in local-space, of the surface of
+    /// the collider this builder will build.
+    ///
+    /// See [`ColliderMaterial::surface_linvel`] for details.
+    pub fn surface_velocity(mut self, linvel: Vector<f32>, angvel: AngVector<f32>) -> Self {
+        let material = Arc::make_mut(&mut self.material);
+        material.surface_linvel = linvel;
+        material.surface_angvel = angvel;
+        self
+    }
+
+    /// Sets the maximal distance separating the collider this builder will build from another
+    /// collider that will generate predictive contacts.
+    ///
+    /// See [`Collider::set_prediction_distance`] for details.
+    pub fn prediction_distance(mut self, distance: f32) -> Self {
+        self.prediction_distance = Some(distance);
+        self
+    }
+
+    /// Sets the thickness of the contact skin of the collider this builder will build.
+    ///
+    /// See [`Collider::set_contact_skin`] for details.
+    pub fn contact_skin(mut self, skin: f32) -> Self {
+        self.contact_skin = skin;
+        self
+    }
+
     /// Sets the friction coefficient of the collider this builder will build.
     pub fn friction(mut self, friction: f32) -> Self {
-        self.friction = friction;
+        Arc::make_mut(&mut self.material).friction = friction;
+        self
+    }
+
+    /// Sets the rule to be used to combine two friction coefficients in a contact.
+    pub fn friction_combine_rule(mut self, rule: CoefficientCombineRule) -> Self {
+        Arc::make_mut(&mut self.material).friction_combine_rule = rule;
         self
     }
 
     /// Sets the restitution coefficient of the collider this builder will build.
     pub fn restitution(mut self, restitution: f32) -> Self {
-        self.restitution = restitution;
+        Arc::make_mut(&mut self.material).restitution = restitution;
+        self
+    }
+
+    /// Sets the rule to be used to combine two restitution coefficients in a contact.
+    pub fn restitution_combine_rule(mut self, rule: CoefficientCombineRule) -> Self {
+        Arc::make_mut(&mut self.material).restitution_combine_rule = rule;
+        self
+    }
+
+    /// Sets the material of the collider this builder will build.
+    ///
+    /// The material is shared by all the colliders built with the same `Arc`. Setting the
+    /// friction, restitution, or surface velocity of this builder afterwards doesn't modify
+    /// the shared material.
+    pub fn material(mut self, material: Arc<ColliderMaterial>) -> Self {
+        self.material = material;
         self
     }
 
     /// Sets the density of the collider this builder will build.
+    ///
+    /// This replaces any mass previously set with `self.mass`.
     pub fn density(mut self, density: f32) -> Self {
         self.density = Some(density);
+        self.mass = None;
+        self
+    }
+
+    /// Sets the mass of the collider this builder will build.
+    ///
+    /// The density of the collider will be computed so that its total mass is equal to `mass`.
+    /// This replaces any density previously set with `self.density`.
+    pub fn mass(mut self, mass: f32) -> Self {
+        self.mass = Some(mass);
+        self.density = None;
         self
     }
 
@@ -518,22 +850,152 @@ impl ColliderBuilder {
 
     /// Builds a new collider attached to the given rigid-body.
     pub fn build(&self) -> Collider {
-        let density = self.get_density();
+        let mass_props = match self.mass {
+            Some(mass) => ColliderMassProperties::Mass(mass),
+            None => ColliderMassProperties::Density(self.get_density()),
+        };
 
         Collider {
             shape: self.shape.clone(),
-            density,
-            friction: self.friction,
-            restitution: self.restitution,
+            unit_mass_properties: self.shape.mass_properties(1.0),
+            mass_props,
+            material: self.material.clone(),
+            contact_force_event_threshold: self.contact_force_event_threshold,
+            prediction_distance: self.prediction_distance,
+            contact_skin: self.contact_skin,
             delta: self.delta,
             is_sensor: self.is_sensor,
+            enabled: self.enabled,
+            parent_enabled: true,
             parent: RigidBodySet::invalid_handle(),
             position: Isometry::identity(),
             predicted_position: Isometry::identity(),
             proxy_index: crate::INVALID_USIZE,
+            changes: ColliderChanges::empty(),
+            deformed_local_aabb: AABB::new_invalid(),
             collision_groups: self.collision_groups,
             solver_groups: self.solver_groups,
             user_data: self.user_data,
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::{Collider, ColliderBuilder, SharedShape};
+    use crate::math::{Isometry, Point, Vector};
+
+    fn all_shapes() -> Vec<SharedShape> {
+        let (a, b, c) = (
+            Point::origin(),
+            Point::from(Vector::x()),
+            Point::from(Vector::y()),
+        );
+        #[allow(unused_mut)]
+        let mut shapes = vec![
+            SharedShape::ball(1.0),
+            SharedShape::cuboid(Vector::repeat(1.0)),
+            SharedShape::round_cuboid(Vector::repeat(1.0), 0.1),
+            SharedShape::capsule(a, b, 0.5),
+            SharedShape::segment(a, b),
+            SharedShape::triangle(a, b, c),
+            SharedShape::round_triangle(a, b, c, 0.1),
+            SharedShape::round_cone(a, b, 0.5, 0.2),
+            SharedShape::trimesh(
+                vec![a, b, c, b + c.coords],
+                vec![na::Point3::new(0, 1, 2), na::Point3::new(1, 3, 2)],
+            ),
+        ];
+        #[cfg(feature = "dim2")]
+        shapes.push(SharedShape::heightfield(
+            na::DVector::from_element(5, 1.0),
+            Vector::repeat(2.0),
+        ));
+        #[cfg(feature = "dim3")]
+        {
+            shapes.push(SharedShape::heightfield(
+                na::DMatrix::from_element(5, 5, 1.0),
+                Vector::repeat(2.0),
+            ));
+            shapes.push(SharedShape::cylinder(1.0, 0.5));
+            shapes.push(SharedShape::round_cylinder(1.0, 0.5, 0.1));
+            shapes.push(SharedShape::cone(1.0, 0.5));
+        }
+        shapes
+    }
+
+    #[test]
+    fn collider_serde_roundtrip_all_shapes() {
+        let pos = Isometry::new(Vector::repeat(1.0), na::zero());
+
+        for shape in all_shapes() {
+            let co = ColliderBuilder::new(shape.clone())
+                .friction(0.3)
+                .sensor(true)
+                .build();
+            let bytes = bincode::serialize(&co).unwrap();
+            let co2: Collider = bincode::deserialize(&bytes).unwrap();
+
+            assert_eq!(
+                co.shape().shape_type() as i32,
+                co2.shape().shape_type() as i32
+            );
+            assert_eq!(
+                co.shape().compute_aabb(&pos),
+                co2.shape().compute_aabb(&pos)
+            );
+            assert_eq!(co2.material().friction, 0.3);
+            assert!(co2.is_sensor());
+
+            // The collider definition can be stored as data too.
+            let builder = ColliderBuilder::new(shape).density(2.0);
+            let bytes = bincode::serialize(&builder).unwrap();
+            let builder2: ColliderBuilder = bincode::deserialize(&bytes).unwrap();
+            assert_eq!(builder2.build().mass(), builder.build().mass());
+        }
+    }
+
+    #[test]
+    fn colliders_share_their_shape() {
+        let shape = SharedShape::trimesh(
+            vec![
+                Point::origin(),
+                Point::from(Vector::x()),
+                Point::from(Vector::y()),
+                Point::from(Vector::x() + Vector::y()),
+            ],
+            vec![na::Point3::new(0, 1, 2), na::Point3::new(1, 3, 2)],
+        );
+        let builder = ColliderBuilder::new(shape.clone());
+        let co1 = builder.build();
+        let co2 = builder.build();
+
+        assert!(co1.shared_shape().ptr_eq(&shape));
+        assert!(co2.shared_shape().ptr_eq(&shape));
+        assert!(!SharedShape::ball(1.0).ptr_eq(&SharedShape::ball(1.0)));
+    }
+
+    #[test]
+    #[cfg(feature = "dim3")]
+    fn colliders_along_axis() {
+        let axes = [
+            Vector::y_axis(),
+            -Vector::y_axis(),
+            na::Unit::new_normalize(Vector::new(1.0, -2.0, 3.0)),
+        ];
+
+        for axis in axes.iter() {
+            let builders = [
+                ColliderBuilder::cylinder_along(*axis, 1.0, 0.5),
+                ColliderBuilder::round_cylinder_along(*axis, 1.0, 0.5, 0.1),
+                ColliderBuilder::cone_along(*axis, 1.0, 0.5),
+            ];
+
+            for builder in builders.iter() {
+                let collider = builder.clone().translation(1.0, 2.0, 3.0).build();
+                let y = collider.position_wrt_parent().rotation * Vector::y();
+                assert!((y - axis.into_inner()).norm() < 1.0e-5);
+            }
+        }
+    }
+}
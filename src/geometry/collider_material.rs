@@ -0,0 +1,93 @@
+use crate::dynamics::CoefficientCombineRule;
+use crate::math::{AngVector, Vector};
+
+/// The surface properties of a collider: friction, restitution, and surface velocity.
+///
+/// A material is attached to a collider through an `Arc`, so the same material can be shared by
+/// any number of colliders, e.g., by all the tiles of a floor. All the colliders sharing a material
+/// can then be switched to another material at once with
+/// [`ColliderSet::replace_material`](crate::geometry::ColliderSet::replace_material):
+///
+/// ```ignore
+/// let stone = Arc::new(ColliderMaterial::new(0.9, 0.0));
+/// let ice = Arc::new(ColliderMaterial::new(0.02, 0.0));
+/// for tile in floor_tiles {
+///     let collider = ColliderBuilder::cuboid(0.5, 0.5).material(stone.clone()).build();
+///     colliders.insert(collider, ground, &mut bodies);
+/// }
+/// // Later, freeze the whole floor.
+/// colliders.replace_material(&stone, ice);
+/// ```
+///
+/// The sharing is not preserved by serialization: each collider of a deserialized collider set
+/// has its own copy of its material, so `ColliderSet::replace_material` only modifies one of them.
+/// Give the same `Arc` to `Collider::set_material` of these colliders to share a material again.
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde-serialize", derive(Serialize, Deserialize))]
+pub struct ColliderMaterial {
+    /// The friction coefficient of this material.
+    pub friction: f32,
+    /// The restitution coefficient of this material.
+    pub restitution: f32,
+    /// The rule used to combine the friction coefficient of this material with the one of the
+    /// other collider involved in a contact.
+    pub friction_combine_rule: CoefficientCombineRule,
+    /// The rule used to combine the restitution coefficient of this material with the one of the
+    /// other collider involved in a contact.
+    pub restitution_combine_rule: CoefficientCombineRule,
+    /// The linear velocity of the surface of the colliders with this material, expressed in
+    /// their local-space.
+    ///
+    /// The surface velocity doesn't move the collider. Instead, it is the velocity the friction
+    /// forces attempt to give to the objects touching this collider, relative to this
+    /// collider. This can be used to simulate conveyor belts or treadmills.
+    pub surface_linvel: Vector<f32>,
+    /// The angular velocity of the surface of the colliders with this material, expressed in
+    /// their local-space.
+    ///
+    /// See `surface_linvel` for details.
+    pub surface_angvel: AngVector<f32>,
+}
+
+impl ColliderMaterial {
+    /// Initializes a material with the given friction and restitution coefficients.
+    ///
+    /// The coefficients are combined with the `Average` rule, and the surface is motionless.
+    pub fn new(friction: f32, restitution: f32) -> Self {
+        Self {
+            friction,
+            restitution,
+            friction_combine_rule: CoefficientCombineRule::Average,
+            restitution_combine_rule: CoefficientCombineRule::Average,
+            surface_linvel: Vector::zeros(),
+            surface_angvel: na::zero(),
+        }
+    }
+
+    /// Sets the rule used to combine the friction coefficient of this material.
+    pub fn friction_combine_rule(mut self, rule: CoefficientCombineRule) -> Self {
+        self.friction_combine_rule = rule;
+        self
+    }
+
+    /// Sets the rule used to combine the restitution coefficient of this material.
+    pub fn restitution_combine_rule(mut self, rule: CoefficientCombineRule) -> Self {
+        self.restitution_combine_rule = rule;
+        self
+    }
+
+    /// Sets the linear and angular velocities, expressed in local-space, of the surface of the
+    /// colliders with this material.
+    pub fn surface_velocity(mut self, linvel: Vector<f32>, angvel: AngVector<f32>) -> Self {
+        self.surface_linvel = linvel;
+        self.surface_angvel = angvel;
+        self
+    }
+}
+
+impl Default for ColliderMaterial {
+    /// A material with a friction coefficient of `0.5` and no restitution.
+    fn default() -> Self {
+        Self::new(0.5, 0.0)
+    }
+}
@@ -1,10 +1,15 @@
 use crate::data::arena::Arena;
 use crate::data::pubsub::PubSub;
 use crate::dynamics::{RigidBodyHandle, RigidBodySet};
-use crate::geometry::Collider;
+use crate::geometry::{Collider, ColliderChanges, ColliderMaterial};
 use std::ops::{Index, IndexMut};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
 
 /// The unique identifier of a collider added to a collider set.
+///
+/// Handles are generational: the handle of a removed collider is never valid again, even if
+/// its storage slot is reused by another collider.
 pub type ColliderHandle = crate::data::arena::Index;
 
 #[derive(Copy, Clone, Debug)]
@@ -14,29 +19,107 @@ pub(crate) struct RemovedCollider {
     pub(crate) proxy_index: usize,
 }
 
+/// Generates an identifier that no other collider set has.
+fn new_collider_set_id() -> u64 {
+    static NEXT_ID: AtomicU64 = AtomicU64::new(1);
+    NEXT_ID.fetch_add(1, Ordering::Relaxed)
+}
+
 #[cfg_attr(feature = "serde-serialize", derive(Serialize, Deserialize))]
-#[derive(Clone)]
 /// A set of colliders that can be handled by a physics `World`.
 pub struct ColliderSet {
+    // Unique to each set, including its clones and deserialized copies, so the structures
+    // tracking its `version`, like the `QueryPipeline`, can't mistake one set for another.
+    #[cfg_attr(
+        feature = "serde-serialize",
+        serde(skip, default = "new_collider_set_id")
+    )]
+    pub(crate) id: u64,
     pub(crate) removed_colliders: PubSub<RemovedCollider>,
     pub(crate) colliders: Arena<Collider>,
+    #[cfg_attr(feature = "serde-serialize", serde(skip))]
+    pub(crate) modified_colliders: Vec<ColliderHandle>,
+    // Bumped each time a collider is inserted, removed, or moved.
+    pub(crate) version: u64,
+    // The version of the last insertion or removal.
+    pub(crate) topology_version: u64,
+    // The colliders moved since `moved_colliders_start`, with the version of each move.
+    #[cfg_attr(feature = "serde-serialize", serde(skip))]
+    pub(crate) moved_colliders: Vec<(u64, ColliderHandle)>,
+    // The version of the most recent move no longer recorded by `moved_colliders`.
+    pub(crate) moved_colliders_start: u64,
+}
+
+impl Clone for ColliderSet {
+    fn clone(&self) -> Self {
+        Self {
+            id: new_collider_set_id(),
+            removed_colliders: self.removed_colliders.clone(),
+            colliders: self.colliders.clone(),
+            modified_colliders: self.modified_colliders.clone(),
+            version: self.version,
+            topology_version: self.topology_version,
+            moved_colliders: self.moved_colliders.clone(),
+            moved_colliders_start: self.moved_colliders_start,
+        }
+    }
 }
 
 impl ColliderSet {
     /// Create a new empty set of colliders.
     pub fn new() -> Self {
         ColliderSet {
+            id: new_collider_set_id(),
             removed_colliders: PubSub::new(),
             colliders: Arena::new(),
+            modified_colliders: Vec::new(),
+            version: 0,
+            topology_version: 0,
+            moved_colliders: Vec::new(),
+            moved_colliders_start: 0,
         }
     }
 
+    /// Create a new empty set of colliders, able to hold `capacity` colliders
+    /// without reallocating.
+    pub fn with_capacity(capacity: usize) -> Self {
+        ColliderSet {
+            id: new_collider_set_id(),
+            removed_colliders: PubSub::new(),
+            colliders: Arena::with_capacity(capacity),
+            modified_colliders: Vec::with_capacity(capacity),
+            version: 0,
+            topology_version: 0,
+            moved_colliders: Vec::new(),
+            moved_colliders_start: 0,
+        }
+    }
+
+    /// The number of colliders this set can hold without reallocating.
+    pub fn capacity(&self) -> usize {
+        self.colliders.capacity()
+    }
+
+    /// Reserves enough capacity for inserting at least `additional` more colliders
+    /// into this set without reallocating.
+    pub fn reserve(&mut self, additional: usize) {
+        let available = self.colliders.capacity() - self.colliders.len();
+        if additional > available {
+            self.colliders.reserve(additional - available);
+        }
+        self.modified_colliders.reserve(additional);
+    }
+
     /// An always-invalid collider handle.
     pub fn invalid_handle() -> ColliderHandle {
         ColliderHandle::from_raw_parts(crate::INVALID_USIZE, crate::INVALID_U64)
     }
 
     /// Iterate through all the colliders on this set.
+    ///
+    /// The iteration order only depends on the sequence of insertions and removals performed
+    /// on this set, so it is the same for two sets built the same way, and for a set restored
+    /// from a snapshot.
     pub fn iter(&self) -> impl ExactSizeIterator<Item = (ColliderHandle, &Collider)> {
         self.colliders.iter()
     }
@@ -52,7 +135,24 @@ impl ColliderSet {
     }
 
     /// Inserts a new collider to this set and retrieve its handle.
+    ///
+    /// This is the same as `ColliderSet::insert_with_parent`.
     pub fn insert(
+        &mut self,
+        coll: Collider,
+        parent_handle: RigidBodyHandle,
+        bodies: &mut RigidBodySet,
+    ) -> ColliderHandle {
+        self.insert_with_parent(coll, parent_handle, bodies)
+    }
+
+    /// Inserts a new collider to this set, attached to the given rigid-body, and retrieve its handle.
+    ///
+    /// The collider can be attached to a rigid-body at any time, even if this rigid-body is already
+    /// being simulated or is sleeping. The mass properties of the rigid-body are updated right away,
+    /// and the collider is added to the broad-phase at the next timestep. Sleeping rigid-bodies are
+    /// not woken up.
+    pub fn insert_with_parent(
         &mut self,
         mut coll: Collider,
         parent_handle: RigidBodyHandle,
@@ -69,14 +169,128 @@ impl ColliderSet {
         let parent = bodies
             .get_mut(parent_handle)
             .expect("Parent rigid body not found.");
+        coll.parent_enabled = parent.is_enabled();
         coll.position = parent.position * coll.delta;
         coll.predicted_position = parent.predicted_position * coll.delta;
         let handle = self.colliders.insert(coll);
         let coll = self.colliders.get(handle).unwrap();
         parent.add_collider(handle, &coll);
+        self.version += 1;
+        self.topology_version = self.version;
         handle
     }
 
+    /// Inserts many colliders at once, each attached to its given rigid-body, and retrieve their
+    /// handles in the same order.
+    ///
+    /// This is equivalent to inserting each collider with `insert_with_parent`, except that the
+    /// mass properties of the colliders are computed in parallel if the `parallel` feature is
+    /// enabled, and the mass properties of each rigid-body are updated only once per batch. The
+    /// broad-phase is not affected: the colliders are added to it at the next timestep, exactly as
+    /// if they were inserted one by one.
+    pub fn insert_batch(
+        &mut self,
+        colliders: impl IntoIterator<Item = (Collider, RigidBodyHandle)>,
+        bodies: &mut RigidBodySet,
+    ) -> Vec<ColliderHandle> {
+        let colliders: Vec<_> = colliders.into_iter().collect();
+        let compute_mass_properties = |(coll, _): &(Collider, RigidBodyHandle)| {
+            coll.mass_properties()
+                .transform_by(coll.position_wrt_parent())
+        };
+
+        #[cfg(not(feature = "parallel"))]
+        let mass_properties: Vec<_> = colliders.iter().map(compute_mass_properties).collect();
+        #[cfg(feature = "parallel")]
+        let mass_properties: Vec<_> = {
+            use rayon::prelude::*;
+            colliders.par_iter().map(compute_mass_properties).collect()
+        };
+
+        self.reserve(colliders.len());
+        let mut parents: Vec<RigidBodyHandle> = Vec::new();
+        let handles = colliders
+            .into_iter()
+            .zip(mass_properties)
+            .map(|((mut coll, parent_handle), mass_properties)| {
+                coll.reset_internal_references();
+                coll.parent = parent_handle;
+
+                let parent = bodies
+                    .get_mut(parent_handle)
+                    .expect("Parent rigid body not found.");
+                coll.parent_enabled = parent.is_enabled();
+                coll.position = parent.position * coll.delta;
+                coll.predicted_position = parent.predicted_position * coll.delta;
+                let handle = self.colliders.insert(coll);
+                parent.add_collider_without_update(handle, mass_properties);
+
+                // NOTE: a rigid-body may appear several times here if its colliders are not
+                // contiguous in the batch. This only results in redundant updates.
+                if parents.last() != Some(&parent_handle) {
+                    parents.push(parent_handle);
+                }
+
+                handle
+            })
+            .collect();
+
+        for parent_handle in parents {
+            bodies
+                .get_mut_internal(parent_handle)
+                .unwrap()
+                .update_mass_properties();
+        }
+
+        self.version += 1;
+        self.topology_version = self.version;
+        handles
+    }
+
+    /// Records that the position of the given collider changed.
+    pub(crate) fn mark_collider_moved(&mut self, handle: ColliderHandle) {
+        self.version += 1;
+        self.moved_colliders.push((self.version, handle));
+
+        // Bound the size of the log. Its oldest half is forgotten, forcing the consumers
+        // that didn't read it yet to refit all the colliders.
+        if self.moved_colliders.len() > 2 * self.colliders.len() + 16 {
+            let num_forgotten = self.moved_colliders.len() / 2;
+            self.moved_colliders_start = self.moved_colliders[num_forgotten - 1].0;
+            let _ = self.moved_colliders.drain(..num_forgotten);
+        }
+    }
+
+    /// The colliders moved after the given version of this set.
+    ///
+    /// Returns `None` if some of these moves are no longer recorded.
+    pub(crate) fn moved_colliders_since(
+        &self,
+        version: u64,
+    ) -> Option<impl Iterator<Item = ColliderHandle> + '_> {
+        if self.moved_colliders_start > version {
+            return None;
+        }
+
+        // NOTE: the versions are unique and sorted.
+        let first = match self
+            .moved_colliders
+            .binary_search_by_key(&(version + 1), |(v, _)| *v)
+        {
+            Ok(i) | Err(i) => i,
+        };
+        Some(self.moved_colliders[first..].iter().map(|(_, h)| *h))
+    }
+
+    /// Resets the modification flags of all the colliders modified since the last call to this method.
+    pub(crate) fn clear_modified_colliders(&mut self) {
+        for handle in self.modified_colliders.drain(..) {
+            if let Some(collider) = self.colliders.get_mut(handle) {
+                collider.changes = ColliderChanges::empty();
+            }
+        }
+    }
+
     /// Remove a collider from this set and update its parent accordingly.
     ///
     /// If `wake_up` is `true`, the rigid-body the removed collider is attached to
@@ -88,6 +302,8 @@ impl ColliderSet {
         wake_up: bool,
     ) -> Option<Collider> {
         let collider = self.colliders.remove(handle)?;
+        self.version += 1;
+        self.topology_version = self.version;
 
         /*
          * Delete the collider from its parent body.
@@ -95,7 +311,7 @@ impl ColliderSet {
         // NOTE: we use `get_mut` instead of `get_mut_internal` so that the
         // modification flag is updated properly.
         if let Some(parent) = bodies.get_mut(collider.parent) {
-            parent.remove_collider_internal(handle, &collider);
+            parent.remove_collider_internal(handle);
 
             if wake_up {
                 bodies.wake_up(collider.parent, true);
@@ -138,7 +354,12 @@ impl ColliderSet {
     /// Using this is discouraged in favor of `self.get_mut(handle)` which does not
     /// suffer form the ABA problem.
     pub fn get_unknown_gen_mut(&mut self, i: usize) -> Option<(&mut Collider, ColliderHandle)> {
-        self.colliders.get_unknown_gen_mut(i)
+        let result = self.colliders.get_unknown_gen_mut(i)?;
+        if !result.0.changes.contains(ColliderChanges::MODIFIED) {
+            result.0.changes = ColliderChanges::MODIFIED;
+            self.modified_colliders.push(result.1);
+        }
+        Some(result)
     }
 
     /// Get the collider with the given handle.
@@ -148,7 +369,38 @@ impl ColliderSet {
 
     /// Gets a mutable reference to the collider with the given handle.
     pub fn get_mut(&mut self, handle: ColliderHandle) -> Option<&mut Collider> {
-        self.colliders.get_mut(handle)
+        let result = self.colliders.get_mut(handle)?;
+        if !result.changes.contains(ColliderChanges::MODIFIED) {
+            result.changes = ColliderChanges::MODIFIED;
+            self.modified_colliders.push(handle);
+        }
+        Some(result)
+    }
+
+    /// Replaces the material of all the colliders sharing the material `old` by `new`.
+    ///
+    /// Two materials are shared if they are the same `Arc` allocation, e.g., if `old` was given to
+    /// the `ColliderBuilder::material` of all these colliders. Returns the number of colliders that were modified.
+    /// As with `Collider::set_material`, the contacts involving these colliders are updated at
+    /// the next timestep.
+    ///
+    /// This goes through all the colliders of this set, so its cost is linear in the total number
+    /// of colliders, not in the number of colliders sharing `old`.
+    pub fn replace_material(
+        &mut self,
+        old: &Arc<ColliderMaterial>,
+        new: Arc<ColliderMaterial>,
+    ) -> usize {
+        let mut num_replaced = 0;
+
+        for (_, collider) in self.colliders.iter_mut() {
+            if Arc::ptr_eq(collider.shared_material(), old) {
+                collider.set_material(new.clone());
+                num_replaced += 1;
+            }
+        }
+
+        num_replaced
     }
 
     // pub(crate) fn get2_mut_internal(
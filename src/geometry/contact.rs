@@ -1,8 +1,9 @@
 use crate::data::MaybeSerializableData;
-use crate::dynamics::BodyPair;
+use crate::dynamics::{BodyPair, CoefficientCombineRule};
 use crate::geometry::contact_generator::{ContactGeneratorWorkspace, ContactPhase};
-use crate::geometry::{Collider, ColliderPair, ColliderSet};
-use crate::math::{Isometry, Point, Vector};
+use crate::geometry::{Collider, ColliderHandle, ColliderPair, ColliderSet};
+use crate::math::{AngVector, Isometry, Point, Vector};
+use crate::utils::WCross;
 #[cfg(feature = "simd-is-enabled")]
 use {
     crate::math::{SimdFloat, SIMD_WIDTH},
@@ -114,7 +115,8 @@ pub struct Contact {
     pub fid2: u8,
     /// The distance between the two colliders along the contact normal.
     ///
-    /// If this is negative, the colliders are penetrating.
+    /// If this is negative, the colliders are penetrating. This distance, as well as the
+    /// contact points, account for the contact skins of both colliders.
     pub dist: f32,
 }
 
@@ -158,17 +160,32 @@ impl Contact {
         self.dist = contact.dist;
     }
 
-    // pub(crate) fn swap(self) -> Self {
-    //     Self {
-    //         local_p1: self.local_p2,
-    //         local_p2: self.local_p1,
-    //         impulse: self.impulse,
-    //         tangent_impulse: self.tangent_impulse,
-    //         fid1: self.fid2,
-    //         fid2: self.fid1,
-    //         dist: self.dist,
-    //     }
-    // }
+    pub(crate) fn swap(self) -> Self {
+        Self {
+            local_p1: self.local_p2,
+            local_p2: self.local_p1,
+            impulse: self.impulse,
+            tangent_impulse: self.tangent_impulse,
+            fid1: self.fid2,
+            fid2: self.fid1,
+            dist: self.dist,
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+/// A single contact between two colliders, expressed in world-space.
+pub struct WorldContact {
+    /// The contact point on the first collider.
+    pub point1: Point<f32>,
+    /// The contact point on the second collider.
+    pub point2: Point<f32>,
+    /// The distance between the two colliders along the contact normal.
+    ///
+    /// If this is negative, the colliders are penetrating.
+    pub dist: f32,
+    /// The impulse, along the contact normal, applied by this contact to the first collider's rigid-body.
+    pub impulse: f32,
 }
 
 #[cfg_attr(feature = "serde-serialize", derive(Serialize, Deserialize))]
@@ -213,6 +230,21 @@ impl ContactPair {
         false
     }
 
+    /// Resets to zero the cached impulses of all the contacts between the two colliders.
+    pub fn clear_impulses(&mut self) {
+        for manifold in &mut self.manifolds {
+            manifold.clear_impulses();
+        }
+    }
+
+    /// Removes all the contacts between the two colliders.
+    pub(crate) fn clear_contacts(&mut self) {
+        for manifold in &mut self.manifolds {
+            manifold.points.clear();
+            manifold.num_active_contacts = 0;
+        }
+    }
+
     pub(crate) fn single_manifold<'a, 'b>(
         &'a mut self,
         colliders: &'b ColliderSet,
@@ -271,8 +303,13 @@ pub struct ContactManifold {
     /// Active contacts are these that may result in contact forces.
     pub num_active_contacts: usize,
     /// The contact normal of all the contacts of this manifold, expressed in the local space of the first collider.
+    ///
+    /// This normal always points from the first collider of `self.pair` toward the second one.
     pub local_n1: Vector<f32>,
     /// The contact normal of all the contacts of this manifold, expressed in the local space of the second collider.
+    ///
+    /// This normal always points from the second collider of `self.pair` toward the first one, i.e., it is the
+    /// opposite of `self.local_n1` once both are expressed in world-space.
     pub local_n2: Vector<f32>,
     /// The contact kinematics of all the contacts of this manifold.
     pub kinematics: ContactKinematics,
@@ -280,19 +317,38 @@ pub struct ContactManifold {
     /// The pair of body involved in this contact manifold.
     pub body_pair: BodyPair,
     /// The pair of colliders involved in this contact manifold.
+    ///
+    /// All the geometric data of this manifold, e.g., `self.local_n1` or the `local_p1` of its contacts, refer
+    /// to `self.pair.collider1` as the first collider. Because the contact generators may swap the colliders,
+    /// this isn't necessarily the first collider of the `ContactPair` containing this manifold.
     pub pair: ColliderPair,
     /// The pair of subshapes involved in this contact manifold.
     pub subshape_index_pair: (usize, usize),
     pub(crate) warmstart_multiplier: f32,
+    // The contact skins of both colliders, as applied to the contacts of this manifold.
+    pub(crate) contact_skin1: f32,
+    pub(crate) contact_skin2: f32,
     // The two following are set by the constraints solver.
     pub(crate) constraint_index: usize,
     pub(crate) position_constraint_index: usize,
     // We put the following fields here to avoids reading the colliders inside of the
     // contact preparation method.
     /// The friction coefficient for of all the contacts on this contact manifold.
+    ///
+    /// This is combined from the materials of both colliders at each timestep.
     pub friction: f32,
     /// The restitution coefficient for all the contacts on this contact manifold.
+    ///
+    /// This is combined from the materials of both colliders at each timestep.
     pub restitution: f32,
+    /// The linear surface velocity of the first collider, expressed in its local-space.
+    pub surface_linvel1: Vector<f32>,
+    /// The angular surface velocity of the first collider, expressed in its local-space.
+    pub surface_angvel1: AngVector<f32>,
+    /// The linear surface velocity of the second collider, expressed in its local-space.
+    pub surface_linvel2: Vector<f32>,
+    /// The angular surface velocity of the second collider, expressed in its local-space.
+    pub surface_angvel2: AngVector<f32>,
     /// The relative position between the first collider and its parent at the time the
     /// contact points were generated.
     pub delta1: Isometry<f32>,
@@ -327,8 +383,14 @@ impl ContactManifold {
             body_pair,
             kinematics: ContactKinematics::default(),
             warmstart_multiplier: Self::min_warmstart_multiplier(),
+            contact_skin1: 0.0,
+            contact_skin2: 0.0,
             friction,
             restitution,
+            surface_linvel1: Vector::zeros(),
+            surface_angvel1: na::zero(),
+            surface_linvel2: Vector::zeros(),
+            surface_angvel2: na::zero(),
             delta1,
             delta2,
             constraint_index: 0,
@@ -351,8 +413,14 @@ impl ContactManifold {
             pair: self.pair,
             subshape_index_pair: self.subshape_index_pair,
             warmstart_multiplier: self.warmstart_multiplier,
+            contact_skin1: self.contact_skin1,
+            contact_skin2: self.contact_skin2,
             friction: self.friction,
             restitution: self.restitution,
+            surface_linvel1: self.surface_linvel1,
+            surface_angvel1: self.surface_angvel1,
+            surface_linvel2: self.surface_linvel2,
+            surface_angvel2: self.surface_angvel2,
             delta1: self.delta1,
             delta2: self.delta2,
             constraint_index: self.constraint_index,
@@ -378,16 +446,83 @@ impl ContactManifold {
         subshape2: usize,
         solver_flags: SolverFlags,
     ) -> Self {
-        Self::new(
+        let mut manifold = Self::new(
             pair,
             (subshape1, subshape2),
             BodyPair::new(coll1.parent, coll2.parent),
             *coll1.position_wrt_parent(),
             *coll2.position_wrt_parent(),
-            (coll1.friction + coll2.friction) * 0.5,
-            (coll1.restitution + coll2.restitution) * 0.5,
+            0.0,
+            0.0,
             solver_flags,
-        )
+        );
+        manifold.update_materials(coll1, coll2);
+        manifold
+    }
+
+    pub(crate) fn update_materials(&mut self, coll1: &Collider, coll2: &Collider) {
+        let (material1, material2) = (coll1.material(), coll2.material());
+        self.friction = CoefficientCombineRule::combine(
+            material1.friction,
+            material2.friction,
+            material1.friction_combine_rule,
+            material2.friction_combine_rule,
+        );
+        self.restitution = CoefficientCombineRule::combine(
+            material1.restitution,
+            material2.restitution,
+            material1.restitution_combine_rule,
+            material2.restitution_combine_rule,
+        );
+        self.surface_linvel1 = material1.surface_linvel;
+        self.surface_angvel1 = material1.surface_angvel;
+        self.surface_linvel2 = material2.surface_linvel;
+        self.surface_angvel2 = material2.surface_angvel;
+    }
+
+    /// Dilates the contact geometry of this manifold by the given contact skins.
+    ///
+    /// The skins applied previously must have been removed with `remove_contact_skins`.
+    pub(crate) fn apply_contact_skins(&mut self, skin1: f32, skin2: f32) {
+        if skin1 != 0.0 || skin2 != 0.0 {
+            for pt in &mut self.points {
+                pt.local_p1 += self.local_n1 * skin1;
+                pt.local_p2 += self.local_n2 * skin2;
+                pt.dist -= skin1 + skin2;
+            }
+
+            self.kinematics.radius1 += skin1;
+            self.kinematics.radius2 += skin2;
+        }
+
+        self.contact_skin1 = skin1;
+        self.contact_skin2 = skin2;
+    }
+
+    /// Removes the contact skins applied by `apply_contact_skins`.
+    ///
+    /// This must be done before the contacts are updated by a contact generator: the contact
+    /// generators keep some of the contacts from one timestep to the next, and these must not
+    /// be dilated twice.
+    pub(crate) fn remove_contact_skins(&mut self) {
+        let (skin1, skin2) = (self.contact_skin1, self.contact_skin2);
+        self.apply_contact_skins(-skin1, -skin2);
+        self.contact_skin1 = 0.0;
+        self.contact_skin2 = 0.0;
+    }
+
+    /// The world-space velocity of the surface of the first collider relative to the
+    /// surface of the second collider, at the given contact point.
+    #[inline]
+    pub(crate) fn relative_surface_velocity(
+        &self,
+        contact: &Contact,
+        coll_pos1: &Isometry<f32>,
+        coll_pos2: &Isometry<f32>,
+    ) -> Vector<f32> {
+        let vel1 = self.surface_linvel1 + self.surface_angvel1.gcross(contact.local_p1.coords);
+        let vel2 = self.surface_linvel2 + self.surface_angvel2.gcross(contact.local_p2.coords);
+        coll_pos1 * vel1 - coll_pos2 * vel2
     }
 
     pub(crate) fn min_warmstart_multiplier() -> f32 {
@@ -423,11 +558,92 @@ impl ContactManifold {
         &self.points
     }
 
+    /// The contact normal of this manifold, expressed in world-space.
+    ///
+    /// This normal points from the first collider of `self.pair` toward the second one. Use
+    /// `self.relative_to` to orient it from a chosen collider instead.
+    pub fn world_normal(&self, colliders: &ColliderSet) -> Vector<f32> {
+        colliders[self.pair.collider1].position() * self.local_n1
+    }
+
+    /// The active contacts of this manifold, expressed in world-space.
+    ///
+    /// The contact points are attached to the colliders, and moved with them. So, after a timestep, they are
+    /// the contact points computed at the beginning of this timestep, moved by the integration of the colliders'
+    /// positions. The distance of each contact is the one of the beginning of the timestep.
+    pub fn world_contacts<'a>(
+        &'a self,
+        colliders: &'a ColliderSet,
+    ) -> impl Iterator<Item = WorldContact> + 'a {
+        let pos1 = colliders[self.pair.collider1].position();
+        let pos2 = colliders[self.pair.collider2].position();
+        self.active_contacts().iter().map(move |pt| WorldContact {
+            point1: pos1 * pt.local_p1,
+            point2: pos2 * pt.local_p2,
+            dist: pt.dist,
+            impulse: pt.impulse,
+        })
+    }
+
+    /// A copy of this manifold with `collider` as its first collider.
+    ///
+    /// This returns a copy of `self` if `collider` is `self.pair.collider1`, a flipped copy of `self` if `collider`
+    /// is `self.pair.collider2`, and `None` otherwise. The flipped copy has its colliders, rigid-bodies, subshapes,
+    /// contact points, and normals swapped, so its normal points from `collider` toward the other collider. The
+    /// impulses keep their values: they are applied to `collider` along the flipped contact normal and tangents.
+    /// The kinematics category of the flipped copy is `PointPoint` because `PlanePoint` would require a plane on
+    /// the other collider.
+    pub fn relative_to(&self, collider: ColliderHandle) -> Option<ContactManifold> {
+        if self.pair.collider1 == collider {
+            Some(self.clone())
+        } else if self.pair.collider2 == collider {
+            let mut flipped = self.clone();
+            flipped.flip();
+            Some(flipped)
+        } else {
+            None
+        }
+    }
+
+    /// Swaps the two colliders of this manifold, and all their data.
+    fn flip(&mut self) {
+        self.swap_identifiers();
+        for pt in &mut self.points {
+            *pt = pt.swap();
+        }
+        std::mem::swap(&mut self.local_n1, &mut self.local_n2);
+        std::mem::swap(&mut self.contact_skin1, &mut self.contact_skin2);
+        std::mem::swap(&mut self.kinematics.radius1, &mut self.kinematics.radius2);
+        self.kinematics.category = KinematicsCategory::PointPoint;
+    }
+
+    /// The sum of the impulses applied along the contact normal by the active contacts of this manifold
+    /// during the last timestep.
+    ///
+    /// These impulses are cached and re-used to warm-start the constraints solver at the next timestep.
+    #[inline]
+    pub fn total_impulse(&self) -> f32 {
+        self.active_contacts().iter().map(|pt| pt.impulse).sum()
+    }
+
+    /// Resets to zero the cached impulses of all the contacts of this manifold.
+    ///
+    /// The constraints solver will not be warm-started with these impulses at the next timestep.
+    /// This is useful to avoid applying stale impulses after a rigid-body has been teleported.
+    pub fn clear_impulses(&mut self) {
+        for pt in &mut self.points {
+            pt.impulse = 0.0;
+            pt.tangent_impulse = Contact::zero_tangent_impulse();
+        }
+    }
+
     pub(crate) fn swap_identifiers(&mut self) {
         self.pair = self.pair.swap();
         self.body_pair = self.body_pair.swap();
         self.subshape_index_pair = (self.subshape_index_pair.1, self.subshape_index_pair.0);
         std::mem::swap(&mut self.delta1, &mut self.delta2);
+        std::mem::swap(&mut self.surface_linvel1, &mut self.surface_linvel2);
+        std::mem::swap(&mut self.surface_angvel1, &mut self.surface_angvel2);
     }
 
     pub(crate) fn update_warmstart_multiplier(&mut self) {
@@ -528,4 +744,202 @@ impl ContactManifold {
             }
         }
     }
+
+    /// Keeps at most `MAX_MANIFOLD_POINTS` contacts on this manifold, selected such that they
+    /// span the largest possible area on the plane orthogonal to `local_n1`.
+    ///
+    /// The deepest contact is always kept. The contacts for which `is_persistent` returns `true`,
+    /// i.e., the ones that were already part of this manifold at the previous timestep, are preferred
+    /// over slightly better contacts. This avoids switching between contacts with almost identical
+    /// depths or positions at each timestep, which prevents warm-starting.
+    #[cfg(feature = "dim3")]
+    pub(crate) fn reduce_contacts(&mut self, is_persistent: impl Fn(&Contact) -> bool) {
+        const DEPTH_TOLERANCE: f32 = 1.0e-3; // FIXME: this should not be hard-coded.
+        const PERSISTENCE_BONUS: f32 = 1.1;
+
+        if self.points.len() <= crate::math::MAX_MANIFOLD_POINTS {
+            return;
+        }
+
+        let persistent: Vec<bool> = self.points.iter().map(is_persistent).collect();
+        let bonus = |i: usize| {
+            if persistent[i] {
+                PERSISTENCE_BONUS
+            } else {
+                1.0
+            }
+        };
+        let mut deepest = 0;
+
+        for (i, pt) in self.points.iter().enumerate() {
+            if pt.dist < self.points[deepest].dist {
+                deepest = i;
+            }
+        }
+
+        let max_dist = self.points[deepest].dist + DEPTH_TOLERANCE;
+        let mut deepest_persistent: Option<usize> = None;
+
+        for (i, pt) in self.points.iter().enumerate() {
+            if persistent[i] && pt.dist <= max_dist {
+                match deepest_persistent {
+                    Some(j) if self.points[j].dist <= pt.dist => {}
+                    _ => deepest_persistent = Some(i),
+                }
+            }
+        }
+
+        let deepest = deepest_persistent.unwrap_or(deepest);
+
+        // The contact furthest from the deepest one.
+        let p1 = self.points[deepest].local_p1;
+        let mut furthest = deepest;
+        let mut furthest_dist = 0.0;
+
+        for (i, pt) in self.points.iter().enumerate() {
+            let dist = na::distance_squared(&pt.local_p1, &p1) * bonus(i) * bonus(i);
+
+            if dist > furthest_dist {
+                furthest = i;
+                furthest_dist = dist;
+            }
+        }
+
+        let p2 = self.points[furthest].local_p1;
+
+        // The contacts forming the triangles with the largest areas on
+        // each side of the segment joining the two first contacts.
+        let (mut left, mut right) = (deepest, deepest);
+        let (mut left_area, mut right_area) = (0.0, 0.0);
+
+        for (i, pt) in self.points.iter().enumerate() {
+            let area = (pt.local_p1 - p1).cross(&(p2 - p1)).dot(&self.local_n1) * bonus(i);
+
+            if area > left_area {
+                left = i;
+                left_area = area;
+            } else if area < right_area {
+                right = i;
+                right_area = area;
+            }
+        }
+
+        let mut selected = [deepest, left, furthest, right];
+        selected.sort_unstable();
+        let mut num_selected = 0;
+
+        for k in 0..selected.len() {
+            if k == 0 || selected[k] != selected[k - 1] {
+                self.points.swap(num_selected, selected[k]);
+                num_selected += 1;
+            }
+        }
+
+        self.points.truncate(num_selected);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::dynamics::RigidBodyBuilder;
+    use crate::geometry::{ColliderBuilder, ColliderSet};
+    use crate::math::{Isometry, Vector};
+    use crate::pipeline::TestWorld;
+
+    #[test]
+    fn world_space_contact_manifolds() {
+        let mut world = TestWorld::new(Vector::zeros());
+
+        // Shapes slightly penetrating a ground with its top face at y = 0. Some are inserted
+        // before the ground so the colliders of the contact pairs are in both orders.
+        let ground = world.bodies.insert(
+            RigidBodyBuilder::new_static()
+                .position(Isometry::new(Vector::y() * -0.5, na::zero()))
+                .build(),
+        );
+        #[cfg(feature = "dim2")]
+        let ground_shape = ColliderBuilder::cuboid(10.0, 0.5);
+        #[cfg(feature = "dim3")]
+        let ground_shape = ColliderBuilder::cuboid(10.0, 0.5, 10.0);
+        #[cfg(feature = "dim2")]
+        let cuboid = ColliderBuilder::cuboid(0.5, 0.5);
+        #[cfg(feature = "dim3")]
+        let cuboid = ColliderBuilder::cuboid(0.5, 0.5, 0.5);
+        let shapes = [
+            (ColliderBuilder::ball(0.5), 0.45),
+            (cuboid, 0.45),
+            (ColliderBuilder::capsule_y(0.5, 0.5), 0.95),
+        ];
+
+        let mut ground_collider = None;
+        for (i, (shape, height)) in shapes.iter().enumerate() {
+            if i == 1 {
+                ground_collider = Some(world.attach(ground_shape.build(), ground));
+            }
+
+            let position = Vector::x() * (i as f32 * 3.0) + Vector::y() * *height;
+            let body = RigidBodyBuilder::new_dynamic()
+                .position(Isometry::new(position, na::zero()))
+                .build();
+            world.insert(body, shape.build());
+        }
+
+        world.step();
+
+        let colliders = &world.colliders;
+        let ground_collider = ground_collider.unwrap();
+        let mut num_manifolds = 0;
+        for pair in world.narrow_phase.contact_pairs() {
+            for manifold in pair.manifolds.iter() {
+                if manifold.num_active_contacts() == 0 {
+                    continue;
+                }
+
+                num_manifolds += 1;
+                let (handle1, handle2) = (manifold.pair.collider1, manifold.pair.collider2);
+                let pos1 = colliders[handle1].position();
+                let pos2 = colliders[handle2].position();
+
+                // The normal points from the first collider toward the second one.
+                let normal = manifold.world_normal(colliders);
+                let dir = pos2.translation.vector - pos1.translation.vector;
+                assert!(normal.dot(&dir) > 0.0);
+                assert!((pos2 * manifold.local_n2 + normal).norm() < 1.0e-2);
+
+                for contact in manifold.world_contacts(colliders) {
+                    let dist = (contact.point2 - contact.point1).dot(&normal);
+                    assert!((dist - contact.dist).abs() < 1.0e-2);
+                    assert!(contact.dist < 0.0);
+                }
+
+                // Orienting the manifold from either collider.
+                let other = if handle1 == ground_collider {
+                    handle2
+                } else {
+                    handle1
+                };
+                let from_ground = manifold.relative_to(ground_collider).unwrap();
+                let from_other = manifold.relative_to(other).unwrap();
+                assert_eq!(from_ground.pair.collider1, ground_collider);
+                assert_eq!(from_other.pair.collider1, other);
+                assert_eq!(from_ground.body_pair.body1, ground);
+                assert!(from_ground.world_normal(colliders).y > 0.99);
+                assert!(from_other.world_normal(colliders).y < -0.99);
+
+                let contacts1: Vec<_> = from_ground.world_contacts(colliders).collect();
+                let contacts2: Vec<_> = from_other.world_contacts(colliders).collect();
+                for (c1, c2) in contacts1.iter().zip(contacts2.iter()) {
+                    assert_eq!(c1.point1, c2.point2);
+                    assert_eq!(c1.point2, c2.point1);
+                    assert_eq!(c1.dist, c2.dist);
+                }
+
+                assert!(manifold
+                    .relative_to(ColliderSet::invalid_handle())
+                    .is_none());
+            }
+        }
+
+        assert_eq!(num_manifolds, shapes.len());
+    }
 }
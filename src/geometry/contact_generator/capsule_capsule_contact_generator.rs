@@ -4,7 +4,6 @@ use crate::math::Isometry;
 use crate::math::Vector;
 use approx::AbsDiffEq;
 use na::Unit;
-#[cfg(feature = "dim2")]
 use ncollide::shape::SegmentPointLocation;
 
 pub fn generate_contacts_capsule_capsule(ctxt: &mut PrimitiveContactGenerationContext) {
@@ -23,7 +22,6 @@ pub fn generate_contacts_capsule_capsule(ctxt: &mut PrimitiveContactGenerationCo
     ctxt.manifold.sort_contacts(ctxt.prediction_distance);
 }
 
-#[cfg(feature = "dim2")]
 pub fn generate_contacts<'a>(
     prediction_distance: f32,
     capsule1: &'a Capsule,
@@ -71,11 +69,13 @@ pub fn generate_contacts<'a>(
 
     let local_n1 =
         Unit::try_new(local_p2 - local_p1, f32::default_epsilon()).unwrap_or(Vector::y_axis());
-    let dist = (local_p2 - local_p1).dot(&local_n1) - capsule1.radius - capsule2.radius;
+    // NOTE: the radii are taken into account at the end for all the contacts.
+    let segments_dist = (local_p2 - local_p1).dot(&local_n1);
+    let dist = segments_dist - capsule1.radius - capsule2.radius;
 
     if dist <= prediction_distance {
         let local_n2 = pos21 * -local_n1;
-        let contact = Contact::new(local_p1, pos21 * local_p2, fid1, fid2, dist);
+        let contact = Contact::new(local_p1, pos21 * local_p2, fid1, fid2, segments_dist);
         manifold.points.push(contact);
 
         manifold.local_n1 = *local_n1;
@@ -94,11 +94,16 @@ pub fn generate_contacts<'a>(
         {
             // Capsules axes are almost parallel and are almost perpendicular to the normal.
             // Find a second contact point.
-            if let Some((clip_a, clip_b)) = crate::geometry::clip_segments_with_normal(
+            #[cfg(feature = "dim2")]
+            let clips = crate::geometry::clip_segments_with_normal(
                 (seg1.a, seg1.b),
                 (seg2_1.a, seg2_1.b),
                 *local_n1,
-            ) {
+            );
+            #[cfg(feature = "dim3")]
+            let clips = crate::geometry::clip_segments((seg1.a, seg1.b), (seg2_1.a, seg2_1.b));
+
+            if let Some((clip_a, clip_b)) = clips {
                 let contact =
                     if (clip_a.0 - local_p1).norm_squared() > f32::default_epsilon() * 100.0 {
                         // Use clip_a as the second contact.
@@ -142,58 +147,34 @@ pub fn generate_contacts<'a>(
     super::match_contacts(manifold, &old_manifold_points, swapped);
 }
 
-#[cfg(feature = "dim3")]
-pub fn generate_contacts<'a>(
-    prediction_distance: f32,
-    capsule1: &'a Capsule,
-    pos1: &'a Isometry<f32>,
-    capsule2: &'a Capsule,
-    pos2: &'a Isometry<f32>,
-    manifold: &mut ContactManifold,
-) {
-    let pos12 = pos1.inverse() * pos2;
-    let pos21 = pos12.inverse();
+#[cfg(test)]
+mod test {
+    use crate::dynamics::RigidBodyBuilder;
+    use crate::geometry::ColliderBuilder;
+    use crate::math::{Isometry, Vector};
+    use crate::pipeline::TestWorld;
 
-    let seg1 = capsule1.segment;
-    let seg2_1 = capsule2.segment.transformed(&pos12);
-    let (loc1, loc2) = ncollide::query::closest_points_segment_segment_with_locations_nD(
-        (&seg1.a, &seg1.b),
-        (&seg2_1.a, &seg2_1.b),
-    );
+    #[test]
+    fn parallel_capsules_have_two_contacts() {
+        let mut world = TestWorld::new(Vector::zeros());
 
-    {
-        let bcoords1 = loc1.barycentric_coordinates();
-        let bcoords2 = loc2.barycentric_coordinates();
-        let local_p1 = seg1.a * bcoords1[0] + seg1.b.coords * bcoords1[1];
-        let local_p2 = seg2_1.a * bcoords2[0] + seg2_1.b.coords * bcoords2[1];
-
-        let local_n1 =
-            Unit::try_new(local_p2 - local_p1, f32::default_epsilon()).unwrap_or(Vector::y_axis());
-        let dist = (local_p2 - local_p1).dot(&local_n1) - capsule1.radius - capsule2.radius;
-
-        if dist <= prediction_distance {
-            let local_n2 = pos21 * -local_n1;
-            let contact = Contact::new(
-                local_p1 + *local_n1 * capsule1.radius,
-                pos21 * local_p2 + *local_n2 * capsule2.radius,
-                0,
-                0,
-                dist,
-            );
+        let co = ColliderBuilder::capsule_x(1.0, 0.5).build();
+        let (_, co1) = world.insert(RigidBodyBuilder::new_static().build(), co);
 
-            if manifold.points.len() != 0 {
-                manifold.points[0].copy_geometry_from(contact);
-            } else {
-                manifold.points.push(contact);
-            }
+        let body = RigidBodyBuilder::new_dynamic()
+            .position(Isometry::new(Vector::y() * 0.99, na::zero()))
+            .build();
+        let (_, co2) = world.insert(body, ColliderBuilder::capsule_x(1.0, 0.5).build());
+
+        world.step();
+
+        let pair = world.narrow_phase.contact_pair(co1, co2).unwrap();
+        assert_eq!(pair.manifolds.len(), 1);
+        let manifold = &pair.manifolds[0];
+        assert_eq!(manifold.all_contacts().len(), 2);
 
-            manifold.local_n1 = *local_n1;
-            manifold.local_n2 = *local_n2;
-            manifold.kinematics.category = KinematicsCategory::PlanePoint;
-            manifold.kinematics.radius1 = 0.0;
-            manifold.kinematics.radius2 = 0.0;
-        } else {
-            manifold.points.clear();
+        for point in manifold.all_contacts() {
+            assert!((point.dist + 0.01).abs() < 1.0e-4);
         }
     }
 }
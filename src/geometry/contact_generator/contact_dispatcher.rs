@@ -1,9 +1,7 @@
-#[cfg(feature = "dim3")]
-use crate::geometry::contact_generator::PfmPfmContactManifoldGeneratorWorkspace;
 use crate::geometry::contact_generator::{
     ContactGenerator, ContactGeneratorWorkspace, ContactPhase,
-    HeightFieldShapeContactGeneratorWorkspace, PrimitiveContactGenerator,
-    TrimeshShapeContactGeneratorWorkspace,
+    HeightFieldShapeContactGeneratorWorkspace, PfmPfmContactManifoldGeneratorWorkspace,
+    PrimitiveContactGenerator, TrimeshShapeContactGeneratorWorkspace,
 };
 use crate::geometry::ShapeType;
 
@@ -27,6 +25,18 @@ pub trait ContactDispatcher {
 /// The default contact dispatcher used by Rapier.
 pub struct DefaultContactDispatcher;
 
+fn pfm_pfm_contact_generator() -> (PrimitiveContactGenerator, Option<ContactGeneratorWorkspace>) {
+    (
+        PrimitiveContactGenerator {
+            generate_contacts: super::generate_contacts_pfm_pfm,
+            ..PrimitiveContactGenerator::default()
+        },
+        Some(ContactGeneratorWorkspace::from(
+            PfmPfmContactManifoldGeneratorWorkspace::default(),
+        )),
+    )
+}
+
 impl ContactDispatcher for DefaultContactDispatcher {
     fn dispatch_primitives(
         &self,
@@ -78,6 +88,8 @@ impl ContactDispatcher for DefaultContactDispatcher {
                 },
                 None,
             ),
+            // NOTE: in 2D, triangles are handled by the polygonal feature map generator.
+            #[cfg(feature = "dim3")]
             (ShapeType::Triangle, ShapeType::Cuboid) | (ShapeType::Cuboid, ShapeType::Triangle) => {
                 (
                     PrimitiveContactGenerator {
@@ -93,17 +105,19 @@ impl ContactDispatcher for DefaultContactDispatcher {
             | (ShapeType::Cone, _)
             | (_, ShapeType::Cone)
             | (ShapeType::RoundCylinder, _)
-            | (_, ShapeType::RoundCylinder)
+            | (_, ShapeType::RoundCylinder) => pfm_pfm_contact_generator(),
+            (ShapeType::RoundCone, _)
+            | (_, ShapeType::RoundCone)
             | (ShapeType::Capsule, _)
-            | (_, ShapeType::Capsule) => (
-                PrimitiveContactGenerator {
-                    generate_contacts: super::generate_contacts_pfm_pfm,
-                    ..PrimitiveContactGenerator::default()
-                },
-                Some(ContactGeneratorWorkspace::from(
-                    PfmPfmContactManifoldGeneratorWorkspace::default(),
-                )),
-            ),
+            | (_, ShapeType::Capsule)
+            | (ShapeType::Segment, _)
+            | (_, ShapeType::Segment)
+            | (ShapeType::Triangle, _)
+            | (_, ShapeType::Triangle)
+            | (ShapeType::RoundTriangle, _)
+            | (_, ShapeType::RoundTriangle)
+            | (ShapeType::RoundCuboid, _)
+            | (_, ShapeType::RoundCuboid) => pfm_pfm_contact_generator(),
             _ => (PrimitiveContactGenerator::default(), None),
         }
     }
@@ -153,3 +153,44 @@ pub fn generate_contacts<'a>(
     // Transfer impulses.
     super::match_contacts(manifold, &old_manifold_points, swapped);
 }
+
+#[cfg(test)]
+mod test {
+    use crate::dynamics::RigidBodyBuilder;
+    use crate::geometry::ColliderBuilder;
+    use crate::math::{Isometry, Vector};
+    use crate::pipeline::TestWorld;
+
+    #[test]
+    fn stacked_cuboids_have_full_manifold() {
+        let mut world = TestWorld::new(Vector::zeros());
+
+        #[cfg(feature = "dim2")]
+        let (cuboid, rotation, max_contacts) = (ColliderBuilder::cuboid(0.5, 0.5), 0.0, 2);
+        // Rotate the top cuboid so the clipped faces of both cuboids form an octagon.
+        #[cfg(feature = "dim3")]
+        let (cuboid, rotation, max_contacts) = (
+            ColliderBuilder::cuboid(0.5, 0.5, 0.5),
+            Vector::y() * std::f32::consts::FRAC_PI_4,
+            4,
+        );
+
+        let (_, co1) = world.insert(RigidBodyBuilder::new_static().build(), cuboid.build());
+
+        let body = RigidBodyBuilder::new_dynamic()
+            .position(Isometry::new(Vector::y() * 0.99, rotation))
+            .build();
+        let (_, co2) = world.insert(body, cuboid.build());
+
+        world.step();
+
+        // All the contacts are generated at the first timestep.
+        let manifold = &world.narrow_phase.contact_pair(co1, co2).unwrap().manifolds[0];
+        assert_eq!(manifold.all_contacts().len(), max_contacts);
+        assert_eq!(manifold.num_active_contacts(), max_contacts);
+
+        for point in manifold.all_contacts() {
+            assert!((point.dist + 0.01).abs() < 1.0e-4);
+        }
+    }
+}
@@ -1,13 +1,8 @@
 use crate::geometry::contact_generator::PrimitiveContactGenerationContext;
-#[cfg(feature = "dim3")]
-use crate::geometry::PolyhedronFace;
-use crate::geometry::{cuboid, sat, ContactManifold, Cuboid, KinematicsCategory, Triangle};
-use crate::math::Isometry;
-#[cfg(feature = "dim2")]
-use crate::{
-    geometry::{triangle, CuboidFeature},
-    math::Vector,
+use crate::geometry::{
+    cuboid, sat, ContactManifold, Cuboid, KinematicsCategory, PolyhedronFace, Triangle,
 };
+use crate::math::Isometry;
 
 pub fn generate_contacts_cuboid_triangle(ctxt: &mut PrimitiveContactGenerationContext) {
     if let (Some(cube1), Some(triangle2)) = (ctxt.shape1.as_cuboid(), ctxt.shape2.as_triangle()) {
@@ -78,9 +73,6 @@ pub fn generate_contacts<'a>(
      * Edge-Edge cases.
      *
      */
-    #[cfg(feature = "dim2")]
-    let sep3 = (-f32::MAX, Vector::x()); // This case does not exist in 2D.
-    #[cfg(feature = "dim3")]
     let sep3 =
         sat::cube_triangle_find_local_separating_edge_twoway(cube1, triangle2, &pos12, &pos21);
     if sep3.0 > prediction_distance {
@@ -111,25 +103,12 @@ pub fn generate_contacts<'a>(
     let feature1;
     let mut feature2;
 
-    #[cfg(feature = "dim2")]
-    {
-        if swapped_reference {
-            feature1 = triangle::support_face(triangle2, best_sep.1);
-            feature2 = cuboid::support_face(cube1, pos21 * -best_sep.1);
-        } else {
-            feature1 = cuboid::support_face(cube1, best_sep.1);
-            feature2 = triangle::support_face(triangle2, pos21 * -best_sep.1);
-        }
-    }
-    #[cfg(feature = "dim3")]
-    {
-        if swapped_reference {
-            feature1 = PolyhedronFace::from(*triangle2);
-            feature2 = cuboid::polyhedron_support_face(cube1, pos21 * -best_sep.1);
-        } else {
-            feature1 = cuboid::polyhedron_support_face(cube1, best_sep.1);
-            feature2 = PolyhedronFace::from(*triangle2);
-        }
+    if swapped_reference {
+        feature1 = PolyhedronFace::from(*triangle2);
+        feature2 = cuboid::polyhedron_support_face(cube1, pos21 * -best_sep.1);
+    } else {
+        feature1 = cuboid::polyhedron_support_face(cube1, best_sep.1);
+        feature2 = PolyhedronFace::from(*triangle2);
     }
 
     feature2.transform_by(&pos12);
@@ -143,16 +122,6 @@ pub fn generate_contacts<'a>(
     let old_manifold_points = manifold.points.clone();
     manifold.points.clear();
 
-    #[cfg(feature = "dim2")]
-    CuboidFeature::face_face_contacts(
-        prediction_distance,
-        &feature1,
-        &best_sep.1,
-        &feature2,
-        &pos21,
-        manifold,
-    );
-    #[cfg(feature = "dim3")]
     PolyhedronFace::contacts(
         prediction_distance,
         &feature1,
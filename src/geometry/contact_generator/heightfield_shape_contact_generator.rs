@@ -1,12 +1,15 @@
 use crate::data::hashmap::{Entry, HashMap};
 use crate::data::MaybeSerializableData;
+use crate::geometry::contact_generator::{correct_internal_edge_normal, InternalEdge};
 use crate::geometry::contact_generator::{
     ContactGenerationContext, ContactGeneratorWorkspace, PrimitiveContactGenerationContext,
     PrimitiveContactGenerator,
 };
 #[cfg(feature = "dim2")]
 use crate::geometry::Capsule;
-use crate::geometry::{Collider, ContactManifold, HeightField, Shape};
+use crate::geometry::{Collider, ContactManifold, HeightField, HeightFieldCellFlags, Shape};
+#[cfg(feature = "dim3")]
+use crate::math::Point;
 use crate::ncollide::bounding_volume::BoundingVolume;
 #[cfg(feature = "serde-serialize")]
 use erased_serde::Serialize;
@@ -56,7 +59,7 @@ fn do_generate_contacts(
     collider1: &Collider,
     collider2: &Collider,
     ctxt: &mut ContactGenerationContext,
-    _flipped: bool,
+    flipped: bool,
 ) {
     let workspace: &mut HeightFieldShapeContactGeneratorWorkspace = ctxt
         .pair
@@ -81,12 +84,18 @@ fn do_generate_contacts(
 
     std::mem::swap(&mut workspace.old_manifolds, &mut ctxt.pair.manifolds);
     ctxt.pair.manifolds.clear();
-    let coll_pair = ctxt.pair.pair;
+    // The manifolds are computed with the heightfield as their first collider.
+    let coll_pair = if flipped {
+        ctxt.pair.pair.swap()
+    } else {
+        ctxt.pair.pair
+    };
     let manifolds = &mut ctxt.pair.manifolds;
     let prediction_distance = ctxt.prediction_distance;
     let dispatcher = ctxt.dispatcher;
     let solver_flags = ctxt.solver_flags;
     let shape_type2 = collider2.shape().shape_type();
+    let heightfield_handle = coll_pair.collider1;
 
     heightfield1.map_elements_in_local_aabb(&ls_aabb2, &mut |i, part1, _| {
         let position1 = collider1.position();
@@ -168,7 +177,105 @@ fn do_generate_contacts(
             }
         };
 
-        (sub_detector.generator.unwrap().generate_contacts)(&mut ctxt2)
+        (sub_detector.generator.unwrap().generate_contacts)(&mut ctxt2);
+
+        #[cfg(feature = "dim2")]
+        {
+            if let Some(face_normal) = part1.normal() {
+                let adjacent_normal = |j: Option<usize>| {
+                    j.and_then(|j| heightfield1.segment_at(j))
+                        .and_then(|seg| seg.normal())
+                        .map(|n| n.into_inner())
+                };
+                let edges = [
+                    InternalEdge {
+                        a: part1.a,
+                        b: part1.a,
+                        adjacent_normal: adjacent_normal(i.checked_sub(1)),
+                    },
+                    InternalEdge {
+                        a: part1.b,
+                        b: part1.b,
+                        adjacent_normal: adjacent_normal(Some(i + 1)),
+                    },
+                ];
+
+                correct_internal_edge_normal(
+                    &mut manifolds[sub_detector.manifold_id],
+                    heightfield_handle,
+                    position1,
+                    collider2.position(),
+                    *face_normal,
+                    na::center(&part1.a, &part1.b),
+                    &edges,
+                    prediction_distance,
+                );
+            }
+        }
+
+        #[cfg(feature = "dim3")]
+        {
+            if let Some(face_normal) = part1.normal() {
+                let adjacent_normals = heightfield1.adjacent_normals(i);
+                let edges = [
+                    InternalEdge {
+                        a: part1.a,
+                        b: part1.b,
+                        adjacent_normal: adjacent_normals[0],
+                    },
+                    InternalEdge {
+                        a: part1.b,
+                        b: part1.c,
+                        adjacent_normal: adjacent_normals[1],
+                    },
+                    InternalEdge {
+                        a: part1.c,
+                        b: part1.a,
+                        adjacent_normal: adjacent_normals[2],
+                    },
+                ];
+
+                correct_internal_edge_normal(
+                    &mut manifolds[sub_detector.manifold_id],
+                    heightfield_handle,
+                    position1,
+                    collider2.position(),
+                    *face_normal,
+                    Point::from((part1.a.coords + part1.b.coords + part1.c.coords) / 3.0),
+                    &edges,
+                    prediction_distance,
+                );
+            }
+        }
+
+        if heightfield1
+            .element_cell_flags(i)
+            .contains(HeightFieldCellFlags::ONE_SIDED)
+        {
+            let manifold = &mut manifolds[sub_detector.manifold_id];
+            // The contact normal pointing from the heightfield toward the other
+            // shape, expressed in the local-space of the heightfield.
+            let local_n = if manifold.pair.collider1 == heightfield_handle {
+                manifold.local_n1
+            } else {
+                manifold.local_n2
+            };
+
+            if let Some(face_normal) = part1.normal() {
+                // Heightfield cells always face the local `+y` direction.
+                let up = if face_normal.y < 0.0 {
+                    -*face_normal
+                } else {
+                    *face_normal
+                };
+
+                if local_n.dot(&up) < 0.0 {
+                    // The other shape is below the one-sided cell.
+                    manifold.points.clear();
+                    manifold.num_active_contacts = 0;
+                }
+            }
+        }
     });
 
     workspace
@@ -189,3 +296,85 @@ impl MaybeSerializableData for HeightFieldShapeContactGeneratorWorkspace {
         Box::new(self.clone())
     }
 }
+
+#[cfg(test)]
+mod test {
+    use crate::dynamics::{RigidBodyBuilder, RigidBodyHandle};
+    use crate::geometry::{ColliderBuilder, HeightField, HeightFieldCellFlags, SharedShape};
+    use crate::math::Vector;
+    use crate::pipeline::TestWorld;
+
+    const BALL_RADIUS: f32 = 0.25;
+
+    // A world with a flat heightfield made of 4 unit cells, the cell at the origin having
+    // the given flags, and a ball located above the origin.
+    fn world_with_ball(
+        flags: HeightFieldCellFlags,
+        y: f32,
+        vy: f32,
+    ) -> (TestWorld, RigidBodyHandle) {
+        #[cfg(feature = "dim2")]
+        let heightfield = {
+            let mut heightfield = HeightField::new(na::DVector::zeros(5), Vector::new(4.0, 1.0));
+            heightfield.set_cell_flags(2, flags);
+            heightfield
+        };
+        #[cfg(feature = "dim3")]
+        let heightfield = {
+            let mut heightfield =
+                HeightField::new(na::DMatrix::zeros(5, 5), Vector::new(4.0, 1.0, 4.0));
+            heightfield.set_cell_flags(2, 2, flags);
+            heightfield
+        };
+
+        let mut world = TestWorld::falling();
+        let ground = RigidBodyBuilder::new_static().build();
+        let co = ColliderBuilder::new(SharedShape::new(heightfield)).build();
+        world.insert(ground, co);
+
+        #[cfg(feature = "dim2")]
+        let rb = RigidBodyBuilder::new_dynamic()
+            .translation(0.5, y)
+            .linvel(0.0, vy);
+        #[cfg(feature = "dim3")]
+        let rb = RigidBodyBuilder::new_dynamic()
+            .translation(0.5, y, 0.5)
+            .linvel(0.0, vy, 0.0);
+        let (ball, _) = world.insert(rb.build(), ColliderBuilder::ball(BALL_RADIUS).build());
+
+        (world, ball)
+    }
+
+    fn ball_height(world: &TestWorld, ball: RigidBodyHandle) -> f32 {
+        world.bodies[ball].position().translation.vector.y
+    }
+
+    #[test]
+    fn ball_rests_on_regular_cell() {
+        let (mut world, ball) = world_with_ball(HeightFieldCellFlags::empty(), 1.0, 0.0);
+        world.steps(120);
+        assert!((ball_height(&world, ball) - BALL_RADIUS).abs() < 0.05);
+    }
+
+    #[test]
+    fn ball_falls_through_removed_cell() {
+        let (mut world, ball) = world_with_ball(HeightFieldCellFlags::REMOVED, 1.0, 0.0);
+        world.steps(120);
+        assert!(ball_height(&world, ball) < -1.0);
+    }
+
+    #[test]
+    fn ball_goes_up_but_not_down_through_one_sided_cell() {
+        // The ball is thrown upward from below the cell, goes through it, then lands on it.
+        let (mut world, ball) = world_with_ball(HeightFieldCellFlags::ONE_SIDED, -1.0, 8.0);
+        let mut max_height = f32::MIN;
+
+        for _ in 0..240 {
+            world.step();
+            max_height = max_height.max(ball_height(&world, ball));
+        }
+
+        assert!(max_height > 1.0);
+        assert!((ball_height(&world, ball) - BALL_RADIUS).abs() < 0.05);
+    }
+}
@@ -0,0 +1,296 @@
+use crate::geometry::{ColliderHandle, ContactManifold};
+use crate::math::{Isometry, Point, Vector};
+use std::cmp::Ordering;
+
+/// An edge of a triangle (or a vertex of a segment in 2D) together with the normal of the
+/// face adjacent to it, if any.
+///
+/// The adjacent normal must be oriented consistently with the face normal, i.e., both normals
+/// must point toward the same side of the surface.
+pub(crate) struct InternalEdge {
+    pub a: Point<f32>,
+    pub b: Point<f32>,
+    pub adjacent_normal: Option<Vector<f32>>,
+}
+
+/// Fixes the normal of a contact manifold that may have been generated by an internal edge
+/// of a mesh (a triangle mesh in 3D, or a polyline in 2D).
+///
+/// When a shape slides over a flat tiled surface, contacts with the edges shared by
+/// two triangles may have normals that are not perpendicular to the surface. This results
+/// in the shape catching on those edges. This detects the contacts lying on an internal edge
+/// which normal is outside of the cone of normals that this edge can legitimately have, and
+/// replaces them with the contact face normal.
+pub(crate) fn correct_internal_edge_normal(
+    manifold: &mut ContactManifold,
+    mesh_handle: ColliderHandle,
+    pos_mesh: &Isometry<f32>,
+    pos_other: &Isometry<f32>,
+    face_normal: Vector<f32>,
+    interior_point: Point<f32>,
+    edges: &[InternalEdge],
+    prediction_distance: f32,
+) {
+    const EPS: f32 = 1.0e-4;
+
+    if manifold.num_active_contacts == 0 {
+        return;
+    }
+
+    let mesh_is_first = manifold.pair.collider1 == mesh_handle;
+    let deepest = manifold
+        .active_contacts()
+        .iter()
+        .min_by(|a, b| a.dist.partial_cmp(&b.dist).unwrap_or(Ordering::Equal))
+        .unwrap();
+    let (local_n, deepest) = if mesh_is_first {
+        (manifold.local_n1, deepest.local_p1)
+    } else {
+        (manifold.local_n2, deepest.local_p2)
+    };
+
+    // Orient the face normal so it points toward the other shape. Note that this can't be
+    // based on the contact normal since it may be orthogonal to the face normal.
+    let pos12 = pos_mesh.inv_mul(pos_other);
+    let (face_normal, flip) =
+        if face_normal.dot(&(pos12.translation.vector - interior_point.coords)) < 0.0 {
+            (-face_normal, -1.0)
+        } else {
+            (face_normal, 1.0)
+        };
+
+    if local_n.dot(&face_normal) >= crate::utils::COS_1_DEGREES {
+        // This is already a face contact.
+        return;
+    }
+
+    // Find the edge closest to the contact point.
+    let mut closest = None;
+    let mut closest_dist = f32::MAX;
+
+    for edge in edges {
+        let ab = edge.b - edge.a;
+        let ab_norm2 = ab.norm_squared();
+        let t = if ab_norm2 > EPS {
+            ((deepest - edge.a).dot(&ab) / ab_norm2).clamp(0.0, 1.0)
+        } else {
+            0.0
+        };
+        let dist = na::distance_squared(&(edge.a + ab * t), &deepest);
+
+        if dist < closest_dist {
+            closest_dist = dist;
+            closest = Some(edge);
+        }
+    }
+
+    let edge = if let Some(edge) = closest {
+        edge
+    } else {
+        return;
+    };
+
+    let adjacent_normal = if let Some(n) = edge.adjacent_normal {
+        n * flip
+    } else {
+        // This is a boundary edge, its contact normal is legitimate.
+        return;
+    };
+
+    // Project the normals on the plane orthogonal to the edge.
+    let ab = edge.b - edge.a;
+    let (local_n_proj, interior_dir) = if let Some(dir) = ab.try_normalize(EPS) {
+        let ai = interior_point - edge.a;
+        (local_n - dir * local_n.dot(&dir), ai - dir * ai.dot(&dir))
+    } else {
+        (local_n, interior_point - edge.a)
+    };
+
+    let local_n_proj = local_n_proj.try_normalize(EPS).unwrap_or(face_normal);
+    let interior_dir = interior_dir - face_normal * interior_dir.dot(&face_normal);
+    let interior_dir = if let Some(dir) = interior_dir.try_normalize(EPS) {
+        dir
+    } else {
+        return;
+    };
+
+    // The edge is convex if the adjacent face bends away from the face normal.
+    let is_convex = adjacent_normal.dot(&interior_dir) < -EPS;
+    let is_in_cone = local_n_proj.dot(&interior_dir) <= EPS
+        && local_n_proj.dot(&face_normal) >= adjacent_normal.dot(&face_normal) - EPS;
+
+    if is_convex && is_in_cone {
+        return;
+    }
+
+    /*
+     * Replace the normal by the face normal, and update the contact geometry accordingly.
+     */
+    let other_n = -pos_other.inverse_transform_vector(&(pos_mesh * face_normal));
+
+    for pt in &mut manifold.points {
+        if mesh_is_first {
+            let p2 = pos12 * pt.local_p2;
+            pt.dist = (p2 - pt.local_p1).dot(&face_normal);
+            pt.local_p1 = p2 - face_normal * pt.dist;
+        } else {
+            let p1 = pos12 * pt.local_p1;
+            pt.dist = (p1 - pt.local_p2).dot(&face_normal);
+            pt.local_p2 = p1 - face_normal * pt.dist;
+        }
+    }
+
+    if mesh_is_first {
+        manifold.local_n1 = face_normal;
+        manifold.local_n2 = other_n;
+    } else {
+        manifold.local_n1 = other_n;
+        manifold.local_n2 = face_normal;
+    }
+
+    manifold.sort_contacts(prediction_distance);
+}
+
+#[cfg(test)]
+mod test {
+    use crate::dynamics::RigidBodyBuilder;
+    use crate::geometry::{Collider, ColliderBuilder};
+    use crate::math::{Point, Vector};
+    use crate::pipeline::TestWorld;
+    use na::Point3;
+
+    const NUM_TILES: u32 = 20;
+
+    // A flat floor made of unit tiles sharing their vertices, with its top at `y = 0`.
+    #[cfg(feature = "dim2")]
+    fn tiled_floor() -> Collider {
+        let mut vertices = Vec::new();
+        let mut indices = Vec::new();
+
+        for i in 0..=NUM_TILES {
+            let x = i as f32 - NUM_TILES as f32 / 2.0;
+            vertices.push(Point::new(x, 0.0));
+            vertices.push(Point::new(x, -1.0));
+        }
+
+        for i in 0..NUM_TILES {
+            let (top0, bottom0, top1, bottom1) = (2 * i, 2 * i + 1, 2 * i + 2, 2 * i + 3);
+            indices.push(Point3::new(bottom0, bottom1, top1));
+            indices.push(Point3::new(bottom0, top1, top0));
+        }
+
+        ColliderBuilder::trimesh(vertices, indices)
+            .friction(0.0)
+            .build()
+    }
+
+    #[cfg(feature = "dim3")]
+    fn tiled_floor() -> Collider {
+        let mut vertices = Vec::new();
+        let mut indices = Vec::new();
+        let n = NUM_TILES + 1;
+
+        for i in 0..n {
+            for j in 0..n {
+                let x = i as f32 - NUM_TILES as f32 / 2.0;
+                let z = j as f32 - NUM_TILES as f32 / 2.0;
+                vertices.push(Point::new(x, 0.0, z));
+            }
+        }
+
+        for i in 0..NUM_TILES {
+            for j in 0..NUM_TILES {
+                let (p00, p01, p10, p11) = (
+                    i * n + j,
+                    i * n + j + 1,
+                    (i + 1) * n + j,
+                    (i + 1) * n + j + 1,
+                );
+                indices.push(Point3::new(p00, p01, p11));
+                indices.push(Point3::new(p00, p11, p10));
+            }
+        }
+
+        ColliderBuilder::trimesh(vertices, indices)
+            .friction(0.0)
+            .build()
+    }
+
+    #[cfg(feature = "dim2")]
+    fn flat_heightfield() -> Collider {
+        let heights = na::DVector::zeros(NUM_TILES as usize + 1);
+        let scale = Vector::new(NUM_TILES as f32, 1.0);
+        ColliderBuilder::heightfield(heights, scale)
+            .friction(0.0)
+            .build()
+    }
+
+    #[cfg(feature = "dim3")]
+    fn flat_heightfield() -> Collider {
+        let n = NUM_TILES as usize + 1;
+        let heights = na::DMatrix::zeros(n, n);
+        let scale = Vector::new(NUM_TILES as f32, 1.0, NUM_TILES as f32);
+        ColliderBuilder::heightfield(heights, scale)
+            .friction(0.0)
+            .build()
+    }
+
+    // Slides a box over the floor, and checks all the contacts keep the floor face normal.
+    fn check_box_sliding_keeps_face_normal(floor: Collider) {
+        let mut world = TestWorld::falling();
+        // Keep the box slightly embedded in the floor while it slides, so that vertex
+        // contacts with the next tiles are deeper than they are wide.
+        world.params.erp = 0.0;
+        let (_, floor) = world.insert(RigidBodyBuilder::new_static().build(), floor);
+
+        #[cfg(feature = "dim2")]
+        let (rb, co) = (
+            RigidBodyBuilder::new_dynamic()
+                .translation(-8.1, 0.45)
+                .linvel(5.0, 0.0),
+            ColliderBuilder::cuboid(0.5, 0.5),
+        );
+        #[cfg(feature = "dim3")]
+        let (rb, co) = (
+            RigidBodyBuilder::new_dynamic()
+                .translation(-8.1, 0.45, 0.3)
+                .linvel(5.0, 0.0, 0.0),
+            ColliderBuilder::cuboid(0.5, 0.5, 0.5),
+        );
+        let (body, _) = world.insert(rb.build(), co.friction(0.0).build());
+
+        for _ in 0..120 {
+            world.step();
+
+            for pair in world.narrow_phase.contact_pairs() {
+                for manifold in &pair.manifolds {
+                    if manifold.num_active_contacts == 0 {
+                        continue;
+                    }
+
+                    // The floor is at the origin so its local normal is also its world normal.
+                    let n = if manifold.pair.collider1 == floor {
+                        manifold.local_n1
+                    } else {
+                        manifold.local_n2
+                    };
+                    assert!(n.y >= crate::utils::COS_1_DEGREES);
+                }
+            }
+
+            assert!(world.bodies[body].linvel().x > 4.9);
+        }
+
+        assert!(world.bodies[body].position().translation.vector.x > 0.0);
+    }
+
+    #[test]
+    fn box_sliding_on_tiled_trimesh_keeps_face_normal() {
+        check_box_sliding_keeps_face_normal(tiled_floor());
+    }
+
+    #[test]
+    fn box_sliding_on_heightfield_keeps_face_normal() {
+        check_box_sliding_keeps_face_normal(flat_heightfield());
+    }
+}
@@ -14,11 +14,11 @@ pub use self::contact_generator::{
 };
 pub use self::cuboid_capsule_contact_generator::generate_contacts_cuboid_capsule;
 pub use self::cuboid_cuboid_contact_generator::generate_contacts_cuboid_cuboid;
+#[cfg(feature = "dim3")]
 pub use self::cuboid_triangle_contact_generator::generate_contacts_cuboid_triangle;
 pub use self::heightfield_shape_contact_generator::{
     generate_contacts_heightfield_shape, HeightFieldShapeContactGeneratorWorkspace,
 };
-#[cfg(feature = "dim3")]
 pub use self::pfm_pfm_contact_generator::{
     generate_contacts_pfm_pfm, PfmPfmContactManifoldGeneratorWorkspace,
 };
@@ -32,6 +32,7 @@ pub(crate) use self::polygon_polygon_contact_generator::clip_segments;
 #[cfg(feature = "dim2")]
 pub(crate) use self::polygon_polygon_contact_generator::clip_segments_with_normal;
 
+pub(self) use self::internal_edges::{correct_internal_edge_normal, InternalEdge};
 pub(self) use self::serializable_workspace_tag::WorkspaceSerializationTag;
 
 mod ball_ball_contact_generator;
@@ -44,9 +45,10 @@ mod contact_generator_workspace;
 mod cuboid_capsule_contact_generator;
 mod cuboid_cuboid_contact_generator;
 mod cuboid_polygon_contact_generator;
+#[cfg(feature = "dim3")]
 mod cuboid_triangle_contact_generator;
 mod heightfield_shape_contact_generator;
-#[cfg(feature = "dim3")]
+mod internal_edges;
 mod pfm_pfm_contact_generator;
 mod polygon_polygon_contact_generator;
 mod serializable_workspace_tag;
@@ -54,28 +56,113 @@ mod trimesh_shape_contact_generator;
 
 use crate::geometry::{Contact, ContactManifold};
 
+// Do both contacts involve the same pair of features?
+//
+// If `swapped` is `true`, the features of `old_contact` are in the reverse order.
+fn same_features(contact: &Contact, old_contact: &Contact, swapped: bool) -> bool {
+    if swapped {
+        contact.fid1 == old_contact.fid2 && contact.fid2 == old_contact.fid1
+    } else {
+        contact.fid1 == old_contact.fid1 && contact.fid2 == old_contact.fid2
+    }
+}
+
+/// Reduces the number of contacts of a newly generated manifold, and transfers the cached
+/// impulses of the contacts of the previous timestep involving the same features.
+///
+/// In 3D, the contacts that were already part of `old_contacts` are preferred when reducing
+/// the manifold, so that the same contacts are kept, and warm-started, from one timestep to the next.
 pub(crate) fn match_contacts(
     manifold: &mut ContactManifold,
     old_contacts: &[Contact],
     swapped: bool,
 ) {
+    #[cfg(feature = "dim3")]
+    manifold.reduce_contacts(|contact| {
+        old_contacts
+            .iter()
+            .any(|old_contact| same_features(contact, old_contact, swapped))
+    });
+
     for contact in &mut manifold.points {
-        if !swapped {
-            for old_contact in old_contacts {
-                if contact.fid1 == old_contact.fid1 && contact.fid2 == old_contact.fid2 {
-                    // Transfer impulse cache.
-                    contact.impulse = old_contact.impulse;
-                    contact.tangent_impulse = old_contact.tangent_impulse;
-                }
-            }
-        } else {
-            for old_contact in old_contacts {
-                if contact.fid1 == old_contact.fid2 && contact.fid2 == old_contact.fid1 {
-                    // Transfer impulse cache.
-                    contact.impulse = old_contact.impulse;
-                    contact.tangent_impulse = old_contact.tangent_impulse;
-                }
+        for old_contact in old_contacts {
+            if same_features(contact, old_contact, swapped) {
+                // Transfer impulse cache.
+                contact.impulse = old_contact.impulse;
+                contact.tangent_impulse = old_contact.tangent_impulse;
             }
         }
     }
 }
+
+#[cfg(test)]
+#[cfg(feature = "dim3")]
+mod test {
+    use crate::data::arena::Index;
+    use crate::dynamics::BodyPair;
+    use crate::geometry::{ColliderPair, Contact, ContactManifold, SolverFlags};
+    use crate::math::{Isometry, Point, Vector};
+
+    // A manifold with 8 contacts located on a regular octagon.
+    fn octagon_manifold(depths: &[f32; 8]) -> ContactManifold {
+        let handle = Index::from_raw_parts(0, 0);
+        let mut manifold = ContactManifold::new(
+            ColliderPair::new(handle, handle),
+            (0, 0),
+            BodyPair::new(handle, handle),
+            Isometry::identity(),
+            Isometry::identity(),
+            0.5,
+            0.0,
+            SolverFlags::COMPUTE_IMPULSES,
+        );
+        manifold.local_n1 = Vector::y();
+        manifold.local_n2 = -Vector::y();
+
+        for (i, depth) in depths.iter().enumerate() {
+            let angle = i as f32 * std::f32::consts::FRAC_PI_4;
+            let local_p1 = Point::new(angle.cos(), 0.0, angle.sin());
+            let local_p2 = local_p1 + Vector::y() * *depth;
+            let contact = Contact::new(local_p1, local_p2, i as u8, 0, -*depth);
+            manifold.points.push(contact);
+        }
+
+        manifold
+    }
+
+    fn fids(manifold: &ContactManifold) -> Vec<u8> {
+        let mut fids: Vec<_> = manifold.points.iter().map(|pt| pt.fid1).collect();
+        fids.sort();
+        fids
+    }
+
+    #[test]
+    fn reduction_keeps_persistent_contacts() {
+        let mut manifold = octagon_manifold(&[0.01; 8]);
+        super::match_contacts(&mut manifold, &[], false);
+        assert_eq!(manifold.points.len(), 4);
+
+        for pt in &mut manifold.points {
+            pt.impulse = 1.0;
+        }
+
+        let old_contacts = manifold.points.clone();
+        let kept = fids(&manifold);
+        let dropped = (0..8).find(|i| !kept.contains(i)).unwrap();
+
+        // A dropped contact becomes slightly deeper: the persistent contacts are kept.
+        let mut depths = [0.01; 8];
+        depths[dropped as usize] += 1.0e-4;
+        let mut manifold = octagon_manifold(&depths);
+        super::match_contacts(&mut manifold, &old_contacts, false);
+        assert_eq!(fids(&manifold), kept);
+        assert!(manifold.points.iter().all(|pt| pt.impulse == 1.0));
+
+        // A dropped contact becomes significantly deeper: it is selected.
+        depths[dropped as usize] += 0.01;
+        let mut manifold = octagon_manifold(&depths);
+        super::match_contacts(&mut manifold, &old_contacts, false);
+        assert_eq!(manifold.points.len(), 4);
+        assert!(fids(&manifold).contains(&dropped));
+    }
+}
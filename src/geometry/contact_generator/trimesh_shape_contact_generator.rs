@@ -1,8 +1,11 @@
 use crate::data::MaybeSerializableData;
+use crate::geometry::contact_generator::{correct_internal_edge_normal, InternalEdge};
 use crate::geometry::contact_generator::{
     ContactGenerationContext, PrimitiveContactGenerationContext,
 };
 use crate::geometry::{Collider, ContactManifold, ShapeType, Trimesh};
+#[cfg(feature = "dim3")]
+use crate::math::Point;
 use crate::ncollide::bounding_volume::{BoundingVolume, AABB};
 #[cfg(feature = "serde-serialize")]
 use erased_serde::Serialize;
@@ -26,6 +29,12 @@ impl TrimeshShapeContactGeneratorWorkspace {
             old_manifolds: Vec::new(),
         }
     }
+
+    /// Forces the triangles intersecting the other shape to be searched again at the next
+    /// contact generation, e.g., because the mesh vertices moved.
+    pub(crate) fn invalidate_interferences(&mut self) {
+        self.local_aabb2 = AABB::new_invalid();
+    }
 }
 
 pub fn generate_contacts_trimesh_shape(ctxt: &mut ContactGenerationContext) {
@@ -203,6 +212,91 @@ fn do_generate_contacts(
         };
 
         (generator.generate_contacts)(&mut ctxt2);
+
+        #[cfg(feature = "dim3")]
+        {
+            if let Some(face_normal) = triangle1.normal() {
+                let adjacent_normals = trimesh1.adjacent_normals(*triangle_id);
+                let edges = [
+                    InternalEdge {
+                        a: triangle1.a,
+                        b: triangle1.b,
+                        adjacent_normal: adjacent_normals[0],
+                    },
+                    InternalEdge {
+                        a: triangle1.b,
+                        b: triangle1.c,
+                        adjacent_normal: adjacent_normals[1],
+                    },
+                    InternalEdge {
+                        a: triangle1.c,
+                        b: triangle1.a,
+                        adjacent_normal: adjacent_normals[2],
+                    },
+                ];
+
+                correct_internal_edge_normal(
+                    &mut ctxt.pair.manifolds[i],
+                    ctxt_pair_pair.collider1,
+                    collider1.position(),
+                    collider2.position(),
+                    *face_normal,
+                    Point::from(
+                        (triangle1.a.coords + triangle1.b.coords + triangle1.c.coords) / 3.0,
+                    ),
+                    &edges,
+                    ctxt.prediction_distance,
+                );
+            }
+        }
+
+        #[cfg(feature = "dim2")]
+        {
+            let manifold = &mut ctxt.pair.manifolds[i];
+            let local_n = if manifold.pair.collider1 == ctxt_pair_pair.collider1 {
+                manifold.local_n1
+            } else {
+                manifold.local_n2
+            };
+
+            // The boundary edge of this triangle facing the other shape.
+            let boundary_edge = trimesh1
+                .boundary_edges(*triangle_id)
+                .iter()
+                .enumerate()
+                .filter_map(|(k, edge)| edge.map(|edge| (k, edge)))
+                .max_by(|(_, e1), (_, e2)| {
+                    e1.normal.dot(&local_n).total_cmp(&e2.normal.dot(&local_n))
+                });
+
+            if let Some((k, edge)) = boundary_edge {
+                let vertices = [triangle1.a, triangle1.b, triangle1.c];
+                let (a, b) = (vertices[k], vertices[(k + 1) % 3]);
+                let edges = [
+                    InternalEdge {
+                        a,
+                        b: a,
+                        adjacent_normal: edge.adjacent_normals[0],
+                    },
+                    InternalEdge {
+                        a: b,
+                        b,
+                        adjacent_normal: edge.adjacent_normals[1],
+                    },
+                ];
+
+                correct_internal_edge_normal(
+                    manifold,
+                    ctxt_pair_pair.collider1,
+                    collider1.position(),
+                    collider2.position(),
+                    edge.normal,
+                    na::center(&a, &b),
+                    &edges,
+                    ctxt.prediction_distance,
+                );
+            }
+        }
     }
 }
 
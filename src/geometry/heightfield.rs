@@ -0,0 +1,449 @@
+#[cfg(feature = "dim3")]
+use crate::geometry::Triangle;
+use crate::geometry::{PointProjection, Ray, RayIntersection};
+use crate::math::{Isometry, Point, Vector};
+#[cfg(feature = "dim3")]
+use na::DMatrix;
+#[cfg(feature = "dim2")]
+use na::DVector;
+use ncollide::bounding_volume::AABB;
+use ncollide::query::{PointQuery, RayCast};
+use ncollide::shape::FeatureId;
+#[cfg(feature = "dim3")]
+use ncollide::shape::HeightFieldCellStatus;
+use std::ops::Deref;
+
+bitflags::bitflags! {
+    #[cfg_attr(feature = "serde-serialize", derive(Serialize, Deserialize))]
+    #[derive(Default)]
+    /// Flags controlling how a single cell of an heightfield interacts with other shapes.
+    pub struct HeightFieldCellFlags: u8 {
+        /// The cell is a hole: it won't generate any contact.
+        const REMOVED = 1 << 0;
+        /// The cell only generates contacts with shapes located above its surface.
+        ///
+        /// Shapes coming from below the cell will go through it without being affected.
+        const ONE_SIDED = 1 << 1;
+    }
+}
+
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde-serialize", derive(Serialize, Deserialize))]
+/// An heightfield shape with per-cell collision flags.
+///
+/// This dereferences to the underlying `ncollide` heightfield so all its geometric queries
+/// remain accessible.
+pub struct HeightField {
+    heightfield: ncollide::shape::HeightField<f32>,
+    cell_flags: Vec<HeightFieldCellFlags>,
+}
+
+impl HeightField {
+    /// Initializes a new heightfield with the given heights and a scale factor.
+    #[cfg(feature = "dim2")]
+    pub fn new(heights: DVector<f32>, scale: Vector<f32>) -> Self {
+        let heightfield = ncollide::shape::HeightField::new(heights, scale);
+        let cell_flags = vec![HeightFieldCellFlags::empty(); heightfield.num_cells()];
+        Self {
+            heightfield,
+            cell_flags,
+        }
+    }
+
+    /// Initializes a new heightfield on the x-z plane with the given heights and a scale factor.
+    #[cfg(feature = "dim3")]
+    pub fn new(heights: DMatrix<f32>, scale: Vector<f32>) -> Self {
+        let heightfield = ncollide::shape::HeightField::new(heights, scale);
+        let num_cells = heightfield.nrows() * heightfield.ncols();
+        let cell_flags = vec![HeightFieldCellFlags::empty(); num_cells];
+        Self {
+            heightfield,
+            cell_flags,
+        }
+    }
+
+    /// The local-space axis-aligned bounding box of this heightfield.
+    pub fn local_aabb(&self) -> &AABB<f32> {
+        self.heightfield.aabb()
+    }
+
+    /// The flags of the `i`-th cell of this heightfield.
+    #[cfg(feature = "dim2")]
+    pub fn cell_flags(&self, i: usize) -> HeightFieldCellFlags {
+        self.cell_flags[i]
+    }
+
+    /// Sets the flags of the `i`-th cell of this heightfield.
+    #[cfg(feature = "dim2")]
+    pub fn set_cell_flags(&mut self, i: usize, flags: HeightFieldCellFlags) {
+        self.cell_flags[i] = flags;
+        self.heightfield
+            .set_segment_removed(i, flags.contains(HeightFieldCellFlags::REMOVED));
+    }
+
+    /// The flags of the cell at the `i`-th row and `j`-th column of this heightfield.
+    #[cfg(feature = "dim3")]
+    pub fn cell_flags(&self, i: usize, j: usize) -> HeightFieldCellFlags {
+        self.cell_flags[self.cell_index(i, j)]
+    }
+
+    /// Sets the flags of the cell at the `i`-th row and `j`-th column of this heightfield.
+    #[cfg(feature = "dim3")]
+    pub fn set_cell_flags(&mut self, i: usize, j: usize, flags: HeightFieldCellFlags) {
+        let id = self.cell_index(i, j);
+        self.cell_flags[id] = flags;
+
+        let mut status = self.heightfield.cell_status(i, j);
+        status.set(
+            HeightFieldCellStatus::CELL_REMOVED,
+            flags.contains(HeightFieldCellFlags::REMOVED),
+        );
+        self.heightfield.set_cell_status(i, j, status);
+    }
+
+    /// Sets the height of the `i`-th vertex of this heightfield.
+    ///
+    /// Use [`HeightField::update_heights`] to modify several heights at once.
+    #[cfg(feature = "dim2")]
+    pub fn set_height(&mut self, i: usize, height: f32) {
+        let _ = self.update_heights(std::iter::once((i, height)));
+    }
+
+    /// Sets the height of the vertex at the `i`-th row and `j`-th column of this heightfield.
+    ///
+    /// Use [`HeightField::update_heights`] to modify several heights at once.
+    #[cfg(feature = "dim3")]
+    pub fn set_height(&mut self, i: usize, j: usize, height: f32) {
+        let _ = self.update_heights(std::iter::once(((i, j), height)));
+    }
+
+    /// Sets the heights of the vertices with the given indices.
+    ///
+    /// The cell flags are preserved. Returns the local-space AABB containing all the cells
+    /// affected by the modification, computed from the modified vertices and their neighbors
+    /// only. This AABB is invalid if no height was modified.
+    #[cfg(feature = "dim2")]
+    pub fn update_heights(&mut self, heights: impl IntoIterator<Item = (usize, f32)>) -> AABB<f32> {
+        let mut new_heights = None;
+        let scale = *self.heightfield.scale();
+        let cell_width = self.heightfield.cell_width();
+        let start_x = self.heightfield.start_x();
+        let mut affected_cells = AABB::new_invalid();
+
+        for (i, height) in heights {
+            let new_heights = new_heights.get_or_insert_with(|| self.heightfield.heights().clone());
+            let old_height = std::mem::replace(&mut new_heights[i], height);
+
+            if old_height == height {
+                continue;
+            }
+
+            // The cells on both sides of this vertex are affected. If a neighbor vertex
+            // is modified too, its old height is taken into account once it is its turn.
+            let x = start_x + cell_width * i as f32;
+            affected_cells.take_point(Point::new(x, old_height * scale.y));
+
+            for k in i.saturating_sub(1)..(i + 2).min(new_heights.len()) {
+                let x = start_x + cell_width * k as f32;
+                affected_cells.take_point(Point::new(x, new_heights[k] * scale.y));
+            }
+        }
+
+        let new_heights = match new_heights {
+            Some(new_heights) if affected_cells.mins.x <= affected_cells.maxs.x => new_heights,
+            _ => return AABB::new_invalid(),
+        };
+
+        // NOTE: the ncollide heightfield doesn't give mutable access to its heights,
+        // so it has to be rebuilt from them.
+        self.heightfield = ncollide::shape::HeightField::new(new_heights, scale);
+
+        for (i, flags) in self.cell_flags.iter().enumerate() {
+            if flags.contains(HeightFieldCellFlags::REMOVED) {
+                self.heightfield.set_segment_removed(i, true);
+            }
+        }
+
+        affected_cells
+    }
+
+    /// Sets the heights of the vertices with the given (row, column) indices.
+    ///
+    /// The cell flags are preserved. Returns the local-space AABB containing all the cells
+    /// affected by the modification, computed from the modified vertices and their neighbors
+    /// only. This AABB is invalid if no height was modified.
+    #[cfg(feature = "dim3")]
+    pub fn update_heights(
+        &mut self,
+        heights: impl IntoIterator<Item = ((usize, usize), f32)>,
+    ) -> AABB<f32> {
+        let mut new_heights = None;
+        let scale = *self.heightfield.scale();
+        let mut affected_cells = AABB::new_invalid();
+
+        for ((i, j), height) in heights {
+            let new_heights = new_heights.get_or_insert_with(|| self.heightfield.heights().clone());
+            let old_height = std::mem::replace(&mut new_heights[(i, j)], height);
+
+            if old_height == height {
+                continue;
+            }
+
+            // The four cells around this vertex are affected. If a neighbor vertex
+            // is modified too, its old height is taken into account once it is its turn.
+            let (x, z) = (self.heightfield.x_at(j), self.heightfield.z_at(i));
+            affected_cells.take_point(Point::new(x, old_height * scale.y, z));
+
+            for ni in i.saturating_sub(1)..(i + 2).min(new_heights.nrows()) {
+                for nj in j.saturating_sub(1)..(j + 2).min(new_heights.ncols()) {
+                    let (x, z) = (self.heightfield.x_at(nj), self.heightfield.z_at(ni));
+                    let y = new_heights[(ni, nj)] * scale.y;
+                    affected_cells.take_point(Point::new(x, y, z));
+                }
+            }
+        }
+
+        let new_heights = match new_heights {
+            Some(new_heights) if affected_cells.mins.x <= affected_cells.maxs.x => new_heights,
+            _ => return AABB::new_invalid(),
+        };
+
+        // NOTE: the ncollide heightfield doesn't give mutable access to its heights,
+        // so it has to be rebuilt from them.
+        let statuses = self.heightfield.cells_statuses().clone();
+        self.heightfield = ncollide::shape::HeightField::new(new_heights, scale);
+        *self.heightfield.cells_statuses_mut() = statuses;
+
+        affected_cells
+    }
+
+    #[cfg(feature = "dim3")]
+    fn cell_index(&self, i: usize, j: usize) -> usize {
+        // NOTE: this matches the column-major layout used by ncollide for
+        // its triangle identifiers.
+        j * self.heightfield.nrows() + i
+    }
+
+    /// The flags of the cell containing the element (segment in 2D, triangle in 3D)
+    /// with the given identifier.
+    pub(crate) fn element_cell_flags(&self, element_id: usize) -> HeightFieldCellFlags {
+        #[cfg(feature = "dim2")]
+        return self.cell_flags[element_id];
+        #[cfg(feature = "dim3")]
+        return self.cell_flags[element_id % self.cell_flags.len()];
+    }
+
+    /// The normals of the triangles adjacent to each edge `(a, b)`, `(b, c)`, and `(c, a)` of the
+    /// triangle with the given identifier.
+    ///
+    /// An entry is `None` if the corresponding edge is on the boundary of the heightfield, or
+    /// next to a removed cell. All the normals point toward the `+y` side of the heightfield.
+    #[cfg(feature = "dim3")]
+    pub fn adjacent_normals(&self, triangle_id: usize) -> [Option<Vector<f32>>; 3] {
+        let num_cells = self.cell_flags.len();
+        let left = triangle_id < num_cells;
+        let cell = triangle_id % num_cells;
+        let (i, j) = (cell % self.nrows(), cell / self.nrows());
+        let mut result = [None; 3];
+
+        let vertices = if let Some(vertices) = self.triangle_grid_vertices(i, j, left) {
+            vertices
+        } else {
+            return result;
+        };
+
+        for (k, adjacent_normal) in result.iter_mut().enumerate() {
+            let (a, b) = (vertices[k], vertices[(k + 1) % 3]);
+
+            // The adjacent triangle is either the other triangle of this cell,
+            // or one of the triangles of the neighboring cells.
+            for ni in i.saturating_sub(1)..(i + 2).min(self.nrows()) {
+                for nj in j.saturating_sub(1)..(j + 2).min(self.ncols()) {
+                    for &nleft in &[true, false] {
+                        if (ni, nj, nleft) == (i, j, left) {
+                            continue;
+                        }
+
+                        if let Some(other) = self.triangle_grid_vertices(ni, nj, nleft) {
+                            if other.contains(&a) && other.contains(&b) {
+                                *adjacent_normal = self.grid_triangle(&other).normal().map(|n| *n);
+                            }
+                        }
+                    }
+                }
+            }
+        }
+
+        result
+    }
+
+    /// The (row, column) grid indices of the vertices of the left or right triangle of the
+    /// cell at the `i`-th row and `j`-th column, in the same order as the triangles generated
+    /// by `map_elements_in_local_aabb`.
+    ///
+    /// Returns `None` if this triangle has been removed.
+    #[cfg(feature = "dim3")]
+    fn triangle_grid_vertices(
+        &self,
+        i: usize,
+        j: usize,
+        left: bool,
+    ) -> Option<[(usize, usize); 3]> {
+        let status = self.heightfield.cell_status(i, j);
+        let removed = if left {
+            HeightFieldCellStatus::LEFT_TRIANGLE_REMOVED
+        } else {
+            HeightFieldCellStatus::RIGHT_TRIANGLE_REMOVED
+        };
+
+        if status.intersects(HeightFieldCellStatus::CELL_REMOVED | removed) {
+            return None;
+        }
+
+        let (p00, p10, p01, p11) = ((i, j), (i + 1, j), (i, j + 1), (i + 1, j + 1));
+        let zigzag = status.contains(HeightFieldCellStatus::ZIGZAG_SUBDIVISION);
+
+        Some(match (zigzag, left) {
+            (true, true) => [p00, p10, p11],
+            (true, false) => [p00, p11, p01],
+            (false, true) => [p00, p10, p01],
+            (false, false) => [p10, p11, p01],
+        })
+    }
+
+    #[cfg(feature = "dim3")]
+    fn grid_triangle(&self, vertices: &[(usize, usize); 3]) -> Triangle {
+        let point = |(i, j): (usize, usize)| {
+            Point::new(
+                self.heightfield.x_at(j),
+                self.heightfield.heights()[(i, j)] * self.heightfield.scale().y,
+                self.heightfield.z_at(i),
+            )
+        };
+
+        Triangle::new(point(vertices[0]), point(vertices[1]), point(vertices[2]))
+    }
+}
+
+impl Deref for HeightField {
+    type Target = ncollide::shape::HeightField<f32>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.heightfield
+    }
+}
+
+impl PointQuery<f32> for HeightField {
+    fn project_point(&self, m: &Isometry<f32>, pt: &Point<f32>, solid: bool) -> PointProjection {
+        self.heightfield.project_point(m, pt, solid)
+    }
+
+    fn project_point_with_feature(
+        &self,
+        m: &Isometry<f32>,
+        pt: &Point<f32>,
+    ) -> (PointProjection, FeatureId) {
+        self.heightfield.project_point_with_feature(m, pt)
+    }
+}
+
+impl RayCast<f32> for HeightField {
+    fn toi_and_normal_with_ray(
+        &self,
+        m: &Isometry<f32>,
+        ray: &Ray,
+        max_toi: f32,
+        solid: bool,
+    ) -> Option<RayIntersection> {
+        self.heightfield
+            .toi_and_normal_with_ray(m, ray, max_toi, solid)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::HeightField;
+    use crate::dynamics::RigidBodyBuilder;
+    use crate::geometry::ColliderBuilder;
+    use crate::math::{Isometry, Point, Vector};
+    use crate::pipeline::TestWorld;
+    use approx::assert_relative_eq;
+
+    #[test]
+    fn update_heights_returns_the_affected_cells() {
+        // A flat heightfield with cells of width 1, centered at the origin.
+        #[cfg(feature = "dim2")]
+        let mut heightfield = HeightField::new(na::DVector::zeros(11), Vector::new(10.0, 1.0));
+        #[cfg(feature = "dim3")]
+        let mut heightfield =
+            HeightField::new(na::DMatrix::zeros(11, 11), Vector::new(10.0, 1.0, 10.0));
+
+        #[cfg(feature = "dim2")]
+        let aabb = heightfield.update_heights(vec![(5, 2.0), (0, 0.0)]);
+        #[cfg(feature = "dim3")]
+        let aabb = heightfield.update_heights(vec![((5, 5), 2.0), ((0, 0), 0.0)]);
+
+        #[cfg(feature = "dim2")]
+        let (mins, maxs) = (Point::new(-1.0, 0.0), Point::new(1.0, 2.0));
+        #[cfg(feature = "dim3")]
+        let (mins, maxs) = (Point::new(-1.0, 0.0, -1.0), Point::new(1.0, 2.0, 1.0));
+        assert_relative_eq!(aabb.mins, mins, epsilon = 1.0e-5);
+        assert_relative_eq!(aabb.maxs, maxs, epsilon = 1.0e-5);
+        assert_eq!(heightfield.local_aabb().maxs.y, 2.0);
+
+        // Setting a height to its current value doesn't affect any cell.
+        #[cfg(feature = "dim2")]
+        let aabb = heightfield.update_heights(vec![(5, 2.0)]);
+        #[cfg(feature = "dim3")]
+        let aabb = heightfield.update_heights(vec![((5, 5), 2.0)]);
+        assert!(aabb.mins.x > aabb.maxs.x);
+    }
+
+    #[test]
+    fn heightfield_heights_update() {
+        let mut world = TestWorld::falling();
+
+        // A flat terrain with cells of width 1.
+        #[cfg(feature = "dim2")]
+        let terrain = ColliderBuilder::heightfield(na::DVector::zeros(11), Vector::new(10.0, 1.0));
+        #[cfg(feature = "dim3")]
+        let terrain =
+            ColliderBuilder::heightfield(na::DMatrix::zeros(11, 11), Vector::new(10.0, 1.0, 10.0));
+        let (_, terrain) = world.insert(RigidBodyBuilder::new_static().build(), terrain.build());
+
+        let mut balls = Vec::new();
+        for x in &[-3.0, 3.0] {
+            let ball = RigidBodyBuilder::new_dynamic()
+                .position(Isometry::new(
+                    Vector::x() * *x + Vector::y() * 0.5,
+                    na::zero(),
+                ))
+                .build();
+            let (ball, _) = world.insert(ball, ColliderBuilder::ball(0.5).build());
+            balls.push(ball);
+        }
+
+        world.steps(300);
+        assert!(world.bodies[balls[0]].is_sleeping() && world.bodies[balls[1]].is_sleeping());
+
+        // Dig a crater under the first ball only.
+        #[cfg(feature = "dim2")]
+        let crater = (1..=3).map(|i| (i, -1.0));
+        #[cfg(feature = "dim3")]
+        let crater = (1..=3).flat_map(|i| (4..=6).map(move |j| ((j, i), -1.0)));
+        let updated = world
+            .colliders
+            .get_mut(terrain)
+            .unwrap()
+            .update_heightfield_heights(crater);
+        assert!(updated);
+
+        world.step();
+        assert!(!world.bodies[balls[0]].is_sleeping());
+        assert!(world.bodies[balls[1]].is_sleeping());
+
+        world.steps(200);
+        assert!(world.bodies[balls[0]].position().translation.vector.y < 0.0);
+        assert!((world.bodies[balls[1]].position().translation.vector.y - 0.5).abs() < 0.05);
+    }
+}
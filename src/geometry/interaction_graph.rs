@@ -79,6 +79,14 @@ impl<T> InteractionGraph<T> {
         self.graph.raw_edges().iter().map(move |edge| &edge.weight)
     }
 
+    /// All the mutable references to the interactions on this graph.
+    pub fn interactions_mut(&mut self) -> impl Iterator<Item = &mut T> {
+        self.graph
+            .edges
+            .iter_mut()
+            .map(move |edge| &mut edge.weight)
+    }
+
     /// The interaction between the two collision objects identified by their graph index.
     pub fn interaction_pair(
         &self,
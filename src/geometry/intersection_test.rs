@@ -0,0 +1,160 @@
+use crate::geometry::{HeightField, Shape, Trimesh};
+use crate::math::Isometry;
+use ncollide::query::{self, Proximity, Unsupported};
+use ncollide::shape::SupportMap;
+
+/// Tests whether two shapes are intersecting.
+///
+/// This is much cheaper than computing the contacts between the two shapes since no
+/// contact manifold is constructed. Convex shapes are tested with the GJK algorithm. Triangle
+/// meshes and heightfields are tested element-wise against the other shape if it is convex.
+///
+/// Returns `Err(Unsupported)` if the two shapes are both triangle meshes or heightfields because
+/// this combination is not supported yet.
+pub fn intersection_test(
+    shape1: &dyn Shape,
+    position1: &Isometry<f32>,
+    shape2: &dyn Shape,
+    position2: &Isometry<f32>,
+) -> Result<bool, Unsupported> {
+    if let (Some(sm1), Some(sm2)) = (shape1.as_support_map(), shape2.as_support_map()) {
+        Ok(intersection_test_support_map_support_map(
+            position1, sm1, position2, sm2,
+        ))
+    } else if let Some(sm2) = shape2.as_support_map() {
+        intersection_test_composite_support_map(shape1, position1, sm2, shape2, position2)
+    } else if let Some(sm1) = shape1.as_support_map() {
+        intersection_test_composite_support_map(shape2, position2, sm1, shape1, position1)
+    } else {
+        Err(Unsupported)
+    }
+}
+
+fn intersection_test_support_map_support_map<G1, G2>(
+    position1: &Isometry<f32>,
+    shape1: &G1,
+    position2: &Isometry<f32>,
+    shape2: &G2,
+) -> bool
+where
+    G1: ?Sized + SupportMap<f32>,
+    G2: ?Sized + SupportMap<f32>,
+{
+    query::proximity_support_map_support_map(position1, shape1, position2, shape2, 0.0)
+        == Proximity::Intersecting
+}
+
+fn intersection_test_composite_support_map(
+    composite1: &dyn Shape,
+    position1: &Isometry<f32>,
+    support_map2: &dyn SupportMap<f32>,
+    shape2: &dyn Shape,
+    position2: &Isometry<f32>,
+) -> Result<bool, Unsupported> {
+    let pos12 = position1.inverse() * position2;
+    let local_aabb2 = shape2.compute_aabb(&pos12);
+
+    if let Some(trimesh1) = composite1.as_trimesh() {
+        Ok(intersection_test_trimesh_support_map(
+            trimesh1,
+            &pos12,
+            support_map2,
+            &local_aabb2,
+        ))
+    } else if let Some(heightfield1) = composite1.as_heightfield() {
+        Ok(intersection_test_heightfield_support_map(
+            heightfield1,
+            &pos12,
+            support_map2,
+            &local_aabb2,
+        ))
+    } else {
+        Err(Unsupported)
+    }
+}
+
+fn intersection_test_trimesh_support_map(
+    trimesh1: &Trimesh,
+    pos12: &Isometry<f32>,
+    support_map2: &dyn SupportMap<f32>,
+    local_aabb2: &crate::geometry::AABB,
+) -> bool {
+    // TODO: avoid allocation?
+    let mut interferences = Vec::new();
+    trimesh1
+        .waabbs()
+        .intersect_aabb(local_aabb2, &mut interferences);
+
+    let identity = Isometry::identity();
+    interferences.into_iter().any(|i| {
+        let triangle = trimesh1.triangle(i);
+        intersection_test_support_map_support_map(&identity, &triangle, pos12, support_map2)
+    })
+}
+
+fn intersection_test_heightfield_support_map(
+    heightfield1: &HeightField,
+    pos12: &Isometry<f32>,
+    support_map2: &dyn SupportMap<f32>,
+    local_aabb2: &crate::geometry::AABB,
+) -> bool {
+    let identity = Isometry::identity();
+    let mut found = false;
+
+    heightfield1.map_elements_in_local_aabb(local_aabb2, &mut |_, element, _| {
+        found = found
+            || intersection_test_support_map_support_map(&identity, element, pos12, support_map2);
+    });
+
+    found
+}
+
+#[cfg(test)]
+mod test {
+    use super::intersection_test;
+    use crate::geometry::{Ball, Cuboid, Trimesh, Unsupported};
+    use crate::math::{Isometry, Point, Vector};
+
+    fn floor() -> Trimesh {
+        let vertices = vec![
+            Point::from(-Vector::x() * 10.0),
+            Point::from(Vector::x() * 10.0),
+            Point::from(Vector::repeat(10.0)),
+            Point::from(Vector::repeat(10.0) - Vector::x() * 20.0),
+        ];
+        let indices = vec![na::Point3::new(0, 1, 2), na::Point3::new(0, 2, 3)];
+        Trimesh::new(vertices, indices)
+    }
+
+    #[test]
+    fn intersection_test_convex_and_composite_shapes() {
+        let ball = Ball::new(0.5);
+        let cuboid = Cuboid::new(Vector::repeat(0.5));
+        let mut close = Isometry::identity();
+        close.translation.vector.x = 0.8;
+        let mut far = Isometry::identity();
+        far.translation.vector.x = 1.2;
+        let origin = Isometry::identity();
+
+        assert_eq!(intersection_test(&ball, &origin, &cuboid, &close), Ok(true));
+        assert_eq!(intersection_test(&ball, &origin, &cuboid, &far), Ok(false));
+
+        // The composite shape can be either the first or the second shape.
+        let floor = floor();
+        assert_eq!(intersection_test(&floor, &origin, &ball, &origin), Ok(true));
+        assert_eq!(intersection_test(&ball, &origin, &floor, &origin), Ok(true));
+        let mut above = Isometry::identity();
+        above.translation.vector = -Vector::y() * 2.0;
+        assert_eq!(intersection_test(&ball, &above, &floor, &origin), Ok(false));
+    }
+
+    #[test]
+    fn intersection_test_between_composite_shapes_is_unsupported() {
+        let floor = floor();
+        let origin = Isometry::identity();
+        assert_eq!(
+            intersection_test(&floor, &origin, &floor, &origin),
+            Err(Unsupported)
+        );
+    }
+}
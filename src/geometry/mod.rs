@@ -2,28 +2,39 @@
 
 pub use self::broad_phase_multi_sap::BroadPhase;
 pub use self::capsule::Capsule;
-pub use self::collider::{Collider, ColliderBuilder, ColliderShape};
+pub use self::collider::{Collider, ColliderBuilder, ColliderMassProperties};
+pub use self::collider_material::ColliderMaterial;
 pub use self::collider_set::{ColliderHandle, ColliderSet};
 pub use self::contact::{
     Contact, ContactKinematics, ContactManifold, ContactPair, KinematicsCategory, SolverFlags,
+    WorldContact,
 };
 pub use self::contact_generator::{ContactDispatcher, DefaultContactDispatcher};
 #[cfg(feature = "dim2")]
 pub(crate) use self::cuboid_feature2d::{CuboidFeature, CuboidFeatureFace};
 #[cfg(feature = "dim3")]
 pub(crate) use self::cuboid_feature3d::{CuboidFeature, CuboidFeatureFace};
+pub use self::heightfield::{HeightField, HeightFieldCellFlags};
 pub use self::interaction_graph::{
     ColliderGraphIndex, InteractionGraph, RigidBodyGraphIndex, TemporaryInteractionIndex,
 };
+pub use self::intersection_test::intersection_test;
 pub use self::narrow_phase::NarrowPhase;
+pub use self::nonlinear_time_of_impact::nonlinear_time_of_impact;
+pub(crate) use self::nonlinear_time_of_impact::swept_aabb;
 pub use self::polygon::Polygon;
 pub use self::proximity::ProximityPair;
 pub use self::proximity_detector::{DefaultProximityDispatcher, ProximityDispatcher};
+pub use self::round_cone::RoundCone;
+pub use self::round_cuboid::RoundCuboid;
 #[cfg(feature = "dim3")]
 pub use self::round_cylinder::RoundCylinder;
+pub use self::round_triangle::RoundTriangle;
+#[allow(deprecated)]
+pub use self::shared_shape::{ColliderShape, SharedShape};
 pub use self::trimesh::Trimesh;
 pub use self::user_callbacks::{ContactPairFilter, PairFilterContext, ProximityPairFilter};
-pub use ncollide::query::Proximity;
+pub use ncollide::query::{Proximity, Unsupported};
 
 /// A segment shape.
 pub type Segment = ncollide::shape::Segment<f32>;
@@ -33,8 +44,6 @@ pub type Cuboid = ncollide::shape::Cuboid<f32>;
 pub type Triangle = ncollide::shape::Triangle<f32>;
 /// A ball shape.
 pub type Ball = ncollide::shape::Ball<f32>;
-/// A heightfield shape.
-pub type HeightField = ncollide::shape::HeightField<f32>;
 /// A cylindrical shape.
 #[cfg(feature = "dim3")]
 pub type Cylinder = ncollide::shape::Cylinder<f32>;
@@ -53,10 +62,17 @@ pub type Ray = ncollide::query::Ray<f32>;
 pub type RayIntersection = ncollide::query::RayIntersection<f32>;
 /// The the projection of a point on a collider.
 pub type PointProjection = ncollide::query::PointProjection<f32>;
+/// The time of impact between two shapes.
+pub type TOI = ncollide::query::TOI<f32>;
+/// The status of a time of impact computation.
+pub type TOIStatus = ncollide::query::TOIStatus;
+/// A rigid motion with constant linear and angular velocities, used for nonlinear time of impact computations.
+pub type NonlinearRigidMotion = ncollide::interpolation::ConstantVelocityRigidMotion<f32>;
 
 #[cfg(feature = "simd-is-enabled")]
 pub(crate) use self::ball::WBall;
 pub(crate) use self::broad_phase_multi_sap::{BroadPhasePairEvent, ColliderPair};
+pub(crate) use self::collider::ColliderChanges;
 pub(crate) use self::collider_set::RemovedCollider;
 #[cfg(feature = "simd-is-enabled")]
 pub(crate) use self::contact::WContact;
@@ -64,8 +80,9 @@ pub(crate) use self::contact_generator::clip_segments;
 #[cfg(feature = "dim2")]
 pub(crate) use self::contact_generator::clip_segments_with_normal;
 pub(crate) use self::narrow_phase::ContactManifoldIndex;
-#[cfg(feature = "dim3")]
 pub(crate) use self::polygonal_feature_map::PolygonalFeatureMap;
+#[cfg(feature = "dim2")]
+pub(crate) use self::polyhedron_feature2d::PolyhedronFace;
 #[cfg(feature = "dim3")]
 pub(crate) use self::polyhedron_feature3d::PolyhedronFace;
 pub(crate) use self::waabb::{WRay, WAABB};
@@ -77,6 +94,7 @@ pub use self::shape::{Shape, ShapeType};
 mod ball;
 mod broad_phase_multi_sap;
 mod collider;
+mod collider_material;
 mod collider_set;
 mod contact;
 mod contact_generator;
@@ -85,24 +103,32 @@ pub(crate) mod cuboid;
 mod cuboid_feature2d;
 #[cfg(feature = "dim3")]
 mod cuboid_feature3d;
+mod heightfield;
 mod interaction_graph;
+mod intersection_test;
 mod narrow_phase;
+mod nonlinear_time_of_impact;
 mod polygon;
+#[cfg(feature = "dim2")]
+mod polyhedron_feature2d;
 #[cfg(feature = "dim3")]
 mod polyhedron_feature3d;
 mod proximity;
 mod proximity_detector;
+pub mod query;
 pub(crate) mod sat;
-pub(crate) mod triangle;
 mod trimesh;
 mod waabb;
 mod wquadtree;
 //mod z_order;
 mod capsule;
 mod interaction_groups;
-#[cfg(feature = "dim3")]
 mod polygonal_feature_map;
+mod round_cone;
+mod round_cuboid;
 #[cfg(feature = "dim3")]
 mod round_cylinder;
+mod round_triangle;
 mod shape;
+mod shared_shape;
 mod user_callbacks;
@@ -1,9 +1,10 @@
 #[cfg(feature = "parallel")]
 use rayon::prelude::*;
 
-use crate::dynamics::RigidBodySet;
+use crate::dynamics::{JointSet, RigidBodySet};
 use crate::geometry::contact_generator::{
     ContactDispatcher, ContactGenerationContext, DefaultContactDispatcher,
+    TrimeshShapeContactGeneratorWorkspace,
 };
 use crate::geometry::proximity_detector::{
     DefaultProximityDispatcher, ProximityDetectionContext, ProximityDispatcher,
@@ -14,15 +15,16 @@ use crate::geometry::proximity_detector::{
 //    proximity_detector::ProximityDetectionContextSimd, WBall,
 //};
 use crate::geometry::{
-    BroadPhasePairEvent, ColliderGraphIndex, ColliderHandle, ContactEvent, ContactPairFilter,
-    PairFilterContext, ProximityEvent, ProximityPair, ProximityPairFilter, RemovedCollider,
-    SolverFlags,
+    BroadPhasePairEvent, ColliderChanges, ColliderGraphIndex, ColliderHandle, ContactEvent,
+    ContactPairFilter, PairFilterContext, ProximityEvent, ProximityPair, ProximityPairFilter,
+    RemovedCollider, ShapeType, SolverFlags,
 };
 use crate::geometry::{ColliderSet, ContactManifold, ContactPair, InteractionGraph};
 //#[cfg(feature = "simd-is-enabled")]
 //use crate::math::{SimdFloat, SIMD_WIDTH};
 use crate::data::pubsub::Subscription;
 use crate::data::Coarena;
+use crate::ncollide::bounding_volume::BoundingVolume;
 use crate::ncollide::query::Proximity;
 use crate::pipeline::EventHandler;
 use std::collections::HashMap;
@@ -45,6 +47,15 @@ impl ColliderGraphIndices {
 }
 
 /// The narrow-phase responsible for computing precise contact information between colliders.
+///
+/// Cloning the narrow-phase copies all its contact manifolds and contact generator caches.
+/// The caches of the proximity detectors are not copied: they are rebuilt at the next timestep
+/// without affecting the proximity results.
+///
+/// When restoring a snapshot of a simulation, restore the narrow-phase too so the contact impulses
+/// keep warm-starting the constraints solver. A new narrow-phase used with restored colliders works
+/// too, but the contacts are then computed again from scratch, so stacked objects may jitter
+/// while the solver converges again.
 #[cfg_attr(feature = "serde-serialize", derive(Serialize, Deserialize))]
 #[derive(Clone)]
 pub struct NarrowPhase {
@@ -86,6 +97,10 @@ impl NarrowPhase {
     }
 
     /// All the contacts involving the given collider.
+    ///
+    /// Returns `None` if the collider is not known by this narrow-phase. Some of the yielded contact pairs
+    /// may not have any active contact. Check the [`ContactPair::has_any_active_contact`] method to see
+    /// if there is an actual contact.
     pub fn contacts_with(
         &self,
         collider: ColliderHandle,
@@ -106,6 +121,19 @@ impl NarrowPhase {
         )
     }
 
+    /// All the intersections involving the given collider.
+    ///
+    /// Returns `None` if the collider is not known by this narrow-phase. Otherwise, this
+    /// yields the handles of the two colliders of each proximity pair involving `collider`,
+    /// together with a boolean indicating if they are actually intersecting.
+    pub fn intersections_with(
+        &self,
+        collider: ColliderHandle,
+    ) -> Option<impl Iterator<Item = (ColliderHandle, ColliderHandle, bool)> + '_> {
+        self.proximities_with(collider)
+            .map(|it| it.map(|(h1, h2, pair)| (h1, h2, pair.proximity == Proximity::Intersecting)))
+    }
+
     /// The contact pair involving two specific colliders.
     ///
     /// If this returns `None`, there is no contact between the two colliders.
@@ -140,7 +168,32 @@ impl NarrowPhase {
             .map(|c| c.2)
     }
 
+    /// Resets to zero the cached impulses of all the contacts involving the given collider.
+    ///
+    /// The constraints solver will not be warm-started with these impulses at the next timestep. This
+    /// avoids applying stale impulses after teleporting the rigid-body this collider is attached to.
+    pub fn clear_impulses_with(&mut self, collider: ColliderHandle) {
+        if let Some(id) = self.graph_indices.get(collider) {
+            for (_, _, _, pair) in self
+                .contact_graph
+                .interactions_with_mut(id.contact_graph_index)
+            {
+                pair.clear_impulses();
+            }
+        }
+    }
+
+    /// Resets to zero the cached impulses of all the contacts maintained by this narrow-phase.
+    pub fn clear_impulses(&mut self) {
+        for pair in self.contact_graph.interactions_mut() {
+            pair.clear_impulses();
+        }
+    }
+
     /// All the contact pairs maintained by this narrow-phase.
+    ///
+    /// The pairs are yielded in a deterministic order that only depends on the history of the
+    /// simulation, not on any hash seed or hashmap layout.
     pub fn contact_pairs(&self) -> impl Iterator<Item = &ContactPair> {
         self.contact_graph.interactions()
     }
@@ -155,6 +208,11 @@ impl NarrowPhase {
     //     &mut self.contact_graph.interactions
     // }
 
+    // Has this narrow-phase never been maintained with a collider set yet?
+    pub(crate) fn is_new(&self) -> bool {
+        self.removed_colliders.is_none()
+    }
+
     /// Maintain the narrow-phase internal state by taking collider removal into account.
     pub fn maintain(&mut self, colliders: &mut ColliderSet, bodies: &mut RigidBodySet) {
         // Ensure we already subscribed.
@@ -199,6 +257,161 @@ impl NarrowPhase {
 
         colliders.removed_colliders.ack(&mut cursor);
         self.removed_colliders = Some(cursor);
+
+        self.handle_modified_colliders(colliders, bodies);
+        self.handle_disabled_colliders(colliders, bodies);
+    }
+
+    fn handle_modified_colliders(&mut self, colliders: &ColliderSet, bodies: &mut RigidBodySet) {
+        for handle in &colliders.modified_colliders {
+            let collider = match colliders.get(*handle) {
+                Some(collider) => collider,
+                None => continue,
+            };
+
+            let graph_idx = match self.graph_indices.get(*handle) {
+                Some(graph_idx) => graph_idx,
+                None => continue,
+            };
+
+            if collider.changes.contains(ColliderChanges::POSITION) {
+                // The collider has been teleported, so the impulses of its contacts
+                // are no longer meaningful for warm-starting.
+                for (_, _, _, pair) in self
+                    .contact_graph
+                    .interactions_with_mut(graph_idx.contact_graph_index)
+                {
+                    pair.clear_impulses();
+                }
+            }
+
+            if collider.changes.contains(ColliderChanges::SHAPE) {
+                // The contact generators and their workspaces depend on the shapes
+                // so they have to be re-dispatched.
+                for (h1, h2, _, pair) in self
+                    .contact_graph
+                    .interactions_with_mut(graph_idx.contact_graph_index)
+                {
+                    pair.generator = None;
+                    pair.generator_workspace = None;
+
+                    // Manifolds involving sub-shapes are no longer meaningful. Other
+                    // manifolds are kept so that warm-starting still applies.
+                    let is_composite = |h| {
+                        colliders
+                            .get(h)
+                            .map(|c| {
+                                matches!(
+                                    c.shape().shape_type(),
+                                    ShapeType::Trimesh | ShapeType::HeightField
+                                )
+                            })
+                            .unwrap_or(true)
+                    };
+                    if pair.manifolds.len() > 1
+                        || pair
+                            .manifolds
+                            .iter()
+                            .any(|m| m.subshape_index_pair != (0, 0))
+                        || is_composite(h1)
+                        || is_composite(h2)
+                    {
+                        pair.manifolds.clear();
+                    }
+                }
+
+                for (_, _, _, pair) in self
+                    .proximity_graph
+                    .interactions_with_mut(graph_idx.proximity_graph_index)
+                {
+                    pair.detector = None;
+                    pair.detector_workspace = None;
+                }
+            }
+
+            if collider.changes.contains(ColliderChanges::SHAPE_DEFORMED) {
+                // The shape was modified in-place so the parts of the shape interfering
+                // with other colliders must be searched again. The manifolds are kept
+                // so that warm-starting still applies.
+                let inv_pos = collider.position().inverse();
+
+                for (h1, h2, _, pair) in self
+                    .contact_graph
+                    .interactions_with_mut(graph_idx.contact_graph_index)
+                {
+                    // Only the pairs close to the modified part of the shape are affected.
+                    let other = if h1 == *handle { h2 } else { h1 };
+                    let is_affected = colliders
+                        .get(other)
+                        .map(|co| {
+                            co.compute_aabb()
+                                .transform_by(&inv_pos)
+                                .intersects(&collider.deformed_local_aabb)
+                        })
+                        .unwrap_or(false);
+
+                    if !is_affected {
+                        continue;
+                    }
+
+                    if let Some(workspace) = pair
+                        .generator_workspace
+                        .as_mut()
+                        .and_then(|w| w.0.downcast_mut::<TrimeshShapeContactGeneratorWorkspace>())
+                    {
+                        workspace.invalidate_interferences();
+                    }
+
+                    // The bodies resting on the deformed shape may no longer be supported.
+                    for h in [h1, h2].iter() {
+                        if let Some(co) = colliders.get(*h) {
+                            bodies.wake_up(co.parent, true);
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    fn handle_disabled_colliders(&mut self, colliders: &ColliderSet, bodies: &mut RigidBodySet) {
+        for handle in &colliders.modified_colliders {
+            let collider = match colliders.get(*handle) {
+                Some(collider) => collider,
+                None => continue,
+            };
+
+            if !collider.changes.contains(ColliderChanges::ENABLED)
+                || collider.is_enabled_with_parent()
+            {
+                continue;
+            }
+
+            if let Some(graph_idx) = self.graph_indices.get(*handle) {
+                // NOTE: we only remove the edges, not the graph nodes, so the graph
+                // indices of the other colliders remain valid.
+                let contact_graph_id = graph_idx.contact_graph_index;
+                let proximity_graph_id = graph_idx.proximity_graph_index;
+
+                while let Some(edge) = self.contact_graph.graph.edges(contact_graph_id).next() {
+                    let edge = edge.id();
+                    if let Some(ctct) = self.contact_graph.graph.remove_edge(edge) {
+                        // Wake up the dynamic bodies that were in contact.
+                        if ctct.has_any_active_contact() {
+                            for h in &[ctct.pair.collider1, ctct.pair.collider2] {
+                                if let Some(parent) = colliders.get(*h).map(|c| c.parent) {
+                                    bodies.wake_up(parent, true)
+                                }
+                            }
+                        }
+                    }
+                }
+
+                while let Some(edge) = self.proximity_graph.graph.edges(proximity_graph_id).next() {
+                    let edge = edge.id();
+                    let _ = self.proximity_graph.graph.remove_edge(edge);
+                }
+            }
+        }
     }
 
     pub(crate) fn remove_collider<'a>(
@@ -431,6 +644,8 @@ impl NarrowPhase {
                 let context = PairFilterContext {
                     rigid_body1: rb1,
                     rigid_body2: rb2,
+                    collider_handle1: pair.pair.collider1,
+                    collider_handle2: pair.pair.collider2,
                     collider1: co1,
                     collider2: co2,
                 };
@@ -471,6 +686,7 @@ impl NarrowPhase {
         prediction_distance: f32,
         bodies: &RigidBodySet,
         colliders: &ColliderSet,
+        joints: Option<&JointSet>,
         pair_filter: Option<&dyn ContactPairFilter>,
         events: &dyn EventHandler,
     ) {
@@ -496,6 +712,21 @@ impl NarrowPhase {
                 return;
             }
 
+            if let Some(joints) = joints {
+                if !joints.contacts_enabled_between(rb1, rb2) {
+                    // The bodies are attached by a joint with contacts disabled.
+                    // Remove the contacts that may have been computed before the joint was added.
+                    if pair.has_any_active_contact() {
+                        events.handle_contact_event(ContactEvent::Stopped(
+                            pair.pair.collider1,
+                            pair.pair.collider2,
+                        ));
+                    }
+                    pair.clear_contacts();
+                    return;
+                }
+            }
+
             if pair_filter.is_none() && !rb1.is_dynamic() && !rb2.is_dynamic() {
                 // Default filtering rule: no contact between two non-dynamic bodies.
                 return;
@@ -505,6 +736,8 @@ impl NarrowPhase {
                 let context = PairFilterContext {
                     rigid_body1: rb1,
                     rigid_body2: rb2,
+                    collider_handle1: pair.pair.collider1,
+                    collider_handle2: pair.pair.collider2,
                     collider1: co1,
                     collider2: co2,
                 };
@@ -523,6 +756,15 @@ impl NarrowPhase {
                 solver_flags.remove(SolverFlags::COMPUTE_IMPULSES);
             }
 
+            // The materials may have been modified by the user.
+            for manifold in &mut pair.manifolds {
+                if manifold.pair.collider1 == pair.pair.collider1 {
+                    manifold.update_materials(co1, co2);
+                } else {
+                    manifold.update_materials(co2, co1);
+                }
+            }
+
             let dispatcher = DefaultContactDispatcher;
             if pair.generator.is_none() {
                 // We need a redispatch for this generator.
@@ -537,11 +779,22 @@ impl NarrowPhase {
                 }
             }
 
+            // The contact generators work on the shapes without their contact skins.
+            for manifold in &mut pair.manifolds {
+                manifold.remove_contact_skins();
+            }
+
+            let (skin1, skin2) = (co1.contact_skin(), co2.contact_skin());
+            let prediction_distance = co1
+                .effective_prediction_distance(prediction_distance)
+                .max(co2.effective_prediction_distance(prediction_distance))
+                + skin1
+                + skin2;
             let context = ContactGenerationContext {
                 dispatcher: &dispatcher,
                 prediction_distance,
                 colliders,
-                pair,
+                pair: &mut *pair,
                 solver_flags,
             };
 
@@ -550,6 +803,14 @@ impl NarrowPhase {
                 .generator
                 .unwrap()
                 .generate_contacts(context, events);
+
+            for manifold in &mut pair.manifolds {
+                if manifold.pair.collider1 == pair.pair.collider1 {
+                    manifold.apply_contact_skins(skin1, skin2);
+                } else {
+                    manifold.apply_contact_skins(skin2, skin1);
+                }
+            }
         });
     }
 
@@ -591,3 +852,57 @@ impl NarrowPhase {
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use crate::dynamics::RigidBodyBuilder;
+    use crate::geometry::ColliderBuilder;
+    use crate::math::{Isometry, Vector};
+    use crate::pipeline::TestWorld;
+
+    #[test]
+    fn intersections_with() {
+        let mut world = TestWorld::new(Vector::zeros());
+        let ground = world.bodies.insert(RigidBodyBuilder::new_static().build());
+        let sensor = ColliderBuilder::ball(1.0).sensor(true).build();
+        let sensor = world.attach(sensor, ground);
+
+        let ball_at = |world: &mut TestWorld, x: f32, y: f32| {
+            let mut position = Isometry::identity();
+            position.translation.vector.x = x;
+            position.translation.vector.y = y;
+            let rb = RigidBodyBuilder::new_dynamic().position(position).build();
+            world.insert(rb, ColliderBuilder::ball(0.5).build()).1
+        };
+
+        // The AABBs of both balls overlap the AABB of the sensor, but only one of them is
+        // actually intersecting it.
+        let inside = ball_at(&mut world, 1.2, 0.0);
+        let outside = ball_at(&mut world, 1.2, 1.2);
+        world.step();
+        let unknown = ball_at(&mut world, 0.0, 0.0);
+
+        let mut intersections: Vec<_> = world
+            .narrow_phase
+            .intersections_with(sensor)
+            .unwrap()
+            .map(|(h1, h2, intersecting)| {
+                assert!(h1 == sensor || h2 == sensor);
+                (if h1 == sensor { h2 } else { h1 }, intersecting)
+            })
+            .collect();
+        intersections.sort_unstable_by_key(|(handle, _)| handle.into_raw_parts());
+        assert_eq!(intersections, vec![(inside, true), (outside, false)]);
+
+        let inside_intersections: Vec<_> = world
+            .narrow_phase
+            .intersections_with(inside)
+            .unwrap()
+            .collect();
+        assert_eq!(inside_intersections.len(), 1);
+        assert!(inside_intersections[0].2);
+
+        // The collider inserted after the last timestep is not known by the narrow-phase yet.
+        assert!(world.narrow_phase.intersections_with(unknown).is_none());
+    }
+}
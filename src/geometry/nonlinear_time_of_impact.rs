@@ -0,0 +1,228 @@
+use crate::geometry::{
+    HeightField, HeightFieldCellFlags, NonlinearRigidMotion, Shape, Trimesh, TOI,
+};
+use crate::math::{AngVector, Isometry};
+use ncollide::bounding_volume::{BoundingVolume, AABB};
+use ncollide::query;
+use ncollide::shape::SupportMap;
+
+/// Computes the smallest time of impact of two shapes moving with constant linear and angular velocities.
+///
+/// Unlike a linear shape cast, this accounts for the rotation of both shapes during their motion,
+/// so it does not miss the impacts of fast spinning long shapes. The returned time of impact is
+/// the first time in `[0, max_toi]` at which the distance between both shapes becomes smaller than
+/// `target_distance`. Its witness points and normals are expressed in the local-space of each shape.
+///
+/// Convex shapes are handled with conservative advancement. Triangle meshes and heightfields are
+/// handled element-wise against the other shape if it is convex. Returns `None` if the
+/// two shapes are both triangle meshes or heightfields because this combination is not supported yet.
+pub fn nonlinear_time_of_impact(
+    motion1: &NonlinearRigidMotion,
+    shape1: &dyn Shape,
+    motion2: &NonlinearRigidMotion,
+    shape2: &dyn Shape,
+    max_toi: f32,
+    target_distance: f32,
+) -> Option<TOI> {
+    if let (Some(sm1), Some(sm2)) = (shape1.as_support_map(), shape2.as_support_map()) {
+        query::nonlinear_time_of_impact_support_map_support_map(
+            motion1,
+            sm1,
+            motion2,
+            sm2,
+            max_toi,
+            target_distance,
+        )
+    } else if let Some(sm2) = shape2.as_support_map() {
+        nonlinear_time_of_impact_composite_support_map(
+            motion1,
+            shape1,
+            motion2,
+            sm2,
+            shape2,
+            max_toi,
+            target_distance,
+        )
+    } else if let Some(sm1) = shape1.as_support_map() {
+        nonlinear_time_of_impact_composite_support_map(
+            motion2,
+            shape2,
+            motion1,
+            sm1,
+            shape1,
+            max_toi,
+            target_distance,
+        )
+        .map(|toi| toi.swapped())
+    } else {
+        None
+    }
+}
+
+/// A conservative AABB of all the positions of `shape` following `motion` during the time interval
+/// `[0, max_toi]`, enlarged by `margin`.
+pub(crate) fn swept_aabb(
+    motion: &NonlinearRigidMotion,
+    shape: &dyn Shape,
+    max_toi: f32,
+    margin: f32,
+) -> AABB<f32> {
+    // Bound the rotating shape by a ball centered at its center of rotation. This center
+    // follows a straight line.
+    let local_aabb = shape.compute_aabb(&Isometry::identity());
+    let radius = (local_aabb.center() - motion.local_center).norm()
+        + local_aabb.half_extents().norm()
+        + margin;
+    let start = motion.start * motion.local_center;
+    let end = start + motion.linvel * max_toi;
+
+    AABB::new(start.inf(&end), start.sup(&end)).loosened(radius)
+}
+
+/// A conservative AABB of all the positions of `shape2` relative to the local-space of the
+/// composite shape `composite1`, during the time interval `[0, max_toi]`.
+fn swept_local_aabb(
+    motion1: &NonlinearRigidMotion,
+    composite1: &dyn Shape,
+    motion2: &NonlinearRigidMotion,
+    shape2: &dyn Shape,
+    max_toi: f32,
+    target_distance: f32,
+) -> AABB<f32> {
+    if motion1.angvel != na::zero::<AngVector<f32>>() {
+        // The composite shape rotates so any of its elements may be hit.
+        return composite1.compute_aabb(&Isometry::identity());
+    }
+
+    // Since the composite shape doesn't rotate, the motion of shape2 relative to
+    // the composite shape has a constant velocity too.
+    let inv_start1 = motion1.start.inverse();
+    let relative_motion2 = NonlinearRigidMotion::new(
+        0.0,
+        inv_start1 * motion2.start,
+        motion2.local_center,
+        inv_start1 * (motion2.linvel - motion1.linvel),
+        motion2.angvel,
+    );
+
+    swept_aabb(&relative_motion2, shape2, max_toi, target_distance)
+}
+
+fn nonlinear_time_of_impact_composite_support_map(
+    motion1: &NonlinearRigidMotion,
+    composite1: &dyn Shape,
+    motion2: &NonlinearRigidMotion,
+    support_map2: &dyn SupportMap<f32>,
+    shape2: &dyn Shape,
+    max_toi: f32,
+    target_distance: f32,
+) -> Option<TOI> {
+    let local_aabb2 = swept_local_aabb(
+        motion1,
+        composite1,
+        motion2,
+        shape2,
+        max_toi,
+        target_distance,
+    );
+
+    if let Some(trimesh1) = composite1.as_trimesh() {
+        nonlinear_time_of_impact_trimesh_support_map(
+            motion1,
+            trimesh1,
+            motion2,
+            support_map2,
+            &local_aabb2,
+            max_toi,
+            target_distance,
+        )
+    } else if let Some(heightfield1) = composite1.as_heightfield() {
+        nonlinear_time_of_impact_heightfield_support_map(
+            motion1,
+            heightfield1,
+            motion2,
+            support_map2,
+            &local_aabb2,
+            max_toi,
+            target_distance,
+        )
+    } else {
+        None
+    }
+}
+
+fn keep_earliest(best: &mut Option<TOI>, toi: Option<TOI>) {
+    if let Some(toi) = toi {
+        if best.as_ref().map(|best| toi.toi < best.toi).unwrap_or(true) {
+            *best = Some(toi);
+        }
+    }
+}
+
+fn nonlinear_time_of_impact_trimesh_support_map(
+    motion1: &NonlinearRigidMotion,
+    trimesh1: &Trimesh,
+    motion2: &NonlinearRigidMotion,
+    support_map2: &dyn SupportMap<f32>,
+    local_aabb2: &AABB<f32>,
+    max_toi: f32,
+    target_distance: f32,
+) -> Option<TOI> {
+    // TODO: avoid allocation?
+    let mut interferences = Vec::new();
+    trimesh1
+        .waabbs()
+        .intersect_aabb(local_aabb2, &mut interferences);
+
+    let mut best = None;
+
+    for i in interferences {
+        // NOTE: the triangles are expressed in the local-space of the mesh,
+        // so they follow the same motion.
+        let triangle = trimesh1.triangle(i);
+        let toi = query::nonlinear_time_of_impact_support_map_support_map(
+            motion1,
+            &triangle,
+            motion2,
+            support_map2,
+            max_toi,
+            target_distance,
+        );
+        keep_earliest(&mut best, toi);
+    }
+
+    best
+}
+
+fn nonlinear_time_of_impact_heightfield_support_map(
+    motion1: &NonlinearRigidMotion,
+    heightfield1: &HeightField,
+    motion2: &NonlinearRigidMotion,
+    support_map2: &dyn SupportMap<f32>,
+    local_aabb2: &AABB<f32>,
+    max_toi: f32,
+    target_distance: f32,
+) -> Option<TOI> {
+    let mut best = None;
+
+    heightfield1.map_elements_in_local_aabb(local_aabb2, &mut |i, element, _| {
+        if heightfield1
+            .element_cell_flags(i)
+            .contains(HeightFieldCellFlags::REMOVED)
+        {
+            return;
+        }
+
+        let toi = query::nonlinear_time_of_impact_support_map_support_map(
+            motion1,
+            element,
+            motion2,
+            support_map2,
+            max_toi,
+            target_distance,
+        );
+        keep_earliest(&mut best, toi);
+    });
+
+    best
+}
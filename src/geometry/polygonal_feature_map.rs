@@ -1,9 +1,15 @@
 use crate::geometry::PolyhedronFace;
-use crate::geometry::{cuboid, Cone, Cuboid, Cylinder, Segment, Triangle};
-use crate::math::{Point, Vector};
-use approx::AbsDiffEq;
-use na::{Unit, Vector2};
+use crate::geometry::{cuboid, Cuboid, RoundCone, Segment, Triangle};
+use crate::math::Vector;
+use na::Unit;
 use ncollide::shape::SupportMap;
+#[cfg(feature = "dim3")]
+use {
+    crate::geometry::{Cone, Cylinder},
+    crate::math::Point,
+    approx::AbsDiffEq,
+    na::Vector2,
+};
 
 /// Trait implemented by convex shapes with features with polyhedral approximations.
 pub trait PolygonalFeatureMap: SupportMap<f32> {
@@ -16,12 +22,54 @@ impl PolygonalFeatureMap for Segment {
     }
 }
 
+#[cfg(feature = "dim2")]
+impl PolygonalFeatureMap for Triangle {
+    fn local_support_feature(&self, dir: &Unit<Vector<f32>>, out_feature: &mut PolyhedronFace) {
+        // We return the edge with the outward normal closest to `dir`.
+        // The vertices have feature ids 0, 2, 4 and the edges 1, 3, 5.
+        let vertices = [self.a, self.b, self.c];
+        let mut best_dot = -f32::MAX;
+
+        for i in 0..3 {
+            let (a, b, c) = (vertices[i], vertices[(i + 1) % 3], vertices[(i + 2) % 3]);
+            let edge = b - a;
+            let mut normal = Vector::new(edge.y, -edge.x);
+            if normal.dot(&(c - a)) > 0.0 {
+                normal = -normal;
+            }
+
+            let dot = normal.normalize().dot(dir);
+            if dot > best_dot {
+                best_dot = dot;
+                out_feature.vertices = [a, b];
+                out_feature.vids = [i as u8 * 2, ((i + 1) % 3) as u8 * 2];
+                out_feature.fid = i as u8 * 2 + 1;
+            }
+        }
+    }
+}
+
+#[cfg(feature = "dim3")]
 impl PolygonalFeatureMap for Triangle {
     fn local_support_feature(&self, _: &Unit<Vector<f32>>, out_feature: &mut PolyhedronFace) {
         *out_feature = PolyhedronFace::from(*self);
     }
 }
 
+impl PolygonalFeatureMap for RoundCone {
+    fn local_support_feature(&self, dir: &Unit<Vector<f32>>, out_feature: &mut PolyhedronFace) {
+        // We return the segment joining the support points of both balls. This segment
+        // lies on the lateral surface if `dir` is orthogonal to it. Otherwise, the
+        // non-supporting endpoint lies inside of the round cone and will generate a
+        // contact with a larger distance, like for capsules.
+        let seg = Segment::new(
+            self.segment.a + **dir * self.radius_a,
+            self.segment.b + **dir * self.radius_b,
+        );
+        *out_feature = PolyhedronFace::from(seg);
+    }
+}
+
 impl PolygonalFeatureMap for Cuboid {
     fn local_support_feature(&self, dir: &Unit<Vector<f32>>, out_feature: &mut PolyhedronFace) {
         let face = cuboid::support_face(self, **dir);
@@ -29,6 +77,7 @@ impl PolygonalFeatureMap for Cuboid {
     }
 }
 
+#[cfg(feature = "dim3")]
 impl PolygonalFeatureMap for Cylinder {
     fn local_support_feature(&self, dir: &Unit<Vector<f32>>, out_features: &mut PolyhedronFace) {
         // About feature ids.
@@ -85,6 +134,7 @@ impl PolygonalFeatureMap for Cylinder {
     }
 }
 
+#[cfg(feature = "dim3")]
 impl PolygonalFeatureMap for Cone {
     fn local_support_feature(&self, dir: &Unit<Vector<f32>>, out_features: &mut PolyhedronFace) {
         // About feature ids. It is very similar to the feature ids of cylinders.
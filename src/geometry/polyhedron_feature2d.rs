@@ -0,0 +1,91 @@
+use crate::geometry::{self, Contact, ContactManifold, CuboidFeatureFace};
+use crate::math::{Isometry, Point, Vector};
+use ncollide::shape::Segment;
+
+#[derive(Debug, Clone)]
+pub struct PolyhedronFace {
+    pub vertices: [Point<f32>; 2],
+    pub vids: [u8; 2], // Feature ID of the vertices.
+    pub fid: u8,       // Feature ID of the face.
+}
+
+impl Default for PolyhedronFace {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl From<CuboidFeatureFace> for PolyhedronFace {
+    fn from(face: CuboidFeatureFace) -> Self {
+        Self {
+            vertices: face.vertices,
+            vids: face.vids,
+            fid: face.fid,
+        }
+    }
+}
+
+impl From<Segment<f32>> for PolyhedronFace {
+    fn from(seg: Segment<f32>) -> Self {
+        // Vertices have feature ids 0 and 2.
+        // The segment interior has feature id 1.
+        Self {
+            vertices: [seg.a, seg.b],
+            vids: [0, 2],
+            fid: 1,
+        }
+    }
+}
+
+impl PolyhedronFace {
+    pub fn new() -> Self {
+        Self {
+            vertices: [Point::origin(); 2],
+            vids: [0; 2],
+            fid: 0,
+        }
+    }
+
+    pub fn transform_by(&mut self, iso: &Isometry<f32>) {
+        self.vertices[0] = iso * self.vertices[0];
+        self.vertices[1] = iso * self.vertices[1];
+    }
+
+    /// Computes the contacts between two faces, both expressed in the local-space of `face1`.
+    ///
+    /// The faces are clipped along the tangent of `sep_axis1`, and only the
+    /// contacts with a distance smaller than `prediction_distance` are kept.
+    pub fn contacts(
+        prediction_distance: f32,
+        face1: &PolyhedronFace,
+        sep_axis1: &Vector<f32>,
+        face2: &PolyhedronFace,
+        pos21: &Isometry<f32>,
+        manifold: &mut ContactManifold,
+    ) {
+        if let Some(clips) = geometry::clip_segments_with_normal(
+            (face1.vertices[0], face1.vertices[1]),
+            (face2.vertices[0], face2.vertices[1]),
+            *sep_axis1,
+        ) {
+            let fids1 = [face1.vids[0], face1.fid, face1.vids[1]];
+            let fids2 = [face2.vids[0], face2.fid, face2.vids[1]];
+
+            for clip in [clips.0, clips.1].iter() {
+                let dist = (clip.1 - clip.0).dot(sep_axis1);
+
+                if dist <= prediction_distance {
+                    manifold.points.push(Contact {
+                        local_p1: clip.0,
+                        local_p2: pos21 * clip.1,
+                        impulse: 0.0,
+                        tangent_impulse: Contact::zero_tangent_impulse(),
+                        fid1: fids1[clip.2],
+                        fid2: fids2[clip.3],
+                        dist,
+                    });
+                }
+            }
+        }
+    }
+}
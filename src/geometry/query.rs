@@ -0,0 +1,170 @@
+//! Geometric queries between shapes, independent from any collider set.
+//!
+//! The queries on convex shapes are based on their support map: distances and closest points are
+//! computed with the GJK algorithm, and penetrations with the EPA algorithm. These functions
+//! return `None` if one of the shapes isn't convex, i.e., if `Shape::as_support_map` returns `None`.
+
+use crate::geometry::{Shape, TOI};
+use crate::math::{Isometry, Point, Vector};
+use na::Unit;
+use ncollide::query::{self, ClosestPoints};
+
+pub use crate::geometry::{intersection_test, nonlinear_time_of_impact};
+
+/// The penetration between two intersecting convex shapes.
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde-serialize", derive(Serialize, Deserialize))]
+pub struct Penetration {
+    /// The point of the first shape deepest inside of the second shape, in world-space.
+    pub point1: Point<f32>,
+    /// The point of the second shape deepest inside of the first shape, in world-space.
+    pub point2: Point<f32>,
+    /// The penetration direction, pointing toward the exterior of the first shape.
+    ///
+    /// Translating the second shape by `normal1 * depth` separates both shapes.
+    pub normal1: Unit<Vector<f32>>,
+    /// The penetration depth of both shapes.
+    pub depth: f32,
+}
+
+/// The point of a convex shape with the greatest projection on the given direction.
+pub fn support_point(
+    shape: &dyn Shape,
+    position: &Isometry<f32>,
+    dir: &Vector<f32>,
+) -> Option<Point<f32>> {
+    Some(shape.as_support_map()?.support_point(position, dir))
+}
+
+/// The distance between two convex shapes, computed with the GJK algorithm.
+///
+/// This is zero if the shapes are intersecting.
+pub fn distance(
+    shape1: &dyn Shape,
+    position1: &Isometry<f32>,
+    shape2: &dyn Shape,
+    position2: &Isometry<f32>,
+) -> Option<f32> {
+    let sm1 = shape1.as_support_map()?;
+    let sm2 = shape2.as_support_map()?;
+    Some(query::distance_support_map_support_map(
+        position1, sm1, position2, sm2,
+    ))
+}
+
+/// The closest points, in world-space, between two convex shapes separated by at most `max_dist`,
+/// computed with the GJK algorithm.
+///
+/// Returns `None` if the shapes are intersecting or separated by more than `max_dist`.
+pub fn closest_points(
+    shape1: &dyn Shape,
+    position1: &Isometry<f32>,
+    shape2: &dyn Shape,
+    position2: &Isometry<f32>,
+    max_dist: f32,
+) -> Option<(Point<f32>, Point<f32>)> {
+    let sm1 = shape1.as_support_map()?;
+    let sm2 = shape2.as_support_map()?;
+
+    match query::closest_points_support_map_support_map(position1, sm1, position2, sm2, max_dist) {
+        ClosestPoints::WithinMargin(point1, point2) => Some((point1, point2)),
+        ClosestPoints::Intersecting | ClosestPoints::Disjoint => None,
+    }
+}
+
+/// The penetration between two intersecting convex shapes, computed with the EPA algorithm.
+///
+/// Returns `None` if the shapes aren't intersecting.
+pub fn penetration(
+    shape1: &dyn Shape,
+    position1: &Isometry<f32>,
+    shape2: &dyn Shape,
+    position2: &Isometry<f32>,
+) -> Option<Penetration> {
+    let sm1 = shape1.as_support_map()?;
+    let sm2 = shape2.as_support_map()?;
+    let contact = query::contact_support_map_support_map(position1, sm1, position2, sm2, 0.0)?;
+
+    Some(Penetration {
+        point1: contact.world1,
+        point2: contact.world2,
+        normal1: contact.normal,
+        depth: contact.depth,
+    })
+}
+
+/// The smallest time of impact of two convex shapes moving with constant linear velocities.
+///
+/// The returned time of impact is the first time in `[0, max_toi]` at which the distance between
+/// both shapes becomes smaller than `target_distance`. Its witness points and normals are
+/// expressed in the local-space of each shape. Use `nonlinear_time_of_impact` to account for
+/// the angular velocities of the shapes too.
+pub fn time_of_impact(
+    shape1: &dyn Shape,
+    position1: &Isometry<f32>,
+    velocity1: &Vector<f32>,
+    shape2: &dyn Shape,
+    position2: &Isometry<f32>,
+    velocity2: &Vector<f32>,
+    max_toi: f32,
+    target_distance: f32,
+) -> Option<TOI> {
+    let sm1 = shape1.as_support_map()?;
+    let sm2 = shape2.as_support_map()?;
+    query::time_of_impact_support_map_support_map(
+        position1,
+        velocity1,
+        sm1,
+        position2,
+        velocity2,
+        sm2,
+        max_toi,
+        target_distance,
+    )
+}
+
+#[cfg(test)]
+mod test {
+    use crate::geometry::{query, Ball, HeightField};
+    use crate::math::{Isometry, Vector};
+
+    #[test]
+    fn ball_ball_queries() {
+        let ball = Ball::new(1.0);
+        let pos1 = Isometry::identity();
+        let pos2 = Isometry::new(Vector::x() * 3.0, na::zero());
+
+        let dist = query::distance(&ball, &pos1, &ball, &pos2).unwrap();
+        assert!((dist - 1.0).abs() < 1.0e-5);
+
+        let (point1, point2) = query::closest_points(&ball, &pos1, &ball, &pos2, 2.0).unwrap();
+        assert!((point1.x - 1.0).abs() < 1.0e-5 && (point2.x - 2.0).abs() < 1.0e-5);
+        assert!(query::closest_points(&ball, &pos1, &ball, &pos2, 0.5).is_none());
+
+        let toi = query::time_of_impact(
+            &ball,
+            &pos1,
+            &Vector::zeros(),
+            &ball,
+            &pos2,
+            &(-Vector::x() * 2.0),
+            f32::MAX,
+            0.0,
+        )
+        .unwrap();
+        assert!((toi.toi - 0.5).abs() < 1.0e-4);
+
+        let pos2 = Isometry::new(Vector::x() * 1.5, na::zero());
+        let penetration = query::penetration(&ball, &pos1, &ball, &pos2).unwrap();
+        assert!((penetration.depth - 0.5).abs() < 1.0e-5);
+        assert!((penetration.normal1.x - 1.0).abs() < 1.0e-5);
+        assert_eq!(query::distance(&ball, &pos1, &ball, &pos2), Some(0.0));
+
+        // Non-convex shapes aren't supported.
+        #[cfg(feature = "dim2")]
+        let heightfield = HeightField::new(na::DVector::zeros(4), Vector::repeat(1.0));
+        #[cfg(feature = "dim3")]
+        let heightfield = HeightField::new(na::DMatrix::zeros(4, 4), Vector::repeat(1.0));
+        assert!(query::distance(&heightfield, &pos1, &ball, &pos2).is_none());
+    }
+}
@@ -0,0 +1,300 @@
+use crate::geometry::{Ray, RayIntersection, AABB};
+use crate::math::{Isometry, Point, Vector};
+use approx::AbsDiffEq;
+use na::Unit;
+use ncollide::query::{algorithms::VoronoiSimplex, PointProjection, PointQuery, RayCast};
+use ncollide::shape::{FeatureId, Segment, SupportMap};
+
+#[derive(Copy, Clone, Debug)]
+#[cfg_attr(feature = "serde-serialize", derive(Serialize, Deserialize))]
+/// A round cone, also known as a tapered capsule.
+///
+/// This is the convex hull of two balls with possibly different radii. It is equivalent
+/// to a capsule when both radii are equal.
+pub struct RoundCone {
+    /// The segment joining the centers of the two balls.
+    pub segment: Segment<f32>,
+    /// The radius of the ball centered at `self.segment.a`.
+    pub radius_a: f32,
+    /// The radius of the ball centered at `self.segment.b`.
+    pub radius_b: f32,
+}
+
+impl RoundCone {
+    /// Creates a new round cone aligned with the `y` axis.
+    ///
+    /// The ball with radius `radius_a` is centered at `-half_height` and the ball with
+    /// radius `radius_b` is centered at `half_height`.
+    pub fn new_y(half_height: f32, radius_a: f32, radius_b: f32) -> Self {
+        let b = Point::from(Vector::y() * half_height);
+        Self::new(-b, b, radius_a, radius_b)
+    }
+
+    /// Creates a new round cone defined as the convex hull of the ball of radius `radius_a`
+    /// centered at `a` and the ball of radius `radius_b` centered at `b`.
+    pub fn new(a: Point<f32>, b: Point<f32>, radius_a: f32, radius_b: f32) -> Self {
+        let segment = Segment::new(a, b);
+        Self {
+            segment,
+            radius_a,
+            radius_b,
+        }
+    }
+
+    /// The axis-aligned bounding box of this round cone.
+    pub fn aabb(&self, pos: &Isometry<f32>) -> AABB {
+        let a = pos * self.segment.a;
+        let b = pos * self.segment.b;
+        let mins = (a.coords - Vector::repeat(self.radius_a))
+            .inf(&(b.coords - Vector::repeat(self.radius_b)));
+        let maxs = (a.coords + Vector::repeat(self.radius_a))
+            .sup(&(b.coords + Vector::repeat(self.radius_b)));
+        AABB::new(mins.into(), maxs.into())
+    }
+
+    /// Creates a new round cone equal to `self` with all its endpoints transformed by `pos`.
+    pub fn transform_by(&self, pos: &Isometry<f32>) -> Self {
+        Self::new(
+            pos * self.segment.a,
+            pos * self.segment.b,
+            self.radius_a,
+            self.radius_b,
+        )
+    }
+
+    /// Projects a point given in the local-space of this round cone on its boundary.
+    ///
+    /// Also returns whether the point is inside of this round cone, and the feature it is
+    /// projected on: `FeatureId::Vertex(0)` and `FeatureId::Vertex(1)` for the balls centered at
+    /// `self.segment.a` and `self.segment.b`, and, as for a `Capsule`, `FeatureId::Face(0)` and
+    /// `FeatureId::Face(1)` in 2D, or `FeatureId::Edge(0)` in 3D, for the lateral surface.
+    fn project_local_point_on_boundary(&self, pt: &Point<f32>) -> (Point<f32>, bool, FeatureId) {
+        let (a, b) = (self.segment.a, self.segment.b);
+        let ab = b - a;
+        let length = ab.norm();
+
+        // If one ball contains the other, the round cone is just the biggest ball.
+        if length <= (self.radius_a - self.radius_b).abs() {
+            return if self.radius_a >= self.radius_b {
+                project_on_ball(&a, self.radius_a, pt, 0)
+            } else {
+                project_on_ball(&b, self.radius_b, pt, 1)
+            };
+        }
+
+        // Work on the plane containing the principal axis and the point.
+        let axis = ab / length;
+        let ap = *pt - a;
+        let y = ap.dot(&axis);
+        let radial_dir = Unit::try_new(ap - axis * y, f32::default_epsilon())
+            .map(|dir| dir.into_inner())
+            .unwrap_or_else(|| any_orthogonal(&axis));
+        let x = ap.dot(&radial_dir);
+
+        // The lateral surface is tangent to both balls, with the normal `n`.
+        let sin = (self.radius_a - self.radius_b) / length;
+        let cos = (1.0 - sin * sin).sqrt();
+        let n = axis * sin + radial_dir * cos;
+        // The coordinate of the point along the lateral surface, starting from `a`.
+        let h = y * cos - x * sin;
+
+        if h < 0.0 {
+            project_on_ball(&a, self.radius_a, pt, 0)
+        } else if h > length * cos {
+            project_on_ball(&b, self.radius_b, pt, 1)
+        } else {
+            let dist = y * sin + x * cos - self.radius_a;
+            #[cfg(feature = "dim2")]
+            let feature = if radial_dir.perp(&axis) >= 0.0 {
+                FeatureId::Face(0)
+            } else {
+                FeatureId::Face(1)
+            };
+            #[cfg(feature = "dim3")]
+            let feature = FeatureId::Edge(0);
+            (*pt - n * dist, dist < 0.0, feature)
+        }
+    }
+}
+
+fn project_on_ball(
+    center: &Point<f32>,
+    radius: f32,
+    pt: &Point<f32>,
+    vertex: usize,
+) -> (Point<f32>, bool, FeatureId) {
+    let dpt = *pt - center;
+    let (dir, dist) =
+        Unit::try_new_and_get(dpt, f32::default_epsilon()).unwrap_or((Vector::y_axis(), 0.0));
+    (
+        center + *dir * radius,
+        dist < radius,
+        FeatureId::Vertex(vertex),
+    )
+}
+
+fn any_orthogonal(v: &Vector<f32>) -> Vector<f32> {
+    #[cfg(feature = "dim2")]
+    {
+        Vector::new(-v.y, v.x)
+    }
+
+    #[cfg(feature = "dim3")]
+    {
+        use crate::utils::WBasis;
+        v.orthonormal_basis()[0]
+    }
+}
+
+impl SupportMap<f32> for RoundCone {
+    fn local_support_point(&self, dir: &Vector<f32>) -> Point<f32> {
+        let dir = Unit::try_new(*dir, 0.0).unwrap_or(Vector::y_axis());
+        self.local_support_point_toward(&dir)
+    }
+
+    fn local_support_point_toward(&self, dir: &Unit<Vector<f32>>) -> Point<f32> {
+        if dir.dot(&self.segment.a.coords) + self.radius_a
+            > dir.dot(&self.segment.b.coords) + self.radius_b
+        {
+            self.segment.a + **dir * self.radius_a
+        } else {
+            self.segment.b + **dir * self.radius_b
+        }
+    }
+}
+
+impl RayCast<f32> for RoundCone {
+    fn toi_and_normal_with_ray(
+        &self,
+        m: &Isometry<f32>,
+        ray: &Ray,
+        max_toi: f32,
+        solid: bool,
+    ) -> Option<RayIntersection> {
+        let ls_ray = ray.inverse_transform_by(m);
+
+        ncollide::query::ray_intersection_with_support_map_with_params(
+            &Isometry::identity(),
+            self,
+            &mut VoronoiSimplex::new(),
+            &ls_ray,
+            max_toi,
+            solid,
+        )
+        .map(|mut res| {
+            res.normal = m * res.normal;
+            res
+        })
+    }
+}
+
+impl PointQuery<f32> for RoundCone {
+    #[inline]
+    fn project_point(
+        &self,
+        m: &Isometry<f32>,
+        pt: &Point<f32>,
+        solid: bool,
+    ) -> PointProjection<f32> {
+        let local_pt = m.inverse_transform_point(pt);
+        let (proj, inside, _) = self.project_local_point_on_boundary(&local_pt);
+
+        if solid && inside {
+            PointProjection::new(true, *pt)
+        } else {
+            PointProjection::new(inside, m * proj)
+        }
+    }
+
+    #[inline]
+    fn project_point_with_feature(
+        &self,
+        m: &Isometry<f32>,
+        pt: &Point<f32>,
+    ) -> (PointProjection<f32>, FeatureId) {
+        let local_pt = m.inverse_transform_point(pt);
+        let (proj, inside, feature) = self.project_local_point_on_boundary(&local_pt);
+        (PointProjection::new(inside, m * proj), feature)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::RoundCone;
+    use crate::geometry::Ray;
+    use crate::math::{Isometry, Point, Vector};
+    use approx::assert_relative_eq;
+    use ncollide::query::{PointQuery, RayCast};
+    use ncollide::shape::FeatureId;
+
+    #[test]
+    fn round_cone_point_and_ray_queries() {
+        // A round cone with a lateral surface tilted by 30 degrees.
+        let cone = RoundCone::new_y(1.0, 1.0, 0.0);
+        let identity = Isometry::identity();
+
+        // Projection on the ball with the largest radius.
+        let proj = cone.project_point(&identity, &Point::from(-Vector::y() * 3.0), false);
+        assert!(!proj.is_inside);
+        assert_relative_eq!(
+            proj.point,
+            Point::from(-Vector::y() * 2.0),
+            epsilon = 1.0e-5
+        );
+
+        // Projection on the apex.
+        let proj = cone.project_point(&identity, &Point::from(Vector::y() * 3.0), false);
+        assert_relative_eq!(proj.point, Point::from(Vector::y()), epsilon = 1.0e-5);
+
+        // Projection on the lateral surface, tilted by 30 degrees wrt. the principal axis.
+        let pt = Point::from(Vector::x() + Vector::y() * 0.5);
+        let dist = cone.distance_to_point(&identity, &pt, true);
+        let cos = 3.0f32.sqrt() / 2.0;
+        assert_relative_eq!(dist, 0.75 + cos - 1.0, epsilon = 1.0e-5);
+
+        // The lateral surface projection must agree with the ray-cast.
+        let ray = Ray::new(pt, -Vector::x());
+        let toi = cone.toi_with_ray(&identity, &ray, 10.0, true).unwrap();
+        assert_relative_eq!(toi, dist / cos, epsilon = 1.0e-4);
+        let hit = ray.point_at(toi);
+        let proj = cone.project_point(&identity, &hit, false);
+        assert_relative_eq!(proj.point, hit, epsilon = 1.0e-4);
+
+        // Points inside of the round cone.
+        assert!(cone.contains_point(&identity, &Point::origin()));
+        assert!(cone.contains_point(&identity, &Point::from(Vector::y() * 0.9)));
+        assert!(!cone.contains_point(
+            &identity,
+            &Point::from(Vector::x() * 0.5 + Vector::y() * 0.9)
+        ));
+    }
+
+    #[test]
+    fn round_cone_features() {
+        let cone = RoundCone::new_y(1.0, 1.0, 0.0);
+        let identity = Isometry::identity();
+        let feature = |pt: Vector<f32>| {
+            cone.project_point_with_feature(&identity, &Point::from(pt))
+                .1
+        };
+
+        assert_eq!(feature(Vector::y() * -3.0), FeatureId::Vertex(0));
+        assert_eq!(feature(Vector::y() * 3.0), FeatureId::Vertex(1));
+        #[cfg(feature = "dim2")]
+        {
+            assert_eq!(feature(Vector::x() + Vector::y() * 0.5), FeatureId::Face(0));
+            assert_eq!(
+                feature(-Vector::x() + Vector::y() * 0.5),
+                FeatureId::Face(1)
+            );
+        }
+        #[cfg(feature = "dim3")]
+        assert_eq!(feature(Vector::x() + Vector::y() * 0.5), FeatureId::Edge(0));
+
+        // The projection agrees with `project_point`.
+        let (proj, _) = cone.project_point_with_feature(&identity, &Point::from(Vector::x()));
+        let expected = cone.project_point(&identity, &Point::from(Vector::x()), false);
+        assert_relative_eq!(proj.point, expected.point, epsilon = 1.0e-6);
+        assert_eq!(proj.is_inside, expected.is_inside);
+    }
+}
@@ -0,0 +1,114 @@
+use crate::geometry::Cuboid;
+use crate::math::{Isometry, Point, Vector};
+use na::Unit;
+use ncollide::query::{
+    algorithms::VoronoiSimplex, PointProjection, PointQuery, Ray, RayCast, RayIntersection,
+};
+use ncollide::shape::{FeatureId, SupportMap};
+
+/// A cuboid with rounded edges and vertices.
+///
+/// The contacts with a round cuboid are computed on its core cuboid, and then shifted by its
+/// border radius. Therefore, the border radius acts as a collision margin: thin cuboids that
+/// penetrate each other by less than the sum of their border radii only require the computation
+/// of the distance between their core cuboids, which is much more robust than the computation
+/// of a penetration depth.
+#[cfg_attr(feature = "serde-serialize", derive(Serialize, Deserialize))]
+#[derive(Copy, Clone, Debug)]
+pub struct RoundCuboid {
+    /// The cuboid being rounded.
+    pub cuboid: Cuboid,
+    /// The rounding radius.
+    pub border_radius: f32,
+}
+
+impl RoundCuboid {
+    /// Creates a new cuboid where all its edges and vertices are rounded by a radius of `border_radius`.
+    ///
+    /// This is done by applying a dilation of the given radius to the cuboid with the given half-extents.
+    /// Therefore, the half-extents of the resulting shape are `half_extents + border_radius`.
+    pub fn new(half_extents: Vector<f32>, border_radius: f32) -> Self {
+        Self {
+            cuboid: Cuboid::new(half_extents),
+            border_radius,
+        }
+    }
+}
+
+impl SupportMap<f32> for RoundCuboid {
+    fn local_support_point(&self, dir: &Vector<f32>) -> Point<f32> {
+        self.local_support_point_toward(&Unit::new_normalize(*dir))
+    }
+
+    fn local_support_point_toward(&self, dir: &Unit<Vector<f32>>) -> Point<f32> {
+        self.cuboid.local_support_point_toward(dir) + **dir * self.border_radius
+    }
+
+    fn support_point(&self, transform: &Isometry<f32>, dir: &Vector<f32>) -> Point<f32> {
+        let local_dir = transform.inverse_transform_vector(dir);
+        transform * self.local_support_point(&local_dir)
+    }
+
+    fn support_point_toward(
+        &self,
+        transform: &Isometry<f32>,
+        dir: &Unit<Vector<f32>>,
+    ) -> Point<f32> {
+        let local_dir = Unit::new_unchecked(transform.inverse_transform_vector(dir));
+        transform * self.local_support_point_toward(&local_dir)
+    }
+}
+
+impl RayCast<f32> for RoundCuboid {
+    fn toi_and_normal_with_ray(
+        &self,
+        m: &Isometry<f32>,
+        ray: &Ray<f32>,
+        max_toi: f32,
+        solid: bool,
+    ) -> Option<RayIntersection<f32>> {
+        let ls_ray = ray.inverse_transform_by(m);
+
+        ncollide::query::ray_intersection_with_support_map_with_params(
+            &Isometry::identity(),
+            self,
+            &mut VoronoiSimplex::new(),
+            &ls_ray,
+            max_toi,
+            solid,
+        )
+        .map(|mut res| {
+            res.normal = m * res.normal;
+            res
+        })
+    }
+}
+
+impl PointQuery<f32> for RoundCuboid {
+    #[inline]
+    fn project_point(
+        &self,
+        m: &Isometry<f32>,
+        point: &Point<f32>,
+        solid: bool,
+    ) -> PointProjection<f32> {
+        ncollide::query::point_projection_on_support_map(
+            m,
+            self,
+            &mut VoronoiSimplex::new(),
+            point,
+            solid,
+        )
+    }
+
+    #[inline]
+    fn project_point_with_feature(
+        &self,
+        m: &Isometry<f32>,
+        point: &Point<f32>,
+    ) -> (PointProjection<f32>, FeatureId) {
+        // The features of a round cuboid are the features of its core cuboid.
+        let feature = self.cuboid.project_point_with_feature(m, point).1;
+        (self.project_point(m, point, false), feature)
+    }
+}
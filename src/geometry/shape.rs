@@ -1,20 +1,23 @@
 use crate::dynamics::MassProperties;
-use crate::geometry::{Ball, Capsule, Cuboid, HeightField, Segment, Triangle, Trimesh};
+use crate::geometry::{
+    Ball, Capsule, Cuboid, HeightField, PolygonalFeatureMap, RoundCone, RoundCuboid, RoundTriangle,
+    Segment, Triangle, Trimesh,
+};
+#[cfg(feature = "dim3")]
+use crate::geometry::{Cone, Cylinder, RoundCylinder};
 use crate::math::Isometry;
 use downcast_rs::{impl_downcast, DowncastSync};
 #[cfg(feature = "serde-serialize")]
 use erased_serde::Serialize;
+use ncollide::bounding_volume::BoundingVolume;
 use ncollide::bounding_volume::{HasBoundingVolume, AABB};
 use ncollide::query::{PointQuery, RayCast};
+use ncollide::shape::SupportMap;
 use num::Zero;
 use num_derive::FromPrimitive;
-#[cfg(feature = "dim3")]
-use {
-    crate::geometry::{Cone, Cylinder, PolygonalFeatureMap, RoundCylinder},
-    ncollide::bounding_volume::BoundingVolume,
-};
 
 #[derive(Copy, Clone, Debug, FromPrimitive)]
+#[cfg_attr(feature = "serde-serialize", derive(Serialize, Deserialize))]
 /// Enum representing the type of a shape.
 pub enum ShapeType {
     /// A ball shape.
@@ -41,8 +44,6 @@ pub enum ShapeType {
     Cone,
     // /// A custom shape type.
     // Custom(u8),
-    // /// A cuboid with rounded corners.
-    // RoundedCuboid,
     // /// A triangle with rounded corners.
     // RoundedTriangle,
     // /// A triangle-mesh with rounded corners.
@@ -54,9 +55,29 @@ pub enum ShapeType {
     RoundCylinder,
     // /// A cone with rounded corners.
     // RoundedCone,
+    /// A round cone, i.e., a capsule with different radii at each endpoint.
+    RoundCone,
+    /// A triangle with rounded corners.
+    RoundTriangle,
+    /// A cuboid with rounded corners.
+    RoundCuboid,
 }
 
 /// Trait implemented by shapes usable by Rapier.
+///
+/// # Feature identifiers
+///
+/// `PointQuery::project_point_with_feature` identifies the feature of a shape a point is
+/// projected on with the same `FeatureId`s at each call, so they can be used to recognize
+/// one part of a shape over time, e.g., to apply a different friction on each part:
+/// - The features of polygons, polyhedra, cuboids, triangles, and segments are their
+///   vertices, edges (3D only), and faces, as numbered by ncollide.
+/// - A ball has a single feature: `FeatureId::Face(0)`.
+/// - The features of a round shape, e.g., a round cuboid, are the features of the shape
+///   being rounded. A capsule is a round segment: see `Capsule::local_feature_at` for details.
+///   A round cone has the same features as a capsule.
+/// - Cylinders, cones, and round cylinders don't identify their features yet, and return
+///   `FeatureId::Unknown`.
 pub trait Shape: RayCast<f32> + PointQuery<f32> + DowncastSync {
     /// Convert this shape as a serializable entity.
     #[cfg(feature = "serde-serialize")]
@@ -75,8 +96,12 @@ pub trait Shape: RayCast<f32> + PointQuery<f32> + DowncastSync {
     /// Gets the type tag of this shape.
     fn shape_type(&self) -> ShapeType;
 
+    /// Converts this shape to a support-map, if it is a convex shape.
+    fn as_support_map(&self) -> Option<&dyn SupportMap<f32>> {
+        None
+    }
+
     /// Converts this shape to a polygonal feature-map, if it is one.
-    #[cfg(feature = "dim3")]
     fn as_polygonal_feature_map(&self) -> Option<(&dyn PolygonalFeatureMap, f32)> {
         None
     }
@@ -99,16 +124,31 @@ impl dyn Shape {
         self.downcast_ref()
     }
 
+    /// Converts this abstract shape to a round cuboid, if it is one.
+    pub fn as_round_cuboid(&self) -> Option<&RoundCuboid> {
+        self.downcast_ref()
+    }
+
     /// Converts this abstract shape to a capsule, if it is one.
     pub fn as_capsule(&self) -> Option<&Capsule> {
         self.downcast_ref()
     }
 
+    /// Converts this abstract shape to a round cone, if it is one.
+    pub fn as_round_cone(&self) -> Option<&RoundCone> {
+        self.downcast_ref()
+    }
+
     /// Converts this abstract shape to a triangle, if it is one.
     pub fn as_triangle(&self) -> Option<&Triangle> {
         self.downcast_ref()
     }
 
+    /// Converts this abstract shape to a round triangle, if it is one.
+    pub fn as_round_triangle(&self) -> Option<&RoundTriangle> {
+        self.downcast_ref()
+    }
+
     /// Converts this abstract shape to a triangle mesh, if it is one.
     pub fn as_trimesh(&self) -> Option<&Trimesh> {
         self.downcast_ref()
@@ -155,6 +195,10 @@ impl Shape for Ball {
     fn shape_type(&self) -> ShapeType {
         ShapeType::Ball
     }
+
+    fn as_support_map(&self) -> Option<&dyn SupportMap<f32>> {
+        Some(self as &dyn SupportMap<f32>)
+    }
 }
 
 // impl Shape for Polygon {
@@ -194,7 +238,10 @@ impl Shape for Cuboid {
         ShapeType::Cuboid
     }
 
-    #[cfg(feature = "dim3")]
+    fn as_support_map(&self) -> Option<&dyn SupportMap<f32>> {
+        Some(self as &dyn SupportMap<f32>)
+    }
+
     fn as_polygonal_feature_map(&self) -> Option<(&dyn PolygonalFeatureMap, f32)> {
         Some((self as &dyn PolygonalFeatureMap, 0.0))
     }
@@ -218,12 +265,48 @@ impl Shape for Capsule {
         ShapeType::Capsule
     }
 
-    #[cfg(feature = "dim3")]
+    fn as_support_map(&self) -> Option<&dyn SupportMap<f32>> {
+        Some(self as &dyn SupportMap<f32>)
+    }
+
     fn as_polygonal_feature_map(&self) -> Option<(&dyn PolygonalFeatureMap, f32)> {
         Some((&self.segment as &dyn PolygonalFeatureMap, self.radius))
     }
 }
 
+impl Shape for RoundCone {
+    #[cfg(feature = "serde-serialize")]
+    fn as_serialize(&self) -> Option<&dyn Serialize> {
+        Some(self as &dyn Serialize)
+    }
+
+    fn compute_aabb(&self, position: &Isometry<f32>) -> AABB<f32> {
+        self.aabb(position)
+    }
+
+    fn mass_properties(&self, density: f32) -> MassProperties {
+        MassProperties::from_round_cone(
+            density,
+            self.segment.a,
+            self.segment.b,
+            self.radius_a,
+            self.radius_b,
+        )
+    }
+
+    fn shape_type(&self) -> ShapeType {
+        ShapeType::RoundCone
+    }
+
+    fn as_support_map(&self) -> Option<&dyn SupportMap<f32>> {
+        Some(self as &dyn SupportMap<f32>)
+    }
+
+    fn as_polygonal_feature_map(&self) -> Option<(&dyn PolygonalFeatureMap, f32)> {
+        Some((self as &dyn PolygonalFeatureMap, 0.0))
+    }
+}
+
 impl Shape for Triangle {
     #[cfg(feature = "serde-serialize")]
     fn as_serialize(&self) -> Option<&dyn Serialize> {
@@ -234,6 +317,12 @@ impl Shape for Triangle {
         self.bounding_volume(position)
     }
 
+    #[cfg(feature = "dim2")]
+    fn mass_properties(&self, density: f32) -> MassProperties {
+        MassProperties::from_polygon(density, &[self.a, self.b, self.c])
+    }
+
+    #[cfg(feature = "dim3")]
     fn mass_properties(&self, _density: f32) -> MassProperties {
         MassProperties::zero()
     }
@@ -242,7 +331,10 @@ impl Shape for Triangle {
         ShapeType::Triangle
     }
 
-    #[cfg(feature = "dim3")]
+    fn as_support_map(&self) -> Option<&dyn SupportMap<f32>> {
+        Some(self as &dyn SupportMap<f32>)
+    }
+
     fn as_polygonal_feature_map(&self) -> Option<(&dyn PolygonalFeatureMap, f32)> {
         Some((self as &dyn PolygonalFeatureMap, 0.0))
     }
@@ -266,12 +358,104 @@ impl Shape for Segment {
         ShapeType::Segment
     }
 
-    #[cfg(feature = "dim3")]
+    fn as_support_map(&self) -> Option<&dyn SupportMap<f32>> {
+        Some(self as &dyn SupportMap<f32>)
+    }
+
     fn as_polygonal_feature_map(&self) -> Option<(&dyn PolygonalFeatureMap, f32)> {
         Some((self as &dyn PolygonalFeatureMap, 0.0))
     }
 }
 
+impl Shape for RoundCuboid {
+    #[cfg(feature = "serde-serialize")]
+    fn as_serialize(&self) -> Option<&dyn Serialize> {
+        Some(self as &dyn Serialize)
+    }
+
+    fn compute_aabb(&self, position: &Isometry<f32>) -> AABB<f32> {
+        self.cuboid
+            .compute_aabb(position)
+            .loosened(self.border_radius)
+    }
+
+    fn mass_properties(&self, density: f32) -> MassProperties {
+        // We ignore the rounded edges here, and approximate
+        // this shape with its enclosing cuboid.
+        let half_extents = self.cuboid.half_extents.add_scalar(self.border_radius);
+        MassProperties::from_cuboid(density, half_extents)
+    }
+
+    fn shape_type(&self) -> ShapeType {
+        ShapeType::RoundCuboid
+    }
+
+    fn as_support_map(&self) -> Option<&dyn SupportMap<f32>> {
+        Some(self as &dyn SupportMap<f32>)
+    }
+
+    fn as_polygonal_feature_map(&self) -> Option<(&dyn PolygonalFeatureMap, f32)> {
+        Some((&self.cuboid as &dyn PolygonalFeatureMap, self.border_radius))
+    }
+}
+
+impl Shape for RoundTriangle {
+    #[cfg(feature = "serde-serialize")]
+    fn as_serialize(&self) -> Option<&dyn Serialize> {
+        Some(self as &dyn Serialize)
+    }
+
+    fn compute_aabb(&self, position: &Isometry<f32>) -> AABB<f32> {
+        self.triangle
+            .compute_aabb(position)
+            .loosened(self.border_radius)
+    }
+
+    #[cfg(feature = "dim2")]
+    fn mass_properties(&self, density: f32) -> MassProperties {
+        // We ignore the margin here.
+        self.triangle.mass_properties(density)
+    }
+
+    #[cfg(feature = "dim3")]
+    fn mass_properties(&self, density: f32) -> MassProperties {
+        // We ignore the rounded edges here, and approximate
+        // this shape with a prism of thickness `2 * border_radius`.
+        let tri = &self.triangle;
+        let offset = tri.normal().map(|n| *n * self.border_radius);
+        let offset = offset.unwrap_or_else(crate::math::Vector::zeros);
+        let vertices = [
+            tri.a + offset,
+            tri.b + offset,
+            tri.c + offset,
+            tri.a - offset,
+            tri.b - offset,
+            tri.c - offset,
+        ];
+        let hull = ncollide::transformation::convex_hull(&vertices);
+        MassProperties::from_convex_polyhedron(
+            density,
+            &hull.coords,
+            &hull.indices.unwrap_unified(),
+        )
+    }
+
+    fn shape_type(&self) -> ShapeType {
+        ShapeType::RoundTriangle
+    }
+
+    fn as_support_map(&self) -> Option<&dyn SupportMap<f32>> {
+        Some(self as &dyn SupportMap<f32>)
+    }
+
+    fn as_polygonal_feature_map(&self) -> Option<(&dyn PolygonalFeatureMap, f32)> {
+        Some((
+            &self.triangle as &dyn PolygonalFeatureMap,
+            self.border_radius,
+        ))
+    }
+}
+
 impl Shape for Trimesh {
     #[cfg(feature = "serde-serialize")]
     fn as_serialize(&self) -> Option<&dyn Serialize> {
@@ -282,8 +466,12 @@ impl Shape for Trimesh {
         self.aabb(position)
     }
 
-    fn mass_properties(&self, _density: f32) -> MassProperties {
-        MassProperties::zero()
+    fn mass_properties(&self, density: f32) -> MassProperties {
+        // NOTE: the mass and the angular inertia are proportional to the density.
+        let mut props = *self.unit_mass_properties();
+        props.inv_mass *= crate::utils::inv(density);
+        props.inv_principal_inertia_sqrt *= crate::utils::inv(density.sqrt());
+        props
     }
 
     fn shape_type(&self) -> ShapeType {
@@ -329,6 +517,10 @@ impl Shape for Cylinder {
         ShapeType::Cylinder
     }
 
+    fn as_support_map(&self) -> Option<&dyn SupportMap<f32>> {
+        Some(self as &dyn SupportMap<f32>)
+    }
+
     #[cfg(feature = "dim3")]
     fn as_polygonal_feature_map(&self) -> Option<(&dyn PolygonalFeatureMap, f32)> {
         Some((self as &dyn PolygonalFeatureMap, 0.0))
@@ -354,6 +546,10 @@ impl Shape for Cone {
         ShapeType::Cone
     }
 
+    fn as_support_map(&self) -> Option<&dyn SupportMap<f32>> {
+        Some(self as &dyn SupportMap<f32>)
+    }
+
     #[cfg(feature = "dim3")]
     fn as_polygonal_feature_map(&self) -> Option<(&dyn PolygonalFeatureMap, f32)> {
         Some((self as &dyn PolygonalFeatureMap, 0.0))
@@ -382,6 +578,10 @@ impl Shape for RoundCylinder {
         ShapeType::RoundCylinder
     }
 
+    fn as_support_map(&self) -> Option<&dyn SupportMap<f32>> {
+        Some(self as &dyn SupportMap<f32>)
+    }
+
     #[cfg(feature = "dim3")]
     fn as_polygonal_feature_map(&self) -> Option<(&dyn PolygonalFeatureMap, f32)> {
         Some((
@@ -0,0 +1,241 @@
+#[cfg(feature = "serde-serialize")]
+use crate::geometry::ShapeType;
+use crate::geometry::{
+    Ball, Capsule, Cuboid, HeightField, RoundCone, RoundCuboid, RoundTriangle, Segment, Shape,
+    Triangle, Trimesh,
+};
+#[cfg(feature = "dim3")]
+use crate::geometry::{Cone, Cylinder, RoundCylinder};
+use crate::math::{Point, Vector};
+use na::{Point3, Unit};
+use std::ops::Deref;
+use std::sync::Arc;
+
+/// A reference-counted shape, typically used as the shape of a collider.
+///
+/// Cloning a `SharedShape` is cheap because it does not clone the underlying shape, so the same
+/// shape can be shared by any number of colliders. This is useful when many colliders have the
+/// same shape, particularly for triangle meshes which would otherwise duplicate their vertex
+/// buffers and acceleration structures:
+///
+/// ```ignore
+/// let crate_shape = SharedShape::cuboid(Vector::repeat(0.5));
+/// for body in crate_bodies {
+///     let collider = ColliderBuilder::new(crate_shape.clone()).build();
+///     colliders.insert(collider, body, &mut bodies);
+/// }
+/// ```
+#[derive(Clone)]
+pub struct SharedShape(pub Arc<dyn Shape>);
+
+impl Deref for SharedShape {
+    type Target = dyn Shape;
+    fn deref(&self) -> &dyn Shape {
+        &*self.0
+    }
+}
+
+impl SharedShape {
+    /// Wraps the given shape into a `SharedShape`.
+    pub fn new(shape: impl Shape) -> Self {
+        SharedShape(Arc::new(shape))
+    }
+
+    /// Returns `true` if `self` and `other` share the same underlying shape allocation.
+    pub fn ptr_eq(&self, other: &SharedShape) -> bool {
+        Arc::ptr_eq(&self.0, &other.0)
+    }
+
+    /// Initialize a ball shape defined by its radius.
+    pub fn ball(radius: f32) -> Self {
+        SharedShape(Arc::new(Ball::new(radius)))
+    }
+
+    /// Initialize a cylindrical shape defined by its half-height
+    /// (along along the y axis) and its radius.
+    #[cfg(feature = "dim3")]
+    pub fn cylinder(half_height: f32, radius: f32) -> Self {
+        SharedShape(Arc::new(Cylinder::new(half_height, radius)))
+    }
+
+    /// Initialize a rounded cylindrical shape defined by its half-height
+    /// (along along the y axis), its radius, and its roundedness (the
+    /// radius of the sphere used for dilating the cylinder).
+    #[cfg(feature = "dim3")]
+    pub fn round_cylinder(half_height: f32, radius: f32, border_radius: f32) -> Self {
+        SharedShape(Arc::new(RoundCylinder::new(
+            half_height,
+            radius,
+            border_radius,
+        )))
+    }
+
+    /// Initialize a cone shape defined by its half-height
+    /// (along along the y axis) and its basis radius.
+    #[cfg(feature = "dim3")]
+    pub fn cone(half_height: f32, radius: f32) -> Self {
+        SharedShape(Arc::new(Cone::new(half_height, radius)))
+    }
+
+    /// Initialize a cuboid shape defined by its half-extents.
+    pub fn cuboid(half_extents: Vector<f32>) -> Self {
+        SharedShape(Arc::new(Cuboid::new(half_extents)))
+    }
+
+    /// Initialize a cuboid shape with rounded corners, defined by its half-extents and the radius
+    /// of the sphere used for dilating the cuboid.
+    ///
+    /// The half-extents of the resulting shape are `half_extents + border_radius`.
+    pub fn round_cuboid(half_extents: Vector<f32>, border_radius: f32) -> Self {
+        SharedShape(Arc::new(RoundCuboid::new(half_extents, border_radius)))
+    }
+
+    /// Initialize a capsule shape from its endpoints and radius.
+    pub fn capsule(a: Point<f32>, b: Point<f32>, radius: f32) -> Self {
+        SharedShape(Arc::new(Capsule::new(a, b, radius)))
+    }
+
+    /// Initialize a capsule shape centered at the origin and aligned with the given axis.
+    pub fn capsule_along(axis: Unit<Vector<f32>>, half_height: f32, radius: f32) -> Self {
+        SharedShape(Arc::new(Capsule::new_along(axis, half_height, radius)))
+    }
+
+    /// Initialize a round cone shape from its endpoints and the radius at each endpoint.
+    pub fn round_cone(a: Point<f32>, b: Point<f32>, radius_a: f32, radius_b: f32) -> Self {
+        SharedShape(Arc::new(RoundCone::new(a, b, radius_a, radius_b)))
+    }
+
+    /// Initialize a segment shape from its endpoints.
+    pub fn segment(a: Point<f32>, b: Point<f32>) -> Self {
+        SharedShape(Arc::new(Segment::new(a, b)))
+    }
+
+    /// Initializes a triangle shape.
+    pub fn triangle(a: Point<f32>, b: Point<f32>, c: Point<f32>) -> Self {
+        SharedShape(Arc::new(Triangle::new(a, b, c)))
+    }
+
+    /// Initializes a triangle shape with rounded corners.
+    pub fn round_triangle(a: Point<f32>, b: Point<f32>, c: Point<f32>, border_radius: f32) -> Self {
+        SharedShape(Arc::new(RoundTriangle::new(a, b, c, border_radius)))
+    }
+
+    /// Initializes a triangle mesh shape defined by its vertex and index buffers.
+    pub fn trimesh(vertices: Vec<Point<f32>>, indices: Vec<Point3<u32>>) -> Self {
+        SharedShape(Arc::new(Trimesh::new(vertices, indices)))
+    }
+
+    /// Initializes an heightfield shape defined by its set of height and a scale
+    /// factor along each coordinate axis.
+    #[cfg(feature = "dim2")]
+    pub fn heightfield(heights: na::DVector<f32>, scale: Vector<f32>) -> Self {
+        SharedShape(Arc::new(HeightField::new(heights, scale)))
+    }
+
+    /// Initializes an heightfield shape on the x-z plane defined by its set of height and a scale
+    /// factor along each coordinate axis.
+    #[cfg(feature = "dim3")]
+    pub fn heightfield(heights: na::DMatrix<f32>, scale: Vector<f32>) -> Self {
+        SharedShape(Arc::new(HeightField::new(heights, scale)))
+    }
+}
+
+#[cfg(feature = "serde-serialize")]
+impl serde::Serialize for SharedShape {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        use crate::serde::ser::SerializeStruct;
+
+        if let Some(ser) = self.0.as_serialize() {
+            let typ = self.0.shape_type();
+            let mut state = serializer.serialize_struct("SharedShape", 2)?;
+            state.serialize_field("tag", &(typ as i32))?;
+            state.serialize_field("inner", ser)?;
+            state.end()
+        } else {
+            Err(serde::ser::Error::custom(
+                "Found a non-serializable custom shape.",
+            ))
+        }
+    }
+}
+
+#[cfg(feature = "serde-serialize")]
+impl<'de> serde::Deserialize<'de> for SharedShape {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        struct Visitor {};
+        impl<'de> serde::de::Visitor<'de> for Visitor {
+            type Value = SharedShape;
+            fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+                write!(formatter, "one shape type tag and the inner shape data")
+            }
+
+            fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+            where
+                A: serde::de::SeqAccess<'de>,
+            {
+                use num::cast::FromPrimitive;
+
+                let tag: i32 = seq
+                    .next_element()?
+                    .ok_or_else(|| serde::de::Error::invalid_length(0, &self))?;
+
+                fn deser<'de, A, S: Shape + serde::Deserialize<'de>>(
+                    seq: &mut A,
+                ) -> Result<Arc<dyn Shape>, A::Error>
+                where
+                    A: serde::de::SeqAccess<'de>,
+                {
+                    let shape: S = seq.next_element()?.ok_or_else(|| {
+                        serde::de::Error::custom("Failed to deserialize builtin shape.")
+                    })?;
+                    Ok(Arc::new(shape) as Arc<dyn Shape>)
+                }
+
+                let shape = match ShapeType::from_i32(tag) {
+                    Some(ShapeType::Ball) => deser::<A, Ball>(&mut seq)?,
+                    Some(ShapeType::Polygon) => {
+                        // NOTE: polygons can't be used as collider shapes yet
+                        // so they can't have been serialized.
+                        return Err(serde::de::Error::custom(
+                            "found a polygon shape, which is not supported yet",
+                        ));
+                    }
+                    Some(ShapeType::Cuboid) => deser::<A, Cuboid>(&mut seq)?,
+                    Some(ShapeType::Capsule) => deser::<A, Capsule>(&mut seq)?,
+                    Some(ShapeType::Triangle) => deser::<A, Triangle>(&mut seq)?,
+                    Some(ShapeType::Segment) => deser::<A, Segment>(&mut seq)?,
+                    Some(ShapeType::Trimesh) => deser::<A, Trimesh>(&mut seq)?,
+                    Some(ShapeType::HeightField) => deser::<A, HeightField>(&mut seq)?,
+                    #[cfg(feature = "dim3")]
+                    Some(ShapeType::Cylinder) => deser::<A, Cylinder>(&mut seq)?,
+                    #[cfg(feature = "dim3")]
+                    Some(ShapeType::Cone) => deser::<A, Cone>(&mut seq)?,
+                    #[cfg(feature = "dim3")]
+                    Some(ShapeType::RoundCylinder) => deser::<A, RoundCylinder>(&mut seq)?,
+                    Some(ShapeType::RoundCone) => deser::<A, RoundCone>(&mut seq)?,
+                    Some(ShapeType::RoundTriangle) => deser::<A, RoundTriangle>(&mut seq)?,
+                    Some(ShapeType::RoundCuboid) => deser::<A, RoundCuboid>(&mut seq)?,
+                    None => {
+                        return Err(serde::de::Error::custom(
+                            "found invalid shape type to deserialize",
+                        ))
+                    }
+                };
+
+                Ok(SharedShape(shape))
+            }
+        }
+
+        deserializer.deserialize_struct("SharedShape", &["tag", "inner"], Visitor {})
+    }
+}
+
+/// The shape of a collider.
+#[deprecated(note = "renamed to `SharedShape`")]
+pub type ColliderShape = SharedShape;
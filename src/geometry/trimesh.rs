@@ -1,9 +1,11 @@
+use crate::dynamics::MassProperties;
 use crate::geometry::{PointProjection, Ray, RayIntersection, Triangle, WQuadtree};
-use crate::math::{Isometry, Point};
+use crate::math::{Isometry, Point, Vector};
 use na::Point3;
 use ncollide::bounding_volume::{HasBoundingVolume, AABB};
 use ncollide::query::{PointQuery, RayCast};
 use ncollide::shape::FeatureId;
+use num::Zero;
 
 #[derive(Clone)]
 #[cfg_attr(feature = "serde-serialize", derive(Serialize, Deserialize))]
@@ -13,6 +15,42 @@ pub struct Trimesh {
     aabb: AABB<f32>,
     vertices: Vec<Point<f32>>,
     indices: Vec<Point3<u32>>,
+    // The triangle sharing each edge of each triangle, if there is exactly one, and
+    // whether the windings of both triangles are inconsistent.
+    #[cfg(feature = "dim3")]
+    adjacent_triangles: Vec<[Option<(u32, bool)>; 3]>,
+    #[cfg(feature = "dim3")]
+    adjacent_normals: Vec<[Option<Vector<f32>>; 3]>,
+    #[cfg(feature = "dim2")]
+    boundary_topology: Vec<[Option<BoundaryEdgeTopology>; 3]>,
+    #[cfg(feature = "dim2")]
+    boundary_edges: Vec<[Option<BoundaryEdge>; 3]>,
+    // The mass properties of the convex hull of this mesh, with a density of 1.
+    unit_mass_properties: MassProperties,
+}
+
+/// An edge of a 2D triangle mesh that belongs to a single triangle.
+#[cfg(feature = "dim2")]
+#[derive(Copy, Clone, Debug)]
+#[cfg_attr(feature = "serde-serialize", derive(Serialize, Deserialize))]
+pub(crate) struct BoundaryEdge {
+    /// The normal of this edge, pointing outside of the mesh.
+    pub normal: Vector<f32>,
+    /// The outward normals of the boundary edges sharing the first and the second vertex of
+    /// this edge, if there is exactly one such edge.
+    pub adjacent_normals: [Option<Vector<f32>>; 2],
+}
+
+/// The boundary edges adjacent to a boundary edge of a 2D triangle mesh.
+///
+/// This only depends on the index buffer of the mesh.
+#[cfg(feature = "dim2")]
+#[derive(Copy, Clone, Debug)]
+#[cfg_attr(feature = "serde-serialize", derive(Serialize, Deserialize))]
+struct BoundaryEdgeTopology {
+    // The triangle and edge indices of the boundary edges sharing the first and the second
+    // vertex of this edge, if there is exactly one such edge.
+    adjacent_edges: [Option<(u32, u8)>; 2],
 }
 
 impl Trimesh {
@@ -28,34 +66,313 @@ impl Trimesh {
         );
 
         let aabb = AABB::from_points(&vertices);
-        let data = indices.iter().enumerate().map(|(i, idx)| {
-            let aabb = Triangle::new(
-                vertices[idx[0] as usize],
-                vertices[idx[1] as usize],
-                vertices[idx[2] as usize],
-            )
-            .local_bounding_volume();
-            (i, aabb)
-        });
+        let data = indices
+            .iter()
+            .enumerate()
+            .map(|(i, idx)| (i, Self::triangle_aabb(&vertices, idx)));
 
         let mut wquadtree = WQuadtree::new();
         // NOTE: we apply no dilation factor because we won't
         // update this tree dynamically.
         wquadtree.clear_and_rebuild(data, 0.0);
 
+        #[cfg(feature = "dim3")]
+        let adjacent_triangles = Self::compute_adjacent_triangles(&indices);
+        #[cfg(feature = "dim3")]
+        let adjacent_normals =
+            Self::compute_adjacent_normals(&vertices, &indices, &adjacent_triangles);
+        #[cfg(feature = "dim2")]
+        let boundary_topology = Self::compute_boundary_topology(&indices);
+        #[cfg(feature = "dim2")]
+        let boundary_edges = Self::compute_boundary_edges(&vertices, &indices, &boundary_topology);
+        let unit_mass_properties = Self::compute_unit_mass_properties(&vertices, &aabb);
+
         Self {
             wquadtree,
             aabb,
             vertices,
             indices,
+            #[cfg(feature = "dim3")]
+            adjacent_triangles,
+            #[cfg(feature = "dim3")]
+            adjacent_normals,
+            #[cfg(feature = "dim2")]
+            boundary_topology,
+            #[cfg(feature = "dim2")]
+            boundary_edges,
+            unit_mass_properties,
+        }
+    }
+
+    fn triangle_aabb(vertices: &[Point<f32>], idx: &Point3<u32>) -> AABB<f32> {
+        Triangle::new(
+            vertices[idx[0] as usize],
+            vertices[idx[1] as usize],
+            vertices[idx[2] as usize],
+        )
+        .local_bounding_volume()
+    }
+
+    #[cfg(feature = "dim3")]
+    fn compute_adjacent_triangles(indices: &[Point3<u32>]) -> Vec<[Option<(u32, bool)>; 3]> {
+        use std::collections::HashMap;
+
+        // Associate each edge to the triangles containing it.
+        let mut edges: HashMap<(u32, u32), Vec<(usize, usize)>> = HashMap::new();
+        for (i, idx) in indices.iter().enumerate() {
+            for k in 0..3 {
+                let (a, b) = (idx[k], idx[(k + 1) % 3]);
+                edges.entry((a.min(b), a.max(b))).or_default().push((i, k));
+            }
+        }
+
+        let mut result = vec![[None; 3]; indices.len()];
+
+        for triangles in edges.values() {
+            // Edges that are not shared by exactly two triangles are
+            // considered as boundary edges.
+            if let [(i1, k1), (i2, k2)] = triangles[..] {
+                // If both triangles traverse the edge along the same direction,
+                // their winding are not consistent.
+                let inconsistent = indices[i1][k1] == indices[i2][k2];
+                result[i1][k1] = Some((i2 as u32, inconsistent));
+                result[i2][k2] = Some((i1 as u32, inconsistent));
+            }
+        }
+
+        result
+    }
+
+    #[cfg(feature = "dim3")]
+    fn compute_adjacent_normals(
+        vertices: &[Point<f32>],
+        indices: &[Point3<u32>],
+        adjacent_triangles: &[[Option<(u32, bool)>; 3]],
+    ) -> Vec<[Option<Vector<f32>>; 3]> {
+        let normals: Vec<_> = indices
+            .iter()
+            .map(|idx| {
+                Triangle::new(
+                    vertices[idx[0] as usize],
+                    vertices[idx[1] as usize],
+                    vertices[idx[2] as usize],
+                )
+                .normal()
+                .map(|n| n.into_inner())
+            })
+            .collect();
+
+        normals
+            .iter()
+            .zip(adjacent_triangles.iter())
+            .map(|(normal, adjacent)| {
+                let mut result = [None; 3];
+
+                if normal.is_some() {
+                    for (k, adjacent) in adjacent.iter().enumerate() {
+                        result[k] = adjacent.and_then(|(j, inconsistent)| {
+                            // Flip the normal of the adjacent triangle if its winding
+                            // is not consistent with ours.
+                            let sign = if inconsistent { -1.0 } else { 1.0 };
+                            normals[j as usize].map(|n| n * sign)
+                        });
+                    }
+                }
+
+                result
+            })
+            .collect()
+    }
+
+    #[cfg(feature = "dim2")]
+    fn compute_boundary_topology(
+        indices: &[Point3<u32>],
+    ) -> Vec<[Option<BoundaryEdgeTopology>; 3]> {
+        use std::collections::HashMap;
+
+        // Count the triangles containing each edge.
+        let mut edges: HashMap<(u32, u32), usize> = HashMap::new();
+        for idx in indices {
+            for k in 0..3 {
+                let (a, b) = (idx[k], idx[(k + 1) % 3]);
+                *edges.entry((a.min(b), a.max(b))).or_default() += 1;
+            }
+        }
+
+        // Associate each vertex to the boundary edges containing it.
+        let mut result = vec![[None; 3]; indices.len()];
+        let mut vertex_edges: HashMap<u32, Vec<(usize, usize)>> = HashMap::new();
+
+        for (i, idx) in indices.iter().enumerate() {
+            for k in 0..3 {
+                let (a, b) = (idx[k], idx[(k + 1) % 3]);
+
+                if edges[&(a.min(b), a.max(b))] == 1 {
+                    result[i][k] = Some(BoundaryEdgeTopology {
+                        adjacent_edges: [None; 2],
+                    });
+                    vertex_edges.entry(a).or_default().push((i, k));
+                    vertex_edges.entry(b).or_default().push((i, k));
+                }
+            }
+        }
+
+        for (vertex, incident) in &vertex_edges {
+            // Vertices that are not shared by exactly two boundary edges are
+            // considered as corners.
+            if let [(i1, k1), (i2, k2)] = incident[..] {
+                for &(i, k, adjacent) in &[(i1, k1, (i2, k2)), (i2, k2, (i1, k1))] {
+                    let slot = if indices[i][k] == *vertex { 0 } else { 1 };
+
+                    if let Some(edge) = &mut result[i][k] {
+                        edge.adjacent_edges[slot] = Some((adjacent.0 as u32, adjacent.1 as u8));
+                    }
+                }
+            }
+        }
+
+        result
+    }
+
+    #[cfg(feature = "dim2")]
+    fn compute_boundary_edges(
+        vertices: &[Point<f32>],
+        indices: &[Point3<u32>],
+        topology: &[[Option<BoundaryEdgeTopology>; 3]],
+    ) -> Vec<[Option<BoundaryEdge>; 3]> {
+        let outward_normal = |i: usize, k: usize| {
+            let idx = indices[i];
+            let a = vertices[idx[k] as usize];
+            let b = vertices[idx[(k + 1) % 3] as usize];
+            let c = vertices[idx[(k + 2) % 3] as usize];
+            let ab = b - a;
+            let n = Vector::new(ab.y, -ab.x);
+            let n = if n.dot(&(c - a)) > 0.0 { -n } else { n };
+            n.try_normalize(1.0e-6)
+        };
+
+        let normals: Vec<[Option<Vector<f32>>; 3]> = topology
+            .iter()
+            .enumerate()
+            .map(|(i, edges)| {
+                let mut normals = [None; 3];
+                for (k, edge) in edges.iter().enumerate() {
+                    if edge.is_some() {
+                        normals[k] = outward_normal(i, k);
+                    }
+                }
+                normals
+            })
+            .collect();
+
+        topology
+            .iter()
+            .zip(normals.iter())
+            .map(|(edges, edge_normals)| {
+                let mut result = [None; 3];
+
+                for k in 0..3 {
+                    if let (Some(edge), Some(normal)) = (edges[k], edge_normals[k]) {
+                        let adjacent_normal = |slot: usize| {
+                            edge.adjacent_edges[slot]
+                                .and_then(|(i, k)| normals[i as usize][k as usize])
+                        };
+                        result[k] = Some(BoundaryEdge {
+                            normal,
+                            adjacent_normals: [adjacent_normal(0), adjacent_normal(1)],
+                        });
+                    }
+                }
+
+                result
+            })
+            .collect()
+    }
+
+    // NOTE: we approximate the mass properties of the mesh
+    // with the mass properties of its convex hull.
+    fn compute_unit_mass_properties(vertices: &[Point<f32>], aabb: &AABB<f32>) -> MassProperties {
+        // The convex hull computation fails if the vertices are not affinely independent,
+        // e.g., if all the vertices of a 3D mesh are coplanar. Such a hull has no volume anyway.
+        if Self::is_flat(vertices, aabb.half_extents().norm() * 1.0e-5) {
+            return MassProperties::zero();
+        }
+
+        #[cfg(feature = "dim2")]
+        {
+            let hull = ncollide::transformation::convex_hull(vertices);
+            MassProperties::from_polygon(1.0, hull.coords())
+        }
+
+        #[cfg(feature = "dim3")]
+        {
+            let hull = ncollide::transformation::convex_hull(vertices);
+            MassProperties::from_convex_polyhedron(
+                1.0,
+                &hull.coords,
+                &hull.indices.unwrap_unified(),
+            )
+        }
+    }
+
+    /// Checks if all the vertices are closer than `eps` to a line (in 2D) or a plane (in 3D).
+    fn is_flat(vertices: &[Point<f32>], eps: f32) -> bool {
+        let farthest = |dist: &dyn Fn(&Point<f32>) -> f32| {
+            vertices
+                .iter()
+                .map(|pt| (dist(pt), *pt))
+                .max_by(|a, b| a.0.partial_cmp(&b.0).unwrap_or(std::cmp::Ordering::Equal))
+                .filter(|(dist, _)| *dist > eps)
+        };
+
+        let p0 = vertices[0];
+        let p1 = match farthest(&|pt| na::distance(&p0, pt)) {
+            Some((_, p1)) => p1,
+            None => return true,
+        };
+        let dir = (p1 - p0).normalize();
+
+        #[cfg(feature = "dim2")]
+        {
+            let normal = Vector::new(-dir.y, dir.x);
+            farthest(&|pt| (pt - p0).dot(&normal).abs()).is_none()
+        }
+
+        #[cfg(feature = "dim3")]
+        {
+            let p2 = match farthest(&|pt| (pt - p0).cross(&dir).norm()) {
+                Some((_, p2)) => p2,
+                None => return true,
+            };
+            let normal = dir.cross(&(p2 - p0)).normalize();
+            farthest(&|pt| (pt - p0).dot(&normal).abs()).is_none()
         }
     }
 
+    /// Recomputes the mass properties of this mesh from the current positions of its vertices.
+    ///
+    /// The mass properties of a mesh are approximated by the mass properties of its convex hull.
+    /// They are computed when the mesh is created, but not by `Self::update_vertices` because
+    /// computing a convex hull is expensive.
+    pub fn update_mass_properties(&mut self) {
+        self.unit_mass_properties = Self::compute_unit_mass_properties(&self.vertices, &self.aabb);
+    }
+
+    /// The mass properties of the convex hull of this mesh, with a density of 1.
+    pub(crate) fn unit_mass_properties(&self) -> &MassProperties {
+        &self.unit_mass_properties
+    }
+
     /// Compute the axis-aligned bounding box of this triangle mesh.
     pub fn aabb(&self, pos: &Isometry<f32>) -> AABB<f32> {
         self.aabb.transform_by(pos)
     }
 
+    /// The local-space axis-aligned bounding box of this triangle mesh.
+    pub fn local_aabb(&self) -> &AABB<f32> {
+        &self.aabb
+    }
+
     pub(crate) fn waabbs(&self) -> &WQuadtree<usize> {
         &self.wquadtree
     }
@@ -86,11 +403,58 @@ impl Trimesh {
         )
     }
 
+    /// The normals of the triangles adjacent to each edge of the `i`-th triangle.
+    ///
+    /// The `k`-th element is the normal of the triangle sharing the edge between the `k`-th and
+    /// `(k + 1) % 3`-th vertices of the `i`-th triangle, if there is exactly one such triangle.
+    /// These normals are oriented consistently with the normal of the `i`-th triangle.
+    #[cfg(feature = "dim3")]
+    pub fn adjacent_normals(&self, i: usize) -> &[Option<Vector<f32>>; 3] {
+        &self.adjacent_normals[i]
+    }
+
+    /// The boundary edges of the `i`-th triangle.
+    ///
+    /// The `k`-th element is set if the edge between the `k`-th and `(k + 1) % 3`-th vertices
+    /// of the `i`-th triangle is not shared by any other triangle.
+    #[cfg(feature = "dim2")]
+    pub(crate) fn boundary_edges(&self, i: usize) -> &[Option<BoundaryEdge>; 3] {
+        &self.boundary_edges[i]
+    }
+
     /// The vertex buffer of this mesh.
     pub fn vertices(&self) -> &[Point<f32>] {
         &self.vertices[..]
     }
 
+    /// Modifies in-place the positions of the vertices of this mesh.
+    ///
+    /// The index buffer is left unchanged. The acceleration structure of this mesh is refitted
+    /// instead of being rebuilt, which is much faster but becomes less efficient if the vertices
+    /// move a lot relative to each other. In this case, consider recreating the mesh instead.
+    ///
+    /// The normals used to handle the internal edges of the mesh are updated too, but its mass
+    /// properties are not: call `Self::update_mass_properties` to recompute them.
+    pub fn update_vertices(&mut self, f: impl FnOnce(&mut [Point<f32>])) {
+        f(&mut self.vertices);
+
+        let (vertices, indices) = (&self.vertices, &self.indices);
+        self.aabb = AABB::from_points(vertices);
+        self.wquadtree
+            .refit(0.0, |i| Self::triangle_aabb(vertices, &indices[*i]));
+
+        #[cfg(feature = "dim3")]
+        {
+            self.adjacent_normals =
+                Self::compute_adjacent_normals(vertices, indices, &self.adjacent_triangles);
+        }
+        #[cfg(feature = "dim2")]
+        {
+            self.boundary_edges =
+                Self::compute_boundary_edges(vertices, indices, &self.boundary_topology);
+        }
+    }
+
     /// The index buffer of this mesh.
     pub fn indices(&self) -> &[Point3<u32>] {
         &self.indices
@@ -190,3 +554,115 @@ impl RayCast<f32> for Trimesh {
         false
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::Trimesh;
+    use crate::dynamics::RigidBodyBuilder;
+    use crate::geometry::ColliderBuilder;
+    use crate::math::{Isometry, Point, Vector};
+    use crate::pipeline::TestWorld;
+    use na::Point3;
+
+    // A 2x2 grid of quads, each split into two triangles.
+    fn grid() -> Trimesh {
+        let mut vertices = Vec::new();
+        let mut indices = Vec::new();
+
+        for i in 0..3 {
+            for j in 0..3 {
+                #[cfg(feature = "dim2")]
+                vertices.push(Point::new(i as f32, j as f32));
+                #[cfg(feature = "dim3")]
+                vertices.push(Point::new(i as f32, 0.0, j as f32));
+            }
+        }
+
+        for i in 0..2 {
+            for j in 0..2 {
+                let a = i * 3 + j;
+                let (b, c, d) = (a + 3, a + 4, a + 1);
+                indices.push(Point3::new(a, b, c));
+                indices.push(Point3::new(a, c, d));
+            }
+        }
+
+        Trimesh::new(vertices, indices)
+    }
+
+    #[test]
+    fn update_vertices_matches_a_rebuilt_mesh() {
+        let mut mesh = grid();
+        mesh.update_vertices(|vertices| {
+            for (i, pt) in vertices.iter_mut().enumerate() {
+                *pt += Vector::repeat(0.1 * (i * i) as f32);
+            }
+        });
+        let rebuilt = Trimesh::new(mesh.vertices().to_vec(), mesh.indices().to_vec());
+
+        assert_eq!(mesh.local_aabb(), rebuilt.local_aabb());
+        for i in 0..mesh.num_triangles() {
+            #[cfg(feature = "dim3")]
+            assert_eq!(mesh.adjacent_normals(i), rebuilt.adjacent_normals(i));
+            #[cfg(feature = "dim2")]
+            for (edge, expected) in mesh
+                .boundary_edges(i)
+                .iter()
+                .zip(rebuilt.boundary_edges(i).iter())
+            {
+                assert_eq!(edge.is_some(), expected.is_some());
+                if let (Some(edge), Some(expected)) = (edge, expected) {
+                    assert_eq!(edge.normal, expected.normal);
+                    assert_eq!(edge.adjacent_normals, expected.adjacent_normals);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn trimesh_vertices_update() {
+        let mut world = TestWorld::falling();
+
+        #[cfg(feature = "dim2")]
+        let vertices = vec![
+            Point::new(-5.0, -1.0),
+            Point::new(5.0, -1.0),
+            Point::new(5.0, 0.0),
+            Point::new(-5.0, 0.0),
+        ];
+        #[cfg(feature = "dim3")]
+        let vertices = vec![
+            Point::new(-5.0, 0.0, -5.0),
+            Point::new(5.0, 0.0, -5.0),
+            Point::new(5.0, 0.0, 5.0),
+            Point::new(-5.0, 0.0, 5.0),
+        ];
+        let indices = vec![Point3::new(0, 1, 2), Point3::new(0, 2, 3)];
+
+        let floor = ColliderBuilder::trimesh(vertices, indices).build();
+        let (_, floor) = world.insert(RigidBodyBuilder::new_static().build(), floor);
+
+        let ball = RigidBodyBuilder::new_dynamic()
+            .position(Isometry::new(Vector::y() * 1.0, na::zero()))
+            .build();
+        let (ball, _) = world.insert(ball, ColliderBuilder::ball(0.5).build());
+
+        world.steps(200);
+        assert!((world.bodies[ball].position().translation.vector.y - 0.5).abs() < 0.05);
+
+        // Lower the floor, the ball must follow it even if it fell asleep.
+        let updated = world
+            .colliders
+            .get_mut(floor)
+            .unwrap()
+            .update_trimesh_vertices(|vertices| {
+                for v in vertices {
+                    v.y -= 1.0;
+                }
+            });
+        assert!(updated);
+
+        world.steps(200);
+        assert!((world.bodies[ball].position().translation.vector.y + 0.5).abs() < 0.05);
+    }
+}
@@ -193,6 +193,39 @@ impl<T: IndexedData> WQuadtree<T> {
         ]);
     }
 
+    /// Recomputes the bounding volumes of all the nodes of this tree without modifying its topology.
+    ///
+    /// This is much faster than a rebuild, but the tree quality decreases if the
+    /// proxies moved significantly since the tree was built.
+    pub fn refit(&mut self, dilation_factor: f32, aabb: impl Fn(&T) -> AABB) {
+        let dilation_factor = SimdFloat::splat(dilation_factor);
+
+        // NOTE: the children of a node are always stored after it, so traversing
+        // the nodes in reverse order updates the children before their parents.
+        for id in (0..self.nodes.len()).rev() {
+            let node = &self.nodes[id];
+            let mut new_aabbs = [AABB::new_invalid(); SIMD_WIDTH];
+
+            for (child_id, new_aabb) in node.children.iter().zip(new_aabbs.iter_mut()) {
+                if node.leaf {
+                    if let Some(proxy) = self.proxies.get(*child_id as usize) {
+                        *new_aabb = aabb(&proxy.data);
+                    }
+                } else if let Some(child) = self.nodes.get(*child_id as usize) {
+                    *new_aabb = child.waabb.to_merged_aabb();
+                }
+            }
+
+            let node = &mut self.nodes[id];
+            node.waabb = WAABB::from(new_aabbs);
+
+            // NOTE: the root is not dilated, like in `clear_and_rebuild`.
+            if id != 0 {
+                node.waabb.dilate_by_factor(dilation_factor);
+            }
+        }
+    }
+
     fn do_recurse_build(
         &mut self,
         indices: &mut [usize],
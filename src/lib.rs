@@ -5,11 +5,28 @@
 //!
 //! Rapier has some unique features for collaborative applications:
 //! - The ability to snapshot the state of the physics engine, and restore it later.
+//! - The ability to clone the state of the physics engine (the `RigidBodySet`, `ColliderSet`,
+//!   `JointSet`, `BroadPhase`, and `NarrowPhase`) cheaply, e.g., for rollback networking. The
+//!   shapes are shared between the clones, and resimulating a clone gives exactly the same results
+//!   as simulating the original state.
 //! - The ability to run a perfectly deterministic simulation on different machine, as long as they
 //! are compliant with the IEEE 754-2008 floating point standard.
+//!
+//! The math types used by Rapier are the ones from `nalgebra`. Enabling the `convert-mint` feature
+//! adds `From`/`Into` conversions between those types (vectors, points, 3D rotations, and matrices)
+//! and their `mint` equivalents, and the [`conversions`] module for the isometries and rotations
+//! that have no `mint` equivalent. This makes Rapier interoperable with other math libraries
+//! supporting `mint`, like `glam` (with its `mint` feature enabled). There are no direct `glam`
+//! conversions since the version of `nalgebra` used by Rapier doesn't provide them.
+//!
+//! The `std` feature, enabled by default, gates the parts of Rapier requiring the standard library:
+//! the timers of the performance counters, the `ChannelEventCollector`, and the `parallel` feature.
+//! Disabling it removes the `instant` and `crossbeam` dependencies, e.g., for targets without
+//! a system clock. A `no_std` build isn't possible yet because ncollide requires the standard library.
 
 #![deny(missing_docs)]
 
+#[cfg(feature = "std")]
 pub extern crate crossbeam;
 pub extern crate nalgebra as na;
 #[cfg(feature = "dim2")]
@@ -129,6 +146,9 @@ pub(crate) const INVALID_U32: u32 = u32::MAX;
 pub(crate) const INVALID_U64: u64 = u64::MAX;
 pub(crate) const INVALID_USIZE: usize = INVALID_U32 as usize;
 
+pub mod control;
+#[cfg(feature = "convert-mint")]
+pub mod conversions;
 pub mod counters;
 pub mod data;
 pub mod dynamics;
@@ -0,0 +1,169 @@
+use crate::dynamics::RigidBodySet;
+use crate::geometry::{ColliderHandle, ColliderSet, NarrowPhase, AABB};
+use crate::math::Vector;
+use crate::utils;
+
+/// An area effector simulating a fluid filling the volume of a sensor collider.
+///
+/// Each call to `Buoyancy::apply_forces` applies a buoyant force and a drag to every dynamic
+/// rigid-body with a (non-sensor) collider intersecting the fluid sensor. It must be called before
+/// each `PhysicsPipeline::step`, since the forces it applies only last for one timestep.
+///
+/// The submerged volume of each collider is approximated from the intersection of its AABB with
+/// the AABB of the fluid sensor. This is exact for axis-aligned boxes, and gives reasonable results
+/// for other shapes as long as the top of the fluid sensor is flat and orthogonal to the gravity.
+#[derive(Copy, Clone, Debug)]
+#[cfg_attr(feature = "serde-serialize", derive(Serialize, Deserialize))]
+pub struct Buoyancy {
+    /// The sensor collider delimiting the volume of the fluid.
+    pub collider: ColliderHandle,
+    /// The density of the fluid.
+    pub density: f32,
+    /// The velocity of the fluid flow.
+    ///
+    /// Bodies immersed into the fluid are dragged toward this velocity.
+    pub flow_velocity: Vector<f32>,
+    /// The linear drag coefficient, i.e., the force applied per unit of velocity
+    /// relative to the flow, for a fully submerged collider.
+    pub linear_drag: f32,
+    /// The angular drag coefficient, i.e., the torque applied per unit of angular velocity,
+    /// for a fully submerged collider.
+    pub angular_drag: f32,
+}
+
+impl Buoyancy {
+    /// Initializes a still fluid with the given density, filling the volume of the given sensor collider.
+    pub fn new(collider: ColliderHandle, density: f32) -> Self {
+        Self {
+            collider,
+            density,
+            flow_velocity: Vector::zeros(),
+            linear_drag: 0.0,
+            angular_drag: 0.0,
+        }
+    }
+
+    /// Sets the velocity of the fluid flow.
+    pub fn flow_velocity(mut self, flow_velocity: Vector<f32>) -> Self {
+        self.flow_velocity = flow_velocity;
+        self
+    }
+
+    /// Sets the linear drag coefficient of the fluid.
+    pub fn linear_drag(mut self, linear_drag: f32) -> Self {
+        self.linear_drag = linear_drag;
+        self
+    }
+
+    /// Sets the angular drag coefficient of the fluid.
+    pub fn angular_drag(mut self, angular_drag: f32) -> Self {
+        self.angular_drag = angular_drag;
+        self
+    }
+
+    /// Applies the buoyant and drag forces to all the rigid-bodies immersed into this fluid.
+    ///
+    /// The intersections between the fluid sensor and the other colliders are read from the
+    /// narrow-phase, so they are the ones detected during the last timestep.
+    pub fn apply_forces(
+        &self,
+        gravity: &Vector<f32>,
+        bodies: &mut RigidBodySet,
+        colliders: &ColliderSet,
+        narrow_phase: &NarrowPhase,
+    ) {
+        let fluid_aabb = match colliders.get(self.collider) {
+            Some(fluid) => fluid.compute_aabb(),
+            None => return,
+        };
+        let intersections = match narrow_phase.intersections_with(self.collider) {
+            Some(intersections) => intersections,
+            None => return,
+        };
+
+        for (h1, h2, intersecting) in intersections {
+            if !intersecting {
+                continue;
+            }
+
+            let collider = &colliders[utils::other_handle((h1, h2), self.collider)];
+            let body = &mut bodies[collider.parent()];
+
+            if collider.is_sensor() || !body.is_dynamic() {
+                continue;
+            }
+
+            let aabb = collider.compute_aabb();
+            let submerged_aabb = AABB::new(
+                aabb.mins.coords.sup(&fluid_aabb.mins.coords).into(),
+                aabb.maxs.coords.inf(&fluid_aabb.maxs.coords).into(),
+            );
+            let aabb_volume = aabb.extents().iter().product::<f32>();
+            let submerged_extents = submerged_aabb.extents();
+
+            if aabb_volume == 0.0 || submerged_extents.iter().any(|e| *e <= 0.0) {
+                continue;
+            }
+
+            let submerged_fraction = submerged_extents.iter().product::<f32>() / aabb_volume;
+            let volume = utils::inv(collider.shape().mass_properties(1.0).inv_mass);
+            let submerged_volume = volume * submerged_fraction;
+            let center = submerged_aabb.center();
+
+            let buoyant_force = -gravity * (self.density * submerged_volume);
+            let relative_vel = body.velocity_at_point(&center) - self.flow_velocity;
+            let drag_force = -relative_vel * (self.linear_drag * submerged_fraction);
+            let drag_torque = -body.angvel() * (self.angular_drag * submerged_fraction);
+
+            body.apply_force_at_point(buoyant_force + drag_force, center, false);
+            body.apply_torque(drag_torque, false);
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Buoyancy;
+    use crate::dynamics::RigidBodyBuilder;
+    use crate::geometry::ColliderBuilder;
+    use crate::pipeline::TestWorld;
+
+    #[test]
+    fn box_floats_at_equilibrium() {
+        let mut world = TestWorld::falling();
+
+        // A pool of water with its surface at y = 0.
+        #[cfg(feature = "dim2")]
+        let co = ColliderBuilder::cuboid(10.0, 5.0).translation(0.0, -5.0);
+        #[cfg(feature = "dim3")]
+        let co = ColliderBuilder::cuboid(10.0, 5.0, 10.0).translation(0.0, -5.0, 0.0);
+        let co = co.sensor(true).build();
+        let (_, water) = world.insert(RigidBodyBuilder::new_static().build(), co);
+
+        // A box with half the density of the water, so it should float half-submerged.
+        #[cfg(feature = "dim2")]
+        let rb = RigidBodyBuilder::new_dynamic().translation(0.0, 1.0);
+        #[cfg(feature = "dim3")]
+        let rb = RigidBodyBuilder::new_dynamic().translation(0.0, 1.0, 0.0);
+        #[cfg(feature = "dim2")]
+        let co = ColliderBuilder::cuboid(0.5, 0.5);
+        #[cfg(feature = "dim3")]
+        let co = ColliderBuilder::cuboid(0.5, 0.5, 0.5);
+        let (body, _) = world.insert(rb.build(), co.density(1.0).build());
+
+        let buoyancy = Buoyancy::new(water, 2.0).linear_drag(5.0);
+
+        for _ in 0..600 {
+            buoyancy.apply_forces(
+                &world.gravity,
+                &mut world.bodies,
+                &world.colliders,
+                &world.narrow_phase,
+            );
+            world.step();
+        }
+
+        let y = world.bodies[body].position().translation.vector.y;
+        assert!(y.abs() < 1.0e-2);
+    }
+}
@@ -48,6 +48,14 @@ impl CollisionPipeline {
         events: &dyn EventHandler,
     ) {
         bodies.maintain(colliders);
+        broad_phase.maintain(colliders);
+        if narrow_phase.is_new() {
+            // The broad-phase must report again the pairs it already found, e.g., if it was
+            // restored from a snapshot without the narrow-phase.
+            broad_phase.reset(colliders);
+        }
+        narrow_phase.maintain(colliders, bodies);
+        colliders.clear_modified_colliders();
         self.broadphase_collider_pairs.clear();
 
         broad_phase.update_aabbs(prediction_distance, bodies, colliders);
@@ -61,6 +69,7 @@ impl CollisionPipeline {
             prediction_distance,
             bodies,
             colliders,
+            None,
             contact_pair_filter,
             events,
         );
@@ -1,6 +1,85 @@
-use crate::geometry::{ContactEvent, ProximityEvent};
+use crate::dynamics::{JointHandle, RigidBodyHandle};
+use crate::geometry::{ColliderHandle, ContactEvent, ProximityEvent};
+use crate::math::Vector;
+#[cfg(feature = "std")]
 use crossbeam::channel::Sender;
 
+/// An event emitted when the contact forces between two colliders exceed the
+/// contact force event threshold of one of these colliders.
+///
+/// The forces are the normal impulses applied by the constraints solver during the last
+/// timestep, divided by the length of this timestep. Friction forces are not included.
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde-serialize", derive(Serialize, Deserialize))]
+pub struct ContactForceEvent {
+    /// The first collider involved in the contact.
+    pub collider1: ColliderHandle,
+    /// The second collider involved in the contact.
+    pub collider2: ColliderHandle,
+    /// The sum of all the forces applied by the first collider on the second collider.
+    pub total_force: Vector<f32>,
+    /// The sum of the magnitudes of all the contact forces between the two colliders.
+    ///
+    /// This is the value compared with the contact force event thresholds of the colliders.
+    pub total_force_magnitude: f32,
+    /// The world-space unit direction of the strongest contact force, pointing from the first
+    /// collider toward the second collider.
+    pub max_force_direction: Vector<f32>,
+    /// The magnitude of the strongest contact force.
+    pub max_force_magnitude: f32,
+}
+
+impl ContactForceEvent {
+    pub(crate) fn new(collider1: ColliderHandle, collider2: ColliderHandle) -> Self {
+        Self {
+            collider1,
+            collider2,
+            total_force: Vector::zeros(),
+            total_force_magnitude: 0.0,
+            max_force_direction: Vector::zeros(),
+            max_force_magnitude: 0.0,
+        }
+    }
+}
+
+/// An event emitted when a joint starts or stops enforcing one of its limits.
+///
+/// The limits are identified by their index in the `limits` array of the joint, e.g.,
+/// [`PrismaticJoint::limits`](crate::dynamics::PrismaticJoint::limits).
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde-serialize", derive(Serialize, Deserialize))]
+pub enum JointLimitEvent {
+    /// The joint started enforcing one of its limits.
+    Reached {
+        /// The joint that reached its limit.
+        joint: JointHandle,
+        /// The index of the limit reached by the joint.
+        limit: usize,
+        /// The impulse applied by the joint to enforce this limit during the last timestep.
+        impulse: f32,
+    },
+    /// The joint stopped enforcing one of its limits.
+    Left {
+        /// The joint that left its limit.
+        joint: JointHandle,
+        /// The index of the limit left by the joint.
+        limit: usize,
+    },
+}
+
+/// An event emitted when a dynamic rigid-body wakes up or falls asleep.
+///
+/// Sleeping rigid-bodies don't move, so this can be used to stop synchronizing the positions
+/// of the sleeping bodies with their graphical representation until they wake up again.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde-serialize", derive(Serialize, Deserialize))]
+pub enum BodyActivationEvent {
+    /// The rigid-body was woken up, or was inserted awake, before the last timestep.
+    Activated(RigidBodyHandle),
+    /// The rigid-body fell asleep during the last timestep.
+    Deactivated(RigidBodyHandle),
+}
+
 /// Trait implemented by structures responsible for handling events generated by the physics engine.
 ///
 /// Implementors of this trait will typically collect these events for future processing.
@@ -14,6 +93,23 @@ pub trait EventHandler: Send + Sync {
     /// A contact event is emitted when two collider start or stop touching, independently from the
     /// number of contact points involved.
     fn handle_contact_event(&self, event: ContactEvent);
+    /// Handle a contact force event.
+    ///
+    /// A contact force event is emitted at the end of each timestep during which the contact
+    /// forces between two colliders exceed the contact force event threshold of one of them.
+    /// See [`Collider::contact_force_event_threshold`](crate::geometry::Collider::contact_force_event_threshold).
+    fn handle_contact_force_event(&self, _event: ContactForceEvent) {}
+    /// Handle a joint limit event.
+    ///
+    /// A joint limit event is emitted at the end of each timestep during which a joint started
+    /// or stopped enforcing one of its limits.
+    fn handle_joint_limit_event(&self, _event: JointLimitEvent) {}
+    /// Handle a body activation event.
+    ///
+    /// A body activation event is emitted during each timestep for every dynamic rigid-body
+    /// that wakes up or falls asleep. No event is emitted for the bodies removed from the
+    /// simulation, disabled, or made non-dynamic.
+    fn handle_body_activation_event(&self, _event: BodyActivationEvent) {}
 }
 
 impl EventHandler for () {
@@ -22,11 +118,32 @@ impl EventHandler for () {
 }
 
 /// A physics event handler that collects events into a crossbeam channel.
+///
+/// This requires the `std` feature.
+///
+/// The events can then be read from the receiving end of the channels after each timestep:
+///
+/// ```ignore
+/// let (contact_send, contact_recv) = crossbeam::channel::unbounded();
+/// let (proximity_send, proximity_recv) = crossbeam::channel::unbounded();
+/// let event_handler = ChannelEventCollector::new(proximity_send, contact_send);
+///
+/// pipeline.step(/* ... */, &event_handler);
+///
+/// while let Ok(contact_event) = contact_recv.try_recv() {
+///     // Handle the contact event.
+/// }
+/// ```
+#[cfg(feature = "std")]
 pub struct ChannelEventCollector {
     proximity_event_sender: Sender<ProximityEvent>,
     contact_event_sender: Sender<ContactEvent>,
+    contact_force_event_sender: Option<Sender<ContactForceEvent>>,
+    joint_limit_event_sender: Option<Sender<JointLimitEvent>>,
+    body_activation_event_sender: Option<Sender<BodyActivationEvent>>,
 }
 
+#[cfg(feature = "std")]
 impl ChannelEventCollector {
     /// Initialize a new physics event handler from crossbeam channel senders.
     pub fn new(
@@ -36,10 +153,38 @@ impl ChannelEventCollector {
         Self {
             proximity_event_sender,
             contact_event_sender,
+            contact_force_event_sender: None,
+            joint_limit_event_sender: None,
+            body_activation_event_sender: None,
         }
     }
+
+    /// Sets the crossbeam channel sender the contact force events are sent to.
+    ///
+    /// Contact force events are dropped if no sender is set.
+    pub fn contact_force_event_sender(mut self, sender: Sender<ContactForceEvent>) -> Self {
+        self.contact_force_event_sender = Some(sender);
+        self
+    }
+
+    /// Sets the crossbeam channel sender the joint limit events are sent to.
+    ///
+    /// Joint limit events are dropped if no sender is set.
+    pub fn joint_limit_event_sender(mut self, sender: Sender<JointLimitEvent>) -> Self {
+        self.joint_limit_event_sender = Some(sender);
+        self
+    }
+
+    /// Sets the crossbeam channel sender the body activation events are sent to.
+    ///
+    /// Body activation events are dropped if no sender is set.
+    pub fn body_activation_event_sender(mut self, sender: Sender<BodyActivationEvent>) -> Self {
+        self.body_activation_event_sender = Some(sender);
+        self
+    }
 }
 
+#[cfg(feature = "std")]
 impl EventHandler for ChannelEventCollector {
     fn handle_proximity_event(&self, event: ProximityEvent) {
         let _ = self.proximity_event_sender.send(event);
@@ -48,4 +193,22 @@ impl EventHandler for ChannelEventCollector {
     fn handle_contact_event(&self, event: ContactEvent) {
         let _ = self.contact_event_sender.send(event);
     }
+
+    fn handle_contact_force_event(&self, event: ContactForceEvent) {
+        if let Some(sender) = &self.contact_force_event_sender {
+            let _ = sender.send(event);
+        }
+    }
+
+    fn handle_joint_limit_event(&self, event: JointLimitEvent) {
+        if let Some(sender) = &self.joint_limit_event_sender {
+            let _ = sender.send(event);
+        }
+    }
+
+    fn handle_body_activation_event(&self, event: BodyActivationEvent) {
+        if let Some(sender) = &self.body_activation_event_sender {
+            let _ = sender.send(event);
+        }
+    }
 }
@@ -1,11 +1,25 @@
 //! Structure for combining the various physics components to perform an actual simulation.
 
+pub use buoyancy::Buoyancy;
 pub use collision_pipeline::CollisionPipeline;
-pub use event_handler::{ChannelEventCollector, EventHandler};
+#[cfg(feature = "std")]
+pub use event_handler::ChannelEventCollector;
+pub use event_handler::{BodyActivationEvent, ContactForceEvent, EventHandler, JointLimitEvent};
+pub use one_way_platform::OneWayPlatform;
 pub use physics_pipeline::PhysicsPipeline;
-pub use query_pipeline::QueryPipeline;
+pub use query_filter::{QueryFilter, QueryFilterFlags};
+pub use query_pipeline::{QueryPipeline, RadialFalloff};
+#[cfg(test)]
+pub(crate) use test_world::TestWorld;
+pub use wind::Wind;
 
+mod buoyancy;
 mod collision_pipeline;
 mod event_handler;
+mod one_way_platform;
 mod physics_pipeline;
+mod query_filter;
 mod query_pipeline;
+#[cfg(test)]
+mod test_world;
+mod wind;
@@ -0,0 +1,198 @@
+use crate::dynamics::RigidBody;
+use crate::geometry::{
+    Collider, ColliderHandle, ContactPairFilter, PairFilterContext, SolverFlags,
+};
+use crate::math::Vector;
+use na::Unit;
+
+/// A contact pair filter turning some colliders into one-way platforms.
+///
+/// Bodies go through a one-way platform when they move along its `local_direction`, e.g., a
+/// character jumping onto the platform from below, but they land on it when moving the opposite way.
+///
+/// The contacts with a platform are solved only if the other collider lies entirely on the
+/// `local_direction` side of the platform (up to `penetration_tolerance`), and doesn't move
+/// away from it along `local_direction` faster than `velocity_tolerance`. Otherwise, they
+/// are still computed but ignored by the constraints solver. Therefore a body jumping through a
+/// platform keeps going through it if it starts falling before being entirely above the platform,
+/// instead of being pushed on top of it.
+///
+/// This replaces the default contact filtering rule, so it must be given to
+/// `PhysicsPipeline::step` instead of any other contact pair filter. The contacts between two
+/// non-dynamic bodies are still ignored.
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde-serialize", derive(Serialize, Deserialize))]
+pub struct OneWayPlatform {
+    /// The colliders behaving as one-way platforms.
+    pub platforms: Vec<ColliderHandle>,
+    /// The direction, in the local-space of each platform collider, along which bodies go
+    /// through the platform.
+    pub local_direction: Unit<Vector<f32>>,
+    /// The depth a collider may penetrate a platform while still being pushed back on its surface
+    /// (default: `0.1`).
+    ///
+    /// This should be larger than the distance travelled during one timestep by the fastest
+    /// bodies landing on the platforms.
+    pub penetration_tolerance: f32,
+    /// The relative velocity along `local_direction` above which a body is considered to move
+    /// away from a platform (default: `0.1`).
+    pub velocity_tolerance: f32,
+}
+
+impl OneWayPlatform {
+    /// Initializes a filter for one-way platforms that can be gone through along `local_direction`,
+    /// expressed in the local-space of the platform colliders.
+    pub fn new(platforms: Vec<ColliderHandle>, local_direction: Unit<Vector<f32>>) -> Self {
+        Self {
+            platforms,
+            local_direction,
+            penetration_tolerance: 0.1,
+            velocity_tolerance: 0.1,
+        }
+    }
+
+    /// Sets the depth a collider may penetrate a platform while still being pushed back on its surface.
+    pub fn penetration_tolerance(mut self, penetration_tolerance: f32) -> Self {
+        self.penetration_tolerance = penetration_tolerance;
+        self
+    }
+
+    /// Sets the relative velocity above which a body is considered to move away from a platform.
+    pub fn velocity_tolerance(mut self, velocity_tolerance: f32) -> Self {
+        self.velocity_tolerance = velocity_tolerance;
+        self
+    }
+
+    /// Is the given collider a one-way platform?
+    pub fn is_platform(&self, handle: ColliderHandle) -> bool {
+        self.platforms.contains(&handle)
+    }
+
+    fn contacts_enabled(
+        &self,
+        platform_body: &RigidBody,
+        platform: &Collider,
+        other_body: &RigidBody,
+        other: &Collider,
+    ) -> bool {
+        let dir = platform.position() * self.local_direction;
+
+        // Is the other collider on the right side of the platform?
+        let platform_max = max_projection(platform, &dir);
+        let other_min = -max_projection(other, &-dir.into_inner());
+
+        if other_min < platform_max - self.penetration_tolerance {
+            return false;
+        }
+
+        // Does the other collider move toward the platform?
+        let point = other.position().translation.vector.into();
+        let relative_vel =
+            other_body.velocity_at_point(&point) - platform_body.velocity_at_point(&point);
+        relative_vel.dot(&dir) <= self.velocity_tolerance
+    }
+}
+
+// The greatest projection of the points of the collider on the given direction.
+fn max_projection(collider: &Collider, dir: &Vector<f32>) -> f32 {
+    if let Some(support_map) = collider.shape().as_support_map() {
+        support_map
+            .support_point(collider.position(), dir)
+            .coords
+            .dot(dir)
+    } else {
+        let aabb = collider.compute_aabb();
+        aabb.center().coords.dot(dir) + aabb.half_extents().dot(&dir.abs())
+    }
+}
+
+impl ContactPairFilter for OneWayPlatform {
+    fn filter_contact_pair(&self, context: &PairFilterContext) -> Option<SolverFlags> {
+        if !context.rigid_body1.is_dynamic() && !context.rigid_body2.is_dynamic() {
+            // Default filtering rule: no contact between two non-dynamic bodies.
+            return None;
+        }
+
+        let enabled = if self.is_platform(context.collider_handle1) {
+            self.contacts_enabled(
+                context.rigid_body1,
+                context.collider1,
+                context.rigid_body2,
+                context.collider2,
+            )
+        } else if self.is_platform(context.collider_handle2) {
+            self.contacts_enabled(
+                context.rigid_body2,
+                context.collider2,
+                context.rigid_body1,
+                context.collider1,
+            )
+        } else {
+            true
+        };
+
+        if enabled {
+            Some(SolverFlags::COMPUTE_IMPULSES)
+        } else {
+            Some(SolverFlags::empty())
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::OneWayPlatform;
+    use crate::dynamics::RigidBodyBuilder;
+    use crate::geometry::{ColliderBuilder, ContactPairFilter};
+    use crate::math::Vector;
+    use crate::pipeline::TestWorld;
+
+    // Throws a ball upward from below a platform, and returns its final height.
+    fn jump_through_platform(jump_speed: f32, use_platform: bool) -> f32 {
+        let mut world = TestWorld::falling();
+
+        // A thin platform with its top at y = 0.1.
+        #[cfg(feature = "dim2")]
+        let co = ColliderBuilder::cuboid(2.0, 0.1);
+        #[cfg(feature = "dim3")]
+        let co = ColliderBuilder::cuboid(2.0, 0.1, 2.0);
+        let (_, platform) = world.insert(RigidBodyBuilder::new_static().build(), co.build());
+
+        #[cfg(feature = "dim2")]
+        let rb = RigidBodyBuilder::new_dynamic().translation(0.0, -1.5);
+        #[cfg(feature = "dim3")]
+        let rb = RigidBodyBuilder::new_dynamic().translation(0.0, -1.5, 0.0);
+        let (ball, _) = world.insert(rb.build(), ColliderBuilder::ball(0.5).build());
+        world
+            .bodies
+            .get_mut(ball)
+            .unwrap()
+            .set_linvel(Vector::y() * jump_speed, true);
+
+        let platform_filter = OneWayPlatform::new(vec![platform], Vector::y_axis());
+        let filter: Option<&dyn ContactPairFilter> = if use_platform {
+            Some(&platform_filter)
+        } else {
+            None
+        };
+
+        for _ in 0..300 {
+            world.step_with(filter, &());
+        }
+
+        world.bodies[ball].position().translation.vector.y
+    }
+
+    #[test]
+    fn ball_jumps_through_platform() {
+        // The ball is blocked by a regular platform.
+        assert!(jump_through_platform(8.0, false) < 0.0);
+
+        // The ball goes through the one-way platform and lands on it.
+        let y = jump_through_platform(8.0, true);
+        assert!((y - 0.6).abs() < 1.0e-2);
+
+        // The ball starts falling before being entirely above the platform, so it falls back through it.
+        assert!(jump_through_platform(5.5, true) < -1.0);
+    }
+}
@@ -3,15 +3,15 @@
 use crate::counters::Counters;
 #[cfg(not(feature = "parallel"))]
 use crate::dynamics::IslandSolver;
-use crate::dynamics::{IntegrationParameters, JointSet, RigidBodySet};
+use crate::dynamics::{IntegrationParameters, JointParams, JointSet, RigidBodySet};
 #[cfg(feature = "parallel")]
 use crate::dynamics::{JointGraphEdge, ParallelIslandSolver as IslandSolver};
 use crate::geometry::{
-    BroadPhase, BroadPhasePairEvent, ColliderPair, ColliderSet, ContactManifoldIndex,
-    ContactPairFilter, NarrowPhase, ProximityPairFilter,
+    BroadPhase, BroadPhasePairEvent, ColliderPair, ColliderSet, ContactManifold,
+    ContactManifoldIndex, ContactPairFilter, NarrowPhase, ProximityPairFilter,
 };
 use crate::math::Vector;
-use crate::pipeline::EventHandler;
+use crate::pipeline::{BodyActivationEvent, ContactForceEvent, EventHandler, JointLimitEvent};
 
 /// The physics pipeline, responsible for stepping the whole physics simulation.
 ///
@@ -32,6 +32,9 @@ pub struct PhysicsPipeline {
     broadphase_collider_pairs: Vec<ColliderPair>,
     broad_phase_events: Vec<BroadPhasePairEvent>,
     solvers: Vec<IslandSolver>,
+    // NOTE: this buffer is always empty between two timesteps. It only
+    // exists to reuse its allocation from one timestep to the next.
+    manifolds: Vec<&'static mut ContactManifold>,
 }
 
 impl Default for PhysicsPipeline {
@@ -56,6 +59,91 @@ impl PhysicsPipeline {
             joint_constraint_indices: Vec::new(),
             broadphase_collider_pairs: Vec::new(),
             broad_phase_events: Vec::new(),
+            manifolds: Vec::new(),
+        }
+    }
+
+    fn emit_contact_force_events(
+        integration_parameters: &IntegrationParameters,
+        colliders: &ColliderSet,
+        manifolds: &[&mut ContactManifold],
+        events: &dyn EventHandler,
+    ) {
+        let inv_dt = integration_parameters.inv_dt();
+        let mut i = 0;
+
+        // NOTE: the manifolds of a given contact pair are contiguous.
+        while i < manifolds.len() {
+            let pair = manifolds[i].pair;
+            let co1 = &colliders[pair.collider1];
+            let co2 = &colliders[pair.collider2];
+            let threshold = co1
+                .contact_force_event_threshold
+                .min(co2.contact_force_event_threshold);
+            let mut event = ContactForceEvent::new(pair.collider1, pair.collider2);
+
+            while i < manifolds.len() && manifolds[i].pair == pair {
+                let manifold = &manifolds[i];
+                let normal = co1.position() * manifold.local_n1;
+
+                for contact in manifold.active_contacts() {
+                    let force = contact.impulse * inv_dt;
+                    event.total_force += normal * force;
+                    event.total_force_magnitude += force;
+
+                    if force > event.max_force_magnitude {
+                        event.max_force_magnitude = force;
+                        event.max_force_direction = normal;
+                    }
+                }
+
+                i += 1;
+            }
+
+            if threshold != f32::MAX && event.total_force_magnitude > threshold {
+                events.handle_contact_force_event(event);
+            }
+        }
+    }
+
+    fn emit_joint_limit_events(joints: &mut JointSet, events: &dyn EventHandler) {
+        for edge in joints.joints_mut() {
+            let joint = &mut edge.weight;
+
+            if let JointParams::PrismaticJoint(prismatic) = &mut joint.params {
+                if prismatic.active_limit == prismatic.reported_limit {
+                    continue;
+                }
+
+                if let Some(limit) = prismatic.reported_limit {
+                    events.handle_joint_limit_event(JointLimitEvent::Left {
+                        joint: joint.handle,
+                        limit,
+                    });
+                }
+
+                if let Some(limit) = prismatic.active_limit {
+                    events.handle_joint_limit_event(JointLimitEvent::Reached {
+                        joint: joint.handle,
+                        limit,
+                        impulse: prismatic.limits_impulse,
+                    });
+                }
+
+                prismatic.reported_limit = prismatic.active_limit;
+            }
+        }
+    }
+
+    fn emit_body_activation_events(bodies: &RigidBodySet, events: &dyn EventHandler) {
+        let islands = bodies.islands();
+
+        for handle in islands.deactivated_bodies() {
+            events.handle_body_activation_event(BodyActivationEvent::Deactivated(*handle));
+        }
+
+        for handle in islands.activated_bodies() {
+            events.handle_body_activation_event(BodyActivationEvent::Activated(*handle));
         }
     }
 
@@ -76,7 +164,13 @@ impl PhysicsPipeline {
         self.counters.step_started();
         bodies.maintain(colliders);
         broad_phase.maintain(colliders);
+        if narrow_phase.is_new() {
+            // The broad-phase must report again the pairs it already found, e.g., if it was
+            // restored from a snapshot without the narrow-phase.
+            broad_phase.reset(colliders);
+        }
         narrow_phase.maintain(colliders, bodies);
+        colliders.clear_modified_colliders();
 
         // Update kinematic bodies velocities.
         // TODO: what is the best place for this? It should at least be
@@ -85,6 +179,19 @@ impl PhysicsPipeline {
         // bodies it is touching.
         bodies.foreach_active_kinematic_body_mut_internal(|_, body| {
             body.compute_velocity_from_predicted_position(integration_parameters.inv_dt());
+
+            if body.is_ccd_enabled() {
+                body.update_colliders_predicted_positions(colliders);
+            }
+        });
+
+        // Predict the positions of the CCD-enabled dynamic bodies at the end of this timestep,
+        // so the broad-phase accounts for their motion.
+        bodies.foreach_active_dynamic_body_mut_internal(|_, body| {
+            if body.is_ccd_enabled() {
+                body.update_predicted_position(integration_parameters.dt());
+                body.update_colliders_predicted_positions(colliders);
+            }
         });
 
         self.counters.stages.collision_detection_time.start();
@@ -115,6 +222,7 @@ impl PhysicsPipeline {
             integration_parameters.prediction_distance,
             bodies,
             colliders,
+            Some(joints),
             contact_pair_filter,
             events,
         );
@@ -135,6 +243,7 @@ impl PhysicsPipeline {
             integration_parameters.min_island_size,
         );
         self.counters.stages.island_construction_time.pause();
+        Self::emit_body_activation_events(bodies, events);
 
         if self.manifold_indices.len() < bodies.num_islands() {
             self.manifold_indices
@@ -146,7 +255,7 @@ impl PhysicsPipeline {
                 .resize(bodies.num_islands(), Vec::new());
         }
 
-        let mut manifolds = Vec::new();
+        let mut manifolds = recycle_buffer(std::mem::take(&mut self.manifolds));
         narrow_phase.sort_and_select_active_contacts(
             bodies,
             &mut manifolds,
@@ -231,6 +340,9 @@ impl PhysicsPipeline {
             });
         }
 
+        Self::emit_contact_force_events(integration_parameters, colliders, &manifolds, events);
+        Self::emit_joint_limit_events(joints, events);
+
         // Update colliders positions and kinematic bodies positions.
         // FIXME: do this in the solver?
         bodies.foreach_active_body_mut_internal(|_, rb| {
@@ -247,93 +359,66 @@ impl PhysicsPipeline {
 
         self.counters.stages.solver_time.pause();
 
+        self.manifolds = recycle_buffer(manifolds);
         bodies.modified_inactive_set.clear();
         self.counters.step_completed();
     }
 }
 
+/// Empties a buffer of references so it can be reused with another lifetime.
+///
+/// This doesn't reallocate: the standard library collects this iterator in-place.
+fn recycle_buffer<'b, T: ?Sized>(buffer: Vec<&mut T>) -> Vec<&'b mut T> {
+    buffer.into_iter().filter_map(|_| None).collect()
+}
+
 #[cfg(test)]
 mod test {
-    use crate::dynamics::{IntegrationParameters, JointSet, RigidBodyBuilder, RigidBodySet};
-    use crate::geometry::{BroadPhase, ColliderBuilder, ColliderSet, NarrowPhase};
-    use crate::math::Vector;
-    use crate::pipeline::PhysicsPipeline;
+    use crate::dynamics::{JointSet, RigidBodyBuilder, RigidBodySet};
+    use crate::geometry::{ColliderBuilder, ColliderSet};
+    use crate::math::{Isometry, Vector};
+    use crate::pipeline::TestWorld;
 
     #[test]
     fn kinematic_and_static_contact_crash() {
-        let mut colliders = ColliderSet::new();
-        let mut joints = JointSet::new();
-        let mut pipeline = PhysicsPipeline::new();
-        let mut bf = BroadPhase::new();
-        let mut nf = NarrowPhase::new();
-        let mut bodies = RigidBodySet::new();
+        let mut world = TestWorld::new(Vector::zeros());
 
-        let rb = RigidBodyBuilder::new_static().build();
-        let h1 = bodies.insert(rb.clone());
         let co = ColliderBuilder::ball(10.0).build();
-        colliders.insert(co.clone(), h1, &mut bodies);
+        world.insert(RigidBodyBuilder::new_static().build(), co.clone());
 
         // The same but with a kinematic body.
-        let rb = RigidBodyBuilder::new_kinematic().build();
-        let h2 = bodies.insert(rb.clone());
-        colliders.insert(co, h2, &mut bodies);
+        world.insert(RigidBodyBuilder::new_kinematic().build(), co);
 
-        pipeline.step(
-            &Vector::zeros(),
-            &IntegrationParameters::default(),
-            &mut bf,
-            &mut nf,
-            &mut bodies,
-            &mut colliders,
-            &mut joints,
-            None,
-            None,
-            &(),
-        );
+        world.step();
     }
 
     #[test]
     fn rigid_body_removal_before_step() {
-        let mut colliders = ColliderSet::new();
-        let mut joints = JointSet::new();
-        let mut pipeline = PhysicsPipeline::new();
-        let mut bf = BroadPhase::new();
-        let mut nf = NarrowPhase::new();
-
-        let mut bodies = RigidBodySet::new();
+        let mut world = TestWorld::new(Vector::zeros());
 
         // Check that removing the body right after inserting it works.
         // We add two dynamic bodies, one kinematic body and one static body before removing
         // them. This include a non-regression test where deleting a kimenatic body crashes.
         let rb = RigidBodyBuilder::new_dynamic().build();
-        let h1 = bodies.insert(rb.clone());
-        let h2 = bodies.insert(rb.clone());
+        let h1 = world.bodies.insert(rb.clone());
+        let h2 = world.bodies.insert(rb.clone());
 
         // The same but with a kinematic body.
         let rb = RigidBodyBuilder::new_kinematic().build();
-        let h3 = bodies.insert(rb.clone());
+        let h3 = world.bodies.insert(rb.clone());
 
         // The same but with a static body.
         let rb = RigidBodyBuilder::new_static().build();
-        let h4 = bodies.insert(rb.clone());
+        let h4 = world.bodies.insert(rb.clone());
 
         let to_delete = [h1, h2, h3, h4];
         for h in &to_delete {
-            bodies.remove(*h, &mut colliders, &mut joints);
+            world
+                .bodies
+                .remove(*h, &mut world.colliders, &mut world.joints);
         }
 
-        pipeline.step(
-            &Vector::zeros(),
-            &IntegrationParameters::default(),
-            &mut bf,
-            &mut nf,
-            &mut bodies,
-            &mut colliders,
-            &mut joints,
-            None,
-            None,
-            &(),
-        );
+        world.step();
     }
 
     #[test]
@@ -366,4 +451,1836 @@ mod test {
         assert_eq!(h2a, h2b);
         assert_eq!(h3a, h3b);
     }
+
+    #[test]
+    fn collider_mass_modification_updates_parent() {
+        let mut world = TestWorld::new(Vector::zeros());
+
+        let h = world.bodies.insert(RigidBodyBuilder::new_dynamic().build());
+        let co = ColliderBuilder::ball(1.0).mass(2.0).build();
+        let c1 = world.attach(co.clone(), h);
+        let c2 = world.attach(co, h);
+        assert!((world.bodies[h].mass() - 4.0).abs() < 1.0e-5);
+
+        world.colliders.get_mut(c1).unwrap().set_mass(5.0);
+        world.step();
+        assert!((world.bodies[h].mass() - 7.0).abs() < 1.0e-5);
+
+        world.colliders.remove(c2, &mut world.bodies, true);
+        assert!((world.bodies[h].mass() - 5.0).abs() < 1.0e-5);
+    }
+
+    #[test]
+    fn collider_position_wrt_parent_modification() {
+        let mut world = TestWorld::new(Vector::zeros());
+
+        let rb = RigidBodyBuilder::new_static().build();
+        let (h, c) = world.insert(rb, ColliderBuilder::ball(1.0).build());
+
+        let mut delta = Isometry::identity();
+        delta.translation.vector.x = 2.0;
+        world
+            .colliders
+            .get_mut(c)
+            .unwrap()
+            .set_position_wrt_parent(delta);
+
+        world.step();
+
+        assert_eq!(*world.colliders[c].position(), delta);
+        assert_eq!(world.bodies[h].mass_properties().local_com.x, 2.0);
+    }
+
+    #[test]
+    fn collider_shape_modification() {
+        use crate::geometry::SharedShape;
+        use crate::math::Point;
+
+        let mut world = TestWorld::falling();
+
+        #[cfg(feature = "dim2")]
+        let vertices = vec![
+            Point::new(-10.0, 0.0),
+            Point::new(10.0, 0.0),
+            Point::new(10.0, -10.0),
+            Point::new(-10.0, -10.0),
+        ];
+        #[cfg(feature = "dim3")]
+        let vertices = vec![
+            Point::new(-10.0, 0.0, -10.0),
+            Point::new(10.0, 0.0, -10.0),
+            Point::new(10.0, 0.0, 10.0),
+            Point::new(-10.0, 0.0, 10.0),
+        ];
+        let indices = vec![na::Point3::new(0, 1, 2), na::Point3::new(0, 2, 3)];
+        let co = ColliderBuilder::trimesh(vertices, indices).build();
+        let (_, ground_co) = world.insert(RigidBodyBuilder::new_static().build(), co);
+
+        let ball = RigidBodyBuilder::new_dynamic()
+            .position(Isometry::new(Vector::y(), na::zero()))
+            .build();
+        let (_, ball_co) = world.insert(ball, ColliderBuilder::ball(1.0).build());
+
+        for i in 0..3 {
+            if i == 1 {
+                #[cfg(feature = "dim2")]
+                let shape = SharedShape::cuboid(Vector::new(10.0, 0.5));
+                #[cfg(feature = "dim3")]
+                let shape = SharedShape::cuboid(Vector::new(10.0, 0.5, 10.0));
+                world.colliders.get_mut(ground_co).unwrap().set_shape(shape);
+            }
+
+            world.step();
+        }
+
+        // The contacts must have been computed with the new ground shape.
+        let pair = world.narrow_phase.contact_pair(ground_co, ball_co).unwrap();
+        assert_eq!(pair.manifolds.len(), 1);
+        assert!(pair.manifolds[0]
+            .active_contacts()
+            .iter()
+            .any(|c| c.dist < -0.4));
+    }
+
+    #[test]
+    fn prismatic_joint_motor_reaches_target_position() {
+        use crate::dynamics::PrismaticJoint;
+        use crate::math::Point;
+
+        let mut world = TestWorld::new(Vector::zeros());
+
+        let ground = world.bodies.insert(RigidBodyBuilder::new_static().build());
+        let rb = RigidBodyBuilder::new_dynamic().build();
+        let (body, _) = world.insert(rb, ColliderBuilder::ball(0.5).build());
+
+        #[cfg(feature = "dim2")]
+        let mut joint = PrismaticJoint::new(
+            Point::origin(),
+            Vector::x_axis(),
+            Point::origin(),
+            Vector::x_axis(),
+        );
+        #[cfg(feature = "dim3")]
+        let mut joint = PrismaticJoint::new(
+            Point::origin(),
+            Vector::x_axis(),
+            Vector::zeros(),
+            Point::origin(),
+            Vector::x_axis(),
+            Vector::zeros(),
+        );
+        joint.configure_motor_position(2.0, 100.0, 20.0);
+        world.joints.insert(&mut world.bodies, ground, body, joint);
+
+        world.steps(300);
+
+        let translation = world.bodies[body].position().translation.vector;
+        assert!((translation.x - 2.0).abs() < 1.0e-2);
+    }
+
+    #[cfg(feature = "dim3")]
+    #[test]
+    fn revolute_joint_motor_reaches_target_angle() {
+        use crate::dynamics::RevoluteJoint;
+        use crate::math::Point;
+
+        let mut world = TestWorld::new(Vector::zeros());
+
+        let ground = world.bodies.insert(RigidBodyBuilder::new_static().build());
+        let rb = RigidBodyBuilder::new_dynamic().build();
+        let (body, _) = world.insert(rb, ColliderBuilder::cuboid(1.0, 0.1, 0.1).build());
+
+        let mut joint = RevoluteJoint::new(
+            Point::origin(),
+            Vector::y_axis(),
+            Point::origin(),
+            Vector::y_axis(),
+        );
+        joint.configure_motor_position(1.0, 100.0, 20.0);
+        world.joints.insert(&mut world.bodies, ground, body, joint);
+
+        world.steps(300);
+
+        let angle = world.bodies[body].position().rotation.scaled_axis();
+        assert!((angle.y - 1.0).abs() < 1.0e-2);
+    }
+
+    #[cfg(feature = "dim3")]
+    #[test]
+    fn rotating_kinematic_platform_carries_bodies() {
+        use na::UnitQuaternion;
+
+        let mut world = TestWorld::falling();
+        let dt = world.params.dt();
+
+        // The platform is not centered at the origin so its rotation can't be mistaken
+        // for a translation.
+        let center = Vector::new(10.0, 0.0, 0.0);
+        let rb = RigidBodyBuilder::new_kinematic()
+            .translation(center.x, center.y, center.z)
+            .build();
+        let co = ColliderBuilder::cuboid(5.0, 0.5, 5.0).build();
+        let (platform, _) = world.insert(rb, co);
+
+        let rb = RigidBodyBuilder::new_dynamic()
+            .translation(center.x + 2.0, 1.0, center.z)
+            .build();
+        let (body, _) = world.insert(rb, ColliderBuilder::cuboid(0.5, 0.5, 0.5).build());
+
+        let angvel = 0.5;
+        let num_steps = 60;
+
+        for i in 0..num_steps {
+            let rot = UnitQuaternion::new(Vector::y() * angvel * dt * (i + 1) as f32);
+            world
+                .bodies
+                .get_mut(platform)
+                .unwrap()
+                .set_next_kinematic_position(Isometry::from_parts(center.into(), rot));
+            world.step();
+        }
+
+        // The body lags a bit behind the platform because it needs to be accelerated
+        // by friction first.
+        let angle = angvel * dt * num_steps as f32;
+        let dpos = world.bodies[body].position().translation.vector - center;
+        let body_angle = (-dpos.z).atan2(dpos.x);
+        assert!((dpos.xz().norm() - 2.0).abs() < 1.0e-2);
+        assert!(body_angle > 0.0 && body_angle <= angle && angle - body_angle < 0.1);
+    }
+
+    #[test]
+    fn island_activation() {
+        let mut world = TestWorld::new(Vector::zeros());
+        world.params.min_island_size = 1;
+
+        // Two bodies far away from each other, so they are in two different islands.
+        let mut handles = Vec::new();
+        for i in 0..2 {
+            #[cfg(feature = "dim2")]
+            let rb = RigidBodyBuilder::new_dynamic()
+                .translation(i as f32 * 10.0, 0.0)
+                .build();
+            #[cfg(feature = "dim3")]
+            let rb = RigidBodyBuilder::new_dynamic()
+                .translation(i as f32 * 10.0, 0.0, 0.0)
+                .build();
+            let (handle, _) = world.insert(rb, ColliderBuilder::ball(0.5).build());
+            handles.push(handle);
+        }
+
+        world.step();
+        assert_eq!(world.bodies.islands().num_active_islands(), 2);
+        assert_eq!(world.bodies.islands().activated_islands().count(), 2);
+
+        world.step();
+        assert_eq!(world.bodies.islands().num_active_islands(), 2);
+        assert_eq!(world.bodies.islands().activated_islands().count(), 0);
+
+        // Wait for both bodies to fall asleep.
+        world.steps(200);
+        assert_eq!(world.bodies.islands().num_active_islands(), 0);
+
+        world.bodies.wake_up(handles[1], true);
+        world.step();
+        let islands = world.bodies.islands();
+        assert_eq!(islands.num_active_islands(), 1);
+        assert_eq!(islands.active_island(0), &[handles[1]]);
+        assert!(islands.is_island_activated(0));
+    }
+
+    #[test]
+    fn cuboids_rest_on_segment_and_triangle_colliders() {
+        use crate::math::Point;
+
+        let mut world = TestWorld::falling();
+
+        // A thin ground on the left, and a rounded ground (with its top at y = 0) on the right.
+        let ground = world.bodies.insert(RigidBodyBuilder::new_static().build());
+        #[cfg(feature = "dim2")]
+        let (thin, rounded) = (
+            ColliderBuilder::segment(Point::new(-10.0, 0.0), Point::new(0.0, 0.0)),
+            ColliderBuilder::round_triangle(
+                Point::new(0.0, -0.1),
+                Point::new(10.0, -0.1),
+                Point::new(5.0, -5.0),
+                0.1,
+            ),
+        );
+        #[cfg(feature = "dim3")]
+        let (thin, rounded) = (
+            ColliderBuilder::triangle(
+                Point::new(-10.0, 0.0, -10.0),
+                Point::new(0.0, 0.0, 10.0),
+                Point::new(0.0, 0.0, -10.0),
+            ),
+            ColliderBuilder::round_triangle(
+                Point::new(0.0, -0.1, -10.0),
+                Point::new(0.0, -0.1, 10.0),
+                Point::new(10.0, -0.1, -10.0),
+                0.1,
+            ),
+        );
+        world.attach(thin.build(), ground);
+        world.attach(rounded.build(), ground);
+
+        let mut handles = Vec::new();
+        for x in [-3.0, 3.0].iter() {
+            #[cfg(feature = "dim2")]
+            let rb = RigidBodyBuilder::new_dynamic().translation(*x, 1.0);
+            #[cfg(feature = "dim3")]
+            let rb = RigidBodyBuilder::new_dynamic().translation(*x, 1.0, -3.0);
+            #[cfg(feature = "dim2")]
+            let co = ColliderBuilder::cuboid(0.5, 0.5);
+            #[cfg(feature = "dim3")]
+            let co = ColliderBuilder::cuboid(0.5, 0.5, 0.5);
+            let (handle, _) = world.insert(rb.build(), co.build());
+            handles.push(handle);
+        }
+
+        world.steps(200);
+
+        for handle in handles {
+            let y = world.bodies[handle].position().translation.vector.y;
+            assert!((y - 0.5).abs() < 1.0e-2, "y = {}", y);
+        }
+    }
+
+    #[test]
+    fn contact_position_correction_parameters() {
+        let mut world = TestWorld::falling();
+
+        // A ground with its top at y = 0, and a box penetrating it by 0.3.
+        #[cfg(feature = "dim2")]
+        let co = ColliderBuilder::cuboid(10.0, 1.0).translation(0.0, -1.0);
+        #[cfg(feature = "dim3")]
+        let co = ColliderBuilder::cuboid(10.0, 1.0, 10.0).translation(0.0, -1.0, 0.0);
+        world.insert(RigidBodyBuilder::new_static().build(), co.build());
+
+        #[cfg(feature = "dim2")]
+        let rb = RigidBodyBuilder::new_dynamic().translation(0.0, 0.2);
+        #[cfg(feature = "dim3")]
+        let rb = RigidBodyBuilder::new_dynamic().translation(0.0, 0.2, 0.0);
+        #[cfg(feature = "dim2")]
+        let co = ColliderBuilder::cuboid(0.5, 0.5);
+        #[cfg(feature = "dim3")]
+        let co = ColliderBuilder::cuboid(0.5, 0.5, 0.5);
+        let (handle, _) = world.insert(rb.build(), co.build());
+
+        world.params.allowed_linear_error = 0.05;
+        world.params.max_corrective_velocity = 0.6;
+        let max_correction = world.params.max_contact_correction();
+        assert_eq!(max_correction, 0.6 * world.params.dt());
+
+        // The penetration is corrected at the maximum corrective velocity.
+        world.step();
+        let y = world.bodies[handle].position().translation.vector.y;
+        let max_displacement = max_correction * world.params.max_position_iterations as f32;
+        assert!(y > 0.2 && y <= 0.2 + max_displacement + 1.0e-4, "y = {}", y);
+
+        // The box eventually rests with a penetration equal to the allowed linear error.
+        world.steps(600);
+        let y = world.bodies[handle].position().translation.vector.y;
+        assert!((y - 0.45).abs() < 5.0e-3, "y = {}", y);
+    }
+
+    #[test]
+    fn contact_impulses_with_and_without_warmstart() {
+        for warmstart_enabled in [true, false].iter() {
+            let mut world = TestWorld::falling();
+            world.params.warmstart_enabled = *warmstart_enabled;
+
+            #[cfg(feature = "dim2")]
+            let co = ColliderBuilder::cuboid(10.0, 1.0).translation(0.0, -1.0);
+            #[cfg(feature = "dim3")]
+            let co = ColliderBuilder::cuboid(10.0, 1.0, 10.0).translation(0.0, -1.0, 0.0);
+            let (_, ground_collider) =
+                world.insert(RigidBodyBuilder::new_static().build(), co.build());
+
+            #[cfg(feature = "dim2")]
+            let rb = RigidBodyBuilder::new_dynamic().translation(0.0, 0.5);
+            #[cfg(feature = "dim3")]
+            let rb = RigidBodyBuilder::new_dynamic().translation(0.0, 0.5, 0.0);
+            #[cfg(feature = "dim2")]
+            let co = ColliderBuilder::cuboid(0.5, 0.5);
+            #[cfg(feature = "dim3")]
+            let co = ColliderBuilder::cuboid(0.5, 0.5, 0.5);
+            let (handle, collider) = world.insert(rb.build(), co.build());
+
+            world.steps(100);
+
+            // The contact impulses compensate the gravity of the resting box.
+            let weight = 9.81 * world.params.dt() / world.bodies[handle].mass_properties().inv_mass;
+            let impulse: f32 = world
+                .narrow_phase
+                .contact_pair(ground_collider, collider)
+                .unwrap()
+                .manifolds
+                .iter()
+                .map(|m| m.total_impulse())
+                .sum();
+            assert!(
+                (impulse - weight).abs() < 1.0e-2 * weight,
+                "{} != {}",
+                impulse,
+                weight
+            );
+
+            world.narrow_phase.clear_impulses_with(collider);
+            let pair = world
+                .narrow_phase
+                .contact_pair(ground_collider, collider)
+                .unwrap();
+            assert!(pair.manifolds.iter().all(|m| m.total_impulse() == 0.0));
+        }
+    }
+
+    #[test]
+    fn extra_joint_iterations_reduce_joint_drift() {
+        use crate::dynamics::BallJoint;
+        use crate::math::Point;
+
+        let simulate = |extra_iterations: usize| {
+            let mut world = TestWorld::falling();
+
+            // A horizontal chain of light links, with a heavy body at its end.
+            let mut parent = world.bodies.insert(RigidBodyBuilder::new_static().build());
+            for i in 1..=10 {
+                #[cfg(feature = "dim2")]
+                let rb = RigidBodyBuilder::new_dynamic().translation(i as f32, 0.0);
+                #[cfg(feature = "dim3")]
+                let rb = RigidBodyBuilder::new_dynamic().translation(i as f32, 0.0, 0.0);
+                let density = if i == 10 { 1000.0 } else { 1.0 };
+                let co = ColliderBuilder::ball(0.1).density(density).build();
+                let (link, _) = world.insert(rb.build(), co);
+
+                let joint = BallJoint::new(Point::origin(), Point::from(-Vector::x()));
+                let handle = world.joints.insert(&mut world.bodies, parent, link, joint);
+                let joint = world.joints.get_mut(handle).unwrap();
+                joint.extra_velocity_iterations = extra_iterations;
+                joint.extra_position_iterations = extra_iterations;
+                parent = link;
+            }
+
+            world.steps(30);
+
+            // The total distance between the anchors of each joint.
+            let bodies = &world.bodies;
+            world
+                .joints
+                .iter()
+                .map(|(_, joint)| {
+                    let joint_params = joint.params.as_ball_joint().unwrap();
+                    let anchor1 = bodies[joint.body1].position() * joint_params.local_anchor1;
+                    let anchor2 = bodies[joint.body2].position() * joint_params.local_anchor2;
+                    (anchor2 - anchor1).norm()
+                })
+                .sum::<f32>()
+        };
+
+        let drift = simulate(0);
+        let drift_with_extra_iterations = simulate(20);
+        assert!(
+            drift_with_extra_iterations < drift * 0.5,
+            "{} >= {}",
+            drift_with_extra_iterations,
+            drift
+        );
+    }
+
+    #[test]
+    fn body_status_modification() {
+        use crate::dynamics::BodyStatus;
+
+        let mut world = TestWorld::falling();
+
+        // A static platform, and a box resting on it.
+        #[cfg(feature = "dim2")]
+        let (platform, body, co) = (
+            RigidBodyBuilder::new_static().translation(0.0, 2.0),
+            RigidBodyBuilder::new_dynamic().translation(0.0, 3.0),
+            ColliderBuilder::cuboid(0.5, 0.5),
+        );
+        #[cfg(feature = "dim3")]
+        let (platform, body, co) = (
+            RigidBodyBuilder::new_static().translation(0.0, 2.0, 0.0),
+            RigidBodyBuilder::new_dynamic().translation(0.0, 3.0, 0.0),
+            ColliderBuilder::cuboid(0.5, 0.5, 0.5),
+        );
+        let (platform, _) = world.insert(platform.build(), co.build());
+        let (body, _) = world.insert(body.build(), co.build());
+
+        world.steps(300);
+        assert!(world.bodies[body].is_sleeping());
+
+        // Making the platform dynamic wakes up the box resting on it, and both fall.
+        world
+            .bodies
+            .get_mut(platform)
+            .unwrap()
+            .set_body_status(BodyStatus::Dynamic);
+        world.step();
+        assert!(!world.bodies[body].is_sleeping());
+        assert!(world
+            .bodies
+            .islands()
+            .active_dynamic_bodies()
+            .contains(&platform));
+        world.steps(10);
+        assert!(world.bodies[platform].position().translation.vector.y < 2.0);
+
+        // A kinematic body is no longer affected by the gravity.
+        world
+            .bodies
+            .get_mut(body)
+            .unwrap()
+            .set_body_status(BodyStatus::Kinematic);
+        let y = world.bodies[body].position().translation.vector.y;
+        world.steps(10);
+        let islands = world.bodies.islands();
+        assert_eq!(world.bodies[body].position().translation.vector.y, y);
+        assert!(*world.bodies[body].linvel() == Vector::zeros());
+        assert!(!islands.active_dynamic_bodies().contains(&body));
+        assert!(islands.active_kinematic_bodies().contains(&body));
+
+        // Switching back to dynamic.
+        world
+            .bodies
+            .get_mut(body)
+            .unwrap()
+            .set_body_status(BodyStatus::Dynamic);
+        world.steps(10);
+        let islands = world.bodies.islands();
+        assert!(world.bodies[body].position().translation.vector.y < y);
+        assert!(islands.active_dynamic_bodies().contains(&body));
+        assert!(!islands.active_kinematic_bodies().contains(&body));
+    }
+
+    #[test]
+    fn enable_disable_colliders_and_bodies() {
+        let mut world = TestWorld::falling();
+
+        // A static ground, and a box resting on it.
+        #[cfg(feature = "dim2")]
+        let co = ColliderBuilder::cuboid(10.0, 0.5).translation(0.0, -0.5);
+        #[cfg(feature = "dim3")]
+        let co = ColliderBuilder::cuboid(10.0, 0.5, 10.0).translation(0.0, -0.5, 0.0);
+        let (_, ground_co) = world.insert(RigidBodyBuilder::new_static().build(), co.build());
+
+        #[cfg(feature = "dim2")]
+        let rb = RigidBodyBuilder::new_dynamic().translation(0.0, 0.5);
+        #[cfg(feature = "dim3")]
+        let rb = RigidBodyBuilder::new_dynamic().translation(0.0, 0.5, 0.0);
+        #[cfg(feature = "dim2")]
+        let co = ColliderBuilder::cuboid(0.5, 0.5);
+        #[cfg(feature = "dim3")]
+        let co = ColliderBuilder::cuboid(0.5, 0.5, 0.5);
+        let (body, body_co) = world.insert(rb.build(), co.build());
+
+        world.steps(10);
+        assert!(world
+            .narrow_phase
+            .contact_pair(ground_co, body_co)
+            .is_some());
+
+        // A disabled body is not simulated, and does not interact with the ground.
+        let rb = world.bodies.get_mut(body).unwrap();
+        rb.set_enabled(false);
+        rb.set_linvel(Vector::y() * 10.0, false);
+        let y = world.bodies[body].position().translation.vector.y;
+        world.steps(10);
+        assert_eq!(world.bodies[body].position().translation.vector.y, y);
+        assert!(world
+            .narrow_phase
+            .contact_pair(ground_co, body_co)
+            .is_none());
+        assert!(!world
+            .bodies
+            .islands()
+            .active_dynamic_bodies()
+            .contains(&body));
+
+        // Once re-enabled, the body resumes its motion with its retained velocity.
+        world.bodies.get_mut(body).unwrap().set_enabled(true);
+        world.step();
+        assert!(world.bodies[body].position().translation.vector.y > y);
+        assert!(world
+            .bodies
+            .islands()
+            .active_dynamic_bodies()
+            .contains(&body));
+
+        // The body falls through a disabled ground collider.
+        world
+            .colliders
+            .get_mut(ground_co)
+            .unwrap()
+            .set_enabled(false);
+        world.steps(180);
+        assert!(world.bodies[body].position().translation.vector.y < -1.0);
+        assert!(world
+            .narrow_phase
+            .contact_pair(ground_co, body_co)
+            .is_none());
+
+        // Re-enabling the ground collider adds it back to the broad-phase.
+        world
+            .bodies
+            .get_mut(body)
+            .unwrap()
+            .set_position(Isometry::identity(), true);
+        world
+            .colliders
+            .get_mut(ground_co)
+            .unwrap()
+            .set_enabled(true);
+        world.step();
+        assert!(world
+            .narrow_phase
+            .contact_pair(ground_co, body_co)
+            .is_some());
+    }
+
+    #[test]
+    fn teleport_rigid_body() {
+        let mut world = TestWorld::falling();
+
+        #[cfg(feature = "dim2")]
+        let co = ColliderBuilder::cuboid(10.0, 0.5).translation(0.0, -0.5);
+        #[cfg(feature = "dim3")]
+        let co = ColliderBuilder::cuboid(10.0, 0.5, 10.0).translation(0.0, -0.5, 0.0);
+        let (_, ground_co) = world.insert(RigidBodyBuilder::new_static().build(), co.build());
+
+        #[cfg(feature = "dim2")]
+        let rb = RigidBodyBuilder::new_dynamic().translation(0.0, 3.0);
+        #[cfg(feature = "dim3")]
+        let rb = RigidBodyBuilder::new_dynamic().translation(0.0, 3.0, 0.0);
+        #[cfg(feature = "dim2")]
+        let co = ColliderBuilder::cuboid(0.5, 0.5);
+        #[cfg(feature = "dim3")]
+        let co = ColliderBuilder::cuboid(0.5, 0.5, 0.5);
+        let (body, body_co) = world.insert(rb.build(), co.build());
+
+        // Let the box fall until it hits the ground at a high velocity.
+        while world
+            .narrow_phase
+            .contact_pair(ground_co, body_co)
+            .map(|pair| !pair.has_any_active_contact())
+            .unwrap_or(true)
+        {
+            world.step();
+        }
+        let impulse = |world: &TestWorld| -> f32 {
+            world
+                .narrow_phase
+                .contact_pair(ground_co, body_co)
+                .unwrap()
+                .manifolds
+                .iter()
+                .map(|m| m.total_impulse())
+                .sum()
+        };
+        assert!(impulse(&world) > 1.0);
+
+        // Teleport the box so it rests on another part of the ground.
+        #[cfg(feature = "dim2")]
+        let pos = Isometry::translation(5.0, 0.5);
+        #[cfg(feature = "dim3")]
+        let pos = Isometry::translation(5.0, 0.5, 0.0);
+        world
+            .bodies
+            .get_mut(body)
+            .unwrap()
+            .teleport(pos, true, &mut world.colliders);
+        assert_eq!(*world.colliders[body_co].position(), pos);
+        assert_eq!(*world.bodies[body].linvel(), Vector::zeros());
+
+        // The impulses from the previous impact are not applied to the teleported box.
+        world.step();
+        assert!(world.bodies[body].linvel().norm() < 0.5);
+        assert!(impulse(&world) < 0.5);
+        assert!((world.bodies[body].position().translation.vector.y - 0.5).abs() < 0.05);
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn contact_force_events_above_threshold() {
+        use crate::pipeline::ChannelEventCollector;
+
+        let mut world = TestWorld::falling();
+
+        #[cfg(feature = "dim2")]
+        let co = ColliderBuilder::cuboid(10.0, 0.5).translation(0.0, -0.5);
+        #[cfg(feature = "dim3")]
+        let co = ColliderBuilder::cuboid(10.0, 0.5, 10.0).translation(0.0, -0.5, 0.0);
+        let (_, ground_co) = world.insert(RigidBodyBuilder::new_static().build(), co.build());
+
+        // A box with a mass of 1, falling from a height of 2.
+        #[cfg(feature = "dim2")]
+        let rb = RigidBodyBuilder::new_dynamic().translation(0.0, 2.5);
+        #[cfg(feature = "dim3")]
+        let rb = RigidBodyBuilder::new_dynamic().translation(0.0, 2.5, 0.0);
+        #[cfg(feature = "dim2")]
+        let co = ColliderBuilder::cuboid(0.5, 0.5);
+        #[cfg(feature = "dim3")]
+        let co = ColliderBuilder::cuboid(0.5, 0.5, 0.5);
+        let co = co.contact_force_event_threshold(50.0);
+        let (_, body_co) = world.insert(rb.build(), co.build());
+
+        let (contact_send, _contact_recv) = crossbeam::channel::unbounded();
+        let (proximity_send, _proximity_recv) = crossbeam::channel::unbounded();
+        let (force_send, force_recv) = crossbeam::channel::unbounded();
+        let event_handler = ChannelEventCollector::new(proximity_send, contact_send)
+            .contact_force_event_sender(force_send);
+
+        let mut num_events = Vec::new();
+        for _ in 0..60 {
+            world.step_with(None, &event_handler);
+            num_events.push(force_recv.try_iter().count());
+        }
+
+        // The impact is reported, but not the resting contact with a force of about 9.81.
+        assert!(num_events[..40].iter().sum::<usize>() > 0);
+        assert_eq!(num_events[40..].iter().sum::<usize>(), 0);
+
+        // Lowering the threshold reports the resting contact.
+        world.colliders[body_co].contact_force_event_threshold = 5.0;
+        world.step_with(None, &event_handler);
+        let event = force_recv.try_recv().unwrap();
+        assert!((event.total_force_magnitude - 9.81).abs() < 0.5);
+        assert!((event.max_force_direction.y.abs() - 1.0).abs() < 1.0e-5);
+        assert!(event.collider1 == ground_co || event.collider2 == ground_co);
+        assert!(event.total_force.y.abs() > 9.0);
+    }
+
+    #[test]
+    fn stale_handles_are_rejected() {
+        use crate::dynamics::BallJoint;
+        use crate::math::Point;
+
+        let mut colliders = ColliderSet::new();
+        let mut joints = JointSet::new();
+        let mut bodies = RigidBodySet::new();
+
+        let body1 = bodies.insert(RigidBodyBuilder::new_dynamic().build());
+        let body2 = bodies.insert(RigidBodyBuilder::new_dynamic().build());
+        let collider = colliders.insert(ColliderBuilder::ball(0.5).build(), body1, &mut bodies);
+        let joint = BallJoint::new(Point::origin(), Point::origin());
+        let joint = joints.insert(&mut bodies, body1, body2, joint);
+
+        bodies.remove(body1, &mut colliders, &mut joints);
+        assert!(bodies.get(body1).is_none());
+        assert!(colliders.get(collider).is_none());
+        assert!(joints.get(joint).is_none());
+
+        // The new objects reuse the storage of the removed ones, but not their handles.
+        let new_body = bodies.insert(RigidBodyBuilder::new_dynamic().build());
+        let new_collider =
+            colliders.insert(ColliderBuilder::ball(0.5).build(), new_body, &mut bodies);
+        let new_joint = BallJoint::new(Point::origin(), Point::origin());
+        let new_joint = joints.insert(&mut bodies, new_body, body2, new_joint);
+
+        assert_eq!(new_body.into_raw_parts().0, body1.into_raw_parts().0);
+        assert_eq!(new_collider.into_raw_parts().0, collider.into_raw_parts().0);
+        assert_eq!(new_joint.into_raw_parts().0, joint.into_raw_parts().0);
+        assert!(bodies.get(body1).is_none());
+        assert!(colliders.get(collider).is_none());
+        assert!(joints.get(joint).is_none());
+        assert!(bodies.remove(body1, &mut colliders, &mut joints).is_none());
+    }
+
+    fn serde_roundtrip<T: serde::Serialize + serde::de::DeserializeOwned>(value: &T) -> T {
+        bincode::deserialize(&bincode::serialize(value).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn collider_modifications_are_not_serialized() {
+        let mut world = TestWorld::falling();
+        let rb = RigidBodyBuilder::new_dynamic().build();
+        let (_, handle) = world.insert(rb, ColliderBuilder::ball(0.5).build());
+        world.step();
+
+        world.colliders.get_mut(handle).unwrap().set_mass(2.0);
+        let colliders: ColliderSet = serde_roundtrip(&world.colliders);
+
+        assert!(colliders.modified_colliders.is_empty());
+        assert!(colliders[handle].changes.is_empty());
+        assert_eq!(
+            colliders[handle].density(),
+            world.colliders[handle].density()
+        );
+    }
+
+    #[test]
+    fn snapshot_restoration_does_not_diverge() {
+        let mut world = TestWorld::falling();
+
+        #[cfg(feature = "dim2")]
+        let ground = ColliderBuilder::cuboid(100.0, 1.0).build();
+        #[cfg(feature = "dim3")]
+        let ground = ColliderBuilder::cuboid(100.0, 1.0, 100.0).build();
+        world.insert(RigidBodyBuilder::new_static().build(), ground);
+
+        // A pile of balls overlapping several broad-phase regions.
+        let mut handles = Vec::new();
+        for i in 0..10 {
+            for j in 0..4 {
+                let x = (i as f32 - 5.0) * 1.01;
+                let y = 1.5 + j as f32 * 1.01;
+                #[cfg(feature = "dim2")]
+                let rb = RigidBodyBuilder::new_dynamic().translation(x, y).build();
+                #[cfg(feature = "dim3")]
+                let rb = RigidBodyBuilder::new_dynamic()
+                    .translation(x, y, x * 0.1)
+                    .build();
+                let (handle, _) = world.insert(rb, ColliderBuilder::ball(0.5).build());
+                handles.push(handle);
+            }
+        }
+
+        world.steps(20);
+
+        // Introduce some removal history.
+        for handle in handles.iter().step_by(3) {
+            world
+                .bodies
+                .remove(*handle, &mut world.colliders, &mut world.joints);
+        }
+
+        world.steps(10);
+
+        let mut world2 = TestWorld::falling();
+        world2.bodies = serde_roundtrip(&world.bodies);
+        world2.colliders = serde_roundtrip(&world.colliders);
+        world2.joints = serde_roundtrip(&world.joints);
+        world2.broad_phase = serde_roundtrip(&world.broad_phase);
+        world2.narrow_phase = serde_roundtrip(&world.narrow_phase);
+
+        world.steps(50);
+        world2.steps(50);
+
+        let positions1: Vec<_> = world
+            .bodies
+            .iter()
+            .map(|(h, b)| (h, *b.position()))
+            .collect();
+        let positions2: Vec<_> = world2
+            .bodies
+            .iter()
+            .map(|(h, b)| (h, *b.position()))
+            .collect();
+        assert_eq!(positions1, positions2);
+
+        let colliders1: Vec<_> = world.colliders.iter().map(|(h, _)| h).collect();
+        let colliders2: Vec<_> = world2.colliders.iter().map(|(h, _)| h).collect();
+        assert_eq!(colliders1, colliders2);
+
+        let pairs1: Vec<_> = world.narrow_phase.contact_pairs().map(|p| p.pair).collect();
+        let pairs2: Vec<_> = world2
+            .narrow_phase
+            .contact_pairs()
+            .map(|p| p.pair)
+            .collect();
+        assert!(!pairs1.is_empty());
+        assert_eq!(pairs1, pairs2);
+
+        let islands1: Vec<_> = world.bodies.islands().active_islands().collect();
+        let islands2: Vec<_> = world2.bodies.islands().active_islands().collect();
+        assert_eq!(islands1, islands2);
+    }
+
+    #[test]
+    fn partial_snapshot_restoration() {
+        use crate::geometry::{BroadPhase, NarrowPhase};
+
+        let mut world = TestWorld::falling();
+
+        #[cfg(feature = "dim2")]
+        let (ground, box_shape) = (
+            ColliderBuilder::cuboid(10.0, 0.5),
+            ColliderBuilder::cuboid(0.5, 0.5),
+        );
+        #[cfg(feature = "dim3")]
+        let (ground, box_shape) = (
+            ColliderBuilder::cuboid(10.0, 0.5, 10.0),
+            ColliderBuilder::cuboid(0.5, 0.5, 0.5),
+        );
+        world.insert(RigidBodyBuilder::new_static().build(), ground.build());
+
+        // A stack of boxes resting on the ground.
+        let mut handles = Vec::new();
+        for i in 0..4 {
+            let rb = RigidBodyBuilder::new_dynamic()
+                .position(Isometry::new(Vector::y() * (1.0 + i as f32), na::zero()))
+                .can_sleep(false)
+                .build();
+            let (handle, _) = world.insert(rb, box_shape.build());
+            handles.push(handle);
+        }
+
+        world.steps(60);
+        let snapshot = (
+            bincode::serialize(&world.bodies).unwrap(),
+            bincode::serialize(&world.colliders).unwrap(),
+            bincode::serialize(&world.narrow_phase).unwrap(),
+            bincode::serialize(&world.broad_phase).unwrap(),
+        );
+        world.steps(60);
+        let positions: Vec<_> = handles
+            .iter()
+            .map(|h| *world.bodies[*h].position())
+            .collect();
+
+        for (restore_bf, restore_nf) in [(false, true), (true, false), (false, false)].iter() {
+            let mut world2 = TestWorld::falling();
+            world2.bodies = bincode::deserialize(&snapshot.0).unwrap();
+            world2.colliders = bincode::deserialize(&snapshot.1).unwrap();
+            world2.narrow_phase = if *restore_nf {
+                bincode::deserialize(&snapshot.2).unwrap()
+            } else {
+                NarrowPhase::new()
+            };
+            world2.broad_phase = if *restore_bf {
+                bincode::deserialize(&snapshot.3).unwrap()
+            } else {
+                BroadPhase::new()
+            };
+            world2.steps(60);
+
+            for (handle, pos) in handles.iter().zip(positions.iter()) {
+                let pos2 = world2.bodies[*handle].position();
+
+                if *restore_nf {
+                    // The contact manifolds survive the reconstruction of the broad-phase.
+                    assert_eq!(pos2, pos);
+                } else {
+                    // The contacts, including those with the static ground, are found again.
+                    let dist = (pos2.translation.vector - pos.translation.vector).norm();
+                    assert!(dist < 0.05, "{}", dist);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn reserved_sets_capacity() {
+        let mut colliders = ColliderSet::with_capacity(10);
+        let mut joints = JointSet::new();
+        let mut bodies = RigidBodySet::with_capacity(10);
+        assert!(bodies.capacity() >= 10);
+        assert!(colliders.capacity() >= 10);
+
+        let mut handles = Vec::new();
+        for _ in 0..10 {
+            let handle = bodies.insert(RigidBodyBuilder::new_dynamic().build());
+            colliders.insert(ColliderBuilder::ball(0.5).build(), handle, &mut bodies);
+            handles.push(handle);
+        }
+
+        bodies.remove(handles[3], &mut colliders, &mut joints);
+        bodies.reserve(5);
+        colliders.reserve(5);
+        let (bodies_capacity, colliders_capacity) = (bodies.capacity(), colliders.capacity());
+        assert!(bodies_capacity >= bodies.len() + 5);
+        assert!(colliders_capacity >= colliders.len() + 5);
+
+        for _ in 0..5 {
+            let handle = bodies.insert(RigidBodyBuilder::new_dynamic().build());
+            colliders.insert(ColliderBuilder::ball(0.5).build(), handle, &mut bodies);
+        }
+
+        assert_eq!(bodies.capacity(), bodies_capacity);
+        assert_eq!(colliders.capacity(), colliders_capacity);
+    }
+
+    #[test]
+    fn conveyor_belt_surface_velocity() {
+        let mut world = TestWorld::falling();
+
+        // Some boxes are inserted before the conveyor, the others after, so they don't
+        // end up with the same collider order on their contact manifolds.
+        #[cfg(feature = "dim2")]
+        let (box_shape, belt_shape) = (
+            ColliderBuilder::cuboid(0.5, 0.5),
+            ColliderBuilder::cuboid(20.0, 0.5),
+        );
+        #[cfg(feature = "dim3")]
+        let (box_shape, belt_shape) = (
+            ColliderBuilder::cuboid(0.5, 0.5, 0.5),
+            ColliderBuilder::cuboid(20.0, 0.5, 20.0),
+        );
+
+        let insert_box = |world: &mut TestWorld, x: f32| {
+            #[cfg(feature = "dim2")]
+            let rb = RigidBodyBuilder::new_dynamic().translation(x, 1.0).build();
+            #[cfg(feature = "dim3")]
+            let rb = RigidBodyBuilder::new_dynamic()
+                .translation(x, 1.0, 0.0)
+                .build();
+            world.insert(rb, box_shape.clone().friction(1.0).build()).0
+        };
+
+        // NOTE: use enough boxes to exercise the SIMD constraints too.
+        let mut boxes: Vec<_> = (0..4)
+            .map(|i| insert_box(&mut world, -2.0 * (i + 1) as f32))
+            .collect();
+        let belt_collider = belt_shape
+            .friction(1.0)
+            .surface_velocity(Vector::x() * 2.0, na::zero())
+            .build();
+        let (_, belt_collider) =
+            world.insert(RigidBodyBuilder::new_static().build(), belt_collider);
+        boxes.extend((0..4).map(|i| insert_box(&mut world, 2.0 * (i + 1) as f32)));
+
+        world.steps(120);
+
+        for handle in &boxes {
+            let linvel = world.bodies[*handle].linvel;
+            assert!((linvel.x - 2.0).abs() < 1.0e-2, "{:?}", linvel);
+            assert!(linvel.y.abs() < 1.0e-2, "{:?}", linvel);
+        }
+
+        // Stopping the conveyor stops the boxes too.
+        world
+            .colliders
+            .get_mut(belt_collider)
+            .unwrap()
+            .material_mut()
+            .surface_linvel = Vector::zeros();
+        world.steps(60);
+
+        for handle in &boxes {
+            assert!(world.bodies[*handle].linvel.x.abs() < 1.0e-2);
+        }
+    }
+
+    #[test]
+    fn shared_collider_materials_are_replaced_at_once() {
+        use crate::dynamics::CoefficientCombineRule;
+        use crate::geometry::ColliderMaterial;
+        use std::sync::Arc;
+
+        let mut world = TestWorld::falling();
+
+        // A floor made of two tiles sharing the same material, and a wall with its own.
+        let stone = Arc::new(ColliderMaterial::new(1.0, 0.0));
+        let ice = Arc::new(
+            ColliderMaterial::new(0.0, 0.0).friction_combine_rule(CoefficientCombineRule::Min),
+        );
+        let ground = world.bodies.insert(RigidBodyBuilder::new_static().build());
+        #[cfg(feature = "dim2")]
+        let (tile, wall, rb) = (
+            ColliderBuilder::cuboid(5.0, 0.5),
+            ColliderBuilder::cuboid(0.5, 5.0).translation(20.0, 0.0),
+            RigidBodyBuilder::new_dynamic()
+                .translation(-5.0, 1.0)
+                .linvel(1.0, 0.0),
+        );
+        #[cfg(feature = "dim3")]
+        let (tile, wall, rb) = (
+            ColliderBuilder::cuboid(5.0, 0.5, 5.0),
+            ColliderBuilder::cuboid(0.5, 5.0, 5.0).translation(20.0, 0.0, 0.0),
+            RigidBodyBuilder::new_dynamic()
+                .translation(-5.0, 1.0, 0.0)
+                .linvel(1.0, 0.0, 0.0),
+        );
+
+        for x in [-5.0, 5.0].iter() {
+            let tile = tile
+                .clone()
+                .position(Isometry::new(Vector::x() * *x, na::zero()))
+                .material(stone.clone())
+                .build();
+            world.attach(tile, ground);
+        }
+        let wall = world.attach(wall.friction(0.8).build(), ground);
+
+        #[cfg(feature = "dim2")]
+        let box_shape = ColliderBuilder::cuboid(0.5, 0.5);
+        #[cfg(feature = "dim3")]
+        let box_shape = ColliderBuilder::cuboid(0.5, 0.5, 0.5);
+        let (handle, _) = world.insert(rb.build(), box_shape.build());
+
+        // The box stops sliding on the stone floor.
+        world.steps(60);
+        assert!(world.bodies[handle].linvel().x.abs() < 1.0e-2);
+
+        // The box keeps sliding once the whole floor is frozen.
+        assert_eq!(world.colliders.replace_material(&stone, ice.clone()), 2);
+        assert_eq!(world.colliders[wall].material().friction, 0.8);
+        world
+            .bodies
+            .get_mut(handle)
+            .unwrap()
+            .set_linvel(Vector::x() * 1.0, true);
+        world.steps(60);
+        assert!((world.bodies[handle].linvel().x - 1.0).abs() < 1.0e-2);
+    }
+
+    #[cfg(feature = "serde-serialize")]
+    #[test]
+    fn deserialized_colliders_dont_share_materials() {
+        use crate::geometry::ColliderMaterial;
+        use std::sync::Arc;
+
+        let mut world = TestWorld::falling();
+        let stone = Arc::new(ColliderMaterial::new(1.0, 0.0));
+        let ground = world.bodies.insert(RigidBodyBuilder::new_static().build());
+        let tiles: Vec<_> = (0..2)
+            .map(|_| {
+                let tile = ColliderBuilder::ball(0.5).material(stone.clone()).build();
+                world.attach(tile, ground)
+            })
+            .collect();
+
+        let serialized = bincode::serialize(&world.colliders).unwrap();
+        let mut colliders: ColliderSet = bincode::deserialize(&serialized).unwrap();
+
+        // The materials are deserialized, but each collider gets its own copy.
+        let material1 = colliders[tiles[0]].shared_material().clone();
+        let material2 = colliders[tiles[1]].shared_material().clone();
+        assert_eq!(*material1, *stone);
+        assert_eq!(*material2, *stone);
+        assert!(!Arc::ptr_eq(&material1, &material2));
+        let ice = Arc::new(ColliderMaterial::new(0.0, 0.0));
+        assert_eq!(colliders.replace_material(&material1, ice.clone()), 1);
+
+        // Setting the same material again shares it.
+        for tile in &tiles {
+            colliders
+                .get_mut(*tile)
+                .unwrap()
+                .set_material(stone.clone());
+        }
+        assert_eq!(colliders.replace_material(&stone, ice), 2);
+    }
+
+    #[test]
+    fn stiff_springs_settle_at_their_equilibrium_length() {
+        use crate::dynamics::SpringJoint;
+        use crate::math::Point;
+
+        let mut world = TestWorld::falling();
+
+        // A chain of two bodies hanging from the ground by springs too stiff to be
+        // integrated explicitly with the default timestep.
+        let stiffness = 2.0e4;
+        let ground = world.bodies.insert(RigidBodyBuilder::new_static().build());
+        let rb1 = RigidBodyBuilder::new_dynamic()
+            .position(Isometry::new(Vector::y() * -1.0, na::zero()))
+            .build();
+        let rb2 = RigidBodyBuilder::new_dynamic()
+            .position(Isometry::new(Vector::y() * -2.0, na::zero()))
+            .build();
+        let co = ColliderBuilder::ball(0.4).build();
+        let (body1, _) = world.insert(rb1, co.clone());
+        let (body2, _) = world.insert(rb2, co);
+
+        let spring = SpringJoint::new(Point::origin(), Point::origin(), 1.0, stiffness, 20.0);
+        world
+            .joints
+            .insert(&mut world.bodies, ground, body1, spring);
+        let lower_spring = world.joints.insert(&mut world.bodies, body1, body2, spring);
+
+        world.steps(600);
+
+        let mass = world.bodies[body1].mass();
+        let y1 = world.bodies[body1].position().translation.vector.y;
+        let y2 = world.bodies[body2].position().translation.vector.y;
+        let expected_y1 = -1.0 - 2.0 * mass * 9.81 / stiffness;
+        let expected_y2 = expected_y1 - 1.0 - mass * 9.81 / stiffness;
+        assert!((y1 - expected_y1).abs() < 1.0e-5);
+        assert!((y2 - expected_y2).abs() < 1.0e-5);
+        assert!(world.bodies[body2].linvel().norm() < 1.0e-3);
+
+        // The lower spring is stretched, so it pulls the first body down.
+        let joint = world.joints.get(lower_spring).unwrap();
+        let impulse = joint.params.as_spring_joint().unwrap().impulse;
+        assert!((impulse - mass * 9.81 * world.params.dt()).abs() < 1.0e-4);
+    }
+
+    #[cfg(feature = "dim2")]
+    #[test]
+    fn gear_joints_couple_angles_and_translations() {
+        use crate::dynamics::{BallJoint, GearCoordinate, GearJoint, PrismaticJoint};
+        use crate::math::Point;
+
+        let mut world = TestWorld::new(Vector::zeros());
+
+        // A kinematic driver rotating a wheel, which moves a rack.
+        let ground = world.bodies.insert(RigidBodyBuilder::new_static().build());
+        let driver = world
+            .bodies
+            .insert(RigidBodyBuilder::new_kinematic().build());
+        let wheel = RigidBodyBuilder::new_dynamic()
+            .position(Isometry::new(Vector::x() * 3.0, 0.0))
+            .build();
+        let (wheel, _) = world.insert(wheel, ColliderBuilder::ball(0.5).build());
+        let rack = RigidBodyBuilder::new_dynamic()
+            .position(Isometry::new(Vector::y() * -3.0, 0.0))
+            .build();
+        let (rack, _) = world.insert(rack, ColliderBuilder::cuboid(1.0, 0.2).build());
+
+        let wheel_axle = BallJoint::new(Point::new(3.0, 0.0), Point::origin());
+        let rack_slider = PrismaticJoint::new(
+            Point::new(0.0, -3.0),
+            Vector::x_axis(),
+            Point::origin(),
+            Vector::x_axis(),
+        );
+        let joints = &mut world.joints;
+        joints.insert(&mut world.bodies, ground, wheel, wheel_axle);
+        joints.insert(&mut world.bodies, ground, rack, rack_slider);
+
+        // The driver is the second body of the gear so its constraint is flipped.
+        let gear = GearJoint::new(GearCoordinate::Angle, GearCoordinate::Angle, 0.5);
+        let pinion = GearJoint::new(
+            GearCoordinate::Angle,
+            GearCoordinate::Translation(Vector::x_axis()),
+            2.0,
+        );
+        joints.insert(&mut world.bodies, wheel, driver, gear);
+        joints.insert(&mut world.bodies, wheel, rack, pinion);
+
+        for i in 1..=200 {
+            let angle = i as f32 * world.params.dt();
+            world
+                .bodies
+                .get_mut(driver)
+                .unwrap()
+                .set_next_kinematic_position(Isometry::new(Vector::zeros(), angle));
+            world.step();
+        }
+
+        assert!((world.bodies[wheel].angvel() + 0.5).abs() < 1.0e-3);
+        assert!((world.bodies[rack].linvel().x - 0.25).abs() < 1.0e-3);
+    }
+
+    #[cfg(feature = "dim2")]
+    #[test]
+    fn angular_and_linear_joints() {
+        use crate::dynamics::{AngularJoint, LinearJoint};
+
+        let mut world = TestWorld::new(Vector::zeros());
+
+        // A kinematic driver moving and rotating, a follower keeping its orientation
+        // relative to the driver, and a passenger keeping its offset to the driver.
+        let driver = world
+            .bodies
+            .insert(RigidBodyBuilder::new_kinematic().build());
+        let follower = RigidBodyBuilder::new_dynamic()
+            .translation(3.0, 0.0)
+            .linvel(0.0, 1.0)
+            .build();
+        let (follower, _) = world.insert(follower, ColliderBuilder::ball(0.5).build());
+        let passenger = RigidBodyBuilder::new_dynamic()
+            .translation(0.0, -2.0)
+            .angvel(2.0)
+            .build();
+        let (passenger, _) = world.insert(passenger, ColliderBuilder::ball(0.5).build());
+
+        let joints = &mut world.joints;
+        joints.insert(&mut world.bodies, driver, follower, AngularJoint::new(0.5));
+        // The driver is the second body of this joint so its constraint is flipped.
+        joints.insert(
+            &mut world.bodies,
+            passenger,
+            driver,
+            LinearJoint::new(Vector::y() * 2.0),
+        );
+
+        let dt = world.params.dt();
+        for i in 1..=100 {
+            let t = i as f32 * dt;
+            world
+                .bodies
+                .get_mut(driver)
+                .unwrap()
+                .set_next_kinematic_position(Isometry::new(Vector::x() * t, t));
+            world.step();
+        }
+
+        let bodies = &world.bodies;
+        let (driver, follower, passenger) =
+            (&bodies[driver], &bodies[follower], &bodies[passenger]);
+        let relative_angle = driver
+            .position()
+            .rotation
+            .rotation_to(&follower.position().rotation)
+            .angle();
+        assert!((relative_angle - 0.5).abs() < 1.0e-2);
+        assert!((follower.angvel() - 1.0).abs() < 1.0e-2);
+        assert!(
+            (follower.position().translation.vector - Vector::new(3.0, 100.0 * dt)).norm() < 1.0e-2
+        );
+
+        let offset = driver.world_com - passenger.world_com;
+        assert!((offset - Vector::y() * 2.0).norm() < 1.0e-2);
+        assert!((passenger.linvel() - Vector::x()).norm() < 1.0e-2);
+        assert!((passenger.angvel() - 2.0).abs() < 1.0e-2);
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn joint_limit_events() {
+        use crate::dynamics::PrismaticJoint;
+        use crate::math::Point;
+        use crate::pipeline::{ChannelEventCollector, JointLimitEvent};
+
+        let mut world = TestWorld::new(Vector::zeros());
+
+        let ground = world.bodies.insert(RigidBodyBuilder::new_static().build());
+        let rb = RigidBodyBuilder::new_dynamic().build();
+        let (body, _) = world.insert(rb, ColliderBuilder::ball(0.5).build());
+
+        #[cfg(feature = "dim2")]
+        let mut joint = PrismaticJoint::new(
+            Point::origin(),
+            Vector::x_axis(),
+            Point::origin(),
+            Vector::x_axis(),
+        );
+        #[cfg(feature = "dim3")]
+        let mut joint = PrismaticJoint::new(
+            Point::origin(),
+            Vector::x_axis(),
+            Vector::zeros(),
+            Point::origin(),
+            Vector::x_axis(),
+            Vector::zeros(),
+        );
+        joint.limits_enabled = true;
+        joint.limits = [-1.0, 1.0];
+        let handle = world.joints.insert(&mut world.bodies, ground, body, joint);
+
+        let (contact_send, _contact_recv) = crossbeam::channel::unbounded();
+        let (proximity_send, _proximity_recv) = crossbeam::channel::unbounded();
+        let (limit_send, limit_recv) = crossbeam::channel::unbounded();
+        let event_handler = ChannelEventCollector::new(proximity_send, contact_send)
+            .joint_limit_event_sender(limit_send);
+
+        // Push the body against the max limit, then against the min limit.
+        let mut events = Vec::new();
+        for gravity in [Vector::x() * 9.81, Vector::x() * -9.81].iter() {
+            world.gravity = *gravity;
+            world.bodies.get_mut(body).unwrap().wake_up(true);
+
+            for _ in 0..120 {
+                world.step_with(None, &event_handler);
+                events.extend(limit_recv.try_iter());
+            }
+        }
+
+        assert_eq!(events.len(), 3);
+        match events[0] {
+            JointLimitEvent::Reached {
+                joint,
+                limit,
+                impulse,
+            } => {
+                assert_eq!((joint, limit), (handle, 1));
+                assert!(impulse > 0.0);
+            }
+            _ => panic!("The max limit should have been reached."),
+        }
+        assert_eq!(
+            events[1],
+            JointLimitEvent::Left {
+                joint: handle,
+                limit: 1
+            }
+        );
+        assert!(matches!(
+            events[2],
+            JointLimitEvent::Reached { limit: 0, .. }
+        ));
+
+        let joint = world.joints.get(handle).unwrap();
+        let prismatic = joint.params.as_prismatic_joint().unwrap();
+        assert_eq!(prismatic.active_limit(), Some(0));
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn body_activation_events() {
+        use crate::pipeline::{BodyActivationEvent, ChannelEventCollector};
+
+        let mut world = TestWorld::falling();
+
+        #[cfg(feature = "dim2")]
+        let (floor, rb) = (
+            ColliderBuilder::cuboid(10.0, 0.5),
+            RigidBodyBuilder::new_dynamic().translation(0.0, 1.0),
+        );
+        #[cfg(feature = "dim3")]
+        let (floor, rb) = (
+            ColliderBuilder::cuboid(10.0, 0.5, 10.0),
+            RigidBodyBuilder::new_dynamic().translation(0.0, 1.0, 0.0),
+        );
+        world.insert(RigidBodyBuilder::new_static().build(), floor.build());
+        let (ball, _) = world.insert(rb.build(), ColliderBuilder::ball(0.5).build());
+
+        let (contact_send, _contact_recv) = crossbeam::channel::unbounded();
+        let (proximity_send, _proximity_recv) = crossbeam::channel::unbounded();
+        let (activation_send, activation_recv) = crossbeam::channel::unbounded();
+        let event_handler = ChannelEventCollector::new(proximity_send, contact_send)
+            .body_activation_event_sender(activation_send);
+
+        let step = |world: &mut TestWorld, num_steps| {
+            let mut events = Vec::new();
+            for _ in 0..num_steps {
+                world.step_with(None, &event_handler);
+                events.extend(activation_recv.try_iter());
+            }
+            events
+        };
+
+        // The ball is activated by its insertion, and falls asleep once it rests on the floor.
+        let events = step(&mut world, 200);
+        assert!(world.bodies[ball].is_sleeping());
+        assert_eq!(
+            events,
+            vec![
+                BodyActivationEvent::Activated(ball),
+                BodyActivationEvent::Deactivated(ball)
+            ]
+        );
+
+        // Waking it up emits another event, before it falls asleep again.
+        world.bodies.wake_up(ball, true);
+        let events = step(&mut world, 1);
+        assert_eq!(events, vec![BodyActivationEvent::Activated(ball)]);
+        assert_eq!(world.bodies.islands().activated_bodies(), &[ball][..]);
+        assert!(step(&mut world, 1).is_empty());
+        assert!(world.bodies.islands().activated_bodies().is_empty());
+    }
+
+    #[test]
+    fn joints_disable_contacts_between_attached_bodies() {
+        use crate::dynamics::BallJoint;
+        use crate::math::Point;
+
+        let mut world = TestWorld::new(Vector::zeros());
+
+        // Two overlapping bodies attached by a ball joint.
+        let co = ColliderBuilder::ball(1.0).build();
+        let (ground, co1) = world.insert(RigidBodyBuilder::new_static().build(), co.clone());
+        let (body, co2) = world.insert(RigidBodyBuilder::new_dynamic().build(), co);
+        let joint = BallJoint::new(Point::origin(), Point::origin());
+        let handle = world.joints.insert(&mut world.bodies, ground, body, joint);
+
+        world.step();
+        assert!(world
+            .narrow_phase
+            .contact_pair(co1, co2)
+            .map(|pair| !pair.has_any_active_contact())
+            .unwrap_or(true));
+
+        world.joints.get_mut(handle).unwrap().contacts_enabled = true;
+        world.bodies.get_mut(body).unwrap().wake_up(true);
+        world.step();
+        let pair = world.narrow_phase.contact_pair(co1, co2).unwrap();
+        assert!(pair.has_any_active_contact());
+    }
+
+    #[test]
+    fn collider_prediction_distance_prevents_tunneling() {
+        let final_height = |prediction_distance: Option<f32>| {
+            let mut world = TestWorld::falling();
+
+            // A thin ground, and a small ball moving toward it by ~0.67m per timestep.
+            #[cfg(feature = "dim2")]
+            let ground_shape = ColliderBuilder::cuboid(10.0, 0.05);
+            #[cfg(feature = "dim3")]
+            let ground_shape = ColliderBuilder::cuboid(10.0, 0.05, 10.0);
+            world.insert(RigidBodyBuilder::new_static().build(), ground_shape.build());
+
+            let ball = RigidBodyBuilder::new_dynamic()
+                .position(Isometry::new(Vector::y() * 1.0, na::zero()))
+                .build();
+            let ball = world.bodies.insert(ball);
+            world
+                .bodies
+                .get_mut(ball)
+                .unwrap()
+                .set_linvel(Vector::y() * -40.0, true);
+            let mut ball_collider = ColliderBuilder::ball(0.1);
+            ball_collider.prediction_distance = prediction_distance;
+            world.attach(ball_collider.build(), ball);
+
+            world.steps(60);
+
+            world.bodies[ball].position().translation.vector.y
+        };
+
+        assert!(final_height(None) < 0.0);
+        assert!(final_height(Some(1.0)) > 0.0);
+    }
+
+    #[test]
+    fn cloned_world_resimulation_does_not_diverge() {
+        use crate::dynamics::BallJoint;
+        use crate::math::Point;
+
+        let mut world = TestWorld::falling();
+
+        #[cfg(feature = "dim2")]
+        let ground = ColliderBuilder::cuboid(20.0, 1.0);
+        #[cfg(feature = "dim3")]
+        let ground = ColliderBuilder::cuboid(20.0, 1.0, 20.0);
+        let (ground_handle, _) =
+            world.insert(RigidBodyBuilder::new_static().build(), ground.build());
+        let sensor = ColliderBuilder::ball(2.0).sensor(true).build();
+        world.attach(sensor, ground_handle);
+
+        let mut prev = ground_handle;
+        for i in 0..8 {
+            let pos = Isometry::new(Vector::y() * (2.0 + i as f32 * 1.1), na::zero());
+            let rb = RigidBodyBuilder::new_dynamic().position(pos).build();
+            let (handle, _) = world.insert(rb, ColliderBuilder::ball(0.5).build());
+
+            if i % 2 == 1 {
+                let joint = BallJoint::new(Point::origin(), Point::from(Vector::x()));
+                world.joints.insert(&mut world.bodies, prev, handle, joint);
+            }
+            prev = handle;
+        }
+
+        world.steps(30);
+
+        let mut world2 = world.clone();
+
+        // The shapes are shared between both worlds.
+        for ((_, co1), (_, co2)) in world.colliders.iter().zip(world2.colliders.iter()) {
+            assert!(std::sync::Arc::ptr_eq(
+                &co1.shared_shape().0,
+                &co2.shared_shape().0
+            ));
+        }
+
+        // Both worlds are resimulated from the same state.
+        world.steps(60);
+        world2.steps(60);
+
+        let positions1: Vec<_> = world
+            .bodies
+            .iter()
+            .map(|(h, b)| (h, *b.position()))
+            .collect();
+        let positions2: Vec<_> = world2
+            .bodies
+            .iter()
+            .map(|(h, b)| (h, *b.position()))
+            .collect();
+        assert_eq!(positions1, positions2);
+
+        let pairs1: Vec<_> = world.narrow_phase.contact_pairs().map(|p| p.pair).collect();
+        let pairs2: Vec<_> = world2
+            .narrow_phase
+            .contact_pairs()
+            .map(|p| p.pair)
+            .collect();
+        assert!(!pairs1.is_empty());
+        assert_eq!(pairs1, pairs2);
+
+        let proximities1: Vec<_> = world
+            .narrow_phase
+            .proximity_pairs()
+            .map(|p| p.proximity)
+            .collect();
+        let proximities2: Vec<_> = world2
+            .narrow_phase
+            .proximity_pairs()
+            .map(|p| p.proximity)
+            .collect();
+        assert_eq!(proximities1, proximities2);
+    }
+
+    #[test]
+    fn ccd_bodies_have_swept_aabbs() {
+        // Throws a fast ball toward a thin wall it goes through in a single timestep.
+        let wall_pair_exists = |ccd_enabled: bool| {
+            let mut world = TestWorld::new(Vector::zeros());
+
+            #[cfg(feature = "dim2")]
+            let co = ColliderBuilder::cuboid(0.05, 1.0);
+            #[cfg(feature = "dim3")]
+            let co = ColliderBuilder::cuboid(0.05, 1.0, 1.0);
+            let (_, wall) = world.insert(RigidBodyBuilder::new_static().build(), co.build());
+
+            let body = RigidBodyBuilder::new_dynamic()
+                .position(Isometry::new(-Vector::x(), na::zero()))
+                .ccd_enabled(ccd_enabled)
+                .build();
+            let (body, ball) = world.insert(body, ColliderBuilder::ball(0.1).build());
+            world
+                .bodies
+                .get_mut(body)
+                .unwrap()
+                .set_linvel(Vector::x() * 100.0, true);
+
+            world.step();
+
+            world.narrow_phase.contact_pair(wall, ball).is_some()
+        };
+
+        assert!(!wall_pair_exists(false));
+        assert!(wall_pair_exists(true));
+    }
+
+    #[test]
+    fn colliders_attached_after_creation() {
+        let mut world = TestWorld::new(Vector::zeros());
+
+        let ground = world.bodies.insert(RigidBodyBuilder::new_static().build());
+        let co = ColliderBuilder::ball(1.0).build();
+        let ground_co = world
+            .colliders
+            .insert_with_parent(co, ground, &mut world.bodies);
+
+        let body = RigidBodyBuilder::new_dynamic()
+            .position(Isometry::new(Vector::y() * 1.9, na::zero()))
+            .sleeping(true)
+            .build();
+        let body = world.bodies.insert(body);
+
+        world.step();
+        assert_eq!(world.bodies[body].mass(), 0.0);
+
+        // Attach a collider to the sleeping body.
+        let co = ColliderBuilder::ball(1.0).density(2.0).build();
+        let co1 = world
+            .colliders
+            .insert_with_parent(co, body, &mut world.bodies);
+        let mass1 = world.bodies[body].mass();
+        assert!(mass1 > 0.0);
+
+        world.step();
+        assert!(world.bodies[body].is_sleeping());
+        assert!(world.narrow_phase.contact_pair(ground_co, co1).is_some());
+
+        // Attach another collider once the body is awake.
+        world.bodies.get_mut(body).unwrap().wake_up(true);
+        world.step();
+        let co = ColliderBuilder::ball(0.5)
+            .position(Isometry::new(Vector::x() - Vector::y() * 0.5, na::zero()))
+            .build();
+        let co2 = world
+            .colliders
+            .insert_with_parent(co, body, &mut world.bodies);
+        assert!(world.bodies[body].mass() > mass1);
+
+        world.step();
+        assert!(world.narrow_phase.contact_pair(ground_co, co2).is_some());
+    }
+
+    #[test]
+    fn round_cuboids_contacts_are_computed_on_their_cores() {
+        let mut world = TestWorld::new(Vector::zeros());
+
+        // Two thin plates with a margin larger than their core thickness.
+        #[cfg(feature = "dim2")]
+        let (plate, max_contacts) = (ColliderBuilder::round_cuboid(0.5, 0.01, 0.05), 2);
+        #[cfg(feature = "dim3")]
+        let (plate, max_contacts) = (ColliderBuilder::round_cuboid(0.5, 0.01, 0.5, 0.05), 4);
+
+        let (_, co1) = world.insert(RigidBodyBuilder::new_static().build(), plate.build());
+
+        // The outer surfaces overlap by 0.01, but the cores are 0.09 apart.
+        let body = RigidBodyBuilder::new_dynamic()
+            .position(Isometry::new(Vector::y() * 0.11, na::zero()))
+            .build();
+        let (_, co2) = world.insert(body, plate.build());
+
+        world.step();
+
+        let manifold = &world.narrow_phase.contact_pair(co1, co2).unwrap().manifolds[0];
+        assert_eq!(manifold.num_active_contacts(), max_contacts);
+
+        for point in manifold.all_contacts() {
+            assert!((point.dist + 0.01).abs() < 1.0e-4);
+        }
+    }
+
+    #[test]
+    fn body_with_many_contacts() {
+        let mut world = TestWorld::falling();
+
+        // A dynamic plate touching more bodies than the 128 colors of the first block
+        // of the constraints graph coloring performed by the parallel solver.
+        #[cfg(feature = "dim2")]
+        let (ground, plate) = (
+            ColliderBuilder::cuboid(200.0, 0.5),
+            ColliderBuilder::cuboid(200.0, 0.5),
+        );
+        #[cfg(feature = "dim3")]
+        let (ground, plate) = (
+            ColliderBuilder::cuboid(200.0, 0.5, 1.0),
+            ColliderBuilder::cuboid(200.0, 0.5, 1.0),
+        );
+        world.insert(RigidBodyBuilder::new_static().build(), ground.build());
+        let plate_body = RigidBodyBuilder::new_dynamic()
+            .position(Isometry::new(Vector::y() * 1.0, na::zero()))
+            .build();
+        let (plate_handle, _) = world.insert(plate_body, plate.build());
+
+        let mut balls = Vec::new();
+        for i in 0..200 {
+            let position = Vector::x() * (i as f32 * 1.5 - 149.25) + Vector::y() * 2.0;
+            let rb = RigidBodyBuilder::new_dynamic()
+                .position(Isometry::new(position, na::zero()))
+                .build();
+            let (handle, _) = world.insert(rb, ColliderBuilder::ball(0.5).build());
+            balls.push(handle);
+        }
+
+        world.steps(30);
+
+        let bodies = &world.bodies;
+        assert!((bodies[plate_handle].position().translation.y - 1.0).abs() < 1.0e-2);
+        for handle in &balls {
+            assert!((bodies[*handle].position().translation.y - 2.0).abs() < 1.0e-2);
+        }
+    }
+
+    #[test]
+    fn contact_skin_keeps_colliders_apart() {
+        let final_state = |skin: f32| {
+            let mut world = TestWorld::falling();
+
+            // A box resting on a ground with its top face at y = 0.
+            let ground = RigidBodyBuilder::new_static()
+                .position(Isometry::new(Vector::y() * -0.5, na::zero()))
+                .build();
+            #[cfg(feature = "dim2")]
+            let (ground_shape, box_shape) = (
+                ColliderBuilder::cuboid(10.0, 0.5),
+                ColliderBuilder::cuboid(0.5, 0.5),
+            );
+            #[cfg(feature = "dim3")]
+            let (ground_shape, box_shape) = (
+                ColliderBuilder::cuboid(10.0, 0.5, 10.0),
+                ColliderBuilder::cuboid(0.5, 0.5, 0.5),
+            );
+            let (_, ground_collider) = world.insert(ground, ground_shape.build());
+
+            let body = RigidBodyBuilder::new_dynamic()
+                .position(Isometry::new(Vector::y() * 0.55, na::zero()))
+                .build();
+            let (body, collider) = world.insert(body, box_shape.contact_skin(skin).build());
+
+            world.steps(100);
+
+            let pair = world.narrow_phase.contact_pair(collider, ground_collider);
+            let manifold = &pair.unwrap().manifolds[0];
+            assert!(manifold.num_active_contacts() > 0);
+            let min_dist = manifold
+                .active_contacts()
+                .iter()
+                .map(|c| c.dist)
+                .fold(f32::MAX, f32::min);
+            (world.bodies[body].position().translation.y, min_dist)
+        };
+
+        let (height, dist) = final_state(0.0);
+        assert!((height - 0.5).abs() < 1.0e-2 && dist.abs() < 1.0e-2);
+        // The box now rests on its skin, and the contact distances account for it.
+        let (height, dist) = final_state(0.1);
+        assert!((height - 0.6).abs() < 1.0e-2 && dist.abs() < 1.0e-2);
+    }
+
+    #[test]
+    fn custom_joints_solve_user_defined_constraints() {
+        use crate::dynamics::{
+            CustomConstraint, CustomConstraintBody, CustomConstraintRow, CustomJoint,
+            IntegrationParameters, RigidBody,
+        };
+        use std::sync::Arc;
+
+        // Makes both bodies move at the same velocity along the `x` axis.
+        struct SameVelocityX;
+        impl CustomConstraint for SameVelocityX {
+            fn rows(
+                &self,
+                _: &IntegrationParameters,
+                _: &RigidBody,
+                _: &RigidBody,
+                rows: &mut Vec<CustomConstraintRow>,
+            ) {
+                rows.push(CustomConstraintRow::new(
+                    -Vector::x(),
+                    na::zero(),
+                    Vector::x(),
+                    na::zero(),
+                ));
+            }
+        }
+
+        // Cancels the vertical velocity of the second body.
+        struct NoFall;
+        impl CustomConstraint for NoFall {
+            fn solve_velocities(
+                &self,
+                _: f32,
+                _: &mut CustomConstraintBody,
+                body2: &mut CustomConstraintBody,
+            ) {
+                let impulse = -Vector::y() * (body2.linvel().y / body2.inv_mass());
+                body2.apply_impulse(impulse, na::zero());
+            }
+        }
+
+        let mut world = TestWorld::falling();
+
+        let ground = world.bodies.insert(RigidBodyBuilder::new_static().build());
+        let handles: Vec<_> = [-5.0, 5.0, 15.0]
+            .iter()
+            .map(|x| {
+                let mut position = Isometry::identity();
+                position.translation.vector.x = *x;
+                let rb = RigidBodyBuilder::new_dynamic().position(position).build();
+                world.insert(rb, ColliderBuilder::ball(0.5).build()).0
+            })
+            .collect();
+        world
+            .bodies
+            .get_mut(handles[0])
+            .unwrap()
+            .set_linvel(Vector::x() * 2.0, true);
+
+        let synced = CustomJoint::new(Arc::new(SameVelocityX));
+        let synced = world
+            .joints
+            .insert(&mut world.bodies, handles[0], handles[1], synced);
+        let no_fall = CustomJoint::new(Arc::new(NoFall));
+        world
+            .joints
+            .insert(&mut world.bodies, ground, handles[2], no_fall);
+
+        let custom_impulses = |joints: &JointSet| {
+            let joint = joints.get(synced).unwrap();
+            joint.params.as_custom_joint().unwrap().impulses.clone()
+        };
+        let mass = world.bodies[handles[1]].mass();
+
+        for i in 0..10 {
+            world.step();
+
+            // Both bodies have the same mass, so the first step transfers half the momentum of
+            // the first body to the second one. No impulse is needed afterwards.
+            let impulses = custom_impulses(&world.joints);
+            assert_eq!(impulses.len(), 1);
+            let expected = if i == 0 { mass } else { 0.0 };
+            assert!((impulses[0] - expected).abs() < 1.0e-3);
+        }
+
+        let bodies = &world.bodies;
+        assert!((bodies[handles[0]].linvel().x - 1.0).abs() < 1.0e-4);
+        assert!((bodies[handles[1]].linvel().x - 1.0).abs() < 1.0e-4);
+
+        // The third body doesn't fall, while the others do.
+        assert!(bodies[handles[2]].linvel().y.abs() < 1.0e-4);
+        assert!(bodies[handles[2]].position().translation.y.abs() < 1.0e-4);
+        assert!(bodies[handles[0]].position().translation.y < -0.1);
+    }
 }
@@ -0,0 +1,209 @@
+use crate::dynamics::{RigidBodyHandle, RigidBodySet};
+use crate::geometry::{Collider, ColliderHandle, InteractionGroups};
+
+bitflags::bitflags! {
+    #[cfg_attr(feature = "serde-serialize", derive(Serialize, Deserialize))]
+    /// Flags for excluding whole sets of colliders from a scene query.
+    pub struct QueryFilterFlags: u32 {
+        /// Exclude from the query any collider attached to a static rigid-body.
+        const EXCLUDE_STATIC = 1 << 0;
+        /// Exclude from the query any collider attached to a kinematic rigid-body.
+        const EXCLUDE_KINEMATIC = 1 << 1;
+        /// Exclude from the query any collider attached to a dynamic rigid-body.
+        const EXCLUDE_DYNAMIC = 1 << 2;
+        /// Exclude from the query any collider that is a sensor.
+        const EXCLUDE_SENSORS = 1 << 3;
+        /// Exclude from the query any collider that is not a sensor.
+        const EXCLUDE_SOLIDS = 1 << 4;
+        /// Excludes all colliders not attached to a dynamic rigid-body.
+        const ONLY_DYNAMIC = Self::EXCLUDE_STATIC.bits | Self::EXCLUDE_KINEMATIC.bits;
+        /// Excludes all colliders not attached to a kinematic rigid-body.
+        const ONLY_KINEMATIC = Self::EXCLUDE_STATIC.bits | Self::EXCLUDE_DYNAMIC.bits;
+        /// Excludes all colliders not attached to a static rigid-body.
+        const ONLY_STATIC = Self::EXCLUDE_KINEMATIC.bits | Self::EXCLUDE_DYNAMIC.bits;
+    }
+}
+
+/// A filter selecting the colliders taken into account by a scene query.
+///
+/// The default filter does not exclude any collider. A collider passes the filter iff. it
+/// passes every one of its criteria:
+///
+/// ```ignore
+/// // Cast a ray ignoring the character's own colliders, and the sensors.
+/// let filter = QueryFilter::new()
+///     .exclude_rigid_body(character)
+///     .exclude_sensors();
+/// query_pipeline.cast_ray(&bodies, &colliders, &ray, max_toi, filter);
+/// ```
+#[derive(Copy, Clone)]
+pub struct QueryFilter<'a> {
+    /// Flags excluding whole sets of colliders from the query.
+    pub flags: QueryFilterFlags,
+    /// If set, only the colliders with collision groups compatible with these groups are
+    /// taken into account. See [InteractionGroups::test] for details.
+    pub groups: Option<InteractionGroups>,
+    /// If set, this collider is excluded from the query.
+    pub exclude_collider: Option<ColliderHandle>,
+    /// If set, all the colliders attached to this rigid-body are excluded from the query.
+    pub exclude_rigid_body: Option<RigidBodyHandle>,
+    /// If set, only the colliders for which this predicate returns `true` are taken into account.
+    pub predicate: Option<&'a dyn Fn(ColliderHandle, &Collider) -> bool>,
+}
+
+impl<'a> Default for QueryFilter<'a> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<'a> From<QueryFilterFlags> for QueryFilter<'a> {
+    fn from(flags: QueryFilterFlags) -> Self {
+        Self::new().flags(flags)
+    }
+}
+
+impl<'a> From<InteractionGroups> for QueryFilter<'a> {
+    fn from(groups: InteractionGroups) -> Self {
+        Self::new().groups(groups)
+    }
+}
+
+impl<'a> QueryFilter<'a> {
+    /// A filter that does not exclude any collider.
+    pub fn new() -> Self {
+        Self {
+            flags: QueryFilterFlags::empty(),
+            groups: None,
+            exclude_collider: None,
+            exclude_rigid_body: None,
+            predicate: None,
+        }
+    }
+
+    /// A filter excluding all the colliders not attached to a dynamic rigid-body.
+    pub fn only_dynamic() -> Self {
+        QueryFilterFlags::ONLY_DYNAMIC.into()
+    }
+
+    /// A filter excluding all the colliders not attached to a kinematic rigid-body.
+    pub fn only_kinematic() -> Self {
+        QueryFilterFlags::ONLY_KINEMATIC.into()
+    }
+
+    /// A filter excluding all the colliders not attached to a static rigid-body.
+    pub fn only_static() -> Self {
+        QueryFilterFlags::ONLY_STATIC.into()
+    }
+
+    /// Replaces the flags of this filter.
+    pub fn flags(mut self, flags: QueryFilterFlags) -> Self {
+        self.flags = flags;
+        self
+    }
+
+    /// Excludes from the query all the colliders attached to a static rigid-body.
+    pub fn exclude_static(mut self) -> Self {
+        self.flags |= QueryFilterFlags::EXCLUDE_STATIC;
+        self
+    }
+
+    /// Excludes from the query all the colliders attached to a kinematic rigid-body.
+    pub fn exclude_kinematic(mut self) -> Self {
+        self.flags |= QueryFilterFlags::EXCLUDE_KINEMATIC;
+        self
+    }
+
+    /// Excludes from the query all the colliders attached to a dynamic rigid-body.
+    pub fn exclude_dynamic(mut self) -> Self {
+        self.flags |= QueryFilterFlags::EXCLUDE_DYNAMIC;
+        self
+    }
+
+    /// Excludes from the query all the sensors.
+    pub fn exclude_sensors(mut self) -> Self {
+        self.flags |= QueryFilterFlags::EXCLUDE_SENSORS;
+        self
+    }
+
+    /// Excludes from the query all the colliders that are not sensors.
+    pub fn exclude_solids(mut self) -> Self {
+        self.flags |= QueryFilterFlags::EXCLUDE_SOLIDS;
+        self
+    }
+
+    /// Only takes into account the colliders with collision groups compatible with `groups`.
+    pub fn groups(mut self, groups: InteractionGroups) -> Self {
+        self.groups = Some(groups);
+        self
+    }
+
+    /// Excludes the given collider from the query.
+    pub fn exclude_collider(mut self, collider: ColliderHandle) -> Self {
+        self.exclude_collider = Some(collider);
+        self
+    }
+
+    /// Excludes all the colliders attached to the given rigid-body from the query.
+    pub fn exclude_rigid_body(mut self, body: RigidBodyHandle) -> Self {
+        self.exclude_rigid_body = Some(body);
+        self
+    }
+
+    /// Only takes into account the colliders for which `predicate` returns `true`.
+    pub fn predicate(mut self, predicate: &'a dyn Fn(ColliderHandle, &Collider) -> bool) -> Self {
+        self.predicate = Some(predicate);
+        self
+    }
+
+    /// Tests whether the given collider passes this filter.
+    ///
+    /// The predicate of this filter is only called if the collider passed all the other criteria.
+    pub fn test(&self, bodies: &RigidBodySet, handle: ColliderHandle, collider: &Collider) -> bool {
+        if self.exclude_collider == Some(handle)
+            || self.exclude_rigid_body == Some(collider.parent())
+        {
+            return false;
+        }
+
+        if let Some(groups) = self.groups {
+            if !collider.collision_groups.test(groups) {
+                return false;
+            }
+        }
+
+        let excluded_kind = if collider.is_sensor() {
+            QueryFilterFlags::EXCLUDE_SENSORS
+        } else {
+            QueryFilterFlags::EXCLUDE_SOLIDS
+        };
+
+        if self.flags.contains(excluded_kind) {
+            return false;
+        }
+
+        let status_flags = QueryFilterFlags::EXCLUDE_STATIC
+            | QueryFilterFlags::EXCLUDE_KINEMATIC
+            | QueryFilterFlags::EXCLUDE_DYNAMIC;
+
+        if self.flags.intersects(status_flags) {
+            let body = match bodies.get(collider.parent()) {
+                Some(body) => body,
+                None => return false,
+            };
+            let excluded_status = if body.is_dynamic() {
+                QueryFilterFlags::EXCLUDE_DYNAMIC
+            } else if body.is_kinematic() {
+                QueryFilterFlags::EXCLUDE_KINEMATIC
+            } else {
+                QueryFilterFlags::EXCLUDE_STATIC
+            };
+
+            if self.flags.contains(excluded_status) {
+                return false;
+            }
+        }
+
+        self.predicate.map(|f| f(handle, collider)).unwrap_or(true)
+    }
+}
@@ -1,7 +1,37 @@
 use crate::dynamics::RigidBodySet;
 use crate::geometry::{
-    Collider, ColliderHandle, ColliderSet, InteractionGroups, Ray, RayIntersection, WQuadtree,
+    self, Collider, ColliderHandle, ColliderSet, InteractionGroups, NonlinearRigidMotion, Ray,
+    RayIntersection, Shape, WQuadtree, AABB, TOI,
 };
+use crate::math::{Point, Vector};
+use crate::pipeline::QueryFilter;
+use std::cmp::Ordering;
+
+/// How the strength of a radial impulse decreases with the distance to its center.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde-serialize", derive(Serialize, Deserialize))]
+pub enum RadialFalloff {
+    /// The impulse has the same strength everywhere inside of its radius.
+    Constant,
+    /// The impulse strength decreases linearly from its center to its radius.
+    Linear,
+    /// The impulse strength decreases quadratically from its center to its radius.
+    Quadratic,
+}
+
+impl RadialFalloff {
+    /// The multiplier applied to the impulse strength at the distance `dist` from the center of
+    /// an impulse with the given radius.
+    pub fn factor(self, dist: f32, radius: f32) -> f32 {
+        let remaining = (1.0 - dist / radius).max(0.0);
+
+        match self {
+            RadialFalloff::Constant => 1.0,
+            RadialFalloff::Linear => remaining,
+            RadialFalloff::Quadratic => remaining * remaining,
+        }
+    }
+}
 
 /// A pipeline for performing queries on all the colliders of a scene.
 #[cfg_attr(feature = "serde-serialize", derive(Serialize, Deserialize))]
@@ -10,6 +40,9 @@ pub struct QueryPipeline {
     quadtree: WQuadtree<ColliderHandle>,
     tree_built: bool,
     dilation_factor: f32,
+    // The identifier and version of the collider set at the last update.
+    colliders_id: u64,
+    colliders_version: u64,
 }
 
 impl Default for QueryPipeline {
@@ -25,44 +58,57 @@ impl QueryPipeline {
             quadtree: WQuadtree::new(),
             tree_built: false,
             dilation_factor: 0.01,
+            colliders_id: 0,
+            colliders_version: 0,
         }
     }
 
     /// Update the acceleration structure on the query pipeline.
-    pub fn update(&mut self, bodies: &RigidBodySet, colliders: &ColliderSet) {
-        if !self.tree_built {
+    ///
+    /// Only the colliders that moved since the last update are refitted, so this is cheap
+    /// for mostly-static scenes. The whole structure is rebuilt if colliders were inserted
+    /// or removed since the last update, or if `colliders` isn't the set given to the last update.
+    pub fn update(&mut self, _bodies: &RigidBodySet, colliders: &ColliderSet) {
+        if !self.tree_built
+            || colliders.id != self.colliders_id
+            || colliders.topology_version > self.colliders_version
+        {
             let data = colliders.iter().map(|(h, c)| (h, c.compute_aabb()));
             self.quadtree.clear_and_rebuild(data, self.dilation_factor);
-            // FIXME: uncomment this once we handle insertion/removals properly.
-            // self.tree_built = true;
-            return;
-        }
-
-        for (_, body) in bodies
-            .iter_active_dynamic()
-            .chain(bodies.iter_active_kinematic())
-        {
-            for handle in &body.colliders {
-                self.quadtree.pre_update(*handle)
+            self.tree_built = true;
+        } else if let Some(moved) = colliders.moved_colliders_since(self.colliders_version) {
+            for handle in moved {
+                self.quadtree.pre_update(handle)
             }
+
+            self.quadtree.update(colliders, self.dilation_factor);
+        } else {
+            // Too many moves happened since the last update to know which colliders moved.
+            self.quadtree.refit(self.dilation_factor, |handle| {
+                colliders[*handle].compute_aabb()
+            });
         }
 
-        self.quadtree.update(colliders, self.dilation_factor);
+        self.colliders_id = colliders.id;
+        self.colliders_version = colliders.version;
     }
 
     /// Find the closest intersection between a ray and a set of collider.
     ///
     /// # Parameters
-    /// - `position`: the position of this shape.
+    /// - `bodies`: the set of rigid-bodies the colliders are attached to.
+    /// - `colliders`: the set of colliders tested against the ray.
     /// - `ray`: the ray to cast.
     /// - `max_toi`: the maximum time-of-impact that can be reported by this cast. This effectively
     ///   limits the length of the ray to `ray.dir.norm() * max_toi`. Use `f32::MAX` for an unbounded ray.
+    /// - `filter`: the filter selecting the colliders that can be hit by the ray.
     pub fn cast_ray<'a>(
         &self,
+        bodies: &RigidBodySet,
         colliders: &'a ColliderSet,
         ray: &Ray,
         max_toi: f32,
-        groups: InteractionGroups,
+        filter: QueryFilter,
     ) -> Option<(ColliderHandle, &'a Collider, RayIntersection)> {
         // TODO: avoid allocation?
         let mut inter = Vec::new();
@@ -73,7 +119,7 @@ impl QueryPipeline {
 
         for handle in inter {
             if let Some(collider) = colliders.get(handle) {
-                if collider.collision_groups.test(groups) {
+                if filter.test(bodies, handle, collider) {
                     if let Some(inter) = collider.shape().toi_and_normal_with_ray(
                         collider.position(),
                         ray,
@@ -102,33 +148,805 @@ impl QueryPipeline {
     /// - `callback`: function executed on each collider for which a ray intersection has been found.
     ///   There is no guarantees on the order the results will be yielded. If this callback returns `false`,
     ///   this method will exit early, ignory any further raycast.
+    #[deprecated(note = "renamed to `intersections_with_ray`.")]
     pub fn interferences_with_ray<'a>(
         &self,
         colliders: &'a ColliderSet,
         ray: &Ray,
         max_toi: f32,
         groups: InteractionGroups,
+        callback: impl FnMut(ColliderHandle, &'a Collider, RayIntersection) -> bool,
+    ) {
+        self.filtered_intersections_with_ray(
+            colliders,
+            ray,
+            max_toi,
+            |_, collider| collider.collision_groups.test(groups),
+            false,
+            callback,
+        )
+    }
+
+    /// Find the all intersections between a ray and a set of collider and passes them to a callback.
+    ///
+    /// # Parameters
+    /// - `bodies`: the set of rigid-bodies the colliders are attached to.
+    /// - `colliders`: the set of colliders tested against the ray.
+    /// - `ray`: the ray to cast.
+    /// - `max_toi`: the maximum time-of-impact that can be reported by this cast. This effectively
+    ///   limits the length of the ray to `ray.dir.norm() * max_toi`. Use `f32::MAX` for an unbounded ray.
+    /// - `filter`: the filter selecting the colliders that can be hit by the ray.
+    /// - `sorted`: if `true`, the intersections are yielded by increasing time-of-impact. Otherwise
+    ///   there is no guarantees on the order the results will be yielded, but no extra allocation
+    ///   and sorting are needed.
+    /// - `callback`: function executed on each collider for which a ray intersection has been found.
+    ///   If this callback returns `false`, this method will exit early, ignoring any further raycast.
+    pub fn intersections_with_ray<'a>(
+        &self,
+        bodies: &RigidBodySet,
+        colliders: &'a ColliderSet,
+        ray: &Ray,
+        max_toi: f32,
+        filter: QueryFilter,
+        sorted: bool,
+        callback: impl FnMut(ColliderHandle, &'a Collider, RayIntersection) -> bool,
+    ) {
+        self.filtered_intersections_with_ray(
+            colliders,
+            ray,
+            max_toi,
+            |handle, collider| filter.test(bodies, handle, collider),
+            sorted,
+            callback,
+        )
+    }
+
+    /// Find the first collider hit by a shape moving with constant linear and angular velocities.
+    ///
+    /// Unlike a linear shape cast, this accounts for the rotation of the cast shape during its
+    /// motion. This is necessary to detect the impacts of fast spinning long shapes, e.g., thrown
+    /// hammers, that would be missed by a linear cast.
+    ///
+    /// # Parameters
+    /// - `bodies`: the set of rigid-bodies the colliders are attached to.
+    /// - `colliders`: the set of colliders tested against the cast shape.
+    /// - `shape_motion`: the motion of the cast shape, starting at the time `0`.
+    /// - `shape`: the cast shape.
+    /// - `max_toi`: the maximum time-of-impact that can be reported by this cast.
+    /// - `target_distance`: an impact is reported as soon as the distance between the cast shape
+    ///   and a collider becomes smaller than this value.
+    /// - `use_target_velocities`: if `true`, the colliders attached to non-sleeping rigid-bodies are
+    ///   assumed to move with the current linear and angular velocities of their rigid-bodies. If `false`,
+    ///   all the colliders are assumed to be static.
+    /// - `filter`: the filter selecting the colliders that can be hit by the cast shape.
+    pub fn nonlinear_cast_shape<'a>(
+        &self,
+        bodies: &RigidBodySet,
+        colliders: &'a ColliderSet,
+        shape_motion: &NonlinearRigidMotion,
+        shape: &dyn Shape,
+        max_toi: f32,
+        target_distance: f32,
+        use_target_velocities: bool,
+        filter: QueryFilter,
+    ) -> Option<(ColliderHandle, &'a Collider, TOI)> {
+        let swept_aabb = geometry::swept_aabb(shape_motion, shape, max_toi, target_distance);
+
+        // TODO: avoid allocation?
+        let mut candidates = Vec::new();
+        self.quadtree.intersect_aabb(&swept_aabb, &mut candidates);
+
+        if use_target_velocities {
+            // The moving colliders may reach the cast shape from outside of its swept AABB.
+            for (_, body) in bodies
+                .iter_active_dynamic()
+                .chain(bodies.iter_active_kinematic())
+            {
+                candidates.extend_from_slice(&body.colliders);
+            }
+
+            candidates.sort_unstable();
+            candidates.dedup();
+        }
+
+        let mut best = max_toi;
+        let mut result = None;
+
+        for handle in candidates {
+            if let Some(collider) = colliders.get(handle) {
+                if !filter.test(bodies, handle, collider) {
+                    continue;
+                }
+
+                let target_motion = match bodies.get(collider.parent()) {
+                    Some(body) if use_target_velocities => NonlinearRigidMotion::new(
+                        0.0,
+                        *collider.position(),
+                        collider.position_wrt_parent().inverse() * body.mass_properties().local_com,
+                        body.linvel,
+                        body.angvel,
+                    ),
+                    _ => NonlinearRigidMotion::new(
+                        0.0,
+                        *collider.position(),
+                        Point::origin(),
+                        Vector::zeros(),
+                        na::zero(),
+                    ),
+                };
+
+                if let Some(toi) = geometry::nonlinear_time_of_impact(
+                    shape_motion,
+                    shape,
+                    &target_motion,
+                    collider.shape(),
+                    best,
+                    target_distance,
+                ) {
+                    if toi.toi <= best {
+                        best = toi.toi;
+                        result = Some((handle, collider, toi));
+                    }
+                }
+            }
+        }
+
+        result
+    }
+
+    fn filtered_intersections_with_ray<'a>(
+        &self,
+        colliders: &'a ColliderSet,
+        ray: &Ray,
+        max_toi: f32,
+        filter: impl Fn(ColliderHandle, &Collider) -> bool,
+        sorted: bool,
         mut callback: impl FnMut(ColliderHandle, &'a Collider, RayIntersection) -> bool,
     ) {
         // TODO: avoid allocation?
         let mut inter = Vec::new();
         self.quadtree.cast_ray(ray, max_toi, &mut inter);
 
+        let hits = inter.into_iter().filter_map(|handle| {
+            let collider = colliders.get(handle)?;
+
+            if filter(handle, collider) {
+                collider
+                    .shape()
+                    .toi_and_normal_with_ray(collider.position(), ray, max_toi, true)
+                    .map(|inter| (handle, collider, inter))
+            } else {
+                None
+            }
+        });
+
+        if sorted {
+            let mut hits: Vec<_> = hits.collect();
+            hits.sort_by(|a, b| a.2.toi.partial_cmp(&b.2.toi).unwrap_or(Ordering::Equal));
+
+            for (handle, collider, inter) in hits {
+                if !callback(handle, collider, inter) {
+                    return;
+                }
+            }
+        } else {
+            for (handle, collider, inter) in hits {
+                if !callback(handle, collider, inter) {
+                    return;
+                }
+            }
+        }
+    }
+
+    /// Finds all the colliders containing the given point and passes them to a callback.
+    ///
+    /// The shapes of the colliders are considered solid, so a point inside of a collider is contained
+    /// by it even if it is far from its boundary. This can be used for picking areas with the mouse,
+    /// or to check if a position lies inside of a sensor.
+    ///
+    /// # Parameters
+    /// - `bodies`: the set of rigid-bodies the colliders are attached to.
+    /// - `colliders`: the set of colliders tested against the point.
+    /// - `point`: the point tested, in world-space.
+    /// - `filter`: the filter selecting the colliders tested against the point.
+    /// - `callback`: function executed on each collider containing `point`.
+    ///   There is no guarantees on the order the results will be yielded. If this callback returns `false`,
+    ///   this method will exit early, ignoring any further collider.
+    pub fn colliders_containing_point<'a>(
+        &self,
+        bodies: &RigidBodySet,
+        colliders: &'a ColliderSet,
+        point: &Point<f32>,
+        filter: QueryFilter,
+        mut callback: impl FnMut(ColliderHandle, &'a Collider) -> bool,
+    ) {
+        // TODO: avoid allocation?
+        let mut inter = Vec::new();
+        self.quadtree
+            .intersect_aabb(&AABB::new(*point, *point), &mut inter);
+
         for handle in inter {
-            let collider = &colliders[handle];
-
-            if collider.collision_groups.test(groups) {
-                if let Some(inter) = collider.shape().toi_and_normal_with_ray(
-                    collider.position(),
-                    ray,
-                    max_toi,
-                    true,
-                ) {
-                    if !callback(handle, collider, inter) {
-                        return;
-                    }
+            if let Some(collider) = colliders.get(handle) {
+                if filter.test(bodies, handle, collider)
+                    && collider.shape().contains_point(collider.position(), point)
+                    && !callback(handle, collider)
+                {
+                    return;
+                }
+            }
+        }
+    }
+
+    /// Applies an impulse pushing all the dynamic rigid-bodies located inside of a ball away from its center.
+    ///
+    /// This can be used to simulate explosions. Each rigid-body is affected at most once: the
+    /// impulse is applied at the point of its colliders closest to `center`, so rigid-bodies hit
+    /// off-center also start to rotate. Its direction goes from `center` to this point.
+    ///
+    /// # Parameters
+    /// - `bodies`: the set of rigid-bodies the impulses are applied to.
+    /// - `colliders`: the set of colliders the impulses are applied to.
+    /// - `center`: the center of the radial impulse.
+    /// - `radius`: the maximum distance between `center` and the colliders affected by the impulse.
+    /// - `strength`: the magnitude of the impulse at its center.
+    /// - `falloff`: how the magnitude of the impulse decreases with the distance to `center`.
+    /// - `filter`: the filter selecting the colliders affected by the impulse, and the colliders
+    ///   blocking it. Colliders that are sensors or that are not attached to a dynamic rigid-body
+    ///   are never affected by the impulse.
+    /// - `check_line_of_sight`: if `true`, the colliders located behind a collider attached to another
+    ///   rigid-body are not affected by the impulse. The colliders containing `center` don't block it.
+    pub fn apply_radial_impulse(
+        &self,
+        bodies: &mut RigidBodySet,
+        colliders: &ColliderSet,
+        center: &Point<f32>,
+        radius: f32,
+        strength: f32,
+        falloff: RadialFalloff,
+        filter: QueryFilter,
+        check_line_of_sight: bool,
+    ) {
+        const EPS: f32 = 1.0e-4;
+
+        // TODO: avoid allocation?
+        let mut inter = Vec::new();
+        let aabb = AABB::new(
+            center - Vector::repeat(radius),
+            center + Vector::repeat(radius),
+        );
+        self.quadtree.intersect_aabb(&aabb, &mut inter);
+
+        // The point of each rigid-body closest to the center.
+        let mut hits: Vec<_> = inter
+            .into_iter()
+            .filter_map(|handle| {
+                let collider = colliders.get(handle)?;
+
+                if collider.is_sensor()
+                    || !bodies.get(collider.parent)?.is_dynamic()
+                    || !filter.test(bodies, handle, collider)
+                {
+                    return None;
+                }
+
+                let proj = collider
+                    .shape()
+                    .project_point(collider.position(), center, true);
+                let dist = na::distance(center, &proj.point);
+
+                if dist <= radius {
+                    Some((collider.parent, proj.point, dist))
+                } else {
+                    None
+                }
+            })
+            .collect();
+
+        hits.sort_by(|a, b| {
+            a.0.cmp(&b.0)
+                .then(a.2.partial_cmp(&b.2).unwrap_or(Ordering::Equal))
+        });
+        hits.dedup_by_key(|hit| hit.0);
+
+        for (body_handle, point, dist) in hits {
+            if check_line_of_sight && dist > 0.0 {
+                let ray = Ray::new(*center, point - center);
+                let mut blocked = false;
+
+                self.intersections_with_ray(
+                    bodies,
+                    colliders,
+                    &ray,
+                    1.0,
+                    filter,
+                    false,
+                    |_, co, hit| {
+                        // NOTE: the colliders containing the center have a time of impact of zero.
+                        blocked = co.parent != body_handle
+                            && !co.is_sensor()
+                            && hit.toi > 0.0
+                            && hit.toi < 1.0 - EPS;
+                        !blocked
+                    },
+                );
+
+                if blocked {
+                    continue;
                 }
             }
+
+            let body = &mut bodies[body_handle];
+            let dir = if dist > 0.0 {
+                (point - center) / dist
+            } else if let Some(dir) = (body.world_com - center).try_normalize(0.0) {
+                dir
+            } else {
+                continue;
+            };
+
+            let impulse = dir * (strength * falloff.factor(dist, radius));
+            body.apply_impulse_at_point(impulse, point, true);
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{QueryPipeline, RadialFalloff};
+    use crate::dynamics::{RigidBodyBuilder, RigidBodySet};
+    use crate::geometry::{Collider, ColliderBuilder, ColliderSet, InteractionGroups, Ray};
+    use crate::math::{Point, Vector};
+    use crate::pipeline::{QueryFilter, TestWorld};
+
+    #[test]
+    fn radial_impulse_line_of_sight() {
+        for &check_line_of_sight in &[false, true] {
+            let mut world = TestWorld::new(Vector::zeros());
+            let mut query_pipeline = QueryPipeline::new();
+
+            // The second ball is hidden behind the first one, the third one is out of reach.
+            let mut handles = Vec::new();
+            for &(x, y) in &[(2.0, 0.0), (4.0, 0.0), (0.0, 6.0)] {
+                #[cfg(feature = "dim2")]
+                let rb = RigidBodyBuilder::new_dynamic().translation(x, y);
+                #[cfg(feature = "dim3")]
+                let rb = RigidBodyBuilder::new_dynamic().translation(x, y, 0.0);
+                let (handle, _) = world.insert(rb.build(), ColliderBuilder::ball(0.5).build());
+                handles.push(handle);
+            }
+
+            world.step();
+            let bodies = &mut world.bodies;
+            query_pipeline.update(bodies, &world.colliders);
+            query_pipeline.apply_radial_impulse(
+                bodies,
+                &world.colliders,
+                &Point::origin(),
+                5.0,
+                1.0,
+                RadialFalloff::Linear,
+                QueryFilter::new(),
+                check_line_of_sight,
+            );
+
+            assert!(bodies[handles[0]].linvel().x > 0.0);
+            assert_eq!(bodies[handles[1]].linvel().x > 0.0, !check_line_of_sight);
+            assert_eq!(*bodies[handles[2]].linvel(), Vector::zeros());
+        }
+    }
+
+    #[test]
+    fn ray_cast_with_query_filter() {
+        let mut colliders = ColliderSet::new();
+        let mut bodies = RigidBodySet::new();
+        let mut query_pipeline = QueryPipeline::new();
+
+        // A character above a sensor, itself above the ground.
+        #[cfg(feature = "dim2")]
+        let rb = RigidBodyBuilder::new_dynamic().translation(0.0, 3.0);
+        #[cfg(feature = "dim3")]
+        let rb = RigidBodyBuilder::new_dynamic().translation(0.0, 3.0, 0.0);
+        let character = bodies.insert(rb.build());
+        let character_co =
+            colliders.insert(ColliderBuilder::ball(0.5).build(), character, &mut bodies);
+
+        let ground = bodies.insert(RigidBodyBuilder::new_static().build());
+        #[cfg(feature = "dim2")]
+        let co = ColliderBuilder::ball(0.5)
+            .translation(0.0, 1.5)
+            .sensor(true);
+        #[cfg(feature = "dim3")]
+        let co = ColliderBuilder::ball(0.5)
+            .translation(0.0, 1.5, 0.0)
+            .sensor(true);
+        let sensor_co = colliders.insert(co.build(), ground, &mut bodies);
+        let ground_co = colliders.insert(ColliderBuilder::ball(0.5).build(), ground, &mut bodies);
+
+        query_pipeline.update(&bodies, &colliders);
+
+        let ray = Ray::new(Point::from(Vector::y() * 3.0), -Vector::y());
+        let hit = |filter: QueryFilter| {
+            query_pipeline
+                .cast_ray(&bodies, &colliders, &ray, f32::MAX, filter)
+                .map(|hit| hit.0)
+        };
+
+        assert_eq!(hit(QueryFilter::new()), Some(character_co));
+        assert_eq!(hit(QueryFilter::only_dynamic()), Some(character_co));
+        assert_eq!(
+            hit(QueryFilter::new().exclude_rigid_body(character)),
+            Some(sensor_co)
+        );
+        assert_eq!(
+            hit(QueryFilter::new()
+                .exclude_collider(character_co)
+                .exclude_sensors()),
+            Some(ground_co)
+        );
+        assert_eq!(
+            hit(QueryFilter::only_static().exclude_solids()),
+            Some(sensor_co)
+        );
+        assert_eq!(
+            hit(QueryFilter::new()
+                .groups(InteractionGroups::none())
+                .exclude_dynamic()),
+            None
+        );
+
+        let not_ground = |handle, _: &Collider| handle != ground_co;
+        assert_eq!(
+            hit(QueryFilter::new()
+                .exclude_dynamic()
+                .exclude_sensors()
+                .predicate(&not_ground)),
+            None
+        );
+    }
+
+    #[test]
+    fn intersections_with_ray_sorted_and_early_exit() {
+        let mut world = TestWorld::new(Vector::zeros());
+        let mut query_pipeline = QueryPipeline::new();
+
+        // A row of balls along the ray, inserted in an order unrelated to their distances.
+        let ground = world.bodies.insert(RigidBodyBuilder::new_static().build());
+        let mut balls = Vec::new();
+        for &x in &[6.0, 2.0, 8.0, 4.0] {
+            #[cfg(feature = "dim2")]
+            let co = ColliderBuilder::ball(0.5).translation(x, 0.0);
+            #[cfg(feature = "dim3")]
+            let co = ColliderBuilder::ball(0.5).translation(x, 0.0, 0.0);
+            balls.push((x, world.attach(co.build(), ground)));
         }
+        world.step();
+        query_pipeline.update(&world.bodies, &world.colliders);
+
+        let ray = Ray::new(Point::origin(), Vector::x());
+        let hits = |sorted: bool, max_hits: usize| {
+            let mut hits = Vec::new();
+            query_pipeline.intersections_with_ray(
+                &world.bodies,
+                &world.colliders,
+                &ray,
+                f32::MAX,
+                QueryFilter::new(),
+                sorted,
+                |handle, _, inter| {
+                    hits.push((handle, inter.toi));
+                    hits.len() < max_hits
+                },
+            );
+            hits
+        };
+
+        balls.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap());
+        let sorted_hits = hits(true, usize::MAX);
+        assert_eq!(sorted_hits.len(), 4);
+        for ((x, handle), (hit, toi)) in balls.iter().zip(sorted_hits.iter()) {
+            assert_eq!(hit, handle);
+            assert!((toi - (x - 0.5)).abs() < 1.0e-5);
+        }
+
+        let mut unsorted_hits: Vec<_> = hits(false, usize::MAX).iter().map(|h| h.0).collect();
+        unsorted_hits.sort_unstable_by_key(|handle| handle.into_raw_parts());
+        let mut expected: Vec<_> = balls.iter().map(|b| b.1).collect();
+        expected.sort_unstable_by_key(|handle| handle.into_raw_parts());
+        assert_eq!(unsorted_hits, expected);
+
+        // The traversal stops as soon as the callback returns `false`.
+        let first_hits: Vec<_> = hits(true, 2).iter().map(|h| h.0).collect();
+        assert_eq!(first_hits, vec![balls[0].1, balls[1].1]);
+        assert_eq!(hits(false, 1).len(), 1);
+    }
+
+    #[test]
+    fn nonlinear_cast_shape_hits_with_rotation() {
+        use crate::geometry::{Cuboid, NonlinearRigidMotion};
+        use crate::math::Isometry;
+
+        let mut colliders = ColliderSet::new();
+        let mut bodies = RigidBodySet::new();
+        let mut query_pipeline = QueryPipeline::new();
+
+        // A ball above the center of a long spinning bar.
+        let ground = bodies.insert(RigidBodyBuilder::new_static().build());
+        #[cfg(feature = "dim2")]
+        let co = ColliderBuilder::ball(0.25).translation(0.0, 1.5);
+        #[cfg(feature = "dim3")]
+        let co = ColliderBuilder::ball(0.25).translation(0.0, 1.5, 0.0);
+        let ball_co = colliders.insert(co.build(), ground, &mut bodies);
+        query_pipeline.update(&bodies, &colliders);
+
+        #[cfg(feature = "dim2")]
+        let (bar, angvel) = (Cuboid::new(Vector::new(2.0, 0.1)), 1.0);
+        #[cfg(feature = "dim3")]
+        let (bar, angvel) = (Cuboid::new(Vector::new(2.0, 0.1, 0.1)), Vector::z());
+
+        let cast = |angvel| {
+            let motion = NonlinearRigidMotion::new(
+                0.0,
+                Isometry::identity(),
+                Point::origin(),
+                Vector::zeros(),
+                angvel,
+            );
+            query_pipeline.nonlinear_cast_shape(
+                &bodies,
+                &colliders,
+                &motion,
+                &bar,
+                std::f32::consts::PI,
+                0.0,
+                false,
+                QueryFilter::new(),
+            )
+        };
+
+        // Without rotation, the bar doesn't move at all.
+        assert!(cast(angvel * 0.0).is_none());
+
+        let (handle, _, toi) = cast(angvel).unwrap();
+        assert_eq!(handle, ball_co);
+        assert!(toi.toi > 0.0 && toi.toi < std::f32::consts::FRAC_PI_2);
+    }
+
+    #[test]
+    fn rigid_body_scoped_queries() {
+        use crate::geometry::Ball;
+        use crate::math::Isometry;
+
+        let mut bodies = RigidBodySet::new();
+        let mut colliders = ColliderSet::new();
+
+        // A body with a "torso" collider, and a "head" collider above it.
+        let body = bodies.insert(RigidBodyBuilder::new_dynamic().build());
+        let torso = colliders.insert(ColliderBuilder::ball(1.0).build(), body, &mut bodies);
+        let head = ColliderBuilder::ball(0.5)
+            .position(Isometry::new(Vector::y() * 2.0, na::zero()))
+            .build();
+        let head = colliders.insert(head, body, &mut bodies);
+
+        // The colliders of other bodies are ignored, even if they are hit first.
+        let other = bodies.insert(RigidBodyBuilder::new_static().build());
+        colliders.insert(ColliderBuilder::ball(10.0).build(), other, &mut bodies);
+
+        // The queries use the current position of the body, even before the next timestep.
+        let shift = Isometry::new(Vector::x() * 5.0, na::zero());
+        bodies.get_mut(body).unwrap().set_position(shift, false);
+        let rb = &bodies[body];
+
+        let ray_at_height = |y: f32| {
+            Ray::new(
+                Point::from(Vector::x() * 20.0 + Vector::y() * y),
+                -Vector::x(),
+            )
+        };
+        let (hit, inter) = rb
+            .cast_ray(&colliders, &ray_at_height(2.0), f32::MAX)
+            .unwrap();
+        assert_eq!(hit, head);
+        assert!((inter.toi - 14.5).abs() < 1.0e-4);
+        assert_eq!(
+            rb.cast_ray(&colliders, &ray_at_height(0.0), f32::MAX)
+                .map(|hit| hit.0),
+            Some(torso)
+        );
+        assert!(rb
+            .cast_ray(&colliders, &ray_at_height(3.0), f32::MAX)
+            .is_none());
+
+        let ball = Ball::new(0.1);
+        let ball_pos = Isometry::new(Vector::x() * 5.0 + Vector::y() * 2.55, na::zero());
+        assert_eq!(
+            rb.intersects_shape(&colliders, &ball_pos, &ball),
+            Some(head)
+        );
+        assert!(rb
+            .intersects_shape(&colliders, &Isometry::identity(), &ball)
+            .is_none());
+    }
+
+    #[test]
+    fn incremental_update_tracks_moved_colliders() {
+        use crate::math::Isometry;
+
+        let mut world = TestWorld::new(Vector::zeros());
+
+        let ground_co = ColliderBuilder::ball(1.0)
+            .position(Isometry::new(Vector::y() * -10.0, na::zero()))
+            .build();
+        let (ground, ground_co) = world.insert(RigidBodyBuilder::new_static().build(), ground_co);
+        let rb = RigidBodyBuilder::new_dynamic().build();
+        let (ball, ball_co) = world.insert(rb, ColliderBuilder::ball(0.5).build());
+        world
+            .bodies
+            .get_mut(ball)
+            .unwrap()
+            .set_linvel(Vector::x() * 10.0, true);
+
+        // One pipeline updated at each timestep, and one updated only once at the end.
+        let mut each_step = QueryPipeline::new();
+        let mut at_end = QueryPipeline::new();
+        each_step.update(&world.bodies, &world.colliders);
+        at_end.update(&world.bodies, &world.colliders);
+
+        for _ in 0..60 {
+            world.step();
+            each_step.update(&world.bodies, &world.colliders);
+        }
+        at_end.update(&world.bodies, &world.colliders);
+        let TestWorld {
+            mut bodies,
+            mut colliders,
+            ..
+        } = world;
+
+        let x = bodies[ball].position().translation.vector.x;
+        assert!(x > 9.0);
+        let ray_at = |x: f32| {
+            Ray::new(
+                Point::from(Vector::x() * x + Vector::y() * 5.0),
+                -Vector::y(),
+            )
+        };
+        let hit = |query_pipeline: &QueryPipeline,
+                   bodies: &RigidBodySet,
+                   colliders: &ColliderSet,
+                   x: f32| {
+            query_pipeline
+                .cast_ray(bodies, colliders, &ray_at(x), f32::MAX, QueryFilter::new())
+                .map(|hit| hit.0)
+        };
+
+        for query_pipeline in &[&each_step, &at_end] {
+            assert_eq!(hit(query_pipeline, &bodies, &colliders, x), Some(ball_co));
+            assert_eq!(
+                hit(query_pipeline, &bodies, &colliders, 0.0),
+                Some(ground_co)
+            );
+        }
+
+        // Insertions and removals are taken into account too.
+        let removed_ground_co = colliders.remove(ground_co, &mut bodies, false).unwrap();
+        each_step.update(&bodies, &colliders);
+        assert_eq!(hit(&each_step, &bodies, &colliders, 0.0), None);
+        let ground_co = colliders.insert(removed_ground_co, ground, &mut bodies);
+        each_step.update(&bodies, &colliders);
+        assert_eq!(hit(&each_step, &bodies, &colliders, 0.0), Some(ground_co));
+    }
+
+    #[test]
+    fn update_rebuilds_for_another_collider_set() {
+        let mut world = TestWorld::new(Vector::zeros());
+        let (ball, ball_co) = world.insert(
+            RigidBodyBuilder::new_dynamic().build(),
+            ColliderBuilder::ball(0.5).build(),
+        );
+        let mut other = world.clone();
+        world
+            .bodies
+            .get_mut(ball)
+            .unwrap()
+            .set_linvel(Vector::x() * 10.0, true);
+        other
+            .bodies
+            .get_mut(ball)
+            .unwrap()
+            .set_linvel(-Vector::x() * 10.0, true);
+
+        // Both sets see the same number of moves, so they end up with the same version.
+        world.steps(30);
+        other.steps(30);
+        assert_eq!(world.colliders.version, other.colliders.version);
+
+        let ray = |world: &TestWorld| {
+            let x = world.bodies[ball].position().translation.vector.x;
+            Ray::new(
+                Point::from(Vector::x() * x + Vector::y() * 5.0),
+                -Vector::y(),
+            )
+        };
+        let hit = |query_pipeline: &QueryPipeline, world: &TestWorld| {
+            query_pipeline
+                .cast_ray(
+                    &world.bodies,
+                    &world.colliders,
+                    &ray(world),
+                    f32::MAX,
+                    QueryFilter::new(),
+                )
+                .map(|hit| hit.0)
+        };
+
+        let mut query_pipeline = QueryPipeline::new();
+        query_pipeline.update(&world.bodies, &world.colliders);
+        assert_eq!(hit(&query_pipeline, &world), Some(ball_co));
+        query_pipeline.update(&other.bodies, &other.colliders);
+        assert_eq!(hit(&query_pipeline, &other), Some(ball_co));
+
+        #[cfg(feature = "serde-serialize")]
+        {
+            let serialized = bincode::serialize(&world.colliders).unwrap();
+            world.colliders = bincode::deserialize(&serialized).unwrap();
+            assert_ne!(world.colliders.id, other.colliders.id);
+            query_pipeline.update(&world.bodies, &world.colliders);
+            assert_eq!(hit(&query_pipeline, &world), Some(ball_co));
+        }
+    }
+
+    #[test]
+    fn colliders_containing_point() {
+        let mut colliders = ColliderSet::new();
+        let mut bodies = RigidBodySet::new();
+        let mut query_pipeline = QueryPipeline::new();
+
+        // A lava pool sensor, overlapping a wall.
+        let ground = bodies.insert(RigidBodyBuilder::new_static().build());
+        #[cfg(feature = "dim2")]
+        let (lava, wall) = (
+            ColliderBuilder::cuboid(2.0, 1.0).sensor(true),
+            ColliderBuilder::cuboid(0.5, 3.0).translation(2.0, 0.0),
+        );
+        #[cfg(feature = "dim3")]
+        let (lava, wall) = (
+            ColliderBuilder::cuboid(2.0, 1.0, 2.0).sensor(true),
+            ColliderBuilder::cuboid(0.5, 3.0, 0.5).translation(2.0, 0.0, 0.0),
+        );
+        let lava = colliders.insert(lava.build(), ground, &mut bodies);
+        let wall = colliders.insert(wall.build(), ground, &mut bodies);
+        query_pipeline.update(&bodies, &colliders);
+
+        let containing = |point: Point<f32>, filter: QueryFilter| {
+            let mut result = Vec::new();
+            query_pipeline.colliders_containing_point(
+                &bodies,
+                &colliders,
+                &point,
+                filter,
+                |handle, _| {
+                    result.push(handle);
+                    true
+                },
+            );
+            result.sort();
+            result
+        };
+
+        let mut both = vec![lava, wall];
+        both.sort();
+        assert_eq!(containing(Point::origin(), QueryFilter::new()), vec![lava]);
+        assert_eq!(
+            containing(Point::from(Vector::x() * 1.9), QueryFilter::new()),
+            both
+        );
+        assert_eq!(
+            containing(
+                Point::from(Vector::x() * 1.9),
+                QueryFilter::new().exclude_sensors()
+            ),
+            vec![wall]
+        );
+        assert!(containing(Point::from(Vector::y() * 2.0), QueryFilter::new()).is_empty());
     }
 }
@@ -0,0 +1,105 @@
+//! A complete physics world, used by the tests to step simulations.
+
+use crate::dynamics::{IntegrationParameters, JointSet, RigidBody, RigidBodyHandle, RigidBodySet};
+use crate::geometry::{
+    BroadPhase, Collider, ColliderHandle, ColliderSet, ContactPairFilter, NarrowPhase,
+};
+use crate::math::Vector;
+use crate::pipeline::{EventHandler, PhysicsPipeline};
+
+/// All the structures needed to step a simulation.
+pub(crate) struct TestWorld {
+    pub gravity: Vector<f32>,
+    pub params: IntegrationParameters,
+    pub pipeline: PhysicsPipeline,
+    pub broad_phase: BroadPhase,
+    pub narrow_phase: NarrowPhase,
+    pub bodies: RigidBodySet,
+    pub colliders: ColliderSet,
+    pub joints: JointSet,
+}
+
+impl TestWorld {
+    /// An empty world with the given gravity, and the default integration parameters.
+    pub fn new(gravity: Vector<f32>) -> Self {
+        Self {
+            gravity,
+            params: IntegrationParameters::default(),
+            pipeline: PhysicsPipeline::new(),
+            broad_phase: BroadPhase::new(),
+            narrow_phase: NarrowPhase::new(),
+            bodies: RigidBodySet::new(),
+            colliders: ColliderSet::new(),
+            joints: JointSet::new(),
+        }
+    }
+
+    /// An empty world with a gravity of `9.81` along the `-y` axis.
+    pub fn falling() -> Self {
+        Self::new(Vector::y() * -9.81)
+    }
+
+    /// Inserts a rigid-body with a single collider attached to it.
+    pub fn insert(
+        &mut self,
+        body: RigidBody,
+        collider: Collider,
+    ) -> (RigidBodyHandle, ColliderHandle) {
+        let body = self.bodies.insert(body);
+        let collider = self.colliders.insert(collider, body, &mut self.bodies);
+        (body, collider)
+    }
+
+    /// Attaches a collider to an existing rigid-body.
+    pub fn attach(&mut self, collider: Collider, parent: RigidBodyHandle) -> ColliderHandle {
+        self.colliders.insert(collider, parent, &mut self.bodies)
+    }
+
+    /// Performs a single timestep, without any pair filter nor event handler.
+    pub fn step(&mut self) {
+        self.step_with(None, &());
+    }
+
+    /// Performs `num_steps` timesteps, without any pair filter nor event handler.
+    pub fn steps(&mut self, num_steps: usize) {
+        for _ in 0..num_steps {
+            self.step();
+        }
+    }
+
+    /// Performs a single timestep, with the given contact pair filter and event handler.
+    pub fn step_with(
+        &mut self,
+        contact_pair_filter: Option<&dyn ContactPairFilter>,
+        events: &dyn EventHandler,
+    ) {
+        self.pipeline.step(
+            &self.gravity,
+            &self.params,
+            &mut self.broad_phase,
+            &mut self.narrow_phase,
+            &mut self.bodies,
+            &mut self.colliders,
+            &mut self.joints,
+            contact_pair_filter,
+            None,
+            events,
+        );
+    }
+}
+
+impl Clone for TestWorld {
+    // NOTE: the physics pipeline only contains workspace data, so the clone gets a new one.
+    fn clone(&self) -> Self {
+        Self {
+            gravity: self.gravity,
+            params: self.params.clone(),
+            pipeline: PhysicsPipeline::new(),
+            broad_phase: self.broad_phase.clone(),
+            narrow_phase: self.narrow_phase.clone(),
+            bodies: self.bodies.clone(),
+            colliders: self.colliders.clone(),
+            joints: self.joints.clone(),
+        }
+    }
+}
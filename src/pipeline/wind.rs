@@ -0,0 +1,166 @@
+use crate::dynamics::{RigidBody, RigidBodySet};
+use crate::geometry::{Collider, ColliderHandle, ColliderSet, NarrowPhase, AABB};
+use crate::math::{Isometry, Vector};
+use crate::utils;
+
+/// An area effector simulating a wind, or the drag of a still fluid like the air.
+///
+/// Each call to `Wind::apply_forces` applies a drag force proportional to the velocity of the wind
+/// relative to the collider, and to the area the collider exposes to the wind, to every dynamic
+/// rigid-body with a (non-sensor) collider affected by the wind. It must be called before each
+/// `PhysicsPipeline::step`, since the forces it applies only last for one timestep.
+///
+/// The wind affects either the whole scene, or only the colliders intersecting a sensor collider.
+/// The exposed area of each collider is approximated by the area of the projection of its local
+/// AABB along the direction of the relative wind. Therefore it accounts for the orientation of
+/// flat shapes, e.g., a sail or a falling leaf. The drag force is applied at the center of this
+/// AABB. Sleeping rigid-bodies are not woken up.
+#[derive(Copy, Clone, Debug)]
+#[cfg_attr(feature = "serde-serialize", derive(Serialize, Deserialize))]
+pub struct Wind {
+    /// The sensor collider delimiting the volume affected by the wind.
+    ///
+    /// If this is `None`, the wind affects all the colliders of the scene.
+    pub collider: Option<ColliderHandle>,
+    /// The velocity of the wind.
+    ///
+    /// Use a zero velocity to simulate the drag of a still fluid.
+    pub velocity: Vector<f32>,
+    /// The drag coefficient, i.e., the force applied per unit of exposed area and per unit
+    /// of velocity relative to the wind.
+    pub drag: f32,
+}
+
+impl Wind {
+    /// Initializes a wind with the given velocity and drag coefficient, affecting the whole scene.
+    pub fn new(velocity: Vector<f32>, drag: f32) -> Self {
+        Self {
+            collider: None,
+            velocity,
+            drag,
+        }
+    }
+
+    /// Restricts the wind to the volume of the given sensor collider.
+    pub fn volume(mut self, collider: ColliderHandle) -> Self {
+        self.collider = Some(collider);
+        self
+    }
+
+    /// Applies the drag forces to all the rigid-bodies affected by this wind.
+    ///
+    /// If this wind is restricted to the volume of a sensor collider, the intersections between
+    /// this sensor and the other colliders are read from the narrow-phase, so they are the ones
+    /// detected during the last timestep.
+    pub fn apply_forces(
+        &self,
+        bodies: &mut RigidBodySet,
+        colliders: &ColliderSet,
+        narrow_phase: &NarrowPhase,
+    ) {
+        if let Some(volume) = self.collider {
+            let intersections = match narrow_phase.intersections_with(volume) {
+                Some(intersections) => intersections,
+                None => return,
+            };
+
+            for (h1, h2, intersecting) in intersections {
+                if intersecting {
+                    let collider = &colliders[utils::other_handle((h1, h2), volume)];
+                    self.apply_force(collider, &mut bodies[collider.parent()]);
+                }
+            }
+        } else {
+            for (_, collider) in colliders.iter() {
+                self.apply_force(collider, &mut bodies[collider.parent()]);
+            }
+        }
+    }
+
+    fn apply_force(&self, collider: &Collider, body: &mut RigidBody) {
+        if collider.is_sensor() || !body.is_dynamic() || body.is_sleeping() {
+            return;
+        }
+
+        let local_aabb = collider.shape().compute_aabb(&Isometry::identity());
+        let center = collider.position() * local_aabb.center();
+        let relative_vel = self.velocity - body.velocity_at_point(&center);
+        let speed = relative_vel.norm();
+
+        if speed == 0.0 {
+            return;
+        }
+
+        let local_dir = collider
+            .position()
+            .rotation
+            .inverse_transform_vector(&(relative_vel / speed));
+        let area = projected_area(&local_aabb, &local_dir);
+        body.apply_force_at_point(relative_vel * (self.drag * area), center, false);
+    }
+}
+
+// The area of the projection of the AABB on a plane orthogonal to the direction `dir`.
+fn projected_area(aabb: &AABB, dir: &Vector<f32>) -> f32 {
+    let extents = aabb.extents();
+
+    #[cfg(feature = "dim2")]
+    return dir.x.abs() * extents.y + dir.y.abs() * extents.x;
+
+    #[cfg(feature = "dim3")]
+    return dir.x.abs() * extents.y * extents.z
+        + dir.y.abs() * extents.x * extents.z
+        + dir.z.abs() * extents.x * extents.y;
+}
+
+#[cfg(test)]
+mod test {
+    use super::Wind;
+    use crate::dynamics::RigidBodyBuilder;
+    use crate::geometry::ColliderBuilder;
+    use crate::math::Vector;
+    use crate::pipeline::TestWorld;
+
+    #[test]
+    fn wind_drags_bodies_in_its_volume() {
+        let mut world = TestWorld::new(Vector::zeros());
+
+        // A storm zone around the origin.
+        let co = ColliderBuilder::ball(10.0).sensor(true).build();
+        let (_, zone) = world.insert(RigidBodyBuilder::new_static().build(), co);
+
+        let mut insert_ball = |x: f32| {
+            #[cfg(feature = "dim2")]
+            let rb = RigidBodyBuilder::new_dynamic().translation(x, 0.0);
+            #[cfg(feature = "dim3")]
+            let rb = RigidBodyBuilder::new_dynamic().translation(x, 0.0, 0.0);
+            let rb = rb.can_sleep(false).build();
+            world.insert(rb, ColliderBuilder::ball(0.5).build()).0
+        };
+        let inside = insert_ball(0.0);
+        let outside = insert_ball(-20.0);
+
+        let wind_velocity = Vector::y() * 3.0;
+        let wind = Wind::new(wind_velocity, 1.0).volume(zone);
+
+        let step = |world: &mut TestWorld, wind: &Wind| {
+            wind.apply_forces(&mut world.bodies, &world.colliders, &world.narrow_phase);
+            world.step();
+        };
+
+        for _ in 0..120 {
+            step(&mut world, &wind);
+        }
+
+        // The ball inside of the zone moves with the wind, without exceeding its velocity.
+        let linvel = *world.bodies[inside].linvel();
+        assert!(linvel.y > 2.0 && linvel.y < 3.0);
+        assert!(linvel.x.abs() < 1.0e-5);
+        assert_eq!(*world.bodies[outside].linvel(), Vector::zeros());
+
+        // A global wind affects the other ball too.
+        let wind = Wind::new(wind_velocity, 1.0);
+        step(&mut world, &wind);
+        assert!(world.bodies[outside].linvel().y > 0.0);
+    }
+}
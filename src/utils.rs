@@ -1,6 +1,7 @@
 //! Miscellaneous utilities.
 
 use crate::dynamics::RigidBodyHandle;
+use crate::math::{AngVector, Vector};
 use na::{Matrix2, Matrix3, Matrix3x2, Point2, Point3, Scalar, SimdRealField, Vector2, Vector3};
 use num::Zero;
 use simba::simd::SimdValue;
@@ -16,12 +17,10 @@ use {
 // pub(crate) const COS_10_DEGREES: f32 = 0.98480775301;
 // pub(crate) const COS_45_DEGREES: f32 = 0.70710678118;
 // pub(crate) const SIN_45_DEGREES: f32 = COS_45_DEGREES;
-#[cfg(feature = "dim3")]
 pub(crate) const COS_1_DEGREES: f32 = 0.99984769515;
 pub(crate) const COS_5_DEGREES: f32 = 0.99619469809;
 // #[cfg(feature = "dim2")]
 pub(crate) const COS_FRAC_PI_8: f32 = 0.92387953251;
-#[cfg(feature = "dim2")]
 pub(crate) const SIN_FRAC_PI_8: f32 = 0.38268343236;
 
 pub(crate) fn inv(val: f32) -> f32 {
@@ -32,6 +31,32 @@ pub(crate) fn inv(val: f32) -> f32 {
     }
 }
 
+/// Scales down the given velocities so their norms don't exceed the given maxima.
+pub(crate) fn clamp_velocities(
+    linvel: &mut Vector<f32>,
+    angvel: &mut AngVector<f32>,
+    max_linvel: f32,
+    max_angvel: f32,
+) {
+    let linvel_norm = linvel.norm();
+    if linvel_norm > max_linvel {
+        *linvel *= max_linvel / linvel_norm;
+    }
+
+    #[cfg(feature = "dim2")]
+    {
+        *angvel = angvel.max(-max_angvel).min(max_angvel);
+    }
+
+    #[cfg(feature = "dim3")]
+    {
+        let angvel_norm = angvel.norm();
+        if angvel_norm > max_angvel {
+            *angvel *= max_angvel / angvel_norm;
+        }
+    }
+}
+
 /// Conditionally swaps each lanes of `a` with those of `b`.
 ///
 /// For each `i in [0..SIMD_WIDTH[`, if `do_swap.extract(i)` is `true` then
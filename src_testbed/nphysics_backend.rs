@@ -187,7 +187,7 @@ fn nphysics_collider_from_rapier_collider(
         pos *= capsule.transform_wrt_y();
         ShapeHandle::new(Capsule::new(capsule.half_height(), capsule.radius))
     } else if let Some(heightfield) = shape.as_heightfield() {
-        ShapeHandle::new(heightfield.clone())
+        ShapeHandle::new((**heightfield).clone())
     } else {
         #[cfg(feature = "dim3")]
         if let Some(trimesh) = shape.as_trimesh() {
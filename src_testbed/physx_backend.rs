@@ -185,9 +185,9 @@ impl PhysxWorld {
                 physx_collider_from_rapier_collider(&collider)
             {
                 let material = physics.create_material(
-                    collider.friction,
-                    collider.friction,
-                    collider.restitution,
+                    collider.material().friction,
+                    collider.material().friction,
+                    collider.material().restitution,
                 );
                 let geometry = cooking.make_geometry(px_collider);
                 let flags = if collider.is_sensor() {
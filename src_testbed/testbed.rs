@@ -20,12 +20,14 @@ use na::{self, Point2, Point3, Vector3};
 use rapier::dynamics::{
     ActivationStatus, IntegrationParameters, JointSet, RigidBodyHandle, RigidBodySet,
 };
+#[cfg(feature = "dim3")]
+use rapier::geometry::Ray;
 use rapier::geometry::{
     BroadPhase, ColliderHandle, ColliderSet, ContactEvent, NarrowPhase, ProximityEvent,
 };
-#[cfg(feature = "dim3")]
-use rapier::geometry::{InteractionGroups, Ray};
 use rapier::math::Vector;
+#[cfg(feature = "dim3")]
+use rapier::pipeline::QueryFilter;
 use rapier::pipeline::{ChannelEventCollector, PhysicsPipeline, QueryPipeline};
 
 #[cfg(all(feature = "dim2", feature = "other-backends"))]
@@ -1133,10 +1135,11 @@ impl Testbed {
             .unproject(&self.cursor_pos, &na::convert(size));
         let ray = Ray::new(pos, dir);
         let hit = self.physics.query_pipeline.cast_ray(
+            &self.physics.bodies,
             &self.physics.colliders,
             &ray,
             f32::MAX,
-            InteractionGroups::all(),
+            QueryFilter::new(),
         );
 
         if let Some((_, collider, _)) = hit {